@@ -0,0 +1,38 @@
+use std::future::Future;
+use std::pin::pin;
+use std::sync::Mutex;
+use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+/// A minimal single-threaded executor that polls the future until it completes.
+fn block_on<F: Future>(future: F) -> F::Output {
+    fn noop_raw_waker() -> RawWaker {
+        fn clone(_: *const ()) -> RawWaker {
+            noop_raw_waker()
+        }
+        fn noop(_: *const ()) {}
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+        RawWaker::new(std::ptr::null(), &VTABLE)
+    }
+    let waker = unsafe { Waker::from_raw(noop_raw_waker()) };
+    let mut context = Context::from_waker(&waker);
+    let mut future = pin!(future);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+    }
+}
+
+async fn increment(counter: &Mutex<i32>) {
+    *counter.lock().unwrap() += 1;
+}
+
+async fn increment_twice(counter: &Mutex<i32>) {
+    let _guard = counter.lock().unwrap();
+    increment(counter).await; // cannot lock, since the guard is held across `.await`
+}
+
+fn main() {
+    let counter = Mutex::new(0);
+    block_on(increment_twice(&counter));
+}
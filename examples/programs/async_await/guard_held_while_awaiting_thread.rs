@@ -0,0 +1,41 @@
+use std::future::Future;
+use std::pin::pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+use std::thread::JoinHandle;
+
+/// A minimal single-threaded executor that polls the future until it completes.
+fn block_on<F: Future>(future: F) -> F::Output {
+    fn noop_raw_waker() -> RawWaker {
+        fn clone(_: *const ()) -> RawWaker {
+            noop_raw_waker()
+        }
+        fn noop(_: *const ()) {}
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+        RawWaker::new(std::ptr::null(), &VTABLE)
+    }
+    let waker = unsafe { Waker::from_raw(noop_raw_waker()) };
+    let mut context = Context::from_waker(&waker);
+    let mut future = pin!(future);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+    }
+}
+
+async fn wait_for(handle: JoinHandle<()>) {
+    handle.join().unwrap();
+}
+
+fn main() {
+    let data = Arc::new(Mutex::new(0));
+    let worker_data = Arc::clone(&data);
+    block_on(async move {
+        let _guard = data.lock().unwrap();
+        let handle = std::thread::spawn(move || {
+            *worker_data.lock().unwrap() += 1;
+        });
+        wait_for(handle).await; // the thread cannot lock, since the guard is held across `.await`
+    });
+}
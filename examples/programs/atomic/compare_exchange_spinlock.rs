@@ -0,0 +1,29 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+const UNLOCKED: usize = 0;
+const LOCKED: usize = 1;
+
+fn main() {
+    let lock = Arc::new(AtomicUsize::new(UNLOCKED));
+    let lock_clone = Arc::clone(&lock);
+
+    let handle = thread::spawn(move || {
+        while lock_clone
+            .compare_exchange(UNLOCKED, LOCKED, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            std::hint::spin_loop();
+        }
+        lock_clone.store(UNLOCKED, Ordering::Release);
+    });
+    while lock
+        .compare_exchange(UNLOCKED, LOCKED, Ordering::Acquire, Ordering::Relaxed)
+        .is_err()
+    {
+        std::hint::spin_loop();
+    }
+    lock.store(UNLOCKED, Ordering::Release);
+    handle.join().unwrap();
+}
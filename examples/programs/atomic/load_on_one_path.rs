@@ -0,0 +1,17 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+fn main() {
+    let flag = AtomicBool::new(false);
+    let counter = Mutex::new(0);
+    let load_flag = std::env::args().count() > 1;
+    // The value is only loaded on one path, the other path reaches the `if` without loading it
+    let value = if load_flag {
+        flag.load(Ordering::SeqCst)
+    } else {
+        true
+    };
+    if value {
+        *counter.lock().unwrap() += 1;
+    }
+}
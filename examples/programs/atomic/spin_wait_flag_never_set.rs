@@ -0,0 +1,17 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+fn main() {
+    let ready = Arc::new(AtomicBool::new(false));
+    let ready_clone = Arc::clone(&ready);
+
+    let handle = thread::spawn(move || {
+        ready_clone.store(false, Ordering::Release);
+    });
+    // Nobody sets the flag, the loop spins forever
+    while !ready.load(Ordering::Acquire) {
+        std::hint::spin_loop();
+    }
+    handle.join().unwrap();
+}
@@ -0,0 +1,17 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+fn main() {
+    let ready = Arc::new(AtomicBool::new(false));
+    let ready_clone = Arc::clone(&ready);
+
+    let handle = thread::spawn(move || {
+        ready_clone.store(true, Ordering::Release);
+    });
+    // The loop ends once the other thread sets the flag
+    while !ready.load(Ordering::Acquire) {
+        std::hint::spin_loop();
+    }
+    handle.join().unwrap();
+}
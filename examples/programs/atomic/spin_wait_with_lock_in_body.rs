@@ -0,0 +1,18 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+fn main() {
+    let ready = Arc::new(AtomicBool::new(false));
+    let ready_clone = Arc::clone(&ready);
+    let counter = Mutex::new(0);
+
+    let handle = thread::spawn(move || {
+        ready_clone.store(false, Ordering::Release);
+    });
+    // The body does more than spinning, so the loop is not a spin loop and never blocks
+    while !ready.load(Ordering::Acquire) {
+        *counter.lock().unwrap() += 1;
+    }
+    handle.join().unwrap();
+}
@@ -0,0 +1,21 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+fn main() {
+    let locked = Arc::new(AtomicBool::new(false));
+    let locked_clone = Arc::clone(&locked);
+
+    while locked.swap(true, Ordering::Acquire) {
+        std::hint::spin_loop();
+    }
+    let handle = thread::spawn(move || {
+        while locked_clone.swap(true, Ordering::Acquire) {
+            std::hint::spin_loop();
+        }
+        locked_clone.store(false, Ordering::Release);
+    });
+    // The thread spins forever because the lock is released after joining it
+    handle.join().unwrap();
+    locked.store(false, Ordering::Release);
+}
@@ -0,0 +1,17 @@
+#[path = "../../stubs/crossbeam_channel/lib.rs"]
+mod crossbeam_channel;
+
+use crossbeam_channel::bounded;
+use std::thread;
+
+fn main() {
+    let (sender, receiver) = bounded::<i32>(1);
+    let producer = thread::spawn(move || {
+        // The second send waits until the consumer took the first message.
+        sender.send(1).unwrap();
+        sender.send(2).unwrap();
+    });
+    receiver.recv().unwrap();
+    receiver.recv().unwrap();
+    producer.join().unwrap();
+}
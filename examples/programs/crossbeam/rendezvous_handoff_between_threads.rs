@@ -0,0 +1,15 @@
+#[path = "../../stubs/crossbeam_channel/lib.rs"]
+mod crossbeam_channel;
+
+use crossbeam_channel::bounded;
+use std::thread;
+
+fn main() {
+    let (sender, receiver) = bounded::<i32>(0);
+    let consumer = thread::spawn(move || {
+        receiver.recv().unwrap();
+    });
+    // The send returns once the consumer took the message.
+    sender.send(1).unwrap();
+    consumer.join().unwrap();
+}
@@ -0,0 +1,12 @@
+#[path = "../../stubs/crossbeam_channel/lib.rs"]
+mod crossbeam_channel;
+
+use crossbeam_channel::bounded;
+
+fn main() {
+    let (sender, receiver) = bounded::<i32>(0);
+    // A zero-capacity channel only sends when a receiver takes the message.
+    // The receiver is alive but nobody receives, so the send blocks forever.
+    sender.send(1).unwrap();
+    let _ = receiver.recv();
+}
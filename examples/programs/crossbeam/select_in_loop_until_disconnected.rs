@@ -0,0 +1,23 @@
+#[path = "../../stubs/crossbeam_channel/lib.rs"]
+mod crossbeam_channel;
+
+use crossbeam_channel::{select, unbounded};
+use std::thread;
+
+fn main() {
+    let (numbers_sender, numbers) = unbounded::<i32>();
+    let (done_sender, done) = unbounded::<()>();
+    thread::spawn(move || {
+        numbers_sender.send(1).unwrap();
+        numbers_sender.send(2).unwrap();
+        done_sender.send(()).unwrap();
+    });
+    // The branch that receives a number loops back to the `select!`,
+    // which waits for the next ready operation instead of spinning.
+    loop {
+        select! {
+            recv(numbers) -> _number => {},
+            recv(done) -> _ => break,
+        }
+    }
+}
@@ -0,0 +1,24 @@
+#[path = "../../stubs/crossbeam_channel/lib.rs"]
+mod crossbeam_channel;
+
+use crossbeam_channel::{bounded, select, unbounded};
+use std::thread;
+
+fn main() {
+    let (idle_sender, idle_receiver) = bounded::<i32>(0);
+    let (sender, receiver) = unbounded::<i32>();
+    thread::spawn(move || {
+        sender.send(1).unwrap();
+    });
+    // Only the second channel ever becomes ready, so the first branch is never taken.
+    // Taking it would block forever on the idle channel.
+    select! {
+        recv(idle_receiver) -> _message => {
+            idle_receiver.recv().unwrap();
+        },
+        recv(receiver) -> message => {
+            message.unwrap();
+        },
+    }
+    drop(idle_sender);
+}
@@ -0,0 +1,19 @@
+#[path = "../../stubs/crossbeam_channel/lib.rs"]
+mod crossbeam_channel;
+
+use crossbeam_channel::{bounded, select, unbounded};
+
+fn main() {
+    let (idle_sender, idle_receiver) = bounded::<i32>(0);
+    let (sender, receiver) = unbounded::<i32>();
+    sender.send(1).unwrap();
+    // Nobody receives on the zero-capacity channel, so the send is never ready
+    // and the message already in the other channel is received instead.
+    select! {
+        send(idle_sender, 5) -> _result => {},
+        recv(receiver) -> message => {
+            message.unwrap();
+        },
+    }
+    drop(idle_receiver);
+}
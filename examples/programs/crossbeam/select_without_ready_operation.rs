@@ -0,0 +1,17 @@
+#[path = "../../stubs/crossbeam_channel/lib.rs"]
+mod crossbeam_channel;
+
+use crossbeam_channel::{bounded, select};
+
+fn main() {
+    let (requests_sender, requests) = bounded::<i32>(0);
+    let (replies_sender, replies) = bounded::<i32>(0);
+    // Both channels are alive but nobody sends a request or receives a reply,
+    // so no operation ever becomes ready.
+    select! {
+        recv(requests) -> _request => {},
+        send(replies_sender, 1) -> _result => {},
+    }
+    drop(requests_sender);
+    drop(replies);
+}
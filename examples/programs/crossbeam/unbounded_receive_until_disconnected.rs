@@ -0,0 +1,15 @@
+#[path = "../../stubs/crossbeam_channel/lib.rs"]
+mod crossbeam_channel;
+
+use crossbeam_channel::unbounded;
+use std::thread;
+
+fn main() {
+    let (sender, receiver) = unbounded::<i32>();
+    thread::spawn(move || {
+        sender.send(1).unwrap();
+        sender.send(2).unwrap();
+        // The only sender is dropped when the thread ends, which disconnects the channel.
+    });
+    while let Ok(_message) = receiver.recv() {}
+}
@@ -0,0 +1,9 @@
+fn main() {
+    let debug = false;
+    let data = std::sync::Mutex::new(0);
+    if debug {
+        let _d1 = data.lock();
+        let _d2 = data.lock(); // never executed, since debug is always false
+    }
+    let _d3 = data.lock();
+}
@@ -0,0 +1,8 @@
+fn main() {
+    let debug = true;
+    let data = std::sync::Mutex::new(0);
+    if debug {
+        let _d1 = data.lock();
+        let _d2 = data.lock(); // cannot lock, since d1 is still active
+    }
+}
@@ -0,0 +1,18 @@
+enum Mode {
+    Fast,
+    Careful,
+}
+
+fn main() {
+    let mode = Mode::Fast;
+    let data = std::sync::Mutex::new(0);
+    match mode {
+        Mode::Fast => {
+            let _d1 = data.lock();
+        }
+        Mode::Careful => {
+            let _d1 = data.lock();
+            let _d2 = data.lock(); // never executed, since the mode is always fast
+        }
+    }
+}
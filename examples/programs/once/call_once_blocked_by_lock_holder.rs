@@ -0,0 +1,19 @@
+use std::sync::{Arc, Mutex, Once};
+use std::thread;
+
+fn main() {
+    let once = Arc::new(Once::new());
+    let mutex = Arc::new(Mutex::new(0));
+    let once_clone = once.clone();
+    let mutex_clone = mutex.clone();
+    let handle = thread::spawn(move || {
+        once_clone.call_once(|| {
+            // Blocks if the main thread holds the lock while waiting for the initialization.
+            *mutex_clone.lock().unwrap() += 1;
+        });
+    });
+    let guard = mutex.lock().unwrap();
+    once.call_once(|| {});
+    drop(guard);
+    handle.join().unwrap();
+}
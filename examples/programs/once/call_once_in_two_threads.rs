@@ -0,0 +1,18 @@
+use std::sync::{Arc, Mutex, Once};
+use std::thread;
+
+fn main() {
+    let once = Arc::new(Once::new());
+    let mutex = Arc::new(Mutex::new(0));
+    let once_clone = once.clone();
+    let mutex_clone = mutex.clone();
+    let handle = thread::spawn(move || {
+        once_clone.call_once(|| {
+            *mutex_clone.lock().unwrap() += 1;
+        });
+    });
+    once.call_once(|| {
+        *mutex.lock().unwrap() += 1;
+    });
+    handle.join().unwrap();
+}
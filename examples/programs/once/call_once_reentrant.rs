@@ -0,0 +1,9 @@
+use std::sync::Once;
+
+fn main() {
+    let once = Once::new();
+    once.call_once(|| {
+        // The initialization is still running, so this call blocks forever.
+        once.call_once(|| {});
+    });
+}
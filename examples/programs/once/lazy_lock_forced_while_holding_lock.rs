@@ -0,0 +1,12 @@
+use std::sync::{Arc, LazyLock, Mutex};
+
+fn main() {
+    let mutex = Arc::new(Mutex::new(1));
+    let mutex_clone = mutex.clone();
+    let lazy = LazyLock::new(move || *mutex_clone.lock().unwrap() + 1);
+    let guard = mutex.lock().unwrap();
+    // The initializer locks the mutex held by the caller.
+    let value = *lazy;
+    drop(guard);
+    println!("{value}");
+}
@@ -0,0 +1,14 @@
+use std::sync::{Arc, OnceLock};
+use std::thread;
+
+fn main() {
+    let cell: Arc<OnceLock<i32>> = Arc::new(OnceLock::new());
+    let cell_clone = cell.clone();
+    let handle = thread::spawn(move || {
+        let value = cell_clone.get_or_init(|| 1);
+        assert!(*value > 0);
+    });
+    let value = cell.get_or_init(|| 2);
+    assert!(*value > 0);
+    handle.join().unwrap();
+}
@@ -0,0 +1,19 @@
+use std::sync::{Arc, Mutex, Once};
+use std::thread;
+
+static INIT: Once = Once::new();
+
+fn main() {
+    let lock = Arc::new(Mutex::new(0));
+    let lock_clone = Arc::clone(&lock);
+    let handle = thread::spawn(move || {
+        INIT.call_once(|| {
+            *lock_clone.lock().unwrap() += 1;
+        });
+    });
+    // If the other thread started the initialization, it waits for the lock held here,
+    // while this call to the same static `Once` waits for the initialization to finish.
+    let _guard = lock.lock().unwrap();
+    INIT.call_once(|| {});
+    handle.join().unwrap();
+}
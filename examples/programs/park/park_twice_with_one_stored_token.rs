@@ -0,0 +1,13 @@
+use std::thread;
+
+fn main() {
+    let main_thread = thread::current();
+    let handle = thread::spawn(move || {
+        main_thread.unpark();
+        main_thread.unpark();
+    });
+    thread::park();
+    // Both calls to `unpark` may happen before the first `park`, storing a single token
+    thread::park();
+    handle.join().unwrap();
+}
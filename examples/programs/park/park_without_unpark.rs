@@ -0,0 +1,9 @@
+use std::thread;
+
+fn main() {
+    let handle = thread::spawn(|| {
+        // Nobody unparks this thread
+        thread::park();
+    });
+    handle.join().unwrap();
+}
@@ -0,0 +1,10 @@
+use std::thread;
+
+fn main() {
+    let handle = thread::spawn(|| {
+        // Returns immediately if the main thread already called `unpark`
+        thread::park();
+    });
+    handle.thread().unpark();
+    handle.join().unwrap();
+}
@@ -0,0 +1,10 @@
+use std::thread;
+
+fn main() {
+    let main_thread = thread::current();
+    let handle = thread::spawn(move || {
+        main_thread.unpark();
+    });
+    thread::park();
+    handle.join().unwrap();
+}
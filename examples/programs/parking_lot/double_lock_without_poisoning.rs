@@ -0,0 +1,11 @@
+#[path = "../../stubs/parking_lot/lib.rs"]
+mod parking_lot;
+
+use parking_lot::Mutex;
+
+fn main() {
+    let mutex = Mutex::new(0);
+    let _guard = mutex.lock();
+    // There is no poisoning and no `unwrap`, but locking twice still deadlocks
+    let _second_guard = mutex.lock();
+}
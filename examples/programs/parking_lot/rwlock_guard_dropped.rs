@@ -0,0 +1,18 @@
+#[path = "../../stubs/parking_lot/lib.rs"]
+mod parking_lot;
+
+use parking_lot::RwLock;
+use std::sync::Arc;
+use std::thread;
+
+fn main() {
+    let lock = Arc::new(RwLock::new(0));
+    let lock_clone = Arc::clone(&lock);
+
+    let value = *lock.read();
+    let writer = thread::spawn(move || {
+        *lock_clone.write() += 1;
+    });
+    writer.join().unwrap();
+    println!("{}", value + *lock.read());
+}
@@ -0,0 +1,19 @@
+#[path = "../../stubs/parking_lot/lib.rs"]
+mod parking_lot;
+
+use parking_lot::RwLock;
+use std::sync::Arc;
+use std::thread;
+
+fn main() {
+    let lock = Arc::new(RwLock::new(0));
+    let lock_clone = Arc::clone(&lock);
+
+    let reader = thread::spawn(move || {
+        let first = lock_clone.read();
+        let second = lock_clone.read();
+        *first + *second
+    });
+    let value = *lock.read();
+    println!("{}", value + reader.join().unwrap());
+}
@@ -0,0 +1,26 @@
+//! Example taken from the documentation of `std::sync::Condvar`, using `parking_lot` instead.
+//! `parking_lot::Condvar::wait` receives a mutable reference to the guard.
+#[path = "../../stubs/parking_lot/lib.rs"]
+mod parking_lot;
+
+use parking_lot::{Condvar, Mutex};
+use std::sync::Arc;
+use std::thread;
+
+fn main() {
+    let pair = Arc::new((Mutex::new(false), Condvar::new()));
+    let pair2 = Arc::clone(&pair);
+
+    thread::spawn(move || {
+        let (lock, cvar) = &*pair2;
+        let mut started = lock.lock();
+        *started = true;
+        cvar.notify_one();
+    });
+
+    let (lock, cvar) = &*pair;
+    let mut started = lock.lock();
+    while !*started {
+        cvar.wait(&mut started);
+    }
+}
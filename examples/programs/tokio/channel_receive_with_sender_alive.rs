@@ -0,0 +1,14 @@
+#[path = "../../stubs/tokio/lib.rs"]
+mod tokio;
+
+use tokio::sync::mpsc;
+
+fn main() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    runtime.block_on(async {
+        let (sender, mut receiver) = mpsc::channel::<i32>(1);
+        // The sender is never dropped, so the channel is never closed
+        while let Some(_message) = receiver.recv().await {}
+        drop(sender);
+    });
+}
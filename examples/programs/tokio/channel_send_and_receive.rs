@@ -0,0 +1,18 @@
+#[path = "../../stubs/tokio/lib.rs"]
+mod tokio;
+
+use tokio::sync::mpsc;
+
+async fn produce(sender: mpsc::Sender<i32>) {
+    sender.send(1).await.unwrap();
+}
+
+fn main() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    runtime.block_on(async {
+        let (sender, mut receiver) = mpsc::channel(1);
+        let handle = tokio::spawn(produce(sender));
+        while let Some(_message) = receiver.recv().await {}
+        handle.await.unwrap();
+    });
+}
@@ -0,0 +1,20 @@
+#[path = "../../stubs/tokio/lib.rs"]
+mod tokio;
+
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+async fn increment(counter: Arc<Mutex<i32>>) {
+    *counter.lock().await += 1;
+}
+
+fn main() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    runtime.block_on(async {
+        let counter = Arc::new(Mutex::new(0));
+        let guard = counter.lock().await;
+        drop(guard);
+        let handle = tokio::spawn(increment(Arc::clone(&counter)));
+        handle.await.unwrap();
+    });
+}
@@ -0,0 +1,13 @@
+#[path = "../../stubs/tokio/lib.rs"]
+mod tokio;
+
+use tokio::sync::Notify;
+
+fn main() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    runtime.block_on(async {
+        let notify = Notify::new();
+        // Nobody calls `notify_one`
+        notify.notified().await;
+    });
+}
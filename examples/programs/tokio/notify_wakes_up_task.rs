@@ -0,0 +1,20 @@
+#[path = "../../stubs/tokio/lib.rs"]
+mod tokio;
+
+use std::sync::Arc;
+use tokio::sync::Notify;
+
+async fn wait(notify: Arc<Notify>) {
+    notify.notified().await;
+}
+
+fn main() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    runtime.block_on(async {
+        let notify = Arc::new(Notify::new());
+        let handle = tokio::spawn(wait(Arc::clone(&notify)));
+        // The permit is stored if the task is not waiting yet, so the notification is never lost
+        notify.notify_one();
+        handle.await.unwrap();
+    });
+}
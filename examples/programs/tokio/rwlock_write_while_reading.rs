@@ -0,0 +1,14 @@
+#[path = "../../stubs/tokio/lib.rs"]
+mod tokio;
+
+use tokio::sync::RwLock;
+
+fn main() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    runtime.block_on(async {
+        let lock = RwLock::new(0);
+        let value = lock.read().await;
+        // The read guard is still held
+        *lock.write().await += *value;
+    });
+}
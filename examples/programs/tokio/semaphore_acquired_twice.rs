@@ -0,0 +1,14 @@
+#[path = "../../stubs/tokio/lib.rs"]
+mod tokio;
+
+use tokio::sync::Semaphore;
+
+fn main() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    runtime.block_on(async {
+        let semaphore = Semaphore::new(1);
+        let _first = semaphore.acquire().await.unwrap();
+        // The only permit is still held by the same task
+        let _second = semaphore.acquire().await.unwrap();
+    });
+}
@@ -0,0 +1,19 @@
+#[path = "../../stubs/tokio/lib.rs"]
+mod tokio;
+
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+async fn work(semaphore: Arc<Semaphore>) {
+    let _permit = semaphore.acquire().await.unwrap();
+}
+
+fn main() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    runtime.block_on(async {
+        let semaphore = Arc::new(Semaphore::new(2));
+        let _permit = semaphore.acquire().await.unwrap();
+        let handle = tokio::spawn(work(Arc::clone(&semaphore)));
+        handle.await.unwrap();
+    });
+}
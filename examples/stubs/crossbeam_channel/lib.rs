@@ -0,0 +1,156 @@
+//! A minimal stand-in for the `crossbeam_channel` crate with the same paths as the real one.
+//!
+//! The example programs include it as a module, so that they can be analyzed
+//! without downloading the real crate. The programs are never executed,
+//! therefore only the signatures matter and the bodies are left unimplemented.
+//!
+//! The `select!` macro expands to the `Select` API, unlike the real one, which uses internal functions of the crate.
+//! It supports up to three `recv` or `send` operations and no `default` case.
+
+#![allow(dead_code)]
+
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+pub struct Sender<T> {
+    messages: Arc<Vec<T>>,
+}
+
+unsafe impl<T: Send> Send for Sender<T> {}
+unsafe impl<T: Send> Sync for Sender<T> {}
+
+impl<T> Sender<T> {
+    /// Blocks until the message is sent or the channel is disconnected.
+    pub fn send(&self, msg: T) -> Result<(), SendError<T>> {
+        unimplemented!()
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        unimplemented!()
+    }
+}
+
+pub struct Receiver<T> {
+    messages: Arc<Vec<T>>,
+}
+
+unsafe impl<T: Send> Send for Receiver<T> {}
+unsafe impl<T: Send> Sync for Receiver<T> {}
+
+impl<T> Receiver<T> {
+    /// Blocks until a message is received or the channel is empty and disconnected.
+    pub fn recv(&self) -> Result<T, RecvError> {
+        unimplemented!()
+    }
+}
+
+#[derive(Debug)]
+pub struct SendError<T>(pub T);
+
+#[derive(Debug)]
+pub struct RecvError;
+
+/// Creates a channel that holds at most `cap` messages.
+/// A channel with a capacity of zero only sends a message when a receiver takes it.
+pub fn bounded<T>(cap: usize) -> (Sender<T>, Receiver<T>) {
+    unimplemented!()
+}
+
+/// Creates a channel that holds any number of messages.
+pub fn unbounded<T>() -> (Sender<T>, Receiver<T>) {
+    unimplemented!()
+}
+
+pub struct Select<'a> {
+    operations: Vec<usize>,
+    lifetime: PhantomData<&'a ()>,
+}
+
+impl<'a> Select<'a> {
+    pub fn new() -> Self {
+        unimplemented!()
+    }
+
+    /// Adds a send operation and returns its index.
+    pub fn send<T>(&mut self, s: &'a Sender<T>) -> usize {
+        unimplemented!()
+    }
+
+    /// Adds a receive operation and returns its index.
+    pub fn recv<T>(&mut self, r: &'a Receiver<T>) -> usize {
+        unimplemented!()
+    }
+
+    /// Blocks until one of the operations is ready and selects it.
+    pub fn select(&mut self) -> SelectedOperation<'a> {
+        unimplemented!()
+    }
+}
+
+/// An operation selected by `Select::select`, which must be completed with `send` or `recv`.
+pub struct SelectedOperation<'a> {
+    index: usize,
+    lifetime: PhantomData<&'a ()>,
+}
+
+impl<'a> SelectedOperation<'a> {
+    /// Returns the index of the selected operation.
+    pub fn index(&self) -> usize {
+        unimplemented!()
+    }
+
+    pub fn send<T>(self, s: &Sender<T>, msg: T) -> Result<(), SendError<T>> {
+        unimplemented!()
+    }
+
+    pub fn recv<T>(self, r: &Receiver<T>) -> Result<T, RecvError> {
+        unimplemented!()
+    }
+}
+
+#[allow(unused_macros)]
+macro_rules! select {
+    (@register $sel:ident, recv($r:expr)) => {
+        $sel.recv(&$r)
+    };
+    (@register $sel:ident, send($s:expr, $msg:expr)) => {
+        $sel.send(&$s)
+    };
+    (@complete $oper:ident, recv($r:expr) -> $res:pat => $body:expr) => {{
+        let $res = $oper.recv(&$r);
+        $body
+    }};
+    (@complete $oper:ident, send($s:expr, $msg:expr) -> $res:pat => $body:expr) => {{
+        let $res = $oper.send(&$s, $msg);
+        $body
+    }};
+    (@select $($index:literal: $kind:ident $args:tt -> $res:pat => $body:expr),+) => {{
+        let mut sel = $crate::crossbeam_channel::Select::new();
+        $( $crate::crossbeam_channel::select!(@register sel, $kind $args); )+
+        let oper = sel.select();
+        match oper.index() {
+            $( $index => $crate::crossbeam_channel::select!(@complete oper, $kind $args -> $res => $body), )+
+            _ => unreachable!(),
+        }
+    }};
+    ($k0:ident $a0:tt -> $r0:pat => $b0:expr $(,)?) => {
+        $crate::crossbeam_channel::select!(@select 0: $k0 $a0 -> $r0 => $b0)
+    };
+    ($k0:ident $a0:tt -> $r0:pat => $b0:expr, $k1:ident $a1:tt -> $r1:pat => $b1:expr $(,)?) => {
+        $crate::crossbeam_channel::select!(@select 0: $k0 $a0 -> $r0 => $b0, 1: $k1 $a1 -> $r1 => $b1)
+    };
+    (
+        $k0:ident $a0:tt -> $r0:pat => $b0:expr,
+        $k1:ident $a1:tt -> $r1:pat => $b1:expr,
+        $k2:ident $a2:tt -> $r2:pat => $b2:expr $(,)?
+    ) => {
+        $crate::crossbeam_channel::select!(
+            @select 0: $k0 $a0 -> $r0 => $b0, 1: $k1 $a1 -> $r1 => $b1, 2: $k2 $a2 -> $r2 => $b2
+        )
+    };
+}
+
+#[allow(unused_imports)]
+pub(crate) use select;
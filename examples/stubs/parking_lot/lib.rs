@@ -0,0 +1,139 @@
+//! A minimal stand-in for the `parking_lot` crate with the same paths as the real one.
+//!
+//! The example programs include it as a module, so that they can be analyzed
+//! without downloading the real crate. The programs are never executed,
+//! therefore only the signatures matter and the bodies are left unimplemented.
+
+#![allow(dead_code)]
+
+use std::cell::UnsafeCell;
+use std::sync::atomic::AtomicUsize;
+use std::ops::{Deref, DerefMut};
+
+pub struct Mutex<T> {
+    value: UnsafeCell<T>,
+}
+
+unsafe impl<T: Send> Send for Mutex<T> {}
+unsafe impl<T: Send> Sync for Mutex<T> {}
+
+impl<T> Mutex<T> {
+    pub const fn new(value: T) -> Self {
+        Self {
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    /// Acquires the mutex. There is no poisoning, the guard is returned directly.
+    pub fn lock(&self) -> MutexGuard<'_, T> {
+        unimplemented!()
+    }
+}
+
+pub struct MutexGuard<'a, T> {
+    mutex: &'a Mutex<T>,
+}
+
+impl<T> Deref for MutexGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unimplemented!()
+    }
+}
+
+impl<T> DerefMut for MutexGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unimplemented!()
+    }
+}
+
+impl<T> Drop for MutexGuard<'_, T> {
+    fn drop(&mut self) {}
+}
+
+pub struct RwLock<T> {
+    value: UnsafeCell<T>,
+}
+
+unsafe impl<T: Send> Send for RwLock<T> {}
+unsafe impl<T: Send + Sync> Sync for RwLock<T> {}
+
+impl<T> RwLock<T> {
+    pub const fn new(value: T) -> Self {
+        Self {
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    pub fn read(&self) -> RwLockReadGuard<'_, T> {
+        unimplemented!()
+    }
+
+    pub fn write(&self) -> RwLockWriteGuard<'_, T> {
+        unimplemented!()
+    }
+}
+
+pub struct RwLockReadGuard<'a, T> {
+    lock: &'a RwLock<T>,
+}
+
+impl<T> Deref for RwLockReadGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unimplemented!()
+    }
+}
+
+impl<T> Drop for RwLockReadGuard<'_, T> {
+    fn drop(&mut self) {}
+}
+
+pub struct RwLockWriteGuard<'a, T> {
+    lock: &'a RwLock<T>,
+}
+
+impl<T> Deref for RwLockWriteGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unimplemented!()
+    }
+}
+
+impl<T> DerefMut for RwLockWriteGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unimplemented!()
+    }
+}
+
+impl<T> Drop for RwLockWriteGuard<'_, T> {
+    fn drop(&mut self) {}
+}
+
+pub struct Condvar {
+    waiters: AtomicUsize,
+}
+
+impl Condvar {
+    pub const fn new() -> Self {
+        Self {
+            waiters: AtomicUsize::new(0),
+        }
+    }
+
+    /// Blocks until notified. The guard is unlocked while waiting and locked again afterwards.
+    pub fn wait<T>(&self, _mutex_guard: &mut MutexGuard<'_, T>) {
+        unimplemented!()
+    }
+
+    pub fn notify_one(&self) -> bool {
+        unimplemented!()
+    }
+
+    pub fn notify_all(&self) -> usize {
+        unimplemented!()
+    }
+}
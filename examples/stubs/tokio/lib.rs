@@ -0,0 +1,252 @@
+//! A minimal stand-in for the `tokio` crate with the same paths as the real one.
+//!
+//! The example programs include it as a module, so that they can be analyzed
+//! without downloading the real crate. The programs are never executed,
+//! therefore only the signatures matter and the bodies are left unimplemented.
+
+#![allow(dead_code)]
+
+use std::future::Future;
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Spawns a new asynchronous task.
+pub fn spawn<F>(future: F) -> task::JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    let _ = future;
+    unimplemented!()
+}
+
+pub mod runtime {
+    use std::future::Future;
+
+    pub struct Runtime;
+
+    impl Runtime {
+        pub fn new() -> std::io::Result<Self> {
+            Ok(Self)
+        }
+
+        /// Runs a future to completion on the runtime.
+        pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+            let _ = future;
+            unimplemented!()
+        }
+    }
+}
+
+pub mod task {
+    use super::{Context, Future, PhantomData, Pin, Poll};
+
+    pub struct JoinHandle<T>(PhantomData<T>);
+
+    #[derive(Debug)]
+    pub struct JoinError;
+
+    impl<T> Future for JoinHandle<T> {
+        type Output = Result<T, JoinError>;
+
+        fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Self::Output> {
+            unimplemented!()
+        }
+    }
+}
+
+pub mod sync {
+    use super::{Deref, DerefMut};
+
+    pub struct Mutex<T> {
+        value: T,
+    }
+
+    impl<T> Mutex<T> {
+        pub fn new(value: T) -> Self {
+            Self { value }
+        }
+
+        pub async fn lock(&self) -> MutexGuard<'_, T> {
+            MutexGuard { mutex: self }
+        }
+    }
+
+    pub struct MutexGuard<'a, T> {
+        mutex: &'a Mutex<T>,
+    }
+
+    impl<T> Deref for MutexGuard<'_, T> {
+        type Target = T;
+
+        fn deref(&self) -> &T {
+            &self.mutex.value
+        }
+    }
+
+    impl<T> DerefMut for MutexGuard<'_, T> {
+        fn deref_mut(&mut self) -> &mut T {
+            unimplemented!()
+        }
+    }
+
+    impl<T> Drop for MutexGuard<'_, T> {
+        fn drop(&mut self) {}
+    }
+
+    pub struct RwLock<T> {
+        value: T,
+    }
+
+    impl<T> RwLock<T> {
+        pub fn new(value: T) -> Self {
+            Self { value }
+        }
+
+        pub async fn read(&self) -> RwLockReadGuard<'_, T> {
+            RwLockReadGuard { lock: self }
+        }
+
+        pub async fn write(&self) -> RwLockWriteGuard<'_, T> {
+            RwLockWriteGuard { lock: self }
+        }
+    }
+
+    pub struct RwLockReadGuard<'a, T> {
+        lock: &'a RwLock<T>,
+    }
+
+    impl<T> Deref for RwLockReadGuard<'_, T> {
+        type Target = T;
+
+        fn deref(&self) -> &T {
+            &self.lock.value
+        }
+    }
+
+    impl<T> Drop for RwLockReadGuard<'_, T> {
+        fn drop(&mut self) {}
+    }
+
+    pub struct RwLockWriteGuard<'a, T> {
+        lock: &'a RwLock<T>,
+    }
+
+    impl<T> Deref for RwLockWriteGuard<'_, T> {
+        type Target = T;
+
+        fn deref(&self) -> &T {
+            &self.lock.value
+        }
+    }
+
+    impl<T> DerefMut for RwLockWriteGuard<'_, T> {
+        fn deref_mut(&mut self) -> &mut T {
+            unimplemented!()
+        }
+    }
+
+    impl<T> Drop for RwLockWriteGuard<'_, T> {
+        fn drop(&mut self) {}
+    }
+
+    pub struct Notify;
+
+    impl Notify {
+        pub fn new() -> Self {
+            Self
+        }
+
+        pub fn notify_one(&self) {}
+
+        pub fn notified(&self) -> futures::Notified<'_> {
+            futures::Notified(self)
+        }
+    }
+
+    pub mod futures {
+        use super::super::{Context, Future, Pin, Poll};
+        use super::Notify;
+
+        pub struct Notified<'a>(pub(super) &'a Notify);
+
+        impl Future for Notified<'_> {
+            type Output = ();
+
+            fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+                unimplemented!()
+            }
+        }
+    }
+
+    pub struct Semaphore {
+        permits: usize,
+    }
+
+    #[derive(Debug)]
+    pub struct AcquireError;
+
+    impl Semaphore {
+        pub const fn new(permits: usize) -> Self {
+            Self { permits }
+        }
+
+        pub async fn acquire(&self) -> Result<SemaphorePermit<'_>, AcquireError> {
+            Ok(SemaphorePermit { semaphore: self })
+        }
+    }
+
+    pub struct SemaphorePermit<'a> {
+        semaphore: &'a Semaphore,
+    }
+
+    impl Drop for SemaphorePermit<'_> {
+        fn drop(&mut self) {}
+    }
+
+    pub mod mpsc {
+        use super::super::PhantomData;
+
+        pub fn channel<T>(buffer: usize) -> (Sender<T>, Receiver<T>) {
+            let _ = buffer;
+            unimplemented!()
+        }
+
+        pub struct Sender<T>(PhantomData<T>);
+
+        impl<T> Sender<T> {
+            pub async fn send(&self, value: T) -> Result<(), error::SendError<T>> {
+                Err(error::SendError(value))
+            }
+        }
+
+        impl<T> Clone for Sender<T> {
+            fn clone(&self) -> Self {
+                Self(PhantomData)
+            }
+        }
+
+        impl<T> Drop for Sender<T> {
+            fn drop(&mut self) {}
+        }
+
+        pub struct Receiver<T>(PhantomData<T>);
+
+        impl<T> Receiver<T> {
+            pub async fn recv(&mut self) -> Option<T> {
+                None
+            }
+        }
+
+        impl<T> Drop for Receiver<T> {
+            fn drop(&mut self) {}
+        }
+
+        pub mod error {
+            #[derive(Debug)]
+            pub struct SendError<T>(pub T);
+        }
+    }
+}
@@ -0,0 +1,629 @@
+//! Submodule for the public library API.
+//!
+//! The `Analyzer` is a builder that collects the options for the translation
+//! and the deadlock analysis. It can translate the source code to a Petri net
+//! or additionally run the model checker on the net and return an `AnalysisReport`.
+//! Optionally, the model checker is skipped if the lock graph already proves that no deadlock can be reached.
+//! The model checker can also check whether the main thread or the threads can panic.
+//! The verdicts can be compared with those of other model checkers on the same net.
+//!
+//! Example:
+//!
+//! ```no_run
+//! # #![feature(rustc_private)]
+//! use cargo_check_deadlock::Analyzer;
+//!
+//! let report = Analyzer::from_file("src/main.rs")
+//!     .analyze()
+//!     .expect("The translation should succeed");
+//! assert!(report.is_deadlock_free());
+//! ```
+
+use log::info;
+
+use crate::compiler_config;
+use crate::lock_graph::{LockCycle, LockEdge, LockGraph};
+use crate::model_checker::{
+    lola, write_temporary_net, Checker, Comparison, InconclusiveQuery, Limits, LostSignal,
+    ModelChecker, Property, PropertyResult, ReachablePanic, Statistics, Verdict,
+};
+use crate::models::Models;
+use crate::sysroot;
+use crate::translator::{
+    self, Approximation, LockOrder, NotifyCall, PanicSite, Primitive, PrimitiveKind,
+    UnsupportedPolicy,
+};
+use crate::utils::find_local_function_by_path;
+use crate::PetriNet;
+
+/// The default virtual file name used in diagnostics when the source code is passed as a string.
+const DEFAULT_SOURCE_CODE_FILENAME: &str = "main.rs";
+
+/// The Rust source code to be analyzed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Source {
+    /// A path to a file containing the source code.
+    File(std::path::PathBuf),
+    /// The source code itself.
+    Code {
+        /// The virtual file name shown in diagnostics and source locations.
+        name: String,
+        /// The source code.
+        code: String,
+    },
+    /// The source code read from the standard input.
+    /// Source locations refer to the virtual file `<stdin>`.
+    Stdin(String),
+}
+
+/// The result of a successful translation.
+pub struct Translation {
+    /// The Petri net modelling the program.
+    pub net: PetriNet,
+    /// The constructs that were approximated in the Petri net.
+    /// Besides the imprecise models of supported constructs,
+    /// it is empty when the policy is `UnsupportedPolicy::Abort`.
+    pub approximations: Vec<Approximation>,
+    /// The synchronization primitives modelled in the Petri net.
+    pub primitives: Vec<Primitive>,
+    /// The lock orders declared with `#[check_deadlock::lock_order]`.
+    pub lock_orders: Vec<LockOrder>,
+    /// The lock graph obtained from the Petri net.
+    pub lock_graph: LockGraph,
+    /// The calls to `std::sync::Condvar::notify_one` modelled in the Petri net.
+    pub notify_calls: Vec<NotifyCall>,
+    /// The places in the code where a thread may start to panic.
+    pub panic_sites: Vec<PanicSite>,
+}
+
+/// The result of a successful translation followed by the deadlock analysis.
+pub struct AnalysisReport {
+    /// The Petri net modelling the program.
+    pub net: PetriNet,
+    /// The verdict of the model checker.
+    pub verdict: Verdict,
+    /// The sequence of transition labels that leads to the deadlock.
+    /// It is empty if no deadlock was found.
+    pub witness_path: Vec<String>,
+    /// The statistics of the deadlock analysis reported by `LoLA`.
+    /// `None` if the model checker was skipped, did not give an answer or is not `LoLA`.
+    pub statistics: Option<Statistics>,
+    /// The synchronization primitives modelled in the Petri net.
+    pub primitives: Vec<Primitive>,
+    /// The constructs that were approximated in the Petri net.
+    pub approximations: Vec<Approximation>,
+    /// The lock orders declared with `#[check_deadlock::lock_order]`.
+    pub lock_orders: Vec<LockOrder>,
+    /// The cycles in the lock graph, i.e. potential lock-order inversions.
+    pub lock_cycles: Vec<LockCycle>,
+    /// The acquisitions that contradict the declared lock orders.
+    pub lock_order_violations: Vec<LockEdge>,
+    /// Whether the model checker was skipped because the lock graph proved that no deadlock can be reached.
+    pub skipped_model_checker: bool,
+    /// The notifications on condition variables that may be lost, reported separately from the verdict.
+    pub lost_signals: Vec<LostSignal>,
+    /// The results of the additional properties checked by the model checker.
+    pub property_results: Vec<PropertyResult>,
+    /// The panics that can be reached in the main thread or in the threads.
+    /// It is empty if the panic analysis is disabled.
+    pub panics: Vec<ReachablePanic>,
+    /// The queries of the analysis of lost signals and of panics for which the model checker gave no answer.
+    /// Lost signals and panics may be missing from the report if it is not empty.
+    pub inconclusive_queries: Vec<InconclusiveQuery>,
+    /// The verdicts of the model checker and of the compared model checkers
+    /// on the deadlock property and on every additional property.
+    /// It is empty if no model checkers are compared.
+    pub comparisons: Vec<Comparison>,
+}
+
+impl AnalysisReport {
+    /// Returns `true` if the model checker proved that no deadlock can be reached.
+    #[must_use]
+    pub const fn is_deadlock_free(&self) -> bool {
+        matches!(self.verdict, Verdict::DeadlockFree)
+    }
+
+    /// Returns `true` if the model checker found a deadlock.
+    #[must_use]
+    pub const fn is_deadlock(&self) -> bool {
+        matches!(self.verdict, Verdict::Deadlock)
+    }
+}
+
+/// Builder for the translation and the deadlock analysis.
+#[derive(Clone, Debug)]
+pub struct Analyzer {
+    /// The Rust source code to be analyzed.
+    source: Source,
+    /// The path of the function where the translation starts. `None` means the `main` function.
+    entry_point: Option<String>,
+    /// Additional arguments passed to `rustc`.
+    rustc_args: Vec<String>,
+    /// The model checker used for the deadlock analysis.
+    checker: Checker,
+    /// The model checkers whose verdicts are compared with those of `checker`.
+    compared_checkers: Vec<Checker>,
+    /// Resource limits for the model checker.
+    limits: Limits,
+    /// The path to the `lola` executable. `None` means that it is searched as usual.
+    lola_path: Option<std::path::PathBuf>,
+    /// The policy for constructs that cannot be modelled.
+    on_unsupported: UnsupportedPolicy,
+    /// The user-supplied models of functions.
+    models: Models,
+    /// Whether the model checker searches for deadlocks.
+    deadlock_analysis: bool,
+    /// Whether to skip the model checker if the lock graph proves that no deadlock can be reached.
+    precheck: bool,
+    /// The additional properties checked by the model checker after the deadlock analysis.
+    properties: Vec<Property>,
+    /// Whether to check if the main thread or the threads can panic.
+    panics: bool,
+    /// Whether to model the boolean or small integer values stored in mutexes.
+    mutex_values: bool,
+    /// Whether loops over a range with constant bounds count their iterations.
+    unroll_const_loops: bool,
+}
+
+impl Analyzer {
+    /// Creates a new `Analyzer` for the given source with the default options.
+    #[must_use]
+    pub fn new(source: Source) -> Self {
+        Self {
+            source,
+            entry_point: None,
+            rustc_args: Vec::new(),
+            checker: Checker::default(),
+            compared_checkers: Vec::new(),
+            limits: Limits::default(),
+            lola_path: None,
+            on_unsupported: UnsupportedPolicy::default(),
+            models: Models::default(),
+            deadlock_analysis: true,
+            precheck: false,
+            properties: Vec::new(),
+            panics: false,
+            mutex_values: false,
+            unroll_const_loops: false,
+        }
+    }
+
+    /// Creates a new `Analyzer` for the source code file at `path`.
+    #[must_use]
+    pub fn from_file(path: impl Into<std::path::PathBuf>) -> Self {
+        Self::new(Source::File(path.into()))
+    }
+
+    /// Creates a new `Analyzer` for the source code passed as a string.
+    /// Source locations refer to the virtual file `main.rs`.
+    #[must_use]
+    pub fn from_code(code: impl Into<String>) -> Self {
+        Self::from_named_code(DEFAULT_SOURCE_CODE_FILENAME, code)
+    }
+
+    /// Creates a new `Analyzer` for the source code passed as a string.
+    /// Source locations refer to the virtual file `name`, e.g. `lib.rs`.
+    #[must_use]
+    pub fn from_named_code(name: impl Into<String>, code: impl Into<String>) -> Self {
+        Self::new(Source::Code {
+            name: name.into(),
+            code: code.into(),
+        })
+    }
+
+    /// Creates a new `Analyzer` for the source code read from the standard input.
+    /// Source locations refer to the virtual file `<stdin>`.
+    #[must_use]
+    pub fn from_stdin(code: impl Into<String>) -> Self {
+        Self::new(Source::Stdin(code.into()))
+    }
+
+    /// Sets the path of the function where the translation starts, e.g. `module::function`.
+    /// By default the translation starts at the `main` function.
+    #[must_use]
+    pub fn entry_point(mut self, entry_point: impl Into<String>) -> Self {
+        self.entry_point = Some(entry_point.into());
+        self
+    }
+
+    /// Sets additional arguments passed to `rustc`, e.g. `["--edition=2021"]`.
+    /// If no edition is given and the source is a file, the edition is read from the closest `Cargo.toml`.
+    #[must_use]
+    pub fn rustc_args<I, S>(mut self, rustc_args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.rustc_args = rustc_args.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the model checker used for the deadlock analysis.
+    /// Only `LoLA` finds lost signals and reachable panics, since the other model checkers do not produce witness paths.
+    #[must_use]
+    pub const fn checker(mut self, checker: Checker) -> Self {
+        self.checker = checker;
+        self
+    }
+
+    /// Sets the model checkers whose verdicts are compared with those of the model checker
+    /// on the deadlock property and on every additional property, see `AnalysisReport::comparisons`.
+    /// By default no model checkers are compared.
+    #[must_use]
+    pub fn compare_with(mut self, checkers: impl IntoIterator<Item = Checker>) -> Self {
+        self.compared_checkers = checkers.into_iter().collect();
+        self
+    }
+
+    /// Sets the resource limits for the model checker.
+    #[must_use]
+    pub const fn limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Sets the path to the `lola` executable.
+    /// By default, the path in the environment variable `LOLA_PATH` is used if set,
+    /// otherwise `lola` is searched in the `$PATH` and then in the `./assets/` folder.
+    #[must_use]
+    pub fn lola_path(mut self, lola_path: impl Into<std::path::PathBuf>) -> Self {
+        self.lola_path = Some(lola_path.into());
+        self
+    }
+
+    /// Sets the policy for constructs that cannot be modelled.
+    #[must_use]
+    pub const fn on_unsupported(mut self, on_unsupported: UnsupportedPolicy) -> Self {
+        self.on_unsupported = on_unsupported;
+        self
+    }
+
+    /// Sets the user-supplied models of functions.
+    /// Calls to these functions are translated according to their model.
+    #[must_use]
+    pub fn models(mut self, models: Models) -> Self {
+        self.models = models;
+        self
+    }
+
+    /// Sets whether the model checker searches for deadlocks. Enabled by default.
+    /// If disabled, only the additional properties and the panics are checked,
+    /// no lost signals are reported and the verdict is inconclusive.
+    #[must_use]
+    pub const fn deadlock_analysis(mut self, deadlock_analysis: bool) -> Self {
+        self.deadlock_analysis = deadlock_analysis;
+        self
+    }
+
+    /// Sets whether the lock graph is used as a fast pre-check.
+    /// If enabled and the lock graph proves that no deadlock can be reached,
+    /// the model checker is not run and the program is reported as deadlock-free.
+    #[must_use]
+    pub const fn precheck(mut self, precheck: bool) -> Self {
+        self.precheck = precheck;
+        self
+    }
+
+    /// Sets the additional properties checked by the model checker after the deadlock analysis,
+    /// e.g. `Property::Panic` or a raw formula parsed with `str::parse`.
+    /// The properties are checked even if the deadlock analysis is skipped by the pre-check.
+    #[must_use]
+    pub fn properties(mut self, properties: impl IntoIterator<Item = Property>) -> Self {
+        self.properties = properties.into_iter().collect();
+        self
+    }
+
+    /// Sets whether the model checker checks if the main thread or the threads can panic.
+    /// If enabled, every thread gets its own panic place `THREAD_N_PANIC` in the Petri net
+    /// instead of ending in its end place directly, so that a panicking thread is visible.
+    /// `JoinHandle::join` on a panicked thread returns `Err` and a mutex guard dropped
+    /// during a panic poisons the mutex, so `unwrap` on these results may panic in turn.
+    #[must_use]
+    pub const fn panics(mut self, panics: bool) -> Self {
+        self.panics = panics;
+        self
+    }
+
+    /// Sets whether the values stored in `Mutex<bool>` and mutexes of unsigned integers are modelled.
+    /// If enabled, every value of such a mutex is a place `MUTEX_N_VALUE_V` in the Petri net.
+    /// Booleans have two values, unsigned integers are bounded to `0..4`.
+    /// Values outside these bounds are listed in `AnalysisReport::approximations`.
+    /// Reads through the guard, assignments of constants, `+= 1` and `-= 1` update the places,
+    /// and the condition of `Condvar::wait_while` is evaluated against them.
+    #[must_use]
+    pub const fn mutex_values(mut self, mutex_values: bool) -> Self {
+        self.mutex_values = mutex_values;
+        self
+    }
+
+    /// Sets whether a loop over a range with constant bounds, e.g. `for _ in 0..3`, runs exactly as often as the range has values.
+    /// If enabled, the loop counts its iterations in the places `RANGE_N_ITERATION_I` of the Petri net.
+    /// Otherwise any number of iterations is possible.
+    /// Ranges with more than 16 values are not counted and listed in `AnalysisReport::approximations`.
+    #[must_use]
+    pub const fn unroll_const_loops(mut self, unroll_const_loops: bool) -> Self {
+        self.unroll_const_loops = unroll_const_loops;
+        self
+    }
+
+    /// Translates the source code to a Petri net without running the model checker.
+    ///
+    /// # Errors
+    ///
+    /// If the `sysroot` cannot be found, then an error is returned.
+    /// If the rustc arguments are not valid, then an error is returned.
+    /// If the entry point cannot be found, then an error is returned.
+    /// If the code contains a construct that cannot be modelled and the policy is `UnsupportedPolicy::Abort`,
+    /// then an error with the description of the construct is returned.
+    ///
+    /// # Panics
+    ///
+    /// If the global typing context `rustc_middle::ty::TyCtxt` cannot be found, then the function panics.
+    /// If the translation failed due to a bug, then the function panics.
+    pub fn translate(&self) -> Result<Translation, String> {
+        let sysroot = sysroot::get_from_rustc()?;
+        let input = match &self.source {
+            Source::File(path) => rustc_session::config::Input::File(path.clone()),
+            Source::Code { name, code } => rustc_session::config::Input::Str {
+                name: rustc_span::FileName::Real(rustc_span::RealFileName::LocalPath(name.into())),
+                input: code.clone(),
+            },
+            Source::Stdin(code) => rustc_session::config::Input::Str {
+                name: rustc_span::FileName::Custom("stdin".to_string()),
+                input: code.clone(),
+            },
+        };
+        let mut rustc_args = self.rustc_args.clone();
+        if let Source::File(path) = &self.source {
+            if !compiler_config::contains_edition(&rustc_args) {
+                if let Some(edition) = compiler_config::find_edition_in_manifest(path) {
+                    rustc_args.push(format!("--edition={edition}"));
+                }
+            }
+        }
+        let config = compiler_config::prepare_rustc_config(sysroot, input, &rustc_args)?;
+        let mut translation_result: Result<Translation, String> =
+            Err("Translation did not run".to_string());
+
+        rustc_interface::run_compiler(config, |compiler| {
+            compiler.enter(|queries| {
+                // Get the global typing context (tcx: TyCtxt), which is the central data structure in the compiler.
+                // <https://doc.rust-lang.org/stable/nightly-rustc/rustc_middle/ty/struct.TyCtxt.html>
+                let mut query = queries.global_ctxt().expect(
+                    "BUG: Unable to get the global typing context needed for the `Translator`",
+                );
+
+                // Run the translator as a query to the compiler.
+                // <https://rustc-dev-guide.rust-lang.org/rustc-driver.html>
+                query.enter(|tcx| {
+                    let entry_function_id = match &self.entry_point {
+                        Some(path) => find_local_function_by_path(path, tcx),
+                        None => tcx.entry_fn(()).map(|(def_id, _)| def_id),
+                    };
+                    let Some(entry_function_id) = entry_function_id else {
+                        translation_result = Err(if self.entry_point.is_some() {
+                            "The entry point was not found in the source code".to_string()
+                        } else {
+                            "No main function found in the source code".to_string()
+                        });
+                        return;
+                    };
+                    let mut translator = translator::Translator::new(
+                        tcx,
+                        self.on_unsupported,
+                        self.models.clone(),
+                        self.panics,
+                        self.mutex_values,
+                        self.unroll_const_loops,
+                    );
+                    if let Err(err_str) = translator.run(entry_function_id) {
+                        translation_result = Err(err_str);
+                        return;
+                    }
+                    translation_result = Ok(Translation {
+                        net: translator.get_result(),
+                        approximations: translator.get_approximations(),
+                        primitives: translator.get_primitives(),
+                        lock_orders: translator.get_lock_orders(),
+                        lock_graph: translator.get_lock_graph(),
+                        notify_calls: translator.get_notify_calls(),
+                        panic_sites: translator.get_panic_sites(),
+                    });
+                });
+            });
+        });
+
+        translation_result
+    }
+
+    /// Translates the source code to a Petri net and runs the model checker to find deadlocks.
+    ///
+    /// # Errors
+    ///
+    /// If the translation fails, then an error with the corresponding description is returned.
+    /// If the net cannot be written to a temporary file for the model checker, then an error is returned.
+    ///
+    /// # Panics
+    ///
+    /// If the translation failed due to a bug, then the function panics.
+    pub fn analyze(&self) -> Result<AnalysisReport, String> {
+        let translation = self.translate()?;
+        self.analyze_translation(translation)
+    }
+
+    /// Runs the model checker on the result of `Analyzer::translate` to find deadlocks.
+    /// This allows to export the Petri net before the analysis without translating the source code twice.
+    ///
+    /// # Errors
+    ///
+    /// If the net cannot be written to a temporary file for the model checker, then an error is returned.
+    pub fn analyze_translation(&self, translation: Translation) -> Result<AnalysisReport, String> {
+        let lock_cycles = translation.lock_graph.cycles();
+        let lock_order_violations = translation.lock_graph.violations(&translation.lock_orders);
+
+        let skipped_model_checker =
+            self.deadlock_analysis && self.precheck && translation.lock_graph.rules_out_deadlocks();
+        let mut analysis = lola::DeadlockAnalysis {
+            verdict: Verdict::DeadlockFree,
+            witness_path: Vec::new(),
+            statistics: None,
+        };
+        if !self.deadlock_analysis {
+            analysis.verdict =
+                Verdict::Inconclusive("The deadlock analysis is disabled".to_string());
+        }
+        let check_deadlock = self.deadlock_analysis && !skipped_model_checker;
+        let mut lost_signals = Vec::new();
+        let mut property_results = Vec::new();
+        let mut panics = Vec::new();
+        let mut inconclusive_queries = Vec::new();
+        if skipped_model_checker {
+            info!(
+                "The lock graph proves that no deadlock can be reached, skipping the deadlock analysis"
+            );
+        }
+
+        if check_deadlock || !self.properties.is_empty() || self.panics {
+            match self.checker {
+                Checker::Lola => {
+                    let net_filepath =
+                        write_temporary_net(&translation.net, "lola", PetriNet::to_lola).map_err(
+                            |_| "Could not write the net to a temporary file for the model checker",
+                        )?;
+                    info!(
+                        "Wrote the net for the model checker to {}",
+                        net_filepath.to_string_lossy()
+                    );
+                    let lola = self.lola();
+                    if check_deadlock {
+                        analysis = lola.analyze_deadlock(&net_filepath);
+                        let lost_signal_analysis = lola.analyze_lost_signals(
+                            &net_filepath,
+                            &translation.notify_calls,
+                            analysis.verdict == Verdict::Deadlock,
+                        );
+                        lost_signals = lost_signal_analysis.lost_signals;
+                        inconclusive_queries.extend(lost_signal_analysis.inconclusive);
+                    }
+                    property_results = self
+                        .properties
+                        .iter()
+                        .map(|property| {
+                            lola.check_property(&net_filepath, &translation.net, property)
+                        })
+                        .collect();
+                    if self.panics {
+                        let threads: Vec<usize> = translation
+                            .primitives
+                            .iter()
+                            .filter(|primitive| primitive.kind == PrimitiveKind::Thread)
+                            .map(|primitive| primitive.index)
+                            .collect();
+                        let panic_analysis =
+                            lola.analyze_panics(&net_filepath, &translation.panic_sites, &threads);
+                        panics = panic_analysis.panics;
+                        inconclusive_queries.extend(panic_analysis.inconclusive);
+                    }
+                    // The temporary file is not needed anymore. Failing to delete it is not an error.
+                    let _ = std::fs::remove_file(&net_filepath);
+                }
+                checker => {
+                    // Other model checkers do not produce witness paths,
+                    // so lost signals and the location of panics cannot be found.
+                    let backend = self.backend(checker);
+                    if check_deadlock {
+                        analysis.verdict =
+                            backend.check(&translation.net, &Property::Deadlock).into();
+                    }
+                    property_results = self
+                        .properties
+                        .iter()
+                        .map(|property| PropertyResult {
+                            property: property.clone(),
+                            verdict: backend.check(&translation.net, property),
+                            witness_path: Vec::new(),
+                            statistics: None,
+                        })
+                        .collect();
+                    if self.panics {
+                        info!("The panic analysis requires the model checker `LoLA`, skipping it");
+                    }
+                }
+            }
+        }
+
+        let mut comparisons = Vec::new();
+        if !self.compared_checkers.is_empty() {
+            let name = self.backend(self.checker).name();
+            let backends: Vec<Box<dyn ModelChecker>> = self
+                .compared_checkers
+                .iter()
+                .map(|checker| self.backend(*checker))
+                .collect();
+            let mut verdicts = Vec::new();
+            if check_deadlock {
+                verdicts.push((Property::Deadlock, analysis.verdict.clone().into()));
+            }
+            for result in &property_results {
+                verdicts.push((result.property.clone(), result.verdict.clone()));
+            }
+            for (property, verdict) in verdicts {
+                let mut comparison = Comparison::run(&translation.net, &property, &backends);
+                comparison.verdicts.insert(0, (name, verdict));
+                comparisons.push(comparison);
+            }
+        }
+
+        Ok(AnalysisReport {
+            net: translation.net,
+            verdict: analysis.verdict,
+            witness_path: analysis.witness_path,
+            statistics: analysis.statistics,
+            primitives: translation.primitives,
+            approximations: translation.approximations,
+            lock_orders: translation.lock_orders,
+            lock_cycles,
+            lock_order_violations,
+            skipped_model_checker,
+            lost_signals,
+            property_results,
+            panics,
+            inconclusive_queries,
+            comparisons,
+        })
+    }
+
+    /// Returns the model checker that implements the trait `ModelChecker` for the given checker,
+    /// configured with the limits and, for `LoLA`, the path to the executable.
+    fn backend(&self, checker: Checker) -> Box<dyn ModelChecker> {
+        match checker {
+            Checker::Lola => Box::new(self.lola()),
+            checker => checker.backend(self.limits),
+        }
+    }
+
+    /// Returns the model checker `LoLA` configured with the limits and the path to the executable.
+    fn lola(&self) -> lola::Lola {
+        let lola = lola::Lola::new(self.limits);
+        match &self.lola_path {
+            Some(lola_path) => lola.executable(lola_path),
+            None => lola,
+        }
+    }
+}
+
+/// Translates the source code file at `source_code_filepath` and runs the deadlock analysis
+/// with the default options.
+///
+/// # Errors
+///
+/// If the translation fails, then an error with the corresponding description is returned.
+///
+/// # Panics
+///
+/// If the translation failed due to a bug, then the function panics.
+pub fn analyze(
+    source_code_filepath: impl Into<std::path::PathBuf>,
+) -> Result<AnalysisReport, String> {
+    Analyzer::from_file(source_code_filepath).analyze()
+}
@@ -1,9 +1,34 @@
+use cargo_check_deadlock::{
+    Approximation, InconclusiveQuery, LockCycle, LockEdge, LostSignal, Property, ReachablePanic,
+    Statistics,
+};
+
+/// Additional findings of the translation reported together with the result.
+pub struct Findings {
+    /// The unsupported constructs that were approximated
+    pub approximations: Vec<Approximation>,
+    /// The cycles in the lock graph
+    pub lock_cycles: Vec<LockCycle>,
+    /// The acquisitions that contradict the declared lock orders
+    pub lock_order_violations: Vec<LockEdge>,
+    /// The notifications on condition variables that may be lost
+    pub lost_signals: Vec<LostSignal>,
+    /// The panics that can be reached in the main thread or in the threads
+    pub panics: Vec<ReachablePanic>,
+    /// The queries on lost signals and panics that the model checker could not answer
+    pub inconclusive_queries: Vec<InconclusiveQuery>,
+    /// The statistics reported by `LoLA` for each checked property
+    pub statistics: Vec<(Property, Statistics)>,
+}
+
 /// Possible outcomes of running the `cargo check-deadlock` command.
 pub enum CargoResult {
-    /// A successful translation containing the result of the deadlock analysis
-    DeadlockAnalysis(String),
+    /// A successful translation containing the results of the deadlock analysis
+    /// or of the requested properties and the additional findings
+    DeadlockAnalysis(Vec<String>, Findings),
     /// A successful translation without deadlock analysis
-    SimpleTranslation,
+    /// and the additional findings
+    SimpleTranslation(Findings),
     /// The source file was not found
     SourceFileNotFound(String),
     /// The output folder was not found
@@ -12,4 +37,8 @@ pub enum CargoResult {
     TranslationError(String),
     /// Failure when writing the output files
     OutputGenerationError(String),
+    /// The model file could not be read or is not valid
+    InvalidModels(String),
+    /// The source code could not be read, e.g. from stdin
+    SourceReadError(String),
 }
@@ -1,16 +1,23 @@
 use clap::Parser;
 use log::info;
+use std::io::Read;
 
-use crate::cargo_result::CargoResult;
+use crate::cargo_result::{CargoResult, Findings};
 use crate::output_format::OutputFormat;
 
-use cargo_check_deadlock::model_checker::lola;
+use cargo_check_deadlock::{
+    AnalysisQuery, Analyzer, Checker, Limits, Models, Property, PropertyVerdict, UnsupportedPolicy,
+};
+
+/// The path that indicates that the source code should be read from stdin.
+const STDIN_PATH: &str = "-";
 
 /// Convert a Rust source code file into a Petri net and export
 /// the resulting net in one of the supported formats.
 #[derive(Debug, Parser)]
 pub struct Args {
     /// The path to the Rust source code file to read.
+    /// Use `-` to read the source code from stdin.
     path: std::path::PathBuf,
 
     /// Filename for the resulting net.
@@ -31,10 +38,97 @@ pub struct Args {
     #[arg(long)]
     pnml: bool,
 
+    /// If set, outputs the Petri net in the `.net` format of the TINA toolbox.
+    #[arg(long)]
+    tina: bool,
+
+    /// If set, outputs the Petri net in the PNML dialect accepted by TAPAAL and `verifypn`.
+    /// The file has the extension `.tapaal.pnml`.
+    #[arg(long)]
+    tapaal: bool,
+
     /// If set, the reachability analysis to find deadlocks is skipped.
     #[arg(long)]
     skip_analysis: bool,
 
+    /// If set, the reachability analysis is skipped when the lock graph proves
+    /// that no deadlock can be reached, i.e. when the mutexes are always acquired in a consistent order
+    /// and the program uses no condition variables.
+    #[arg(long)]
+    precheck: bool,
+
+    /// How to handle constructs that cannot be modelled.
+    /// `abort` stops the translation, `havoc` models the construct as a subnet
+    /// that may return, panic or block forever, `skip` ignores the construct.
+    /// Approximated constructs are listed after the result.
+    #[arg(long, default_value = "abort")]
+    on_unsupported: UnsupportedPolicy,
+
+    /// The property to check with the model checker. Can be repeated to check several properties.
+    /// Predefined properties are `deadlock`, `panic`, `thread-never-terminates(N)` and `mutex-held-at-end(N)`.
+    /// Any other value is a raw CTL* or LTL formula for `LoLA`, in which the identifiers
+    /// `mutex(N)`, `thread_start(N)`, `thread_end(N)`, `condvar_notify(N)`, `lost_signal(N)`
+    /// and `resource(NAME)` are replaced with the labels of the Petri net.
+    /// If not specified, the program is checked for deadlocks.
+    #[arg(long)]
+    property: Vec<Property>,
+
+    /// The model checker used to check the properties: `lola` or `verifypn`.
+    /// Can be repeated to check every property with several model checkers and compare their verdicts.
+    /// Only `lola` finds lost signals and reachable panics.
+    /// If not specified, `lola` is used.
+    #[arg(long)]
+    checker: Vec<Checker>,
+
+    /// The path to the `lola` executable.
+    /// If not specified, the path in the environment variable `LOLA_PATH` is used,
+    /// otherwise `lola` is searched in the `$PATH` and then in the `./assets/` folder.
+    #[arg(long)]
+    lola_path: Option<std::path::PathBuf>,
+
+    /// The maximum time in seconds that the model checker may run before it is killed.
+    /// The verdict is inconclusive if the timeout is reached.
+    #[arg(long)]
+    timeout: Option<u64>,
+
+    /// The time limit in seconds passed to `LoLA`, which stops the search by itself when the limit is reached.
+    #[arg(long)]
+    time_limit: Option<u64>,
+
+    /// The maximum number of markings that `LoLA` may explore, which bounds its memory usage.
+    #[arg(long)]
+    marking_limit: Option<u64>,
+
+    /// If set, checks whether the main thread or any thread can panic
+    /// and reports where the panic starts.
+    /// Every thread gets its own panic place `THREAD_N_PANIC` in the net.
+    /// A panicking thread makes `join` return `Err` and poisons the mutexes it holds.
+    #[arg(long)]
+    panics: bool,
+
+    /// If set, models the values stored in `Mutex<bool>` and unsigned integer mutexes
+    /// as places in the net, bounded to `0..4` for integers.
+    /// Enables the evaluation of simple `Condvar::wait_while` conditions.
+    #[arg(long)]
+    mutex_values: bool,
+
+    /// If set, a loop over a range with constant bounds such as `for _ in 0..3`
+    /// runs exactly as many times as the range has values instead of any number of times.
+    /// Ranges with more than 16 values still run any number of times
+    /// and are listed as approximated constructs.
+    #[arg(long)]
+    unroll_const_loops: bool,
+
+    /// The path to a TOML file with models of foreign functions,
+    /// e.g. functions that behave like a lock on a resource.
+    #[arg(long)]
+    models: Option<std::path::PathBuf>,
+
+    /// Additional arguments passed to `rustc`, e.g. `-- --edition=2021 --cfg feature="std"`.
+    /// If no edition is given, the edition is read from the closest `Cargo.toml`.
+    #[arg(last = true)]
+    rustc_args: Vec<String>,
+
     /// Verbosity flag.
     #[clap(flatten)]
     verbose: clap_verbosity_flag::Verbosity,
@@ -47,18 +141,29 @@ impl Args {
             .filter_level(self.verbose.log_level_filter())
             .init();
 
-        // Double check that the file exists before starting the compiler
-        // to generate an error message independent of the rustc output.
-        info!(
-            "Checking that the source code file at {} exists...",
-            self.path.to_string_lossy()
-        );
-        if !self.path.exists() {
-            let err_str = format!(
-                "Source code file at {} does not exist",
-                &self.path.to_string_lossy()
+        let analyzer = if self.path.as_os_str() == STDIN_PATH {
+            info!("Reading the source code from stdin...");
+            let mut source_code = String::new();
+            if let Err(err) = std::io::stdin().read_to_string(&mut source_code) {
+                let err_str = format!("Source code could not be read from stdin: {err}");
+                return CargoResult::SourceReadError(err_str);
+            }
+            Analyzer::from_stdin(source_code)
+        } else {
+            // Double check that the file exists before starting the compiler
+            // to generate an error message independent of the rustc output.
+            info!(
+                "Checking that the source code file at {} exists...",
+                self.path.to_string_lossy()
             );
-            return CargoResult::SourceFileNotFound(err_str);
+            if !self.path.exists() {
+                let err_str = format!(
+                    "Source code file at {} does not exist",
+                    &self.path.to_string_lossy()
+                );
+                return CargoResult::SourceFileNotFound(err_str);
+            };
+            Analyzer::from_file(self.path.clone())
         };
 
         // Double check that the output folder exists before starting the compiler
@@ -76,17 +181,65 @@ impl Args {
         };
 
         info!("Starting the translation...");
-        let petri_net = match cargo_check_deadlock::run(self.path.clone()) {
-            Ok(petri_net) => petri_net,
+        let models = match &self.models {
+            Some(path) => {
+                info!("Reading the models at {}...", path.to_string_lossy());
+                match Models::from_file(path) {
+                    Ok(models) => models,
+                    Err(err_str) => return CargoResult::InvalidModels(err_str),
+                }
+            }
+            None => Models::default(),
+        };
+
+        let properties = if self.property.is_empty() {
+            vec![Property::Deadlock]
+        } else {
+            self.property.clone()
+        };
+        let limits = Limits {
+            time_limit: self.time_limit,
+            marking_limit: self.marking_limit,
+            timeout: self.timeout,
+        };
+        let checkers = if self.checker.is_empty() {
+            vec![Checker::Lola]
+        } else {
+            self.checker.clone()
+        };
+        let mut analyzer = analyzer
+            .rustc_args(self.rustc_args.clone())
+            .on_unsupported(self.on_unsupported)
+            .models(models)
+            .checker(checkers[0])
+            .compare_with(checkers[1..].iter().copied())
+            .limits(limits)
+            .deadlock_analysis(properties.contains(&Property::Deadlock))
+            .precheck(self.precheck)
+            .properties(
+                properties
+                    .iter()
+                    .filter(|property| **property != Property::Deadlock)
+                    .cloned(),
+            )
+            .panics(self.panics)
+            .mutex_values(self.mutex_values)
+            .unroll_const_loops(self.unroll_const_loops);
+        if let Some(lola_path) = &self.lola_path {
+            analyzer = analyzer.lola_path(lola_path);
+        }
+        let translation = match analyzer.translate() {
+            Ok(translation) => translation,
             Err(err_str) => {
                 return CargoResult::TranslationError(err_str.to_string());
             }
         };
+        let petri_net = &translation.net;
 
         if self.dot {
             let format = OutputFormat::Dot;
             if let Err(err_str) =
-                format.create_output_file(&petri_net, &self.filename, &self.output_folder)
+                format.create_output_file(petri_net, &self.filename, &self.output_folder)
             {
                 return CargoResult::OutputGenerationError(err_str.to_string());
             }
@@ -95,32 +248,138 @@ impl Args {
         if self.pnml {
             let format = OutputFormat::Pnml;
             if let Err(err_str) =
-                format.create_output_file(&petri_net, &self.filename, &self.output_folder)
+                format.create_output_file(petri_net, &self.filename, &self.output_folder)
+            {
+                return CargoResult::OutputGenerationError(err_str.to_string());
+            }
+        }
+
+        if self.tina {
+            let format = OutputFormat::Tina;
+            if let Err(err_str) =
+                format.create_output_file(petri_net, &self.filename, &self.output_folder)
+            {
+                return CargoResult::OutputGenerationError(err_str.to_string());
+            }
+        }
+
+        if self.tapaal {
+            let format = OutputFormat::Tapaal;
+            if let Err(err_str) =
+                format.create_output_file(petri_net, &self.filename, &self.output_folder)
             {
                 return CargoResult::OutputGenerationError(err_str.to_string());
             }
         }
-        // Always generate the file in LoLA format for the deadlock analysis
+        // Always generate the file in LoLA format
         let format = OutputFormat::Lola;
         if let Err(err_str) =
-            format.create_output_file(&petri_net, &self.filename, &self.output_folder)
+            format.create_output_file(petri_net, &self.filename, &self.output_folder)
         {
             return CargoResult::OutputGenerationError(err_str.to_string());
         }
 
         if self.skip_analysis {
-            return CargoResult::SimpleTranslation;
+            let findings = Findings {
+                approximations: translation.approximations,
+                lock_cycles: translation.lock_graph.cycles(),
+                lock_order_violations: translation.lock_graph.violations(&translation.lock_orders),
+                lost_signals: Vec::new(),
+                panics: Vec::new(),
+                inconclusive_queries: Vec::new(),
+                statistics: Vec::new(),
+            };
+            return CargoResult::SimpleTranslation(findings);
         }
 
-        let mut filepath = self.output_folder.clone();
-        filepath.push(&self.filename);
-        filepath.set_extension(OutputFormat::Lola.to_string());
-
-        let message = if lola::check_deadlock(&filepath) {
-            "Deadlock can be reached according to the model checker `LoLA`"
-        } else {
-            "The program is deadlock-free according to the model checker `LoLA`"
+        let report = match analyzer.analyze_translation(translation) {
+            Ok(report) => report,
+            Err(err_str) => return CargoResult::OutputGenerationError(err_str.to_string()),
+        };
+        let name = checkers[0].backend(limits).name();
+        let mut statistics = Vec::new();
+        let mut messages = Vec::new();
+        for property in &properties {
+            let (verdict, property_statistics) = if *property == Property::Deadlock {
+                if report.skipped_model_checker {
+                    let message = "The program is deadlock-free according to the lock graph";
+                    messages.push(message.to_string());
+                    continue;
+                }
+                (report.verdict.clone().into(), report.statistics)
+            } else {
+                let Some(result) = report
+                    .property_results
+                    .iter()
+                    .find(|result| result.property == *property)
+                else {
+                    continue;
+                };
+                (result.verdict.clone(), result.statistics)
+            };
+            if let Some(property_statistics) = property_statistics {
+                statistics.push((property.clone(), property_statistics));
+            }
+            match report
+                .comparisons
+                .iter()
+                .find(|comparison| comparison.property == *property)
+            {
+                Some(comparison) => {
+                    for (name, verdict) in &comparison.verdicts {
+                        messages.push(describe_verdict(property, verdict, name));
+                    }
+                    messages.push(comparison.to_string());
+                }
+                None => messages.push(describe_verdict(property, &verdict, name)),
+            }
+        }
+        if self.panics {
+            if checkers[0] != Checker::Lola {
+                let message = "The panic analysis requires the model checker `LoLA`";
+                messages.push(message.to_string());
+            } else if report.panics.is_empty()
+                && !report
+                    .inconclusive_queries
+                    .iter()
+                    .any(|query| matches!(query.query, AnalysisQuery::Panic(_)))
+            {
+                let message = "No panic can be reached according to the model checker `LoLA`";
+                messages.push(message.to_string());
+            }
+        }
+        let findings = Findings {
+            approximations: report.approximations,
+            lock_cycles: report.lock_cycles,
+            lock_order_violations: report.lock_order_violations,
+            lost_signals: report.lost_signals,
+            panics: report.panics,
+            inconclusive_queries: report.inconclusive_queries,
+            statistics,
         };
-        CargoResult::DeadlockAnalysis(message.to_string())
+        CargoResult::DeadlockAnalysis(messages, findings)
+    }
+}
+
+/// Returns a message describing the verdict of the model checker `name` on the property.
+fn describe_verdict(property: &Property, verdict: &PropertyVerdict, name: &str) -> String {
+    match (property, verdict) {
+        (Property::Deadlock, PropertyVerdict::Satisfied) => {
+            format!("Deadlock can be reached according to the model checker `{name}`")
+        }
+        (Property::Deadlock, PropertyVerdict::NotSatisfied) => {
+            format!("The program is deadlock-free according to the model checker `{name}`")
+        }
+        (_, PropertyVerdict::Satisfied) => {
+            format!("The property `{property}` holds according to the model checker `{name}`")
+        }
+        (_, PropertyVerdict::NotSatisfied) => {
+            format!(
+                "The property `{property}` does not hold according to the model checker `{name}`"
+            )
+        }
+        (_, PropertyVerdict::Inconclusive(reason)) => {
+            format!("The property `{property}` could not be checked: {reason}")
+        }
     }
 }
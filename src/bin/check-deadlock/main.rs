@@ -12,9 +12,10 @@ mod output_format;
 use clap::Parser;
 
 use cargo_result::CargoResult::{
-    DeadlockAnalysis, OutputFolderNotFound, OutputGenerationError, SimpleTranslation,
-    SourceFileNotFound, TranslationError,
+    DeadlockAnalysis, InvalidModels, OutputFolderNotFound, OutputGenerationError,
+    SimpleTranslation, SourceFileNotFound, SourceReadError, TranslationError,
 };
+use cargo_result::Findings;
 
 fn main() {
     let args = cli::Command::parse();
@@ -36,9 +37,74 @@ fn main() {
             eprintln!("{err_str}");
             std::process::exit(4);
         }
-        DeadlockAnalysis(message) => {
-            println!("Result: {message}");
+        InvalidModels(err_str) => {
+            eprintln!("{err_str}");
+            std::process::exit(5);
+        }
+        SourceReadError(err_str) => {
+            eprintln!("{err_str}");
+            std::process::exit(6);
+        }
+        DeadlockAnalysis(messages, findings) => {
+            for message in messages {
+                println!("Result: {message}");
+            }
+            print_findings(&findings);
+        }
+        SimpleTranslation(findings) => {
+            print_findings(&findings);
+        }
+    }
+}
+
+/// Prints the unsupported constructs that were approximated in the Petri net,
+/// the cycles in the lock graph, the violations of the declared lock orders, the lost signals,
+/// the reachable panics and the statistics of the model checker.
+/// Nothing is printed for an empty list.
+fn print_findings(findings: &Findings) {
+    if !findings.approximations.is_empty() {
+        println!("Approximated constructs (the result may be imprecise):");
+        for approximation in &findings.approximations {
+            println!("  - {approximation}");
+        }
+    }
+    if !findings.lock_cycles.is_empty() {
+        println!("Lock order cycles (potential deadlocks):");
+        for cycle in &findings.lock_cycles {
+            println!("  - {cycle}");
+            for edge in &cycle.edges {
+                println!("      {edge}");
+            }
+        }
+    }
+    if !findings.lock_order_violations.is_empty() {
+        println!("Violations of the declared lock orders:");
+        for edge in &findings.lock_order_violations {
+            println!("  - {edge}");
+        }
+    }
+    if !findings.lost_signals.is_empty() {
+        println!("Lost signals:");
+        for lost_signal in &findings.lost_signals {
+            println!("  - {lost_signal}");
+        }
+    }
+    if !findings.panics.is_empty() {
+        println!("Reachable panics:");
+        for panic in &findings.panics {
+            println!("  - {panic}");
+        }
+    }
+    if !findings.inconclusive_queries.is_empty() {
+        println!("Inconclusive queries (lost signals or panics may be missing):");
+        for query in &findings.inconclusive_queries {
+            println!("  - {query}");
+        }
+    }
+    if !findings.statistics.is_empty() {
+        println!("Model checker statistics:");
+        for (property, statistics) in &findings.statistics {
+            println!("  - `{property}`: {statistics}");
         }
-        SimpleTranslation => {}
     }
 }
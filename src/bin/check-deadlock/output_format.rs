@@ -1,6 +1,7 @@
 use clap::ValueEnum;
 use log::info;
 
+use cargo_check_deadlock::export::{to_tapaal_pnml, to_tina};
 use cargo_check_deadlock::PetriNet;
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
@@ -12,6 +13,10 @@ pub enum OutputFormat {
     Lola,
     /// DOT (graph description language) - <https://graphviz.org/>
     Dot,
+    /// TINA - TIme petri Net Analyzer - <https://projects.laas.fr/tina/>
+    Tina,
+    /// The PNML dialect accepted by TAPAAL and `verifypn` - <https://www.tapaal.net/>
+    Tapaal,
 }
 
 impl OutputFormat {
@@ -37,6 +42,8 @@ impl OutputFormat {
             Self::Dot => petri_net.to_dot(&mut file),
             Self::Lola => petri_net.to_lola(&mut file),
             Self::Pnml => petri_net.to_pnml(&mut file),
+            Self::Tina => to_tina(petri_net, filename, &mut file),
+            Self::Tapaal => to_tapaal_pnml(petri_net, filename, &mut file),
         }
     }
 }
@@ -47,6 +54,8 @@ impl std::fmt::Display for OutputFormat {
             Self::Dot => write!(f, "dot"),
             Self::Lola => write!(f, "lola"),
             Self::Pnml => write!(f, "pnml"),
+            Self::Tina => write!(f, "net"),
+            Self::Tapaal => write!(f, "tapaal.pnml"),
         }
     }
 }
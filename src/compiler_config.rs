@@ -4,31 +4,80 @@
 //! <https://doc.rust-lang.org/stable/nightly-rustc/rustc_interface/interface/struct.Config.html>
 //!
 //! It includes command-line options as well as internal rustc options.
-//! The relevant parts in this case are the `sysroot` and the input file.
+//! The relevant parts in this case are the `sysroot`, the input and the additional rustc arguments.
+//! The rustc arguments are parsed with the same option parser used by `rustc` itself.
+//! The tool `check_deadlock` is registered to support the attributes `#[check_deadlock::...]`.
+//! The crate attributes that register it are only added if the crate root does not declare them already,
+//! since `rustc` rejects registering the same tool twice.
+//!
+//! If no edition is given explicitly, the edition is read from the `Cargo.toml` closest to the source file.
+//! This way the code is compiled with the same edition as in the real build.
 //!
 //! See the rustc driver examples for other possible example configurations:
 //! <https://rustc-dev-guide.rust-lang.org/rustc-driver.html>
 
+use log::info;
 use rustc_errors::registry;
 
+use crate::translator::TOOL_NAME;
+
+/// The name of the Cargo manifest file.
+const CARGO_MANIFEST_FILENAME: &str = "Cargo.toml";
+
+/// Prepares the rustc config for translating the given `input`.
+/// The `rustc_args` are parsed as if they were passed to `rustc` on the command line.
+///
+/// # Errors
+///
+/// If the rustc arguments are not valid, then an error is returned.
 pub fn prepare_rustc_config(
     sysroot: std::path::PathBuf,
-    source_code_filepath: std::path::PathBuf,
-) -> rustc_interface::Config {
-    rustc_interface::Config {
-        opts: rustc_session::config::Options {
-            maybe_sysroot: Some(sysroot),
-            ..rustc_session::config::Options::default()
-        },
-        crate_cfg: Vec::new(),
-        crate_check_cfg: Vec::new(),
-        input: rustc_session::config::Input::File(source_code_filepath),
+    input: rustc_session::config::Input,
+    rustc_args: &[String],
+) -> Result<rustc_interface::Config, &'static str> {
+    let (opts, crate_cfg, crate_check_cfg) = if rustc_args.is_empty() {
+        (
+            rustc_session::config::Options::default(),
+            Vec::new(),
+            Vec::new(),
+        )
+    } else {
+        parse_rustc_args(rustc_args)?
+    };
+
+    let mut opts = rustc_session::config::Options {
+        maybe_sysroot: Some(sysroot),
+        // Needed to enable the `register_tool` feature below.
+        unstable_features: rustc_feature::UnstableFeatures::from_environment(None),
+        ..opts
+    };
+    // Register the tool to enable the attributes `#[check_deadlock::...]` in the source code.
+    let declared = declared_crate_attributes(&input);
+    if !declared.contains(&"feature(register_tool)".to_string()) {
+        opts.unstable_opts
+            .crate_attr
+            .push("feature(register_tool)".to_string());
+    }
+    if !declared.contains(&format!("register_tool({TOOL_NAME})")) {
+        opts.unstable_opts
+            .crate_attr
+            .push(format!("register_tool({TOOL_NAME})"));
+    }
+    // Set the `cfg` option to enable `#[cfg_attr(check_deadlock, ...)]` in the source code.
+    let mut crate_cfg = crate_cfg;
+    crate_cfg.push(TOOL_NAME.to_string());
+
+    Ok(rustc_interface::Config {
+        opts,
+        crate_cfg,
+        crate_check_cfg,
+        input,
         output_dir: None,
         output_file: None,
         hash_untracked_state: None,
         ice_file: None,
         file_loader: None,
-        locale_resources: Vec::new(),
+        locale_resources: rustc_driver::DEFAULT_LOCALE_RESOURCES.to_vec(),
         lint_caps: rustc_hash::FxHashMap::default(),
         psess_created: None,
         register_lints: None,
@@ -36,6 +85,118 @@ pub fn prepare_rustc_config(
         make_codegen_backend: None,
         registry: registry::Registry::new(rustc_errors::codes::DIAGNOSTICS),
         using_internal_features: std::sync::Arc::new(core::sync::atomic::AtomicBool::new(true)),
-        expanded_args: Vec::new(),
+        expanded_args: rustc_args.to_vec(),
+    })
+}
+
+/// Returns the inner attributes of the crate root in the `input` with every argument split into its own attribute
+/// and without whitespace, e.g. `#![feature(rustc_private, register_tool)]` gives `feature(rustc_private)`
+/// and `feature(register_tool)`.
+///
+/// The source code is scanned as text, so an attribute inside a comment or a string is also returned.
+/// Returns an empty list if the source file cannot be read.
+fn declared_crate_attributes(input: &rustc_session::config::Input) -> Vec<String> {
+    let source = match input {
+        rustc_session::config::Input::File(path) => {
+            std::fs::read_to_string(path).unwrap_or_default()
+        }
+        rustc_session::config::Input::Str { input, .. } => input.clone(),
+    };
+    let mut attributes = Vec::new();
+    for (start, _) in source.match_indices("#![") {
+        let rest = &source[start + 3..];
+        let Some(end) = rest.find(']') else {
+            continue;
+        };
+        let attribute: String = rest[..end].split_whitespace().collect();
+        let Some((name, arguments)) = attribute
+            .strip_suffix(')')
+            .and_then(|attribute| attribute.split_once('('))
+        else {
+            continue;
+        };
+        attributes.extend(
+            arguments
+                .split(',')
+                .filter(|argument| !argument.is_empty())
+                .map(|argument| format!("{name}({argument})")),
+        );
+    }
+    info!("Crate attributes declared in the source code: {attributes:?}");
+    attributes
+}
+
+/// Parses the rustc arguments into the session options and the `--cfg` and `--check-cfg` values.
+///
+/// # Errors
+///
+/// If the rustc arguments are not valid, then an error is returned.
+fn parse_rustc_args(
+    rustc_args: &[String],
+) -> Result<(rustc_session::config::Options, Vec<String>, Vec<String>), &'static str> {
+    let mut early_dcx =
+        rustc_session::EarlyDiagCtxt::new(rustc_session::config::ErrorOutputType::default());
+    // The first argument is the name of the binary and the second one is the input file.
+    // Both are ignored when building the session options.
+    let mut args = vec!["rustc".to_string(), "-".to_string()];
+    args.extend_from_slice(rustc_args);
+    let Some(matches) = rustc_driver::handle_options(&early_dcx, &args) else {
+        return Err("Could not parse the rustc arguments");
+    };
+    let opts = rustc_session::config::build_session_options(&mut early_dcx, &matches);
+    Ok((opts, matches.opt_strs("cfg"), matches.opt_strs("check-cfg")))
+}
+
+/// Checks whether the rustc arguments contain an `--edition` flag.
+pub fn contains_edition(rustc_args: &[String]) -> bool {
+    rustc_args
+        .iter()
+        .any(|arg| arg == "--edition" || arg.starts_with("--edition="))
+}
+
+/// Finds the edition of the package containing the source code file.
+/// Searches for the closest `Cargo.toml` in the ancestor folders of the source code file
+/// and reads the `edition` key of the `[package]` section.
+/// If the edition is inherited from the workspace, i.e. `edition.workspace = true`,
+/// the search continues upwards for the `[workspace.package]` section.
+///
+/// Returns `None` if no manifest is found, it cannot be parsed or it does not specify an edition.
+pub fn find_edition_in_manifest(source_code_filepath: &std::path::Path) -> Option<String> {
+    let source_code_filepath = source_code_filepath.canonicalize().ok()?;
+    let mut inherits_from_workspace = false;
+
+    for folder in source_code_filepath.ancestors().skip(1) {
+        let manifest_path = folder.join(CARGO_MANIFEST_FILENAME);
+        let Ok(contents) = std::fs::read_to_string(&manifest_path) else {
+            continue;
+        };
+        let manifest: toml::Table = contents.parse().ok()?;
+
+        let edition = if inherits_from_workspace {
+            manifest
+                .get("workspace")
+                .and_then(|workspace| workspace.get("package"))
+                .and_then(|package| package.get("edition"))
+        } else {
+            manifest
+                .get("package")
+                .and_then(|package| package.get("edition"))
+        };
+        match edition {
+            Some(toml::Value::String(edition)) => {
+                info!(
+                    "Found edition {edition} in {}",
+                    manifest_path.to_string_lossy()
+                );
+                return Some(edition.clone());
+            }
+            Some(toml::Value::Table(table)) if table.get("workspace").is_some() => {
+                inherits_from_workspace = true;
+            }
+            // The closest manifest does not specify an edition, i.e. Cargo uses the default.
+            _ if !inherits_from_workspace => return None,
+            _ => {}
+        }
     }
+    None
 }
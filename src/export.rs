@@ -0,0 +1,15 @@
+//! Module that implements the export of the Petri net to formats
+//! that are not provided by the library `netcrab`.
+//!
+//! - TINA: The textual `.net` format of the TINA toolbox <https://projects.laas.fr/tina/>.
+//! - TAPAAL: The dialect of the Petri Net Markup Language accepted by TAPAAL and its engine `verifypn`
+//!   <https://www.tapaal.net/>.
+//!
+//! Both writers keep the labels of the places and transitions and the initial marking,
+//! so that the results of the tools can be compared with the results of `LoLA`.
+
+mod tapaal;
+mod tina;
+
+pub use tapaal::to_tapaal_pnml;
+pub use tina::to_tina;
@@ -0,0 +1,212 @@
+//! Submodule for the dialect of the Petri Net Markup Language (PNML) accepted by TAPAAL and `verifypn`.
+//! See the format of the Model Checking Contest: <https://mcc.lip6.fr/2024/pdf/pnml.pdf>
+//!
+//! The differences with the PNML written by `netcrab` are:
+//! - The identifiers of places, transitions and arcs are valid XML identifiers
+//!   and unique across places and transitions, as required by TAPAAL.
+//!   The original label is kept in the name of the place or transition.
+//!   The labels of the translation are already valid identifiers, so they are usually unchanged
+//!   and can be used in the queries for `verifypn`.
+//! - The arcs are identified by a counter instead of the pair of nodes they connect.
+//! - The net has a name.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::data_structures::petri_net_interface::PetriNet;
+
+const XML_PNML_DEFAULT_NAMESPACE: &str = "http://www.pnml.org/version-2009/grammar/pnml";
+const XML_PNML_DEFAULT_GRAMMAR: &str = "http://www.pnml.org/version-2009/grammar/ptnet";
+
+/// Converts the net to the PNML dialect accepted by TAPAAL and `verifypn`.
+/// Writes the output to a trait object which implements `std::io::Write`.
+///
+/// # Errors
+///
+/// If the writer fails to write the contents of the net, then an error is returned.
+pub fn to_tapaal_pnml<T>(net: &PetriNet, name: &str, writer: &mut T) -> Result<(), std::io::Error>
+where
+    T: std::io::Write,
+{
+    let ids = Identifiers::new(net);
+    writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(writer, r#"<pnml xmlns="{XML_PNML_DEFAULT_NAMESPACE}">"#)?;
+    writeln!(
+        writer,
+        r#"  <net id="{}" type="{XML_PNML_DEFAULT_GRAMMAR}">"#,
+        to_identifier(name)
+    )?;
+    write_name(writer, name, "    ")?;
+    writeln!(writer, r#"    <page id="page0">"#)?;
+
+    for (place_ref, place) in net.places_iter() {
+        writeln!(
+            writer,
+            r#"      <place id="{}">"#,
+            ids.get(place_ref.label())
+        )?;
+        write_name(writer, place_ref.label(), "        ")?;
+        if place.marking() > 0 {
+            writeln!(writer, "        <initialMarking>")?;
+            writeln!(writer, "          <text>{}</text>", place.marking())?;
+            writeln!(writer, "        </initialMarking>")?;
+        }
+        writeln!(writer, "      </place>")?;
+    }
+    for (transition_ref, _) in net.transitions_iter() {
+        writeln!(
+            writer,
+            r#"      <transition id="{}">"#,
+            ids.get(transition_ref.label())
+        )?;
+        write_name(writer, transition_ref.label(), "        ")?;
+        writeln!(writer, "      </transition>")?;
+    }
+
+    let arcs_place_transition =
+        net.find_arcs_place_transition()
+            .into_iter()
+            .map(|(place_ref, transition_ref)| {
+                (place_ref.label().clone(), transition_ref.label().clone())
+            });
+    let arcs_transition_place =
+        net.find_arcs_transition_place()
+            .into_iter()
+            .map(|(transition_ref, place_ref)| {
+                (transition_ref.label().clone(), place_ref.label().clone())
+            });
+    for (index, (source, target)) in arcs_place_transition
+        .chain(arcs_transition_place)
+        .enumerate()
+    {
+        writeln!(
+            writer,
+            r#"      <arc id="A{index}" source="{}" target="{}">"#,
+            ids.get(&source),
+            ids.get(&target)
+        )?;
+        writeln!(writer, "        <inscription>")?;
+        writeln!(writer, "          <text>1</text>")?;
+        writeln!(writer, "        </inscription>")?;
+        writeln!(writer, "      </arc>")?;
+    }
+
+    writeln!(writer, "    </page>")?;
+    writeln!(writer, "  </net>")?;
+    writeln!(writer, "</pnml>")?;
+    Ok(())
+}
+
+/// The identifiers of the places and transitions in the net, indexed by their labels.
+struct Identifiers(BTreeMap<String, String>);
+
+impl Identifiers {
+    /// Assigns a unique identifier to every place and transition of the net.
+    /// If two labels are converted to the same identifier, a numeric suffix is added to the later one.
+    fn new(net: &PetriNet) -> Self {
+        let labels = net
+            .places_iter()
+            .map(|(place_ref, _)| place_ref.label())
+            .chain(
+                net.transitions_iter()
+                    .map(|(transition_ref, _)| transition_ref.label()),
+            );
+        let mut used: BTreeSet<String> = BTreeSet::new();
+        let mut ids = BTreeMap::new();
+        for label in labels {
+            let base = to_identifier(label);
+            let mut id = base.clone();
+            let mut suffix = 1;
+            while !used.insert(id.clone()) {
+                id = format!("{base}_{suffix}");
+                suffix += 1;
+            }
+            ids.insert(label.clone(), id);
+        }
+        Self(ids)
+    }
+
+    /// Returns the identifier of the place or transition with the given label.
+    fn get(&self, label: &str) -> &str {
+        self.0
+            .get(label)
+            .map(String::as_str)
+            .expect("BUG: Every place and transition should have an identifier")
+    }
+}
+
+/// Converts a label to an identifier accepted by TAPAAL:
+/// A letter or `_` followed by letters, digits or `_`.
+/// Other characters are replaced with `_`.
+fn to_identifier(label: &str) -> String {
+    let mut id: String = label
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if !id.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        id.insert(0, '_');
+    }
+    id
+}
+
+/// Writes the `<name>` element with the given text at the given indentation.
+fn write_name<T>(writer: &mut T, name: &str, indentation: &str) -> Result<(), std::io::Error>
+where
+    T: std::io::Write,
+{
+    writeln!(writer, "{indentation}<name>")?;
+    writeln!(writer, "{indentation}  <text>{}</text>", escape_xml(name))?;
+    writeln!(writer, "{indentation}</name>")
+}
+
+/// Escapes the characters with a special meaning in XML.
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tapaal_tests {
+    use super::*;
+    use crate::data_structures::petri_net_interface::connect_places;
+
+    #[test]
+    fn writes_places_transitions_and_arcs_with_identifiers() {
+        let mut net = PetriNet::new();
+        let start = net.add_place("PROGRAM_START");
+        let end = net.add_place("PROGRAM_END");
+        net.add_token(&start, 1)
+            .expect("Adding a token should not fail");
+        connect_places(&mut net, &start, &end, "main_RETURN");
+
+        let mut output = Vec::new();
+        to_tapaal_pnml(&net, "net", &mut output).expect("Writing the net should not fail");
+        let output = String::from_utf8(output).expect("The output should be valid UTF-8");
+
+        assert!(output.contains(r#"<place id="PROGRAM_START">"#));
+        assert!(output.contains("<text>1</text>\n        </initialMarking>"));
+        assert!(output.contains(r#"<transition id="main_RETURN">"#));
+        assert!(output.contains(r#"<arc id="A0" source="PROGRAM_START" target="main_RETURN">"#));
+        assert!(output.contains(r#"<arc id="A1" source="main_RETURN" target="PROGRAM_END">"#));
+    }
+
+    #[test]
+    fn identifiers_are_unique_and_valid() {
+        let mut net = PetriNet::new();
+        net.add_place("0 place");
+        net.add_transition("_0_place");
+        let ids = Identifiers::new(&net);
+
+        assert_eq!(ids.get("0 place"), "_0_place");
+        assert_eq!(ids.get("_0_place"), "_0_place_1");
+    }
+}
@@ -0,0 +1,99 @@
+//! Submodule for the textual `.net` format of the TINA toolbox.
+//! See the description of the format: <https://projects.laas.fr/tina/manuals/formats.html>
+//!
+//! The net is written as one line for the name of the net, one line for each place
+//! with its initial marking and one line for each transition with its input and output places:
+//!
+//! ```text
+//! net deadlock
+//! pl PROGRAM_START (1)
+//! pl PROGRAM_END
+//! tr main_RETURN PROGRAM_START -> PROGRAM_END
+//! ```
+
+use crate::data_structures::petri_net_interface::PetriNet;
+
+/// Converts the net to the `.net` format of the TINA toolbox.
+/// Writes the output to a trait object which implements `std::io::Write`.
+///
+/// # Errors
+///
+/// If the writer fails to write the contents of the net, then an error is returned.
+pub fn to_tina<T>(net: &PetriNet, name: &str, writer: &mut T) -> Result<(), std::io::Error>
+where
+    T: std::io::Write,
+{
+    writeln!(writer, "net {}", to_tina_name(name))?;
+    for (place_ref, place) in net.places_iter() {
+        let label = to_tina_name(place_ref.label());
+        match place.marking() {
+            0 => writeln!(writer, "pl {label}")?,
+            marking => writeln!(writer, "pl {label} ({marking})")?,
+        }
+    }
+    for (transition_ref, transition) in net.transitions_iter() {
+        write!(writer, "tr {}", to_tina_name(transition_ref.label()))?;
+        for place_ref in transition.get_preset() {
+            write!(writer, " {}", to_tina_name(place_ref.label()))?;
+        }
+        write!(writer, " ->")?;
+        for place_ref in transition.get_postset() {
+            write!(writer, " {}", to_tina_name(place_ref.label()))?;
+        }
+        writeln!(writer)?;
+    }
+    Ok(())
+}
+
+/// Converts a label to a name accepted by TINA.
+/// Names made of letters, digits, `_` and `'` are written as they are,
+/// other names are enclosed in braces with `{`, `}` and `\` escaped.
+fn to_tina_name(label: &str) -> String {
+    let is_plain = !label.is_empty()
+        && label
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '\'');
+    if is_plain {
+        return label.to_string();
+    }
+    let mut name = String::from("{");
+    for c in label.chars() {
+        if matches!(c, '{' | '}' | '\\') {
+            name.push('\\');
+        }
+        name.push(c);
+    }
+    name.push('}');
+    name
+}
+
+#[cfg(test)]
+mod tina_tests {
+    use super::*;
+    use crate::data_structures::petri_net_interface::connect_places;
+
+    #[test]
+    fn writes_places_with_marking_and_transitions_with_arcs() {
+        let mut net = PetriNet::new();
+        let start = net.add_place("PROGRAM_START");
+        let end = net.add_place("PROGRAM_END");
+        net.add_token(&start, 1)
+            .expect("Adding a token should not fail");
+        connect_places(&mut net, &start, &end, "main_RETURN");
+
+        let mut output = Vec::new();
+        to_tina(&net, "net", &mut output).expect("Writing the net should not fail");
+
+        assert_eq!(
+            String::from_utf8(output).expect("The output should be valid UTF-8"),
+            "net net\npl PROGRAM_END\npl PROGRAM_START (1)\ntr main_RETURN PROGRAM_START -> PROGRAM_END\n"
+        );
+    }
+
+    #[test]
+    fn encloses_names_with_special_characters_in_braces() {
+        assert_eq!(to_tina_name("MUTEX_0"), "MUTEX_0");
+        assert_eq!(to_tina_name("my net"), "{my net}");
+        assert_eq!(to_tina_name("a{b}"), "{a\\{b\\}}");
+    }
+}
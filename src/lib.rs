@@ -17,29 +17,51 @@
 // Compiler crates need to be imported in this way because they are not published on crates.io.
 // These crates are only available when using the nightly toolchain.
 // It suffices to declare them once to use their types and methods in the whole crate.
+extern crate rustc_abi;
+extern crate rustc_ast;
 extern crate rustc_ast_pretty;
 extern crate rustc_const_eval;
 extern crate rustc_driver;
 extern crate rustc_error_codes;
 extern crate rustc_errors;
+extern crate rustc_feature;
 extern crate rustc_hash;
 extern crate rustc_hir;
+extern crate rustc_index;
 extern crate rustc_interface;
 extern crate rustc_middle;
+extern crate rustc_mir_dataflow;
 extern crate rustc_session;
 extern crate rustc_span;
 
+mod analyzer;
 mod compiler_config;
 mod data_structures;
+pub mod export;
+mod lock_graph;
 pub mod model_checker;
+mod models;
 mod naming;
 mod sysroot;
 mod translator;
 mod utils;
 
+pub use analyzer::{analyze, AnalysisReport, Analyzer, Source, Translation};
 pub use data_structures::petri_net_interface::PetriNet;
+pub use lock_graph::{LockCycle, LockEdge, LockGraph};
+pub use model_checker::{
+    AnalysisQuery, Checker, Comparison, InconclusiveQuery, Limits, LostSignal, ModelChecker,
+    Property, PropertyResult, PropertyVerdict, ReachablePanic, Statistics, Verdict,
+};
+pub use models::{Behavior, FunctionModel, Models};
+pub use translator::{
+    Approximation, LockOrder, NotifyCall, PanicSite, Primitive, PrimitiveKind, UnsupportedPolicy,
+};
 
 /// Entry point for the translation of the Rust code to a Petri net.
+/// Unsupported constructs are handled according to `on_unsupported`.
+///
+/// It is a shorthand for `Analyzer::from_file(source_code_filepath).on_unsupported(on_unsupported).translate()`.
 ///
 /// # Errors
 ///
@@ -50,28 +72,11 @@ pub use data_structures::petri_net_interface::PetriNet;
 ///
 /// If the global typing context `rustc_middle::ty::TyCtxt` cannot be found, then the function panics.
 /// If the translation failed due to a bug, then the function panics.
-pub fn run(source_code_filepath: std::path::PathBuf) -> Result<PetriNet, &'static str> {
-    let sysroot = sysroot::get_from_rustc()?;
-    let config = compiler_config::prepare_rustc_config(sysroot, source_code_filepath);
-    let mut translation_result: Result<PetriNet, &'static str> = Err("Translation did not run");
-
-    rustc_interface::run_compiler(config, |compiler| {
-        compiler.enter(|queries| {
-            // Get the global typing context (tcx: TyCtxt), which is the central data structure in the compiler.
-            // <https://doc.rust-lang.org/stable/nightly-rustc/rustc_middle/ty/struct.TyCtxt.html>
-            let mut query = queries
-                .global_ctxt()
-                .expect("BUG: Unable to get the global typing context needed for the `Translator`");
-
-            // Run the translator as a query to the compiler.
-            // <https://rustc-dev-guide.rust-lang.org/rustc-driver.html>
-            query.enter(|tcx| {
-                let mut translator = translator::Translator::new(tcx);
-                translator.run();
-                translation_result = Ok(translator.get_result());
-            });
-        });
-    });
-
-    translation_result
+pub fn run(
+    source_code_filepath: std::path::PathBuf,
+    on_unsupported: UnsupportedPolicy,
+) -> Result<Translation, String> {
+    Analyzer::from_file(source_code_filepath)
+        .on_unsupported(on_unsupported)
+        .translate()
 }
@@ -0,0 +1,540 @@
+//! Submodule for the static analysis of the order in which mutexes are acquired.
+//!
+//! The analysis runs over the translated Petri net. Starting from the start place of every thread,
+//! it follows the control flow of the thread and keeps track of the mutexes held at each place.
+//! Whenever a mutex is acquired while other mutexes are held, an edge "held -> acquired"
+//! is added to the lock graph together with the locations of both acquisitions.
+//!
+//! A cycle in the lock graph whose edges come from different threads is a potential deadlock,
+//! e.g. the classic ABBA lock-order inversion. A self-loop is a double lock in a single thread.
+//! Acquiring a read lock while holding a read lock adds no edge, since readers do not exclude each other.
+//!
+//! The analysis is much faster than the reachability analysis of the model checker.
+//! If the lock graph has no cycles and the program contains no other source of blocking,
+//! such as condition variables, semaphores, channels, spin loops on atomic variables, parked threads or blocking calls while a mutex is held, then no deadlock can be reached
+//! and the model checker can be skipped.
+//!
+//! The places of the condition variables and the modelled resources are not followed,
+//! so the mutexes acquired after a `wait` returns are not part of the graph.
+//!
+//! If the panics are modelled, the places that tell whether a mutex is poisoned
+//! or whether a joined thread panicked are flags: They never block a thread,
+//! because exactly one of the two branches that read them is enabled.
+
+use log::debug;
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::data_structures::petri_net_interface::{PetriNet, PlaceRef, TransitionRef};
+use crate::naming::atomic::value_place_label as atomic_value_place_label;
+use crate::naming::channel::{
+    dropped_place_labels as channel_dropped_place_labels, place_labels as channel_place_labels,
+    received_place_label as channel_received_place_label,
+};
+use crate::naming::condvar::place_labels as condvar_place_labels;
+use crate::naming::mutex::{
+    condition_place_labels, place_label as mutex_place_label, poison_place_labels,
+    unlocked_by_panic_place_label, value_place_label as mutex_value_place_label,
+};
+use crate::naming::notify::place_labels as notify_place_labels;
+use crate::naming::once::place_labels as once_place_labels;
+use crate::naming::range::is_iteration_place_label;
+use crate::naming::semaphore::place_label as semaphore_place_label;
+use crate::naming::thread::{
+    end_place_label, join_result_place_labels, panic_place_label, park_token_place_labels,
+    start_place_label,
+};
+use crate::naming::{PROGRAM_END, PROGRAM_PANIC, PROGRAM_START};
+use crate::translator::{Primitive, PrimitiveKind, ATOMIC_TYPES, CHANNEL_CONSTRUCTORS, ONCE_TYPES};
+use crate::LockOrder;
+
+/// A call to `std::sync::Mutex::<T>::lock` recorded during the translation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LockAcquisition {
+    /// The index of the mutex that is locked.
+    pub mutex: usize,
+    /// The label of the transition that models the call.
+    pub transition: String,
+    /// The location of the call in the source code.
+    pub location: String,
+    /// Whether the lock is shared with other readers, e.g. `parking_lot::RwLock::<T>::read`.
+    pub shared: bool,
+}
+
+/// An edge in the lock graph: A mutex is acquired while another mutex is held.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LockEdge {
+    /// The index of the mutex that is held.
+    pub held: usize,
+    /// The location where the held mutex was acquired.
+    pub held_at: String,
+    /// The index of the mutex that is acquired.
+    pub acquired: usize,
+    /// The location where the mutex is acquired.
+    pub acquired_at: String,
+    /// The index of the thread where the acquisition happens. `None` is the main thread.
+    pub thread: Option<usize>,
+}
+
+impl std::fmt::Display for LockEdge {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "mutex {} (locked at {}) is held while locking mutex {} at {} in ",
+            self.held, self.held_at, self.acquired, self.acquired_at
+        )?;
+        match self.thread {
+            Some(index) => write!(f, "thread {index}"),
+            None => write!(f, "the main thread"),
+        }
+    }
+}
+
+/// A cycle in the lock graph, i.e. a potential deadlock.
+/// Each edge acquires the mutex held by the next edge.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LockCycle {
+    /// The edges that form the cycle, starting at the mutex with the lowest index.
+    pub edges: Vec<LockEdge>,
+}
+
+impl std::fmt::Display for LockCycle {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for edge in &self.edges {
+            write!(f, "mutex {} -> ", edge.held)?;
+        }
+        match self.edges.first() {
+            Some(edge) => write!(f, "mutex {}", edge.held),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The lock graph of the program, obtained from the Petri net.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LockGraph {
+    /// The edges of the graph, sorted and without duplicates.
+    edges: Vec<LockEdge>,
+    /// Whether the program contains condition variables or modelled resources.
+    has_other_primitives: bool,
+    /// Whether a thread may block on something other than a mutex while holding a mutex,
+    /// or finish while holding a mutex.
+    blocks_while_holding: bool,
+    /// Whether a thread may stop at a place other than its end place, e.g. after a diverging call.
+    has_dead_end: bool,
+}
+
+/// A state in the exploration: the place of the control flow and the mutexes held with their acquisition sites.
+/// The flag tells whether the mutex is held by a reader.
+type State = (PlaceRef, BTreeSet<(usize, String, bool)>);
+
+impl LockGraph {
+    /// Builds the lock graph from the Petri net.
+    /// The `primitives` identify the places of the mutexes, condition variables and threads.
+    /// The `acquisitions` give the source locations of the lock transitions.
+    /// The `resource_places` are the places of the resources declared in the user-supplied models.
+    pub fn new(
+        net: &PetriNet,
+        primitives: &[Primitive],
+        acquisitions: &[LockAcquisition],
+        resource_places: &[PlaceRef],
+    ) -> Self {
+        let mut labels: BTreeMap<String, PlaceRef> = BTreeMap::new();
+        for (place_ref, _) in net.places_iter() {
+            labels.insert(place_ref.label().clone(), place_ref.clone());
+        }
+        let find_place = |label: &str| labels.get(label).cloned();
+
+        let mut mutex_places: BTreeMap<PlaceRef, usize> = BTreeMap::new();
+        let mut sync_places: BTreeSet<PlaceRef> = resource_places.iter().cloned().collect();
+        let mut flag_places: BTreeSet<PlaceRef> = BTreeSet::new();
+        let mut end_places: BTreeSet<PlaceRef> = [PROGRAM_END, PROGRAM_PANIC]
+            .into_iter()
+            .filter_map(find_place)
+            .collect();
+        let mut roots: Vec<(Option<usize>, PlaceRef)> = find_place(PROGRAM_START)
+            .map(|place| (None, place))
+            .into_iter()
+            .collect();
+        let mut has_other_primitives = !resource_places.is_empty();
+        // The park token of the main thread is not a primitive created by the program.
+        let (main_token, main_no_token) = park_token_place_labels(None);
+        for place in [main_token, main_no_token]
+            .iter()
+            .filter_map(|l| find_place(l))
+        {
+            has_other_primitives = true;
+            sync_places.insert(place);
+        }
+
+        // The iterations of a loop over a range are not primitives created by the program.
+        // Exactly one of the transitions that read them is enabled, so they never block.
+        flag_places.extend(
+            labels
+                .iter()
+                .filter(|(label, _)| is_iteration_place_label(label))
+                .map(|(_, place)| place.clone()),
+        );
+
+        for primitive in primitives {
+            let index = primitive.index;
+            match primitive.kind {
+                PrimitiveKind::Mutex => {
+                    if let Some(place) = find_place(&mutex_place_label(index)) {
+                        mutex_places.insert(place.clone(), index);
+                        sync_places.insert(place);
+                    }
+                    let (not_set, set) = condition_place_labels(index);
+                    sync_places.extend([not_set, set].iter().filter_map(|l| find_place(l)));
+                    // Dropping a guard during a panic releases the mutex through this place.
+                    if let Some(place) = find_place(&unlocked_by_panic_place_label(index)) {
+                        mutex_places.insert(place.clone(), index);
+                        sync_places.insert(place);
+                    }
+                    let (not_poisoned, poisoned) = poison_place_labels(index);
+                    flag_places.extend(
+                        [not_poisoned, poisoned]
+                            .iter()
+                            .filter_map(|l| find_place(l)),
+                    );
+                    // Every value of the mutex has its own transition, so the values never block.
+                    flag_places.extend(
+                        (0..).map_while(|value| find_place(&mutex_value_place_label(index, value))),
+                    );
+                }
+                PrimitiveKind::Condvar => {
+                    has_other_primitives = true;
+                    let (wait_enabled, notify) = condvar_place_labels(index);
+                    sync_places.extend([wait_enabled, notify].iter().filter_map(|l| find_place(l)));
+                }
+                PrimitiveKind::Thread => {
+                    if let Some(place) = find_place(&start_place_label(index)) {
+                        sync_places.insert(place.clone());
+                        roots.push((Some(index), place));
+                    }
+                    for label in [end_place_label(index), panic_place_label(index)] {
+                        if let Some(place) = find_place(&label) {
+                            sync_places.insert(place.clone());
+                            end_places.insert(place);
+                        }
+                    }
+                    let (token, no_token) = park_token_place_labels(Some(index));
+                    let park_token_places: Vec<PlaceRef> = [token, no_token]
+                        .iter()
+                        .filter_map(|l| find_place(l))
+                        .collect();
+                    has_other_primitives |= !park_token_places.is_empty();
+                    sync_places.extend(park_token_places);
+                    let (ok, err) = join_result_place_labels(index);
+                    flag_places.extend([ok, err].iter().filter_map(|l| find_place(l)));
+                }
+                PrimitiveKind::Semaphore => {
+                    has_other_primitives = true;
+                    sync_places.extend(find_place(&semaphore_place_label(index)));
+                }
+                PrimitiveKind::Notify => {
+                    has_other_primitives = true;
+                    let (permit, no_permit) = notify_place_labels(index);
+                    sync_places.extend([permit, no_permit].iter().filter_map(|l| find_place(l)));
+                }
+                PrimitiveKind::Channel => {
+                    has_other_primitives = true;
+                    // The primitive does not store its constructor, look for the places of every constructor.
+                    for constructor in CHANNEL_CONSTRUCTORS {
+                        let (capacity, messages) = channel_place_labels(constructor, index);
+                        let (sender_dropped, receiver_dropped) =
+                            channel_dropped_place_labels(constructor, index);
+                        let received = channel_received_place_label(constructor, index);
+                        sync_places.extend(
+                            [
+                                capacity,
+                                messages,
+                                received,
+                                sender_dropped,
+                                receiver_dropped,
+                            ]
+                            .iter()
+                            .filter_map(|l| find_place(l)),
+                        );
+                    }
+                }
+                PrimitiveKind::Atomic => {
+                    has_other_primitives = true;
+                    // The primitive does not store the type, look for the places of every type.
+                    for atomic_type in ATOMIC_TYPES {
+                        sync_places.extend((0..).map_while(|value| {
+                            find_place(&atomic_value_place_label(atomic_type, index, value))
+                        }));
+                    }
+                }
+                PrimitiveKind::Once => {
+                    has_other_primitives = true;
+                    // The primitive does not store the type, look for the places of every type.
+                    for once_type in ONCE_TYPES {
+                        let (uninit, running, done) = once_place_labels(once_type, index);
+                        sync_places
+                            .extend([uninit, running, done].iter().filter_map(|l| find_place(l)));
+                    }
+                }
+            }
+        }
+
+        let locations: BTreeMap<&str, &str> = acquisitions
+            .iter()
+            .map(|acquisition| {
+                (
+                    acquisition.transition.as_str(),
+                    acquisition.location.as_str(),
+                )
+            })
+            .collect();
+        let shared_acquisitions: BTreeSet<&str> = acquisitions
+            .iter()
+            .filter(|acquisition| acquisition.shared)
+            .map(|acquisition| acquisition.transition.as_str())
+            .collect();
+
+        sync_places.extend(flag_places.iter().cloned());
+        let mut explorer = Explorer {
+            net,
+            mutex_places,
+            sync_places,
+            flag_places,
+            end_places,
+            locations,
+            shared_acquisitions,
+            edges: BTreeSet::new(),
+            blocks_while_holding: false,
+            has_dead_end: false,
+        };
+        for (thread, root) in roots {
+            explorer.explore_thread(thread, root);
+        }
+
+        Self {
+            edges: explorer.edges.into_iter().collect(),
+            has_other_primitives,
+            blocks_while_holding: explorer.blocks_while_holding,
+            has_dead_end: explorer.has_dead_end,
+        }
+    }
+
+    /// Returns the edges of the lock graph.
+    #[must_use]
+    pub fn edges(&self) -> &[LockEdge] {
+        &self.edges
+    }
+
+    /// Finds the cycles in the lock graph that may lead to a deadlock.
+    /// Self-loops are always reported. Longer cycles are only reported if
+    /// every edge in the cycle can be taken from a different thread.
+    #[must_use]
+    pub fn cycles(&self) -> Vec<LockCycle> {
+        let mut successors: BTreeMap<usize, BTreeSet<usize>> = BTreeMap::new();
+        for edge in &self.edges {
+            successors
+                .entry(edge.held)
+                .or_default()
+                .insert(edge.acquired);
+        }
+
+        let mut cycles = Vec::new();
+        for &start in successors.keys() {
+            let mut path = vec![start];
+            self.find_cycles_from(start, &successors, &mut path, &mut cycles);
+        }
+        cycles
+    }
+
+    /// Finds the edges of the lock graph that contradict the declared lock orders,
+    /// i.e. the second mutex is held while the first mutex is acquired.
+    #[must_use]
+    pub fn violations(&self, lock_orders: &[LockOrder]) -> Vec<LockEdge> {
+        self.edges
+            .iter()
+            .filter(|edge| {
+                lock_orders
+                    .iter()
+                    .any(|order| edge.held == order.second && edge.acquired == order.first)
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Returns `true` if the lock graph proves that no deadlock can be reached.
+    /// This is the case if the graph has no cycles, the program contains no condition variables
+    /// or modelled resources, no thread blocks on a join while holding a mutex,
+    /// no thread finishes while holding a mutex and no thread stops before its end.
+    #[must_use]
+    pub fn rules_out_deadlocks(&self) -> bool {
+        !self.has_other_primitives
+            && !self.blocks_while_holding
+            && !self.has_dead_end
+            && self.cycles().is_empty()
+    }
+
+    /// Extends `path` with every successor greater than `start` (to report each cycle only once)
+    /// and records the paths that return to `start`.
+    fn find_cycles_from(
+        &self,
+        start: usize,
+        successors: &BTreeMap<usize, BTreeSet<usize>>,
+        path: &mut Vec<usize>,
+        cycles: &mut Vec<LockCycle>,
+    ) {
+        let current = *path.last().expect("BUG: The path should not be empty");
+        let Some(next_nodes) = successors.get(&current) else {
+            return;
+        };
+        for &next in next_nodes {
+            if next == start {
+                if let Some(edges) = self.assign_edges(path, &mut Vec::new()) {
+                    cycles.push(LockCycle { edges });
+                }
+            } else if next > start && !path.contains(&next) {
+                path.push(next);
+                self.find_cycles_from(start, successors, path, cycles);
+                path.pop();
+            }
+        }
+    }
+
+    /// Chooses one edge for every step of the cycle `nodes` such that
+    /// the edges come from pairwise different threads.
+    /// Returns `None` if no such choice exists.
+    fn assign_edges(&self, nodes: &[usize], chosen: &mut Vec<LockEdge>) -> Option<Vec<LockEdge>> {
+        let step = chosen.len();
+        if step == nodes.len() {
+            return Some(chosen.clone());
+        }
+        let held = nodes[step];
+        let acquired = nodes[(step + 1) % nodes.len()];
+        for edge in &self.edges {
+            if edge.held != held || edge.acquired != acquired {
+                continue;
+            }
+            if chosen.iter().any(|other| other.thread == edge.thread) {
+                continue;
+            }
+            chosen.push(edge.clone());
+            if let Some(edges) = self.assign_edges(nodes, chosen) {
+                return Some(edges);
+            }
+            chosen.pop();
+        }
+        None
+    }
+}
+
+/// Helper for the exploration of the control flow of the threads in the Petri net.
+struct Explorer<'net> {
+    net: &'net PetriNet,
+    /// The places of the mutexes and the corresponding mutex index.
+    mutex_places: BTreeMap<PlaceRef, usize>,
+    /// The places that do not belong to the control flow of a thread.
+    sync_places: BTreeSet<PlaceRef>,
+    /// The synchronization places that never block a thread.
+    flag_places: BTreeSet<PlaceRef>,
+    /// The places where a thread or the program ends.
+    end_places: BTreeSet<PlaceRef>,
+    /// The source location of each lock transition, indexed by the transition label.
+    locations: BTreeMap<&'net str, &'net str>,
+    /// The labels of the lock transitions of readers.
+    shared_acquisitions: BTreeSet<&'net str>,
+    edges: BTreeSet<LockEdge>,
+    blocks_while_holding: bool,
+    has_dead_end: bool,
+}
+
+impl Explorer<'_> {
+    /// Explores every state reachable from the `root` place of the thread
+    /// and adds the edges found to the graph.
+    fn explore_thread(&mut self, thread: Option<usize>, root: PlaceRef) {
+        let transitions: BTreeMap<&TransitionRef, _> = self.net.transitions_iter().collect();
+        let places: BTreeMap<&PlaceRef, _> = self.net.places_iter().collect();
+
+        let mut visited: BTreeSet<State> = BTreeSet::new();
+        let mut pending: Vec<State> = vec![(root, BTreeSet::new())];
+        while let Some(state) = pending.pop() {
+            if !visited.insert(state.clone()) {
+                continue;
+            }
+            let (place, held) = state;
+            let Some(outgoing) = places.get(&place).map(|place| place.get_postset()) else {
+                continue;
+            };
+            if outgoing.is_empty() && !self.end_places.contains(&place) {
+                debug!("LOCK GRAPH: DEAD END AT {place}");
+                self.has_dead_end = true;
+            }
+            for transition_ref in outgoing {
+                let transition = transitions[transition_ref];
+                let mut new_held = held.clone();
+                let mut blocks = false;
+                for input in transition.get_preset() {
+                    if input == &place || !self.sync_places.contains(input) {
+                        continue;
+                    }
+                    match self.mutex_places.get(input) {
+                        Some(&mutex) => {
+                            let location = self
+                                .locations
+                                .get(transition_ref.label().as_str())
+                                .map_or_else(
+                                    || transition_ref.label().clone(),
+                                    ToString::to_string,
+                                );
+                            let shared = self
+                                .shared_acquisitions
+                                .contains(transition_ref.label().as_str());
+                            for (held_mutex, held_at, held_shared) in &held {
+                                // Readers do not exclude each other.
+                                if shared && *held_shared {
+                                    continue;
+                                }
+                                self.edges.insert(LockEdge {
+                                    held: *held_mutex,
+                                    held_at: held_at.clone(),
+                                    acquired: mutex,
+                                    acquired_at: location.clone(),
+                                    thread,
+                                });
+                            }
+                            new_held.insert((mutex, location, shared));
+                        }
+                        None if self.flag_places.contains(input) => {}
+                        None => blocks = true,
+                    }
+                }
+                if blocks && !held.is_empty() {
+                    debug!("LOCK GRAPH: {transition_ref} MAY BLOCK WHILE HOLDING A MUTEX");
+                    self.blocks_while_holding = true;
+                }
+                for output in transition.get_postset() {
+                    if let Some(&mutex) = self.mutex_places.get(output) {
+                        new_held.retain(|(held_mutex, _, _)| *held_mutex != mutex);
+                    }
+                }
+                let mut successors = transition
+                    .get_postset()
+                    .iter()
+                    .filter(|output| !self.sync_places.contains(*output))
+                    .peekable();
+                let reaches_end = transition
+                    .get_postset()
+                    .iter()
+                    .any(|output| self.end_places.contains(output));
+                if successors.peek().is_none() && !reaches_end {
+                    debug!("LOCK GRAPH: DEAD END AFTER {transition_ref}");
+                    self.has_dead_end = true;
+                }
+                if reaches_end && !new_held.is_empty() {
+                    debug!("LOCK GRAPH: THE THREAD MAY FINISH WHILE HOLDING A MUTEX");
+                    self.blocks_while_holding = true;
+                }
+                for successor in successors {
+                    pending.push((successor.clone(), new_held.clone()));
+                }
+            }
+        }
+    }
+}
@@ -1,3 +1,475 @@
 //! Submodule for the supported model checkers.
+//!
+//! Every model checker implements the trait `ModelChecker`, which checks a property on a Petri net.
+//! `LoLA` is the default model checker and the only one that produces witness paths,
+//! which are needed to locate lost signals and panics.
+//! Other model checkers that read PNML can be used to compare the verdicts,
+//! e.g. for regression testing the translation.
 
 pub mod lola;
+mod property;
+pub mod verifypn;
+
+use std::io::Read;
+use std::process::{Command, Output, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::data_structures::petri_net_interface::PetriNet;
+
+pub use property::Property;
+
+/// A counter to give each temporary net file a unique name within the process.
+static NET_FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// A model checker that can verify properties on a Petri net.
+pub trait ModelChecker {
+    /// Returns the name of the model checker shown in the results.
+    fn name(&self) -> &'static str;
+
+    /// Checks whether the property holds in the Petri net.
+    /// Returns `PropertyVerdict::Inconclusive` if the model checker cannot be run,
+    /// does not support the property or does not give an answer.
+    fn check(&self, net: &PetriNet, property: &Property) -> PropertyVerdict;
+}
+
+/// The supported model checkers.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Checker {
+    /// `LoLA` - A Low Level Petri Net Analyzer
+    #[default]
+    Lola,
+    /// `verifypn` - The verification engine of TAPAAL
+    Verifypn,
+}
+
+impl Checker {
+    /// Returns the model checker that implements the trait `ModelChecker`
+    /// running within the given limits, as far as the model checker supports them.
+    #[must_use]
+    pub fn backend(self, limits: Limits) -> Box<dyn ModelChecker> {
+        match self {
+            Self::Lola => Box::new(lola::Lola::new(limits)),
+            Self::Verifypn => Box::new(verifypn::Verifypn::new(limits)),
+        }
+    }
+}
+
+impl std::str::FromStr for Checker {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "lola" => Ok(Self::Lola),
+            "verifypn" => Ok(Self::Verifypn),
+            _ => Err(format!(
+                "invalid value '{s}', possible values are: lola, verifypn"
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for Checker {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Lola => write!(f, "lola"),
+            Self::Verifypn => write!(f, "verifypn"),
+        }
+    }
+}
+
+/// Resource limits for the model checker.
+/// A limit set to `None` means that the model checker runs without that limit.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Limits {
+    /// The maximum time in seconds that the model checker may run.
+    pub time_limit: Option<u64>,
+    /// The maximum number of markings that the model checker may explore.
+    pub marking_limit: Option<u64>,
+    /// The maximum time in seconds that the model checker process may run before it is killed.
+    /// Unlike the time limit, it does not depend on the model checker checking the limit itself.
+    pub timeout: Option<u64>,
+}
+
+/// Statistics of a run of the model checker.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Statistics {
+    /// The number of states, i.e. markings, explored by the model checker.
+    pub states: u64,
+    /// The number of edges, i.e. transition firings, explored by the model checker.
+    pub edges: u64,
+    /// The time taken by the model checker process.
+    pub time: std::time::Duration,
+}
+
+impl std::fmt::Display for Statistics {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{} states, {} edges in {:.3} seconds",
+            self.states,
+            self.edges,
+            self.time.as_secs_f64()
+        )
+    }
+}
+
+/// The answer of the model checker to the question "Can the program deadlock?".
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    /// A deadlock can be reached.
+    Deadlock,
+    /// No deadlock can be reached.
+    DeadlockFree,
+    /// The model checker could not give an answer, e.g. because a limit was reached.
+    /// Contains a description of the reason.
+    Inconclusive(String),
+}
+
+impl std::fmt::Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Deadlock => write!(f, "Deadlock can be reached"),
+            Self::DeadlockFree => write!(f, "The program is deadlock-free"),
+            Self::Inconclusive(reason) => write!(f, "Inconclusive: {reason}"),
+        }
+    }
+}
+
+impl From<PropertyVerdict> for Verdict {
+    /// Converts the verdict on the property `Property::Deadlock` to the answer to the question "Can the program deadlock?".
+    fn from(verdict: PropertyVerdict) -> Self {
+        match verdict {
+            PropertyVerdict::Satisfied => Self::Deadlock,
+            PropertyVerdict::NotSatisfied => Self::DeadlockFree,
+            PropertyVerdict::Inconclusive(reason) => Self::Inconclusive(reason),
+        }
+    }
+}
+
+impl From<Verdict> for PropertyVerdict {
+    /// Converts the answer to the question "Can the program deadlock?" to the verdict on the property `Property::Deadlock`.
+    fn from(verdict: Verdict) -> Self {
+        match verdict {
+            Verdict::Deadlock => Self::Satisfied,
+            Verdict::DeadlockFree => Self::NotSatisfied,
+            Verdict::Inconclusive(reason) => Self::Inconclusive(reason),
+        }
+    }
+}
+
+/// A notification on a condition variable that may be lost,
+/// i.e. `notify_one` is called while no thread is waiting on the condition variable.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LostSignal {
+    /// The index of the condition variable.
+    pub condvar: usize,
+    /// The location of the call to `notify_one` whose notification is lost.
+    pub location: String,
+    /// Whether the lost notification leads to a deadlock where a thread waits forever on the condition variable.
+    pub causes_deadlock: bool,
+}
+
+impl std::fmt::Display for LostSignal {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "The notification on condition variable {} at {} may be lost",
+            self.condvar, self.location
+        )?;
+        if self.causes_deadlock {
+            write!(f, ", causing a deadlock")?;
+        }
+        Ok(())
+    }
+}
+
+/// A panic that can be reached in the main thread or in a thread.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReachablePanic {
+    /// The index of the thread that panics or `None` for the main thread.
+    pub thread: Option<usize>,
+    /// The location where the panic starts, i.e. the last panic site of the thread in the witness path.
+    pub location: String,
+}
+
+impl std::fmt::Display for ReachablePanic {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self.thread {
+            Some(index) => write!(f, "Thread {index} may panic at {}", self.location),
+            None => write!(f, "The main thread may panic at {}", self.location),
+        }
+    }
+}
+
+/// A query of the analysis of lost signals or of panics.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AnalysisQuery {
+    /// Whether a notification on the condition variable with the given index may be lost.
+    LostSignal(usize),
+    /// Whether the lost notification on the condition variable with the given index causes a deadlock.
+    LostSignalDeadlock(usize),
+    /// Whether the thread with the given index, or the main thread if `None`, may panic.
+    Panic(Option<usize>),
+}
+
+impl std::fmt::Display for AnalysisQuery {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::LostSignal(condvar) => write!(
+                f,
+                "whether a notification on condition variable {condvar} may be lost"
+            ),
+            Self::LostSignalDeadlock(condvar) => write!(
+                f,
+                "whether the lost notification on condition variable {condvar} causes a deadlock"
+            ),
+            Self::Panic(Some(index)) => write!(f, "whether thread {index} may panic"),
+            Self::Panic(None) => write!(f, "whether the main thread may panic"),
+        }
+    }
+}
+
+/// A query for which the model checker could not give an answer, e.g. because a limit was reached.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InconclusiveQuery {
+    /// The query that could not be answered.
+    pub query: AnalysisQuery,
+    /// A description of the reason.
+    pub reason: String,
+}
+
+impl std::fmt::Display for InconclusiveQuery {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Could not check {}: {}", self.query, self.reason)
+    }
+}
+
+/// The answer of the model checker to the question "Does the property hold?".
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PropertyVerdict {
+    /// The formula of the property holds in the Petri net.
+    Satisfied,
+    /// The formula of the property does not hold in the Petri net.
+    NotSatisfied,
+    /// The model checker could not give an answer, e.g. because a limit was reached.
+    /// Contains a description of the reason.
+    Inconclusive(String),
+}
+
+impl std::fmt::Display for PropertyVerdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Satisfied => write!(f, "The property holds"),
+            Self::NotSatisfied => write!(f, "The property does not hold"),
+            Self::Inconclusive(reason) => write!(f, "Inconclusive: {reason}"),
+        }
+    }
+}
+
+/// The result of checking a property with the model checker.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PropertyResult {
+    /// The property that was checked.
+    pub property: Property,
+    /// The verdict of the model checker.
+    pub verdict: PropertyVerdict,
+    /// The sequence of transition labels produced by the model checker as a witness or counterexample.
+    /// It may be empty, e.g. if the model checker does not produce a path for the formula.
+    pub witness_path: Vec<String>,
+    /// The statistics of the model checker, if it reports them.
+    pub statistics: Option<Statistics>,
+}
+
+/// The verdicts of several model checkers on the same property.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Comparison {
+    /// The property that was checked.
+    pub property: Property,
+    /// The name of each model checker and its verdict.
+    pub verdicts: Vec<(&'static str, PropertyVerdict)>,
+}
+
+impl Comparison {
+    /// Checks the property with every model checker on the same Petri net.
+    #[must_use]
+    pub fn run(net: &PetriNet, property: &Property, checkers: &[Box<dyn ModelChecker>]) -> Self {
+        let verdicts = checkers
+            .iter()
+            .map(|checker| (checker.name(), checker.check(net, property)))
+            .collect();
+        Self {
+            property: property.clone(),
+            verdicts,
+        }
+    }
+
+    /// Returns `true` if the model checkers that gave an answer agree on it.
+    /// Inconclusive verdicts are ignored.
+    #[must_use]
+    pub fn is_consistent(&self) -> bool {
+        let mut conclusive = self
+            .verdicts
+            .iter()
+            .map(|(_, verdict)| verdict)
+            .filter(|verdict| !matches!(verdict, PropertyVerdict::Inconclusive(_)));
+        conclusive
+            .next()
+            .is_none_or(|first| conclusive.all(|verdict| verdict == first))
+    }
+}
+
+impl std::fmt::Display for Comparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "The model checkers ")?;
+        if self.is_consistent() {
+            write!(f, "agree on the property `{}`:", self.property)?;
+        } else {
+            write!(f, "disagree on the property `{}`:", self.property)?;
+        }
+        for (index, (name, verdict)) in self.verdicts.iter().enumerate() {
+            let separator = if index == 0 { "" } else { "," };
+            write!(f, "{separator} `{name}`: {verdict}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes the net to a new temporary file with the given extension using the given writer function.
+/// Returns the path of the file, which must be deleted by the caller.
+///
+/// # Errors
+///
+/// If the file cannot be created or written, then an error with the corresponding description is returned.
+pub(crate) fn write_temporary_net<F>(
+    net: &PetriNet,
+    extension: &str,
+    write: F,
+) -> Result<std::path::PathBuf, String>
+where
+    F: FnOnce(&PetriNet, &mut std::fs::File) -> Result<(), std::io::Error>,
+{
+    let filepath = std::env::temp_dir().join(format!(
+        "cargo-check-deadlock-{}-{}.{extension}",
+        std::process::id(),
+        NET_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    let mut file = std::fs::File::create(&filepath).map_err(|err| {
+        format!("Could not create the temporary file for the model checker: {err}")
+    })?;
+    write(net, &mut file).map_err(|err| {
+        format!("Could not write the net to the temporary file for the model checker: {err}")
+    })?;
+    Ok(filepath)
+}
+
+/// The interval between two checks whether the model checker process has finished.
+const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(10);
+
+/// Runs the command and collects its output.
+/// If the timeout in seconds is set and the process runs longer, then it is killed.
+///
+/// # Errors
+///
+/// If the process cannot be started, then the corresponding error is returned.
+/// If the process is killed after the timeout, then an error of kind `std::io::ErrorKind::TimedOut` is returned.
+pub(crate) fn run_with_timeout(
+    mut command: Command,
+    timeout: Option<u64>,
+) -> Result<Output, std::io::Error> {
+    let Some(timeout) = timeout else {
+        return command.output();
+    };
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    // Read the pipes in separate threads so that the process does not block on a full pipe.
+    let read_pipe = |pipe: Option<Box<dyn Read + Send>>| {
+        std::thread::spawn(move || {
+            let mut buffer = Vec::new();
+            if let Some(mut pipe) = pipe {
+                let _ = pipe.read_to_end(&mut buffer);
+            }
+            buffer
+        })
+    };
+    let stdout = read_pipe(child.stdout.take().map(|pipe| Box::new(pipe) as _));
+    let stderr = read_pipe(child.stderr.take().map(|pipe| Box::new(pipe) as _));
+
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(timeout);
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if std::time::Instant::now() >= deadline {
+            child.kill()?;
+            child.wait()?;
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::TimedOut,
+                format!("killed after the timeout of {timeout} seconds"),
+            ));
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    };
+    Ok(Output {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    })
+}
+
+#[cfg(test)]
+mod model_checker_tests {
+    use super::*;
+
+    fn comparison(verdicts: Vec<PropertyVerdict>) -> Comparison {
+        Comparison {
+            property: Property::Deadlock,
+            verdicts: verdicts
+                .into_iter()
+                .map(|verdict| ("checker", verdict))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn process_is_killed_after_timeout() {
+        let mut command = Command::new("sleep");
+        command.arg("5");
+        let err = run_with_timeout(command, Some(0)).expect_err("The process should be killed");
+        assert_eq!(err.kind(), std::io::ErrorKind::TimedOut);
+    }
+
+    #[test]
+    fn output_is_collected_within_timeout() {
+        let mut command = Command::new("echo");
+        command.arg("result: yes");
+        let output = run_with_timeout(command, Some(5)).expect("The process should finish");
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "result: yes\n");
+    }
+
+    #[test]
+    fn equal_verdicts_are_consistent() {
+        let comparison = comparison(vec![PropertyVerdict::Satisfied, PropertyVerdict::Satisfied]);
+        assert!(comparison.is_consistent());
+    }
+
+    #[test]
+    fn different_verdicts_are_not_consistent() {
+        let comparison = comparison(vec![
+            PropertyVerdict::Satisfied,
+            PropertyVerdict::NotSatisfied,
+        ]);
+        assert!(!comparison.is_consistent());
+    }
+
+    #[test]
+    fn inconclusive_verdicts_are_ignored() {
+        let comparison = comparison(vec![
+            PropertyVerdict::Inconclusive("not found".to_string()),
+            PropertyVerdict::NotSatisfied,
+        ]);
+        assert!(comparison.is_consistent());
+    }
+}
@@ -1,53 +1,496 @@
 //! Submodule for running the `LoLA` model checker.
+//!
+//! Besides the deadlock analysis, `LoLA` is used to find lost signals:
+//! A notification on a condition variable is lost if the `lost_signal` transition
+//! of the condition variable can fire. The lost signal causes a deadlock if a deadlock can be reached
+//! where a thread waits on the condition variable and the place `signal_lost`, which is marked
+//! by the first lost signal, has a token.
+//!
+//! It is also used to find reachable panics: The main thread panics if `PROGRAM_PANIC` can be marked.
+//! If the threads have their own panic places, a thread panics if its place `THREAD_N_PANIC` can be marked.
+//! The panic starts at the last panic site of the thread in the witness path.
+//!
+//! Only the result and the number of states and edges are read from the JSON output of `LoLA` (`--json`),
+//! which is written to a file next to the net file with the extension `json`.
+//! The witness path is read from the text output of `--path` and the time is measured around the `lola` process.
+//!
+//! The executable is searched in this order: The path set with `Lola::executable`,
+//! the path in the environment variable `LOLA_PATH`, `lola` in the `$PATH` and `./assets/lola`.
 
 use log::info;
-use std::process::Command;
-
-/// Checks for deadlock using the `LoLA` model checker.
-/// Returns `true` if deadlock can be reached, otherwise returns `false`.
-///
-/// The CTL* formula used is `EF (DEADLOCK AND (PROGRAM_END = 0 AND PROGRAM_PANIC = 0))`.
-/// This excludes the `PROGRAM_PANIC` and `PROGRAM_END` from being considered as deadlock states.
-///
-/// # Panics
-///
-/// If the command `lola` is not found, then the function panics.
-/// If the command `lola` produces an extraneous output, then the function panics.
-#[must_use]
-pub fn check_deadlock(net_filepath: &std::path::PathBuf) -> bool {
-    let mut cmd = Command::new("lola");
-    let cmd = cmd
-        .arg(net_filepath)
-        .arg("--formula=EF (DEADLOCK AND (PROGRAM_END = 0 AND PROGRAM_PANIC = 0))");
-
-    let mut backup_cmd = Command::new("./assets/lola");
-    let backup_cmd = backup_cmd
-        .arg(net_filepath)
-        .arg("--formula=EF (DEADLOCK AND (PROGRAM_END = 0 AND PROGRAM_PANIC = 0))");
-
-    let output = match cmd.output() {
-        Ok(output) => output,
-        Err(err) => {
-            if err.kind() == std::io::ErrorKind::NotFound {
+use serde::Deserialize;
+use std::collections::BTreeSet;
+use std::process::{Command, Output};
+
+use super::property::DEADLOCK_FORMULA;
+use super::{
+    run_with_timeout, write_temporary_net, AnalysisQuery, InconclusiveQuery, Limits, LostSignal,
+    ModelChecker, Property, PropertyResult, PropertyVerdict, ReachablePanic, Statistics, Verdict,
+};
+use crate::data_structures::petri_net_interface::PetriNet;
+use crate::naming::condvar::{
+    lost_signal_again_label, lost_signal_place_labels, place_labels, transition_labels,
+};
+use crate::naming::thread::panic_place_label;
+use crate::naming::PROGRAM_PANIC;
+use crate::translator::{NotifyCall, PanicSite};
+
+/// The environment variable with the path to the `lola` executable.
+pub const LOLA_PATH_ENV_VAR: &str = "LOLA_PATH";
+
+/// The model checker `LoLA` running within the given limits.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Lola {
+    limits: Limits,
+    executable: Option<std::path::PathBuf>,
+}
+
+impl ModelChecker for Lola {
+    fn name(&self) -> &'static str {
+        "LoLA"
+    }
+
+    /// Writes the net to a temporary file in `LoLA` format and checks the property on it.
+    fn check(&self, net: &PetriNet, property: &Property) -> PropertyVerdict {
+        let net_filepath = match write_temporary_net(net, "lola", PetriNet::to_lola) {
+            Ok(net_filepath) => net_filepath,
+            Err(err_str) => return PropertyVerdict::Inconclusive(err_str),
+        };
+        let result = self.check_property(&net_filepath, net, property);
+        // The temporary file is not needed anymore. Failing to delete it is not an error.
+        let _ = std::fs::remove_file(&net_filepath);
+        result.verdict
+    }
+}
+
+/// The result of the deadlock analysis with `LoLA`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeadlockAnalysis {
+    /// The verdict of the model checker.
+    pub verdict: Verdict,
+    /// The sequence of transition labels that leads to the deadlock.
+    /// It is empty if no deadlock was found.
+    pub witness_path: Vec<String>,
+    /// The statistics of the search. `None` if `LoLA` did not give an answer.
+    pub statistics: Option<Statistics>,
+}
+
+/// The result of the analysis of lost signals with `LoLA`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LostSignalAnalysis {
+    /// The notifications on condition variables that may be lost.
+    pub lost_signals: Vec<LostSignal>,
+    /// The queries for which `LoLA` gave no answer.
+    pub inconclusive: Vec<InconclusiveQuery>,
+}
+
+/// The result of the analysis of panics with `LoLA`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PanicAnalysis {
+    /// The panics that can be reached in the main thread or in the threads.
+    pub panics: Vec<ReachablePanic>,
+    /// The queries for which `LoLA` gave no answer.
+    pub inconclusive: Vec<InconclusiveQuery>,
+}
+
+impl Lola {
+    /// Creates a new `Lola` model checker running within the given limits.
+    #[must_use]
+    pub const fn new(limits: Limits) -> Self {
+        Self {
+            limits,
+            executable: None,
+        }
+    }
+
+    /// Sets the path to the `lola` executable.
+    /// It takes precedence over the environment variable `LOLA_PATH` and the `$PATH`.
+    #[must_use]
+    pub fn executable(mut self, executable: impl Into<std::path::PathBuf>) -> Self {
+        self.executable = Some(executable.into());
+        self
+    }
+
+    /// Checks for deadlock using the `LoLA` model checker within the given limits.
+    /// If a deadlock is found, the witness path leading to it is included in the result.
+    ///
+    /// The witness path is written by `LoLA` to a file next to the net file with the extension `path`.
+    /// The file is deleted after reading it.
+    ///
+    /// Instead of panicking, the verdict is `Verdict::Inconclusive` if `LoLA` cannot be run,
+    /// a limit is reached or the output cannot be parsed.
+    #[must_use]
+    pub fn analyze_deadlock(&self, net_filepath: &std::path::Path) -> DeadlockAnalysis {
+        match self.check_formula(net_filepath, DEADLOCK_FORMULA) {
+            Answer::Yes(witness_path, statistics) => DeadlockAnalysis {
+                verdict: Verdict::Deadlock,
+                witness_path,
+                statistics: Some(statistics),
+            },
+            Answer::No(_, statistics) => DeadlockAnalysis {
+                verdict: Verdict::DeadlockFree,
+                witness_path: Vec::new(),
+                statistics: Some(statistics),
+            },
+            Answer::Unknown(reason) => inconclusive(reason),
+        }
+    }
+
+    /// Checks whether the property holds using the `LoLA` model checker within the given limits.
+    /// The `net` is the one written to `net_filepath`, which the formula of the property may depend on.
+    /// The witness path is included in the result if `LoLA` produces one.
+    ///
+    /// Instead of panicking, the verdict is `PropertyVerdict::Inconclusive` if `LoLA` cannot be run,
+    /// a limit is reached or the output cannot be parsed.
+    #[must_use]
+    pub fn check_property(
+        &self,
+        net_filepath: &std::path::Path,
+        net: &PetriNet,
+        property: &Property,
+    ) -> PropertyResult {
+        let (verdict, witness_path, statistics) = match self
+            .check_formula(net_filepath, &property.formula(net))
+        {
+            Answer::Yes(witness_path, statistics) => {
+                (PropertyVerdict::Satisfied, witness_path, Some(statistics))
+            }
+            Answer::No(witness_path, statistics) => (
+                PropertyVerdict::NotSatisfied,
+                witness_path,
+                Some(statistics),
+            ),
+            Answer::Unknown(reason) => (PropertyVerdict::Inconclusive(reason), Vec::new(), None),
+        };
+        PropertyResult {
+            property: property.clone(),
+            verdict,
+            witness_path,
+            statistics,
+        }
+    }
+
+    /// Checks for every condition variable notified in `notify_calls` whether a notification may be lost.
+    /// If `deadlock` is `true`, i.e. a deadlock was found before, it also checks whether the lost signal causes a deadlock.
+    ///
+    /// The location of each lost signal is the last call to `notify_one` on the condition variable in the witness path.
+    /// The queries for which `LoLA` gives no answer are reported as inconclusive.
+    #[must_use]
+    pub fn analyze_lost_signals(
+        &self,
+        net_filepath: &std::path::Path,
+        notify_calls: &[NotifyCall],
+        deadlock: bool,
+    ) -> LostSignalAnalysis {
+        let condvars: BTreeSet<usize> = notify_calls.iter().map(|call| call.condvar).collect();
+        let mut analysis = LostSignalAnalysis::default();
+        for condvar in condvars {
+            let (wait_enabled, _) = place_labels(condvar);
+            let (_, signal_lost) = lost_signal_place_labels(condvar);
+            let (_, lost_signal, _) = transition_labels(condvar);
+            let lost_signal_again = lost_signal_again_label(condvar);
+            let formula = format!("EF FIREABLE({lost_signal})");
+            let witness_path = match self.check_formula(net_filepath, &formula) {
+                Answer::Yes(witness_path, _) => witness_path,
+                Answer::No(..) => continue,
+                Answer::Unknown(reason) => {
+                    analysis.inconclusive.push(InconclusiveQuery {
+                        query: AnalysisQuery::LostSignal(condvar),
+                        reason,
+                    });
+                    continue;
+                }
+            };
+            let mut location = find_notify_location(&witness_path, condvar, notify_calls);
+            let mut causes_deadlock = false;
+            if deadlock {
+                let formula = format!(
+                    "EF (DEADLOCK AND (PROGRAM_END = 0 AND PROGRAM_PANIC = 0 AND {signal_lost} > 0 AND {wait_enabled} = 0))"
+                );
+                match self.check_formula(net_filepath, &formula) {
+                    Answer::Yes(witness_path, _) => {
+                        causes_deadlock = true;
+                        // The notification lost last before the deadlock
+                        if let Some(position) = witness_path
+                            .iter()
+                            .rposition(|t| *t == lost_signal || *t == lost_signal_again)
+                        {
+                            location = find_notify_location(
+                                &witness_path[..position],
+                                condvar,
+                                notify_calls,
+                            )
+                            .or(location);
+                        }
+                    }
+                    Answer::No(..) => {}
+                    Answer::Unknown(reason) => analysis.inconclusive.push(InconclusiveQuery {
+                        query: AnalysisQuery::LostSignalDeadlock(condvar),
+                        reason,
+                    }),
+                }
+            }
+            let location = location.unwrap_or_else(|| "unknown location".to_string());
+            info!("Found a lost signal on condition variable {condvar} at {location}");
+            analysis.lost_signals.push(LostSignal {
+                condvar,
+                location,
+                causes_deadlock,
+            });
+        }
+        analysis
+    }
+
+    /// Checks whether the main thread can panic and whether the given `threads` can panic.
+    /// The threads must have their own panic places in the net, otherwise they are not reported.
+    ///
+    /// The location of each panic is the last site in `panic_sites` of the same thread in the witness path.
+    /// The threads for which `LoLA` gives no answer are reported as inconclusive.
+    #[must_use]
+    pub fn analyze_panics(
+        &self,
+        net_filepath: &std::path::Path,
+        panic_sites: &[PanicSite],
+        threads: &[usize],
+    ) -> PanicAnalysis {
+        let mut analysis = PanicAnalysis::default();
+        let candidates = std::iter::once((None, PROGRAM_PANIC.to_string())).chain(
+            threads
+                .iter()
+                .map(|index| (Some(*index), panic_place_label(*index))),
+        );
+        for (thread, panic_place) in candidates {
+            let formula = format!("EF {panic_place} > 0");
+            let witness_path = match self.check_formula(net_filepath, &formula) {
+                Answer::Yes(witness_path, _) => witness_path,
+                Answer::No(..) => continue,
+                Answer::Unknown(reason) => {
+                    analysis.inconclusive.push(InconclusiveQuery {
+                        query: AnalysisQuery::Panic(thread),
+                        reason,
+                    });
+                    continue;
+                }
+            };
+            let location = witness_path
+                .iter()
+                .rev()
+                .find_map(|transition| {
+                    panic_sites
+                        .iter()
+                        .find(|site| site.thread == thread && site.transition == *transition)
+                })
+                .map_or_else(
+                    || "unknown location".to_string(),
+                    |site| site.location.clone(),
+                );
+            info!("Found a reachable panic in {panic_place} at {location}");
+            analysis.panics.push(ReachablePanic { thread, location });
+        }
+        analysis
+    }
+
+    /// Checks the formula using the `LoLA` model checker within the given limits.
+    /// If the formula holds, the witness path is included in the answer.
+    /// The result and the statistics are read from the JSON output of `LoLA`.
+    fn check_formula(&self, net_filepath: &std::path::Path, formula: &str) -> Answer {
+        let witness_filepath = net_filepath.with_extension("path");
+        let json_filepath = net_filepath.with_extension("json");
+        let mut args = vec![
+            net_filepath.as_os_str().to_os_string(),
+            format!("--formula={formula}").into(),
+            format!("--path={}", witness_filepath.to_string_lossy()).into(),
+            format!("--json={}", json_filepath.to_string_lossy()).into(),
+        ];
+        if let Some(time_limit) = self.limits.time_limit {
+            args.push(format!("--timelimit={time_limit}").into());
+        }
+        if let Some(marking_limit) = self.limits.marking_limit {
+            args.push(format!("--markinglimit={marking_limit}").into());
+        }
+
+        let start = std::time::Instant::now();
+        let output = self.run(&args);
+        let time = start.elapsed();
+        let json_output = std::fs::read_to_string(&json_filepath);
+        let witness_path: Vec<String> = std::fs::read_to_string(&witness_filepath)
+            .map(|contents| contents.lines().map(str::to_string).collect())
+            .unwrap_or_default();
+        // The files may not exist, e.g. if the formula does not have a witness or `lola` was killed.
+        let _ = std::fs::remove_file(&json_filepath);
+        let _ = std::fs::remove_file(&witness_filepath);
+
+        let output = match output {
+            Ok(output) => output,
+            Err(err_str) => return Answer::Unknown(err_str),
+        };
+        match json_output
+            .ok()
+            .and_then(|contents| parse_json_output(&contents, time))
+        {
+            Some((true, statistics)) => Answer::Yes(witness_path, statistics),
+            Some((false, statistics)) => Answer::No(witness_path, statistics),
+            None => {
+                // Prefer the message explaining which limit was reached, otherwise use the last line.
+                let stderr_string = String::from_utf8_lossy(&output.stderr);
+                let reason = stderr_string
+                    .lines()
+                    .find(|line| line.contains("limit reached"))
+                    .or_else(|| stderr_string.lines().last())
+                    .map_or("no output", |line| line.trim_start_matches("lola:").trim());
+                Answer::Unknown(format!("`lola` did not produce a result: {reason}"))
+            }
+        }
+    }
+
+    /// Runs `lola` with the given arguments within the timeout of the limits.
+    /// Tries the executable set with `Lola::executable`, then the one in the environment variable `LOLA_PATH`.
+    /// If neither is set, tries first with the `lola` in the `$PATH` and then with the one in the `./assets/` folder.
+    ///
+    /// # Errors
+    ///
+    /// If `lola` cannot be found or executed, then an error with the corresponding description is returned.
+    /// If `lola` runs longer than the timeout, then it is killed and an error is returned.
+    fn run<S: AsRef<std::ffi::OsStr>>(&self, args: &[S]) -> Result<Output, String> {
+        let run_executable = |executable: &std::path::Path| {
+            let mut command = Command::new(executable);
+            command.args(args);
+            run_with_timeout(command, self.limits.timeout)
+        };
+        let configured_executable = self.executable.clone().or_else(|| {
+            std::env::var_os(LOLA_PATH_ENV_VAR)
+                .filter(|path| !path.is_empty())
+                .map(std::path::PathBuf::from)
+        });
+        if let Some(executable) = configured_executable {
+            return run_executable(&executable).map_err(|err| match err.kind() {
+                std::io::ErrorKind::TimedOut => format!("`lola` was {err}"),
+                _ => format!(
+                    "`lola` could not be executed at {}: {err}",
+                    executable.to_string_lossy()
+                ),
+            });
+        }
+
+        let err = match run_executable(std::path::Path::new("lola")) {
+            Ok(output) => return Ok(output),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
                 info!("`lola` was not found in the $PATH. Please check that the program is installed and added to the $PATH");
-            } else {
-                panic!("There was an unknown error while executing `lola`: {err}");
+                match run_executable(std::path::Path::new("./assets/lola")) {
+                    Ok(output) => return Ok(output),
+                    Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                        return Err(
+                            "`lola` was not found in the $PATH nor in the `./assets/` folder"
+                                .to_string(),
+                        )
+                    }
+                    Err(err) => err,
+                }
             }
-            backup_cmd
-                .output()
-                .expect("`lola` was not found in the $PATH nor in the `./assets/` folder")
+            Err(err) => err,
+        };
+        match err.kind() {
+            std::io::ErrorKind::TimedOut => Err(format!("`lola` was {err}")),
+            _ => Err(format!(
+                "There was an unknown error while executing `lola`: {err}"
+            )),
         }
+    }
+}
+
+/// The answer of `LoLA` to a formula.
+enum Answer {
+    /// The formula holds. Contains the witness path, if any, and the statistics.
+    Yes(Vec<String>, Statistics),
+    /// The formula does not hold. Contains the counterexample path, if any, and the statistics.
+    No(Vec<String>, Statistics),
+    /// `LoLA` could not give an answer. Contains a description of the reason.
+    Unknown(String),
+}
+
+/// The JSON output of `LoLA`. Only the fields needed for the result are deserialized.
+#[derive(Deserialize)]
+struct JsonOutput {
+    /// The analysis is missing if `LoLA` aborted before finishing it, e.g. because a limit was reached.
+    analysis: Option<JsonAnalysis>,
+}
+
+/// The result of the analysis in the JSON output of `LoLA`.
+#[derive(Deserialize)]
+struct JsonAnalysis {
+    /// The result is `null` if `LoLA` could not decide the formula.
+    result: Option<bool>,
+    stats: JsonStatistics,
+}
+
+/// The statistics of the search in the JSON output of `LoLA`.
+#[derive(Deserialize)]
+struct JsonStatistics {
+    states: u64,
+    edges: u64,
+}
+
+/// Parses the JSON output of `LoLA` and returns the result of the formula with the statistics.
+/// `time` is the time taken by the `lola` process, which is not read from the JSON output.
+/// Returns `None` if the output cannot be parsed or does not contain a result.
+fn parse_json_output(contents: &str, time: std::time::Duration) -> Option<(bool, Statistics)> {
+    let output: JsonOutput = serde_json::from_str(contents).ok()?;
+    let analysis = output.analysis?;
+    let statistics = Statistics {
+        states: analysis.stats.states,
+        edges: analysis.stats.edges,
+        time,
     };
+    Some((analysis.result?, statistics))
+}
+
+/// Finds the location of the last call to `notify_one` on the condition variable in the witness path.
+fn find_notify_location(
+    witness_path: &[String],
+    condvar: usize,
+    notify_calls: &[NotifyCall],
+) -> Option<String> {
+    witness_path.iter().rev().find_map(|transition| {
+        notify_calls
+            .iter()
+            .find(|call| call.condvar == condvar && call.transition == *transition)
+            .map(|call| call.location.clone())
+    })
+}
+
+/// Creates an inconclusive result with the given reason.
+const fn inconclusive(reason: String) -> DeadlockAnalysis {
+    DeadlockAnalysis {
+        verdict: Verdict::Inconclusive(reason),
+        witness_path: Vec::new(),
+        statistics: None,
+    }
+}
 
-    // For some reason `LoLA` only generates output to `stderr`.
-    // Parse the answer to the reachability analysis and panic otherwise.
-    let stderr_string =
-        String::from_utf8(output.stderr).expect("Failed to convert the `lola` stderr to UTF-8");
-    if stderr_string.contains("result: yes") {
-        return true;
+#[cfg(test)]
+mod lola_tests {
+    use super::*;
+
+    #[test]
+    fn parses_result_and_statistics() {
+        let contents = r#"{"analysis": {"formula": {"parsed": "EF (PROGRAM_END > 0)", "type": "reachability"}, "result": true, "stats": {"edges": 2, "states": 3}, "type": "modelchecking"}, "call": {"error": null}}"#;
+        let time = std::time::Duration::from_millis(5);
+        assert_eq!(
+            parse_json_output(contents, time),
+            Some((
+                true,
+                Statistics {
+                    states: 3,
+                    edges: 2,
+                    time
+                }
+            ))
+        );
     }
-    if stderr_string.contains("result: no") {
-        return false;
+
+    #[test]
+    fn missing_analysis_gives_no_result() {
+        let contents = r#"{"call": {"error": null}, "files": {"net": {"filename": "net.lola"}}}"#;
+        assert_eq!(parse_json_output(contents, std::time::Duration::ZERO), None);
     }
-    panic!("Unknown output in command `lola`: {stderr_string}");
 }
@@ -0,0 +1,259 @@
+//! Submodule for the properties that the model checker can verify on the Petri net.
+//!
+//! A property is either one of the predefined properties or a raw CTL* or LTL formula
+//! in the syntax of `LoLA`, e.g. `EF (PROGRAM_END > 0 AND MUTEX_0 = 0)`.
+//!
+//! The labels of the Petri net can be written with user-friendly identifiers inside the formula:
+//! - `mutex(N)`: The place of the mutex `N`. It has a token if the mutex is unlocked.
+//! - `thread_start(N)` and `thread_end(N)`: The start and end place of the thread `N`.
+//! - `condvar_notify(N)`: The place that receives the notifications of the condition variable `N`.
+//! - `lost_signal(N)`: The transition that loses a notification of the condition variable `N`.
+//! - `resource(NAME)`: The place of a resource declared in the user-supplied models.
+//!
+//! All the keywords of `LoLA` are written in uppercase, so every lowercase identifier
+//! followed by an opening parenthesis is treated as a user-friendly identifier.
+
+use crate::data_structures::petri_net_interface::PetriNet;
+use crate::naming::condvar::{place_labels as condvar_place_labels, transition_labels};
+use crate::naming::model::resource_place_label;
+use crate::naming::mutex::place_label as mutex_place_label;
+use crate::naming::thread::{end_place_label, panic_place_label, start_place_label};
+
+/// The CTL* formula used for the deadlock analysis.
+/// This excludes the `PROGRAM_PANIC` and `PROGRAM_END` from being considered as deadlock states.
+pub const DEADLOCK_FORMULA: &str = "EF (DEADLOCK AND (PROGRAM_END = 0 AND PROGRAM_PANIC = 0))";
+
+/// A property of the Petri net that the model checker can verify.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Property {
+    /// `deadlock`: A deadlock can be reached.
+    Deadlock,
+    /// `panic`: The program can panic.
+    Panic,
+    /// `thread-never-terminates(N)`: The thread `N` may never terminate, i.e. neither end nor panic.
+    ThreadNeverTerminates(usize),
+    /// `mutex-held-at-end(N)`: The mutex `N` may be locked when the program ends.
+    MutexHeldAtEnd(usize),
+    /// A raw formula with the user-friendly identifiers already resolved to labels of the Petri net.
+    Formula(String),
+}
+
+impl Property {
+    /// Returns the formula in the syntax of `LoLA` that expresses the property on the given net.
+    /// If the thread of `ThreadNeverTerminates` has its own panic place in the net,
+    /// a panic also terminates the thread.
+    #[must_use]
+    pub fn formula(&self, net: &PetriNet) -> String {
+        match self {
+            Self::Deadlock => DEADLOCK_FORMULA.to_string(),
+            Self::Panic => "EF PROGRAM_PANIC > 0".to_string(),
+            Self::ThreadNeverTerminates(index) => {
+                let end_place = end_place_label(*index);
+                let panic_place = panic_place_label(*index);
+                if has_place(net, &panic_place) {
+                    format!("EG ({end_place} = 0 AND {panic_place} = 0)")
+                } else {
+                    format!("EG {end_place} = 0")
+                }
+            }
+            Self::MutexHeldAtEnd(index) => {
+                format!("EF (PROGRAM_END > 0 AND {} = 0)", mutex_place_label(*index))
+            }
+            Self::Formula(formula) => formula.clone(),
+        }
+    }
+}
+
+impl std::fmt::Display for Property {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Deadlock => write!(f, "deadlock"),
+            Self::Panic => write!(f, "panic"),
+            Self::ThreadNeverTerminates(index) => write!(f, "thread-never-terminates({index})"),
+            Self::MutexHeldAtEnd(index) => write!(f, "mutex-held-at-end({index})"),
+            Self::Formula(formula) => write!(f, "{formula}"),
+        }
+    }
+}
+
+impl std::str::FromStr for Property {
+    type Err = String;
+
+    /// Parses a predefined property by its name or a raw formula.
+    /// The user-friendly identifiers in the raw formula are resolved to labels of the Petri net.
+    ///
+    /// # Errors
+    ///
+    /// If the property is empty, an identifier is unknown or an argument is not valid, then an error is returned.
+    fn from_str(property: &str) -> Result<Self, Self::Err> {
+        let property = property.trim();
+        if property.is_empty() {
+            return Err("The property must not be empty".to_string());
+        }
+        match property {
+            "deadlock" => return Ok(Self::Deadlock),
+            "panic" => return Ok(Self::Panic),
+            _ => {}
+        }
+        if let Some((name, argument)) = split_call(property) {
+            match name {
+                "thread-never-terminates" => {
+                    return parse_index(name, argument).map(Self::ThreadNeverTerminates);
+                }
+                "mutex-held-at-end" => {
+                    return parse_index(name, argument).map(Self::MutexHeldAtEnd);
+                }
+                _ => {}
+            }
+        }
+        resolve_identifiers(property).map(Self::Formula)
+    }
+}
+
+/// Checks whether the net contains a place with the given label.
+pub fn has_place(net: &PetriNet, label: &str) -> bool {
+    net.places_iter().any(|(place, _)| place.label() == label)
+}
+
+/// Splits a property of the form `name(argument)` into its name and its argument.
+fn split_call(property: &str) -> Option<(&str, &str)> {
+    let (name, rest) = property.split_once('(')?;
+    let argument = rest.strip_suffix(')')?;
+    Some((name.trim(), argument.trim()))
+}
+
+/// Parses the argument of the identifier `name` as an index.
+fn parse_index(name: &str, argument: &str) -> Result<usize, String> {
+    argument
+        .parse()
+        .map_err(|_| format!("The argument of `{name}` must be a number, found `{argument}`"))
+}
+
+/// Replaces the user-friendly identifiers in the formula with the labels of the Petri net.
+///
+/// # Errors
+///
+/// If an identifier is unknown, its argument is not valid or the parenthesis is not closed,
+/// then an error is returned.
+fn resolve_identifiers(formula: &str) -> Result<String, String> {
+    let mut resolved = String::with_capacity(formula.len());
+    let mut rest = formula;
+    while let Some(start) = rest.find(|c: char| c.is_ascii_alphanumeric() || c == '_') {
+        resolved.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let end = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len());
+        let identifier = &rest[..end];
+        let is_call = rest[end..].starts_with('(');
+        if !is_call || !identifier.starts_with(|c: char| c.is_ascii_lowercase()) {
+            resolved.push_str(identifier);
+            rest = &rest[end..];
+            continue;
+        }
+        let Some(close) = rest[end..].find(')') else {
+            return Err(format!("Missing closing parenthesis after `{identifier}(`"));
+        };
+        let argument = rest[end + 1..end + close].trim();
+        resolved.push_str(&resolve_identifier(identifier, argument)?);
+        rest = &rest[end + close + 1..];
+    }
+    resolved.push_str(rest);
+    Ok(resolved)
+}
+
+/// Resolves a single user-friendly identifier with its argument to a label of the Petri net.
+fn resolve_identifier(identifier: &str, argument: &str) -> Result<String, String> {
+    match identifier {
+        "mutex" => parse_index(identifier, argument).map(mutex_place_label),
+        "thread_start" => parse_index(identifier, argument).map(start_place_label),
+        "thread_end" => parse_index(identifier, argument).map(end_place_label),
+        "condvar_notify" => {
+            parse_index(identifier, argument).map(|index| condvar_place_labels(index).1)
+        }
+        "lost_signal" => parse_index(identifier, argument).map(|index| transition_labels(index).1),
+        "resource" if !argument.is_empty() => Ok(resource_place_label(argument)),
+        "resource" => Err("The argument of `resource` must be the name of a resource".to_string()),
+        _ => Err(format!("Unknown identifier `{identifier}` in the property")),
+    }
+}
+
+#[cfg(test)]
+mod property_tests {
+    use super::*;
+
+    #[test]
+    fn parses_predefined_properties() {
+        assert_eq!("deadlock".parse(), Ok(Property::Deadlock));
+        assert_eq!(" panic ".parse(), Ok(Property::Panic));
+        assert_eq!(
+            "thread-never-terminates(2)".parse(),
+            Ok(Property::ThreadNeverTerminates(2))
+        );
+        assert_eq!(
+            "mutex-held-at-end( 0 )".parse(),
+            Ok(Property::MutexHeldAtEnd(0))
+        );
+    }
+
+    #[test]
+    fn thread_never_terminates_excludes_the_panic_place_if_present() {
+        let property = Property::ThreadNeverTerminates(1);
+        assert_eq!(property.formula(&PetriNet::new()), "EG THREAD_1_END = 0");
+
+        let mut net = PetriNet::new();
+        net.add_place(&panic_place_label(1));
+        assert_eq!(
+            property.formula(&net),
+            "EG (THREAD_1_END = 0 AND THREAD_1_PANIC = 0)"
+        );
+    }
+
+    #[test]
+    fn resolves_identifiers_in_formula() {
+        let property: Property = "EF (PROGRAM_END > 0 AND mutex(0) = 0 AND thread_end(12) = 0)"
+            .parse()
+            .expect("The formula should be valid");
+        assert_eq!(
+            property.formula(&PetriNet::new()),
+            "EF (PROGRAM_END > 0 AND MUTEX_0 = 0 AND THREAD_12_END = 0)"
+        );
+    }
+
+    #[test]
+    fn resolves_transitions_and_resources() {
+        let property: Property = "EF (FIREABLE(lost_signal(1)) AND resource(device) = 0)"
+            .parse()
+            .expect("The formula should be valid");
+        assert_eq!(
+            property.formula(&PetriNet::new()),
+            "EF (FIREABLE(CONDVAR_1_LOST_SIGNAL) AND RESOURCE_device = 0)"
+        );
+    }
+
+    #[test]
+    fn keeps_labels_of_the_net() {
+        let property: Property = "AG main_BB3 = 0"
+            .parse()
+            .expect("The formula should be valid");
+        assert_eq!(property.formula(&PetriNet::new()), "AG main_BB3 = 0");
+    }
+
+    #[test]
+    fn rejects_unknown_identifiers() {
+        assert_eq!(
+            "EF semaphore(0) = 0".parse::<Property>(),
+            Err("Unknown identifier `semaphore` in the property".to_string())
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_arguments() {
+        assert_eq!(
+            "EF mutex(first) = 0".parse::<Property>(),
+            Err("The argument of `mutex` must be a number, found `first`".to_string())
+        );
+        assert!("EF mutex(0 = 0".parse::<Property>().is_err());
+        assert!("   ".parse::<Property>().is_err());
+    }
+}
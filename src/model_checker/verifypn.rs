@@ -0,0 +1,229 @@
+//! Submodule for running `verifypn`, the verification engine of TAPAAL <https://www.tapaal.net/>.
+//!
+//! The net is written in the PNML dialect accepted by TAPAAL and the property
+//! is written as a query in the XML format of the Model Checking Contest: <https://mcc.lip6.fr/2024/pdf/manual.pdf>
+//! Only the predefined properties can be translated to a query.
+//! Raw formulas are written in the syntax of `LoLA` and are not supported.
+//!
+//! `verifypn` is searched in the `$PATH` under the names `verifypn` and `verifypn-linux64`,
+//! the name of the executable in the TAPAAL releases.
+
+use log::info;
+use std::process::{Command, Output};
+
+use super::property::has_place;
+use super::{
+    run_with_timeout, write_temporary_net, Limits, ModelChecker, Property, PropertyVerdict,
+};
+use crate::data_structures::petri_net_interface::PetriNet;
+use crate::export::to_tapaal_pnml;
+use crate::naming::mutex::place_label as mutex_place_label;
+use crate::naming::thread::{end_place_label, panic_place_label};
+use crate::naming::{PROGRAM_END, PROGRAM_PANIC};
+
+/// The names of the `verifypn` executable, in the order they are tried.
+const EXECUTABLE_NAMES: [&str; 2] = ["verifypn", "verifypn-linux64"];
+
+/// The model checker `verifypn`.
+/// Only the timeout of the limits is supported, since the options of `verifypn` differ from those of `LoLA`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Verifypn {
+    limits: Limits,
+}
+
+impl Verifypn {
+    /// Creates a new `Verifypn` model checker running within the timeout of the given limits.
+    #[must_use]
+    pub const fn new(limits: Limits) -> Self {
+        Self { limits }
+    }
+}
+
+impl ModelChecker for Verifypn {
+    fn name(&self) -> &'static str {
+        "verifypn"
+    }
+
+    /// Writes the net and the query to temporary files and checks the property on them.
+    fn check(&self, net: &PetriNet, property: &Property) -> PropertyVerdict {
+        let Some(formula) = to_query_formula(property, net) else {
+            return PropertyVerdict::Inconclusive(format!(
+                "The property `{property}` is not supported by `verifypn`"
+            ));
+        };
+        let net_filepath =
+            match write_temporary_net(net, "pnml", |net, file| to_tapaal_pnml(net, "net", file)) {
+                Ok(net_filepath) => net_filepath,
+                Err(err_str) => return PropertyVerdict::Inconclusive(err_str),
+            };
+        let query_filepath = net_filepath.with_extension("xml");
+        let verdict = match std::fs::write(&query_filepath, to_query(property, &formula)) {
+            Ok(()) => run(&net_filepath, &query_filepath, self.limits.timeout),
+            Err(err) => PropertyVerdict::Inconclusive(format!(
+                "Could not write the query to the temporary file for the model checker: {err}"
+            )),
+        };
+        // The temporary files are not needed anymore. Failing to delete them is not an error.
+        let _ = std::fs::remove_file(&net_filepath);
+        let _ = std::fs::remove_file(&query_filepath);
+        verdict
+    }
+}
+
+/// Runs `verifypn` on the net and the query and parses its answer.
+fn run(
+    net_filepath: &std::path::Path,
+    query_filepath: &std::path::Path,
+    timeout: Option<u64>,
+) -> PropertyVerdict {
+    let output = match run_verifypn(&[net_filepath, query_filepath], timeout) {
+        Ok(output) => output,
+        Err(err_str) => return PropertyVerdict::Inconclusive(err_str),
+    };
+    let stdout_string = String::from_utf8_lossy(&output.stdout);
+    if stdout_string.contains("Query is satisfied") {
+        return PropertyVerdict::Satisfied;
+    }
+    if stdout_string.contains("Query is NOT satisfied") {
+        return PropertyVerdict::NotSatisfied;
+    }
+    let stderr_string = String::from_utf8_lossy(&output.stderr);
+    let reason = stderr_string
+        .lines()
+        .chain(stdout_string.lines())
+        .rfind(|line| !line.trim().is_empty())
+        .map_or("no output", str::trim);
+    PropertyVerdict::Inconclusive(format!("`verifypn` did not produce a result: {reason}"))
+}
+
+/// Converts the property to a formula in the XML format of the Model Checking Contest.
+/// The formula is the same as the one of `Property::formula` on the given net.
+/// Returns `None` if the property is a raw formula.
+fn to_query_formula(property: &Property, net: &PetriNet) -> Option<String> {
+    let formula = match property {
+        Property::Deadlock => exists_finally(&conjunction(&[
+            "<deadlock/>".to_string(),
+            is_empty(PROGRAM_END),
+            is_empty(PROGRAM_PANIC),
+        ])),
+        Property::Panic => exists_finally(&is_marked(PROGRAM_PANIC)),
+        Property::ThreadNeverTerminates(index) => {
+            let end_place = end_place_label(*index);
+            let panic_place = panic_place_label(*index);
+            let never_terminates = if has_place(net, &panic_place) {
+                conjunction(&[is_empty(&end_place), is_empty(&panic_place)])
+            } else {
+                is_empty(&end_place)
+            };
+            format!("<exists-path><globally>{never_terminates}</globally></exists-path>")
+        }
+        Property::MutexHeldAtEnd(index) => exists_finally(&conjunction(&[
+            is_marked(PROGRAM_END),
+            is_empty(&mutex_place_label(*index)),
+        ])),
+        Property::Formula(_) => return None,
+    };
+    Some(formula)
+}
+
+/// Returns the query file containing the formula as its only property.
+fn to_query(property: &Property, formula: &str) -> String {
+    format!(
+        r#"<?xml version="1.0"?>
+<property-set xmlns="http://mcc.lip6.fr/">
+  <property>
+    <id>{property}</id>
+    <description>{property}</description>
+    <formula>{formula}</formula>
+  </property>
+</property-set>
+"#
+    )
+}
+
+/// Returns the formula `EF formula`.
+fn exists_finally(formula: &str) -> String {
+    format!("<exists-path><finally>{formula}</finally></exists-path>")
+}
+
+/// Returns the conjunction of the formulas.
+fn conjunction(formulas: &[String]) -> String {
+    format!("<conjunction>{}</conjunction>", formulas.concat())
+}
+
+/// Returns the formula `place = 0`.
+fn is_empty(place: &str) -> String {
+    format!(
+        "<integer-le><tokens-count><place>{place}</place></tokens-count><integer-constant>0</integer-constant></integer-le>"
+    )
+}
+
+/// Returns the formula `place > 0`.
+fn is_marked(place: &str) -> String {
+    format!(
+        "<integer-le><integer-constant>1</integer-constant><tokens-count><place>{place}</place></tokens-count></integer-le>"
+    )
+}
+
+/// Runs `verifypn` with the given arguments within the timeout in seconds.
+/// Tries the executable names in `EXECUTABLE_NAMES` in order.
+///
+/// # Errors
+///
+/// If `verifypn` cannot be found or executed, then an error with the corresponding description is returned.
+/// If `verifypn` runs longer than the timeout, then it is killed and an error is returned.
+fn run_verifypn<S: AsRef<std::ffi::OsStr>>(
+    args: &[S],
+    timeout: Option<u64>,
+) -> Result<Output, String> {
+    for name in EXECUTABLE_NAMES {
+        let mut command = Command::new(name);
+        command.args(args);
+        match run_with_timeout(command, timeout) {
+            Ok(output) => return Ok(output),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                info!("`{name}` was not found in the $PATH");
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::TimedOut => {
+                return Err(format!("`{name}` was {err}"));
+            }
+            Err(err) => {
+                return Err(format!(
+                    "There was an unknown error while executing `{name}`: {err}"
+                ))
+            }
+        }
+    }
+    Err("`verifypn` was not found in the $PATH".to_string())
+}
+
+#[cfg(test)]
+mod verifypn_tests {
+    use super::*;
+
+    #[test]
+    fn deadlock_query_excludes_end_and_panic() {
+        let formula = to_query_formula(&Property::Deadlock, &PetriNet::new())
+            .expect("The deadlock property is supported");
+        assert!(formula.starts_with("<exists-path><finally><conjunction><deadlock/>"));
+        assert!(formula.contains("<place>PROGRAM_END</place>"));
+        assert!(formula.contains("<place>PROGRAM_PANIC</place>"));
+    }
+
+    #[test]
+    fn thread_never_terminates_query_excludes_the_panic_place_if_present() {
+        let mut net = PetriNet::new();
+        net.add_place(&panic_place_label(0));
+        let formula = to_query_formula(&Property::ThreadNeverTerminates(0), &net)
+            .expect("The property is supported");
+        assert!(formula.starts_with("<exists-path><globally><conjunction>"));
+        assert!(formula.contains("<place>THREAD_0_END</place>"));
+        assert!(formula.contains("<place>THREAD_0_PANIC</place>"));
+    }
+
+    #[test]
+    fn raw_formulas_are_not_supported() {
+        let property = Property::Formula("EF PROGRAM_END > 0".to_string());
+        assert_eq!(to_query_formula(&property, &PetriNet::new()), None);
+    }
+}
@@ -0,0 +1,148 @@
+//! Submodule for user-supplied models of functions.
+//!
+//! Calls to foreign functions (FFI, standard library, third-party crates without MIR)
+//! are translated by default as calls that either return or unwind.
+//! A model file tells the translator that a function behaves like a synchronization primitive instead.
+//!
+//! The model file is written in TOML. It declares named resources with an initial number of tokens
+//! (the capacity) and maps function paths to behaviours on these resources:
+//!
+//! ```toml
+//! [resources]
+//! device = 1
+//!
+//! [functions]
+//! "ffi::acquire_device_lock" = { behavior = "lock", resource = "device" }
+//! "ffi::release_device_lock" = { behavior = "unlock", resource = "device" }
+//! "ffi::abort_process" = { behavior = "diverge" }
+//! ```
+//!
+//! The function paths are the ones printed by the compiler, e.g. `vendor::Semaphore::acquire`.
+//! They can be found in the debug output of the tool (`-vvv`).
+//!
+//! Every resource is a counting semaphore: A call either takes a token from it or puts a token into it.
+//! `lock`, `wait` and `join` are accepted as names for `acquire`, `unlock`, `notify` and `spawn` as names for `release`.
+//! They do not reuse the models of `Condvar` or `std::thread`: A notification without a waiting call
+//! is kept for the next `wait` instead of being lost, and `join` waits for any thread counted
+//! in the resource instead of a specific one.
+
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+
+/// The possible behaviours of a modelled function.
+#[derive(Copy, Clone, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Behavior {
+    /// Takes a token from the resource, blocking until one is available.
+    #[serde(alias = "lock", alias = "wait", alias = "join")]
+    Acquire,
+    /// Puts a token into the resource.
+    #[serde(alias = "unlock", alias = "notify", alias = "spawn")]
+    Release,
+    /// The function never returns.
+    Diverge,
+    /// The function returns normally and never unwinds.
+    NoUnwind,
+}
+
+impl Behavior {
+    /// Checks whether the behaviour operates on a resource.
+    #[must_use]
+    pub const fn uses_resource(self) -> bool {
+        matches!(self, Self::Acquire | Self::Release)
+    }
+
+    /// Checks whether the behaviour takes a token from the resource.
+    /// Otherwise it puts a token into the resource.
+    #[must_use]
+    pub const fn takes_token(self) -> bool {
+        matches!(self, Self::Acquire)
+    }
+}
+
+/// The model of a single function.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct FunctionModel {
+    /// The behaviour of the function.
+    pub behavior: Behavior,
+    /// The name of the resource the function operates on.
+    pub resource: Option<String>,
+}
+
+/// A collection of function models and the resources they operate on.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct Models {
+    /// The resources and their initial number of tokens.
+    /// A sorted map ensures that the places are added to the net in a deterministic order.
+    #[serde(default)]
+    resources: BTreeMap<String, usize>,
+    /// The function models indexed by the function path.
+    #[serde(default)]
+    functions: HashMap<String, FunctionModel>,
+}
+
+impl Models {
+    /// Reads the models from the TOML file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// If the file cannot be read or the models are not valid, then an error with the description is returned.
+    pub fn from_file(path: &std::path::Path) -> Result<Self, String> {
+        let contents = std::fs::read_to_string(path).map_err(|err| {
+            format!(
+                "Model file at {} could not be read: {err}",
+                path.to_string_lossy()
+            )
+        })?;
+        contents.parse()
+    }
+
+    /// Returns the model for the function with the given path, if any.
+    #[must_use]
+    pub fn get(&self, function_name: &str) -> Option<&FunctionModel> {
+        self.functions.get(function_name)
+    }
+
+    /// Returns an iterator over the resources and their initial number of tokens.
+    pub fn resources(&self) -> impl Iterator<Item = (&String, &usize)> {
+        self.resources.iter()
+    }
+
+    /// Checks that every function model is consistent with its behaviour
+    /// and only refers to declared resources.
+    fn validate(&self) -> Result<(), String> {
+        for (function_name, model) in &self.functions {
+            match (&model.resource, model.behavior.uses_resource()) {
+                (None, true) => {
+                    return Err(format!(
+                        "The model for `{function_name}` requires a resource"
+                    ));
+                }
+                (Some(_), false) => {
+                    return Err(format!(
+                        "The model for `{function_name}` does not use a resource"
+                    ));
+                }
+                (Some(resource), true) if !self.resources.contains_key(resource) => {
+                    return Err(format!(
+                        "The model for `{function_name}` refers to the undeclared resource `{resource}`"
+                    ));
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for Models {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let models: Self = toml::from_str(s).map_err(|err| format!("Invalid model file: {err}"))?;
+        models.validate()?;
+        Ok(models)
+    }
+}
@@ -8,10 +8,17 @@
 //! See the reference for more information:
 //! <https://doc.rust-lang.org/stable/reference/attributes/codegen.html>
 
+pub mod atomic;
 pub mod basic_block;
+pub mod channel;
 pub mod condvar;
 pub mod function;
+pub mod model;
 pub mod mutex;
+pub mod notify;
+pub mod once;
+pub mod range;
+pub mod semaphore;
 pub mod thread;
 
 /// Label of the place that models the program start state.
@@ -35,6 +42,7 @@ fn sanitize(function_name: &str) -> String {
         .replace("<T>", "T")
         .replace("[T]", "T")
         .replace("<T, A>", "T_A")
+        .replace("<T, F>", "T_F")
         .replace("<'a>", "a")
         .replace("::", "_")
         .replace("Result_<T, E>", "Result")
@@ -0,0 +1,43 @@
+//! Submodule that defines the naming of places and transitions in the Petri net
+//! that concern the translation of functions related to atomic variables.
+//!
+//! These functions are called every time that a new place or transition
+//! in the resulting net is created.
+//! This ensures a consistent naming and provides a centralized place to tweak
+//! the configuration if needed.
+//!
+//! All functions listed here should have an `#[inline]` attribute for performance reasons.
+//! See the reference for more information:
+//! <https://doc.rust-lang.org/stable/reference/attributes/codegen.html>
+
+use super::sanitize;
+
+/// Label of the place that has a token if the atomic variable holds the given value.
+/// The type of the variable is part of the label, e.g. `ATOMIC_BOOL_0_VALUE_1`
+/// for `std::sync::atomic::AtomicBool`, since every type counts its calls to `new` separately.
+#[inline]
+pub fn value_place_label(atomic_type: &str, index: usize, value: usize) -> String {
+    let type_suffix = atomic_type
+        .rsplit_once("::Atomic")
+        .map_or(atomic_type, |(_, suffix)| suffix);
+    format!(
+        "ATOMIC_{}_{index}_VALUE_{value}",
+        type_suffix.to_uppercase()
+    )
+}
+
+/// Label of the transition for a call on an atomic variable that finds the given value.
+#[inline]
+pub fn value_transition_label(function_name: &str, index: usize, value: usize) -> String {
+    format!("{}_{index}_VALUE_{value}", sanitize(function_name))
+}
+
+/// Label of the place that passes the value read from an atomic variable
+/// to the transition of a switch int terminator that tests it.
+#[inline]
+pub fn tested_value_place_label(function_name: &str, from_index: usize, to_index: usize) -> String {
+    format!(
+        "{}_SWITCH_INT_FROM_BB{from_index}_TO_BB{to_index}_TESTED_VALUE",
+        sanitize(function_name)
+    )
+}
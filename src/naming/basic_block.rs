@@ -72,3 +72,61 @@ pub fn assert_cleanup_transition_label(function_name: &str, index: usize) -> Str
 pub fn unreachable_transition_label(function_name: &str, index: usize) -> String {
     format!("{}_UNREACHABLE_{index}", sanitize(function_name))
 }
+
+/// Label of the transition that skips an unsupported terminator and jumps to another `BasicBlock`.
+#[inline]
+pub fn skip_transition_label(function_name: &str, from_index: usize, to_index: usize) -> String {
+    format!(
+        "{}_SKIP_FROM_BB{from_index}_TO_BB{to_index}",
+        sanitize(function_name)
+    )
+}
+
+/// Label of the transition that skips an unsupported terminator without successors
+/// and jumps to the end place of the function.
+#[inline]
+pub fn skip_return_transition_label(function_name: &str, index: usize) -> String {
+    format!("{}_SKIP_RETURN_{index}", sanitize(function_name))
+}
+
+/// Label of the transition that models an unsupported terminator returning to another `BasicBlock`.
+#[inline]
+pub fn havoc_transition_label(function_name: &str, from_index: usize, to_index: usize) -> String {
+    format!(
+        "{}_HAVOC_FROM_BB{from_index}_TO_BB{to_index}",
+        sanitize(function_name)
+    )
+}
+
+/// Labels of the transitions that model an unsupported terminator
+/// returning to the end place of the function, panicking or blocking forever.
+#[inline]
+pub fn havoc_transition_labels(function_name: &str, index: usize) -> (String, String, String) {
+    (
+        format!("{}_HAVOC_RETURN_{index}", sanitize(function_name)),
+        format!("{}_HAVOC_PANIC_{index}", sanitize(function_name)),
+        format!("{}_HAVOC_BLOCK_{index}", sanitize(function_name)),
+    )
+}
+
+/// Label of the place that models an unsupported terminator that blocks forever.
+#[inline]
+pub fn havoc_blocked_place_label(function_name: &str, index: usize) -> String {
+    format!("{}_BB{index}_HAVOC_BLOCKED", sanitize(function_name))
+}
+
+/// Label of the place that models a coroutine suspended at the end of a `BasicBlock`.
+#[inline]
+pub fn suspended_place_label(function_name: &str, index: usize) -> String {
+    format!("{}_BB{index}_SUSPENDED", sanitize(function_name))
+}
+
+/// Labels of the transitions that suspend a coroutine at the end of a `BasicBlock`
+/// and resume it in another `BasicBlock`.
+#[inline]
+pub fn suspend_transition_labels(function_name: &str, index: usize) -> (String, String) {
+    (
+        format!("{}_SUSPEND_{index}", sanitize(function_name)),
+        format!("{}_RESUME_{index}", sanitize(function_name)),
+    )
+}
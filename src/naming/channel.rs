@@ -0,0 +1,122 @@
+//! Submodule that defines the naming of places and transitions in the Petri net
+//! that concern the translation of functions related to channels
+//! (`tokio::sync::mpsc` and `crossbeam_channel`).
+//!
+//! These functions are called every time that a new place or transition
+//! in the resulting net is created.
+//! This ensures a consistent naming and provides a centralized place to tweak
+//! the configuration if needed.
+//!
+//! All functions listed here should have an `#[inline]` attribute for performance reasons.
+//! See the reference for more information:
+//! <https://doc.rust-lang.org/stable/reference/attributes/codegen.html>
+
+use super::sanitize;
+
+/// Prefix of the labels of a channel.
+/// The name of the function that creates the channel is part of the prefix,
+/// e.g. `CHANNEL_0` for `tokio::sync::mpsc::channel` or `UNBOUNDED_0` for `crossbeam_channel::unbounded`,
+/// since every function counts its calls separately.
+#[inline]
+fn channel_prefix(constructor: &str, index: usize) -> String {
+    let constructor_name = constructor
+        .rsplit_once("::")
+        .map_or(constructor, |(_, constructor_name)| constructor_name);
+    format!("{}_{index}", constructor_name.to_ascii_uppercase())
+}
+
+/// Labels of the two places that model the free capacity and the messages of a channel.
+#[inline]
+pub fn place_labels(constructor: &str, index: usize) -> (String, String) {
+    let prefix = channel_prefix(constructor, index);
+    (format!("{prefix}_CAPACITY"), format!("{prefix}_MESSAGES"))
+}
+
+/// Labels of the two places that model whether a sender or the receiver of a channel was dropped.
+#[inline]
+pub fn dropped_place_labels(constructor: &str, index: usize) -> (String, String) {
+    let prefix = channel_prefix(constructor, index);
+    (
+        format!("{prefix}_SENDER_DROPPED"),
+        format!("{prefix}_RECEIVER_DROPPED"),
+    )
+}
+
+/// Label of the place that models the messages of a zero-capacity channel taken by a receiver,
+/// which lets the waiting sender return.
+#[inline]
+pub fn received_place_label(constructor: &str, index: usize) -> String {
+    format!("{}_RECEIVED", channel_prefix(constructor, index))
+}
+
+/// Labels of the two places that model the receivers waiting on a zero-capacity channel
+/// and the messages handed over directly to one of them.
+#[inline]
+pub fn waiting_receivers_place_labels(constructor: &str, index: usize) -> (String, String) {
+    let prefix = channel_prefix(constructor, index);
+    (
+        format!("{prefix}_WAITING_RECEIVERS"),
+        format!("{prefix}_DELIVERED"),
+    )
+}
+
+/// Label of a call that sends or receives a message.
+/// The labels of its places and transitions are derived from it.
+#[inline]
+pub fn operation_label(function_name: &str, index: usize) -> String {
+    format!("{}_{index}", sanitize(function_name))
+}
+
+/// Label of an operation registered in a `crossbeam_channel::Select`,
+/// performed by the call to `select` when the operation is selected.
+#[inline]
+pub fn selected_operation_label(function_name: &str, index: usize, operation: usize) -> String {
+    format!("{}_{index}_OPERATION_{operation}", sanitize(function_name))
+}
+
+/// Label of the transition that sends or receives the message.
+#[inline]
+pub fn transfer_transition_label(operation: &str) -> String {
+    format!("{operation}_CALL")
+}
+
+/// Label of the transition for sending or receiving on a closed channel.
+#[inline]
+pub fn closed_transition_label(operation: &str) -> String {
+    format!("{operation}_CLOSED")
+}
+
+/// Label of the place where a sender waits until a receiver takes the message from a zero-capacity channel.
+#[inline]
+pub fn waiting_place_label(operation: &str) -> String {
+    format!("{operation}_WAITING_FOR_RECEIVER")
+}
+
+/// Label of the place where a receiver waits until a sender hands over a message on a zero-capacity channel.
+#[inline]
+pub fn receiver_waiting_place_label(operation: &str) -> String {
+    format!("{operation}_WAITING_FOR_SENDER")
+}
+
+/// Label of the transition that starts waiting for the other end of a zero-capacity channel.
+#[inline]
+pub fn offer_transition_label(operation: &str) -> String {
+    format!("{operation}_OFFER")
+}
+
+/// Label of the transition that ends the wait of a receiver on a zero-capacity channel
+/// because a sender handed over a message.
+#[inline]
+pub fn delivered_transition_label(operation: &str) -> String {
+    format!("{operation}_DELIVERED")
+}
+
+/// Labels of the transitions that end the wait on a zero-capacity channel:
+/// Either the other end took or offered the message or the other end was dropped.
+#[inline]
+pub fn rendezvous_transition_labels(operation: &str) -> (String, String) {
+    (
+        format!("{operation}_RECEIVED"),
+        format!("{operation}_WAITING_CLOSED"),
+    )
+}
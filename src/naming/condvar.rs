@@ -29,6 +29,23 @@ pub fn transition_labels(index: usize) -> (String, String, String) {
     )
 }
 
+/// Labels of the places that record whether a notification on the `Condvar` was lost:
+/// No notification was lost yet and at least one notification was lost.
+#[inline]
+pub fn lost_signal_place_labels(index: usize) -> (String, String) {
+    (
+        format!("CONDVAR_{index}_NO_SIGNAL_LOST"),
+        format!("CONDVAR_{index}_SIGNAL_LOST"),
+    )
+}
+
+/// Label of the transition that consumes a notification when `wait()` has not been called yet
+/// and a notification was already lost before.
+#[inline]
+pub fn lost_signal_again_label(index: usize) -> String {
+    format!("CONDVAR_{index}_LOST_SIGNAL_AGAIN")
+}
+
 /// Label of the transition that represents skipping a call
 /// to `std::sync::Condvar::wait` or `std::sync::Condvar::wait_while`
 /// because the condition was already set.
@@ -36,3 +53,20 @@ pub fn transition_labels(index: usize) -> (String, String, String) {
 pub fn wait_skip_label(index: usize) -> String {
     format!("CONDVAR_{index}_WAIT_SKIP")
 }
+
+/// Labels of the places of a call to `std::sync::Condvar::wait_while`:
+/// The condition holds for the value stored in the `Mutex` and the thread waits for the notification.
+#[inline]
+pub fn wait_while_place_labels(index: usize) -> (String, String) {
+    (
+        format!("CONDVAR_{index}_WAIT_WHILE_CONDITION_HOLDS"),
+        format!("CONDVAR_{index}_WAIT_WHILE_WAITING"),
+    )
+}
+
+/// Label of the transition that checks the condition of a call to `std::sync::Condvar::wait_while`
+/// for the given value stored in the `Mutex`.
+#[inline]
+pub fn wait_while_check_label(index: usize, value: usize) -> String {
+    format!("CONDVAR_{index}_WAIT_WHILE_CHECK_VALUE_{value}")
+}
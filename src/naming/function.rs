@@ -45,7 +45,26 @@ pub fn diverging_call_transition_label(function_name: &str) -> String {
 }
 
 /// Label of the transition that represents a call to a `panic!`.
+/// The index distinguishes several calls to `panic!` in the same function.
 #[inline]
-pub fn panic_transition_label(function_name: &str) -> String {
-    format!("{}_PANIC", sanitize(function_name))
+pub fn panic_transition_label(function_name: &str, index: usize) -> String {
+    format!("{}_PANIC_{index}", sanitize(function_name))
+}
+
+/// Labels of the transitions for a call to a function that cannot be modelled exactly.
+/// The call may return, panic or block forever.
+#[inline]
+pub fn havoc_call_transition_labels(function_name: &str, index: usize) -> (String, String, String) {
+    (
+        format!("{}_{index}_HAVOC_RETURN", sanitize(function_name)),
+        format!("{}_{index}_HAVOC_PANIC", sanitize(function_name)),
+        format!("{}_{index}_HAVOC_BLOCK", sanitize(function_name)),
+    )
+}
+
+/// Label of the place that models a call to a function that cannot be modelled exactly
+/// and blocks forever.
+#[inline]
+pub fn havoc_call_blocked_place_label(function_name: &str, index: usize) -> String {
+    format!("{}_{index}_HAVOC_BLOCKED", sanitize(function_name))
 }
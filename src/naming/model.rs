@@ -0,0 +1,25 @@
+//! Submodule that defines the naming of places and transitions in the Petri net
+//! that concern the translation of functions with a user-supplied model.
+//!
+//! These functions are called every time that a new place or transition
+//! in the resulting net is created.
+//! This ensures a consistent naming and provides a centralized place to tweak
+//! the configuration if needed.
+//!
+//! All functions listed here should have an `#[inline]` attribute for performance reasons.
+//! See the reference for more information:
+//! <https://doc.rust-lang.org/stable/reference/attributes/codegen.html>
+
+use super::sanitize;
+
+/// Label of the place that models a resource declared in the model file.
+#[inline]
+pub fn resource_place_label(resource_name: &str) -> String {
+    format!("RESOURCE_{}", sanitize(resource_name))
+}
+
+/// Label of the transition that represents a call to a function modelled as diverging.
+#[inline]
+pub fn diverging_call_transition_label(function_name: &str, index: usize) -> String {
+    format!("{}_{index}_DIVERGING_CALL", sanitize(function_name))
+}
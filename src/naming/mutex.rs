@@ -10,6 +10,8 @@
 //! See the reference for more information:
 //! <https://doc.rust-lang.org/stable/reference/attributes/codegen.html>
 
+use super::sanitize;
+
 /// Label of the single place that models every `Mutex`.
 #[inline]
 pub fn place_label(index: usize) -> String {
@@ -25,3 +27,55 @@ pub fn condition_place_labels(index: usize) -> (String, String) {
         format!("MUTEX_{index}_CONDITION_SET"),
     )
 }
+
+/// Labels of the two places that model whether the `Mutex` is poisoned,
+/// i.e. whether a thread panicked while holding the lock.
+#[inline]
+pub fn poison_place_labels(index: usize) -> (String, String) {
+    (
+        format!("MUTEX_{index}_NOT_POISONED"),
+        format!("MUTEX_{index}_POISONED"),
+    )
+}
+
+/// Label of the place that receives the lock when a mutex guard is dropped during a panic.
+#[inline]
+pub fn unlocked_by_panic_place_label(index: usize) -> String {
+    format!("MUTEX_{index}_UNLOCKED_BY_PANIC")
+}
+
+/// Labels of the two transitions that release the lock after a panic and poison the `Mutex`.
+/// The first one poisons the `Mutex`, the second one is used if the `Mutex` is already poisoned.
+#[inline]
+pub fn poison_transition_labels(index: usize) -> (String, String) {
+    (
+        format!("MUTEX_{index}_POISON"),
+        format!("MUTEX_{index}_REMAIN_POISONED"),
+    )
+}
+
+/// Label of the place that has a token if the `Mutex` holds the given value.
+/// Only used if the values stored in mutexes are modelled.
+#[inline]
+pub fn value_place_label(index: usize, value: usize) -> String {
+    format!("MUTEX_{index}_VALUE_{value}")
+}
+
+/// Label of the transition for a dereference of a mutex guard that finds the old value in the `Mutex`
+/// and leaves the new value in it.
+#[inline]
+pub fn value_transition_label(
+    function_name: &str,
+    index: usize,
+    old_value: usize,
+    new_value: usize,
+) -> String {
+    if old_value == new_value {
+        format!("{}_{index}_VALUE_{old_value}", sanitize(function_name))
+    } else {
+        format!(
+            "{}_{index}_VALUE_{old_value}_TO_{new_value}",
+            sanitize(function_name)
+        )
+    }
+}
@@ -0,0 +1,28 @@
+//! Submodule that defines the naming of places and transitions in the Petri net
+//! that concern the translation of functions related to notifications (`tokio::sync::Notify`).
+//!
+//! These functions are called every time that a new place or transition
+//! in the resulting net is created.
+//! This ensures a consistent naming and provides a centralized place to tweak
+//! the configuration if needed.
+//!
+//! All functions listed here should have an `#[inline]` attribute for performance reasons.
+//! See the reference for more information:
+//! <https://doc.rust-lang.org/stable/reference/attributes/codegen.html>
+
+use super::sanitize;
+
+/// Labels of the two places that model whether a `Notify` stores a permit.
+#[inline]
+pub fn place_labels(index: usize) -> (String, String) {
+    (
+        format!("NOTIFY_{index}_PERMIT"),
+        format!("NOTIFY_{index}_NO_PERMIT"),
+    )
+}
+
+/// Label of the transition for a call to `notify_one` that finds the permit already stored.
+#[inline]
+pub fn keep_permit_transition_label(function_name: &str, index: usize) -> String {
+    format!("{}_{index}_KEEP_PERMIT", sanitize(function_name))
+}
@@ -0,0 +1,65 @@
+//! Submodule that defines the naming of places and transitions in the Petri net
+//! that concern the translation of functions related to one-time initialization
+//! (`std::sync::Once`, `std::sync::OnceLock` and `std::sync::LazyLock`).
+//!
+//! These functions are called every time that a new place or transition
+//! in the resulting net is created.
+//! This ensures a consistent naming and provides a centralized place to tweak
+//! the configuration if needed.
+//!
+//! All functions listed here should have an `#[inline]` attribute for performance reasons.
+//! See the reference for more information:
+//! <https://doc.rust-lang.org/stable/reference/attributes/codegen.html>
+
+use super::sanitize;
+
+/// Labels of the places that model the state of the initialization: not started, running and done.
+/// The type is part of the label, e.g. `ONCE_LOCK_0_UNINIT` for `std::sync::OnceLock`,
+/// since every type counts its calls to `new` separately.
+#[inline]
+pub fn place_labels(once_type: &str, index: usize) -> (String, String, String) {
+    let type_name = once_type
+        .rsplit_once("::")
+        .map_or(once_type, |(_, type_name)| type_name);
+    let mut prefix = String::new();
+    for (position, character) in type_name.chars().enumerate() {
+        if position > 0 && character.is_uppercase() {
+            prefix.push('_');
+        }
+        prefix.push(character.to_ascii_uppercase());
+    }
+    (
+        format!("{prefix}_{index}_UNINIT"),
+        format!("{prefix}_{index}_RUNNING"),
+        format!("{prefix}_{index}_DONE"),
+    )
+}
+
+/// Labels of the start place, the end place and the panic place of the initializer run inline by a call.
+#[inline]
+pub fn initializer_place_labels(function_name: &str, index: usize) -> (String, String, String) {
+    let function_name = sanitize(function_name);
+    (
+        format!("{function_name}_{index}_INITIALIZER_START"),
+        format!("{function_name}_{index}_INITIALIZER_END"),
+        format!("{function_name}_{index}_INITIALIZER_PANIC"),
+    )
+}
+
+/// Labels of the transitions that start the initialization, that finish it
+/// and that reset it after the initializer panicked.
+#[inline]
+pub fn init_transition_labels(function_name: &str, index: usize) -> (String, String, String) {
+    let function_name = sanitize(function_name);
+    (
+        format!("{function_name}_{index}_INIT_START"),
+        format!("{function_name}_{index}_INIT_END"),
+        format!("{function_name}_{index}_INIT_PANIC"),
+    )
+}
+
+/// Label of the transition for a call that finds the initialization already done.
+#[inline]
+pub fn already_done_transition_label(function_name: &str, index: usize) -> String {
+    format!("{}_{index}_ALREADY_DONE", sanitize(function_name))
+}
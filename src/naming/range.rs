@@ -0,0 +1,48 @@
+//! Submodule that defines the naming of places and transitions in the Petri net
+//! that concern the translation of loops over a range with constant bounds.
+//!
+//! These functions are called every time that a new place or transition
+//! in the resulting net is created.
+//! This ensures a consistent naming and provides a centralized place to tweak
+//! the configuration if needed.
+//!
+//! All functions listed here should have an `#[inline]` attribute for performance reasons.
+//! See the reference for more information:
+//! <https://doc.rust-lang.org/stable/reference/attributes/codegen.html>
+
+use super::sanitize;
+
+/// Label of the place that has a token if the loop over the range has run the given number of iterations.
+#[inline]
+pub fn iteration_place_label(index: usize, iteration: usize) -> String {
+    format!("RANGE_{index}_ITERATION_{iteration}")
+}
+
+/// Checks whether the label is the label of an iteration place, see `iteration_place_label`.
+#[inline]
+pub fn is_iteration_place_label(label: &str) -> bool {
+    label
+        .strip_prefix("RANGE_")
+        .is_some_and(|suffix| suffix.contains("_ITERATION_"))
+}
+
+/// Label of the transition for a call to `std::iter::Iterator::next` on the range
+/// that starts the given iteration of the loop.
+#[inline]
+pub fn iteration_transition_label(function_name: &str, index: usize, iteration: usize) -> String {
+    format!("{}_{index}_ITERATION_{iteration}", sanitize(function_name))
+}
+
+/// Label of the transition for a call to `std::iter::Iterator::next` on the range
+/// that exits the loop after the last iteration.
+#[inline]
+pub fn exit_transition_label(function_name: &str, index: usize) -> String {
+    format!("{}_{index}_EXIT", sanitize(function_name))
+}
+
+/// Label of the transition for a call to `std::iter::IntoIterator::into_iter` on the range
+/// that moves the token from the given iteration back to the first one.
+#[inline]
+pub fn reset_transition_label(function_name: &str, index: usize, iteration: usize) -> String {
+    format!("{}_{index}_RESET_{iteration}", sanitize(function_name))
+}
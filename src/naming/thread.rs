@@ -10,6 +10,8 @@
 //! See the reference for more information:
 //! <https://doc.rust-lang.org/stable/reference/attributes/codegen.html>
 
+use super::sanitize;
+
 /// Label of the place that models the thread start state.
 #[inline]
 pub fn start_place_label(index: usize) -> String {
@@ -22,3 +24,46 @@ pub fn start_place_label(index: usize) -> String {
 pub fn end_place_label(index: usize) -> String {
     format!("THREAD_{index}_END")
 }
+
+/// Label of the place that models the thread end state after a `panic!`.
+/// It is only added to the net if the panics of the threads are tracked separately.
+#[inline]
+pub fn panic_place_label(index: usize) -> String {
+    format!("THREAD_{index}_PANIC")
+}
+
+/// Label of the transition that models a call to `join` returning `Err` because the thread panicked.
+#[inline]
+pub fn join_panicked_transition_label(index: usize) -> String {
+    format!("THREAD_{index}_JOIN_PANICKED")
+}
+
+/// Labels of the two places that model the result of joining the thread:
+/// `Ok` if the thread ended normally and `Err` if the thread panicked.
+#[inline]
+pub fn join_result_place_labels(index: usize) -> (String, String) {
+    (
+        format!("THREAD_{index}_JOIN_OK"),
+        format!("THREAD_{index}_JOIN_ERR"),
+    )
+}
+
+/// Labels of the two places that model whether the thread stores the token of `std::thread::park`.
+/// The thread is given by its index or `None` for the main thread.
+#[inline]
+pub fn park_token_place_labels(thread: Option<usize>) -> (String, String) {
+    let prefix = thread.map_or_else(
+        || "MAIN_THREAD".to_string(),
+        |index| format!("THREAD_{index}"),
+    );
+    (
+        format!("{prefix}_PARK_TOKEN"),
+        format!("{prefix}_NO_PARK_TOKEN"),
+    )
+}
+
+/// Label of the transition for a call to `unpark` that finds the token already stored.
+#[inline]
+pub fn keep_park_token_transition_label(function_name: &str, index: usize) -> String {
+    format!("{}_{index}_KEEP_TOKEN", sanitize(function_name))
+}
@@ -24,37 +24,70 @@
 //!
 //! A `HashMapCounter` keeps track of how many time each function name has been seen so far.
 //! After every call the counter for the corresponding function is incremented.
+//!
+//! Futures are translated when they are polled, either by awaiting them or by an executor like `block_on`.
+//! The body of the coroutine that implements the future is translated like a MIR function.
+//!
+//! Calls to functions with a user-supplied model are translated according to the `Models`.
+//! Constructs that cannot be modelled are handled according to the `UnsupportedPolicy`.
+//! Every approximation is recorded so that it can be reported together with the result.
 
+mod attributes;
+mod coroutine;
 mod function;
 mod mir_function;
 mod mir_visitor;
+mod primitive;
+mod range;
 mod special_function;
 mod sync;
+mod unsupported;
 
-use log::{debug, info};
+use log::{debug, info, warn};
 use rustc_middle::mir::visit::Visitor;
 use rustc_middle::mir::UnwindAction;
-use std::collections::{BinaryHeap, VecDeque};
+use std::collections::{BinaryHeap, HashMap, VecDeque};
 use std::rc::Rc;
 
 use crate::data_structures::hash_map_counter::HashMapCounter;
-use crate::data_structures::petri_net_interface::{connect_places, PetriNet, PlaceRef};
+use crate::data_structures::petri_net_interface::{
+    add_arc_place_transition, connect_places, PetriNet, PlaceRef,
+};
 use crate::data_structures::stack::Stack;
-use crate::naming::function::{indexed_mir_function_cleanup_label, indexed_mir_function_name};
+use crate::lock_graph::{LockAcquisition, LockGraph};
+use crate::models::{FunctionModel, Models};
+use crate::naming::function::{
+    foreign_call_transition_labels, indexed_mir_function_cleanup_label, indexed_mir_function_name,
+};
+use crate::naming::model::resource_place_label;
 use crate::naming::{PROGRAM_END, PROGRAM_PANIC, PROGRAM_START};
 use crate::utils::{
-    check_substring_in_place_type, extract_closure, extract_def_id_of_called_function_from_operand,
-    extract_nth_argument_as_place,
+    check_substring_in_place_type, extract_closure, extract_coroutine_def_id,
+    extract_def_id_of_called_function_from_operand, extract_nth_argument_as_place, format_span,
 };
+use attributes::{get_tool_attributes, ToolAttribute};
+pub use attributes::{LockOrder, TOOL_NAME};
+use coroutine::{create_future, is_block_on, Coroutine, POLL_FUNCTION_NAME};
 use function::{Places, PostprocessingTask, Transitions};
-use mir_function::memory::MutexRef;
+use mir_function::memory::{FutureRef, MutexRef, OnceRef, ParkTokenRef, RangeRef, Value};
 use mir_function::MirFunction;
+pub use primitive::{NotifyCall, PanicSite, Primitive, PrimitiveKind};
+use range::Range;
 use special_function::{
-    call_diverging_function, call_foreign_function, call_panic_function, is_foreign_function,
-    is_panic_function,
+    call_diverging_function, call_foreign_function, call_havoc_function, call_modelled_function,
+    call_panic_function, is_foreign_function, is_panic_function,
 };
+pub use sync::atomic::ATOMIC_TYPES;
+pub use sync::channel::CHANNEL_CONSTRUCTORS;
 use sync::mutex;
+pub use sync::once::ONCE_TYPES;
+use sync::once::{self, Once};
+use sync::park::{self, ParkToken};
 use sync::thread::Thread;
+pub use unsupported::{Approximation, UnsupportedPolicy};
+
+/// The name used for labelling calls through a function pointer.
+const FUNCTION_POINTER_NAME: &str = "function_pointer";
 
 /// The central data structure and coordinator for the translation.
 pub struct Translator<'tcx> {
@@ -80,6 +113,45 @@ pub struct Translator<'tcx> {
     /// Translation tasks performed after all threads have been translated.
     /// These tasks usually require to make changes to the final Petri net.
     postprocessing: BinaryHeap<PostprocessingTask>,
+    /// The policy for constructs that cannot be modelled.
+    on_unsupported: UnsupportedPolicy,
+    /// The description of the first unsupported construct found with `UnsupportedPolicy::Abort`.
+    unsupported_construct: Option<String>,
+    /// The constructs that were approximated during the translation.
+    approximations: Vec<Approximation>,
+    /// The synchronization primitives modelled in the Petri net.
+    primitives: Vec<Primitive>,
+    /// The user-supplied models of functions.
+    models: Models,
+    /// The places that model the resources declared in the models, indexed by the resource name.
+    resource_places: HashMap<String, PlaceRef>,
+    /// The tool attributes of the functions, parsed once per function and indexed by its definition ID.
+    tool_attributes: HashMap<rustc_hir::def_id::DefId, Vec<ToolAttribute>>,
+    /// The lock orders declared with `#[check_deadlock::lock_order]`.
+    lock_orders: Vec<LockOrder>,
+    /// The calls to `std::sync::Mutex::<T>::lock` found during the translation.
+    lock_acquisitions: Vec<LockAcquisition>,
+    /// The lock graph computed from the Petri net after the translation.
+    lock_graph: LockGraph,
+    /// The calls to `std::sync::Condvar::notify_one` found during the translation.
+    notify_calls: Vec<NotifyCall>,
+    /// Whether the panics are modelled precisely: Every thread gets its own panic place,
+    /// joining a panicked thread returns `Err` and mutexes are poisoned by panics.
+    model_panics: bool,
+    /// Whether the values stored in mutexes of booleans and integers are modelled, see `mutex::Write`.
+    mutex_values: bool,
+    /// Whether loops over a range with constant bounds run exactly as many times as the range has values.
+    unroll_const_loops: bool,
+    /// The index of the thread being translated or `None` for the main thread.
+    current_thread: Option<usize>,
+    /// The places in the code where a thread may start to panic.
+    panic_sites: Vec<PanicSite>,
+    /// The park tokens of the threads, indexed by the thread index or `None` for the main thread.
+    /// They are created the first time that they are needed.
+    park_tokens: HashMap<Option<usize>, ParkTokenRef>,
+    /// The one-time initializations in `static` items, indexed by the definition ID of the static.
+    /// They are created the first time that a function references the static.
+    static_onces: HashMap<rustc_hir::def_id::DefId, OnceRef>,
 }
 
 impl<'tcx> Translator<'tcx> {
@@ -87,7 +159,21 @@ impl<'tcx> Translator<'tcx> {
     /// Requires a global typing context `rustc_middle::ty::TyCtxt`, the main data structure of the compiler.
     /// The initial Petri net contains three places representing the program start state,
     /// the program end state and the abnormal end state after `panic!()`.
-    pub fn new(tcx: rustc_middle::ty::TyCtxt<'tcx>) -> Self {
+    /// Constructs that cannot be modelled are handled according to `on_unsupported`.
+    /// The resources declared in the `models` are added to the net with their initial tokens.
+    /// If `model_panics` is set, every thread gets its own panic place `THREAD_N_PANIC`,
+    /// joining a panicked thread returns `Err` and mutexes are poisoned by panics.
+    /// If `mutex_values` is set, a mutex created with a constant boolean or integer
+    /// stores its value in the Petri net.
+    /// If `unroll_const_loops` is set, a loop over a range with constant bounds counts its iterations.
+    pub fn new(
+        tcx: rustc_middle::ty::TyCtxt<'tcx>,
+        on_unsupported: UnsupportedPolicy,
+        models: Models,
+        model_panics: bool,
+        mutex_values: bool,
+        unroll_const_loops: bool,
+    ) -> Self {
         let mut net = PetriNet::new();
         let program_panic = net.add_place(PROGRAM_PANIC);
         let program_end = net.add_place(PROGRAM_END);
@@ -96,6 +182,15 @@ impl<'tcx> Translator<'tcx> {
             "BUG: Adding initial token to empty PROGRAM_START place should not cause an overflow",
         );
 
+        let mut resource_places = HashMap::new();
+        for (resource_name, capacity) in models.resources() {
+            let place = net.add_place(&resource_place_label(resource_name));
+            net.add_token(&place, *capacity).expect(
+                "BUG: Adding the initial tokens to an empty resource place should not cause an overflow",
+            );
+            resource_places.insert(resource_name.clone(), place);
+        }
+
         Self {
             tcx,
             net,
@@ -106,6 +201,24 @@ impl<'tcx> Translator<'tcx> {
             function_counter: HashMapCounter::new(),
             threads: VecDeque::new(),
             postprocessing: BinaryHeap::new(),
+            on_unsupported,
+            unsupported_construct: None,
+            approximations: Vec::new(),
+            primitives: Vec::new(),
+            models,
+            resource_places,
+            tool_attributes: HashMap::new(),
+            lock_orders: Vec::new(),
+            lock_acquisitions: Vec::new(),
+            lock_graph: LockGraph::default(),
+            notify_calls: Vec::new(),
+            model_panics,
+            mutex_values,
+            unroll_const_loops,
+            current_thread: None,
+            panic_sites: Vec::new(),
+            park_tokens: HashMap::new(),
+            static_onces: HashMap::new(),
         }
     }
 
@@ -115,31 +228,70 @@ impl<'tcx> Translator<'tcx> {
         std::mem::take(&mut self.net)
     }
 
+    /// Returns the constructs that were approximated during the translation.
+    /// The ownership is transferred to the caller.
+    pub fn get_approximations(&mut self) -> Vec<Approximation> {
+        std::mem::take(&mut self.approximations)
+    }
+
+    /// Returns the synchronization primitives modelled in the Petri net.
+    /// The ownership is transferred to the caller.
+    pub fn get_primitives(&mut self) -> Vec<Primitive> {
+        std::mem::take(&mut self.primitives)
+    }
+
+    /// Returns the lock orders declared in the source code.
+    /// The ownership is transferred to the caller.
+    pub fn get_lock_orders(&mut self) -> Vec<LockOrder> {
+        std::mem::take(&mut self.lock_orders)
+    }
+
+    /// Returns the calls to `std::sync::Condvar::notify_one` modelled in the Petri net.
+    /// The ownership is transferred to the caller.
+    pub fn get_notify_calls(&mut self) -> Vec<NotifyCall> {
+        std::mem::take(&mut self.notify_calls)
+    }
+
+    /// Returns the places in the code where a thread may start to panic.
+    /// The ownership is transferred to the caller.
+    pub fn get_panic_sites(&mut self) -> Vec<PanicSite> {
+        std::mem::take(&mut self.panic_sites)
+    }
+
+    /// Returns the lock graph of the program.
+    /// The ownership is transferred to the caller.
+    pub fn get_lock_graph(&mut self) -> LockGraph {
+        std::mem::take(&mut self.lock_graph)
+    }
+
     /// Translates the source code to a Petri net.
+    /// The translation starts at the function `entry_function_id`, usually `main`.
     ///
     /// # Errors
     ///
-    /// If the translation fails due to a recoverable error, then an error message is set.
-    ///
-    /// # Panics
-    ///
-    /// If the translation fails due to an unsupported feature present in the code, then the function panics.
-    pub fn run(&mut self) {
-        let (main_function_id, _) = self
-            .tcx
-            .entry_fn(())
-            .expect("ERROR: No main function found in the source code");
+    /// If the code contains a construct that cannot be modelled and the policy is `UnsupportedPolicy::Abort`,
+    /// then an error with the description of the first such construct is returned.
+    pub fn run(&mut self, entry_function_id: rustc_hir::def_id::DefId) -> Result<(), String> {
         self.push_function_to_call_stack(
-            main_function_id,
+            entry_function_id,
             self.program_start.clone(),
             self.program_end.clone(),
         );
-        info!("Pushed main function to the translation call stack");
+        info!("Pushed entry function to the translation call stack");
         self.translate_top_call_stack();
         info!("Finished translating the main thread");
         self.translate_threads();
         info!("Running translation postprocessing...");
         self.translation_postprocessing();
+        info!("Building the lock graph...");
+        let resource_places: Vec<PlaceRef> = self.resource_places.values().cloned().collect();
+        self.lock_graph = LockGraph::new(
+            &self.net,
+            &self.primitives,
+            &self.lock_acquisitions,
+            &resource_places,
+        );
+        self.unsupported_construct.take().map_or(Ok(()), Err)
     }
 
     /// Main translation loop for the threads.
@@ -147,6 +299,7 @@ impl<'tcx> Translator<'tcx> {
     /// If sync variables were passed to the thread, move them to the memory of the thread function.
     /// Replaces the program panic place with the thread's end place
     /// since abnormal thread termination does not affect the main thread.
+    /// If the panics of the threads are tracked separately, the thread's panic place is used instead.
     fn translate_threads(&mut self) {
         while let Some(thread) = self.threads.pop_front() {
             let index = thread.index;
@@ -155,7 +308,12 @@ impl<'tcx> Translator<'tcx> {
             let (thread_function_def_id, thread_start_place, thread_end_place) =
                 thread.prepare_for_translation(&mut self.net);
             // Replace the panic place so that unwind transitions and similar point to the thread's end place.
-            self.program_panic = thread_end_place.clone();
+            self.program_panic = if self.model_panics {
+                thread.add_panic_place(&mut self.net)
+            } else {
+                thread_end_place.clone()
+            };
+            self.current_thread = Some(index);
 
             self.push_function_to_call_stack(
                 thread_function_def_id,
@@ -164,9 +322,15 @@ impl<'tcx> Translator<'tcx> {
             );
             info!("Pushed thread function to the translation call stack");
 
-            let new_function = self.call_stack.peek_mut();
-            info!("Moving sync variables to the thread function...");
-            thread.move_sync_variables(&mut new_function.memory, self.tcx);
+            if let Some(future) = thread.future() {
+                // A task runs the coroutine that implements the spawned future.
+                info!("Linking the values captured by the future of the task...");
+                self.prepare_coroutine(future);
+            } else {
+                let new_function = self.call_stack.peek_mut();
+                info!("Moving sync variables to the thread function...");
+                thread.move_sync_variables(&mut new_function.memory, self.tcx);
+            }
 
             self.translate_top_call_stack();
             info!("Finished translating thread {}", index);
@@ -213,7 +377,14 @@ impl<'tcx> Translator<'tcx> {
         end_place: PlaceRef,
     ) {
         let function_name = self.tcx.def_path_str(function_def_id);
-        let function = MirFunction::new(function_def_id, function_name, start_place, end_place);
+        let tool_attributes = self.tool_attributes(function_def_id).to_vec();
+        let function = MirFunction::new(
+            function_def_id,
+            function_name,
+            start_place,
+            end_place,
+            tool_attributes,
+        );
         self.call_stack.push(function);
     }
 
@@ -226,13 +397,91 @@ impl<'tcx> Translator<'tcx> {
         let function = self.call_stack.peek();
         // Obtain the MIR representation of the function.
         let body = self.tcx.optimized_mir(function.def_id);
+        // The fields of a coroutine must always be accessed through the same place.
+        let coroutine_body = function
+            .coroutine
+            .as_ref()
+            .map(|coroutine| coroutine.unify_self_references(body, self.tcx));
+        let visited_body = coroutine_body.as_ref().unwrap_or(body);
+        // Switches on constant values only take the feasible branch.
+        let function = self.call_stack.peek_mut();
+        function.propagate_constants(visited_body, self.tcx);
+        function.memory.find_reassigned_locals(visited_body);
         // Visit the MIR body of the function using the methods of `rustc_middle::mir::visit::Visitor`.
         // <https://doc.rust-lang.org/stable/nightly-rustc/rustc_middle/mir/visit/trait.Visitor.html>
-        self.visit_body(body);
+        self.visit_body(visited_body);
+        // The memory of the function is complete, the declared lock orders can be resolved now.
+        self.resolve_lock_orders(body);
         // Finished processing this function.
         self.call_stack.pop();
     }
 
+    /// Resolves the lock orders declared with `#[check_deadlock::lock_order]`
+    /// on the function on the top of the call stack.
+    /// The variable names in the attribute are mapped to the mutexes linked to them in the memory.
+    /// Variables that do not contain a mutex are reported with a warning and skipped.
+    fn resolve_lock_orders(&mut self, body: &rustc_middle::mir::Body<'tcx>) {
+        let function = self.call_stack.peek();
+        for attribute in &function.tool_attributes {
+            let ToolAttribute::LockOrder { variables, span } = attribute else {
+                continue;
+            };
+            let mut mutexes = Vec::new();
+            for variable in variables {
+                let mutex_index = body
+                    .var_debug_info
+                    .iter()
+                    .filter(|info| info.name.as_str() == variable)
+                    .find_map(|info| match info.value {
+                        rustc_middle::mir::VarDebugInfoContents::Place(place) => function
+                            .memory
+                            .find_mutex(&place)
+                            .map(|mutex| mutex.index()),
+                        rustc_middle::mir::VarDebugInfoContents::Const(_) => None,
+                    });
+                if let Some(mutex_index) = mutex_index {
+                    mutexes.push(mutex_index);
+                } else {
+                    warn!(
+                        "The variable `{variable}` in the lock order of `{}` does not contain a mutex",
+                        function.name
+                    );
+                }
+            }
+            for pair in mutexes.windows(2) {
+                let lock_order = LockOrder {
+                    first: pair[0],
+                    second: pair[1],
+                    function_name: function.name.clone(),
+                    location: format_span(*span, self.tcx),
+                };
+                if !self.lock_orders.contains(&lock_order) {
+                    info!("Found the declared lock order: {lock_order}");
+                    self.lock_orders.push(lock_order);
+                }
+            }
+        }
+    }
+
+    /// Checks whether a call to the function with the given definition ID never unwinds.
+    /// This is the case if the called function or the current function on the top of the call stack
+    /// is annotated with `#[check_deadlock::assume_no_unwind]`.
+    fn assumes_no_unwind(&mut self, function_def_id: rustc_hir::def_id::DefId) -> bool {
+        self.call_stack.peek().assume_no_unwind
+            || self
+                .tool_attributes(function_def_id)
+                .contains(&ToolAttribute::AssumeNoUnwind)
+    }
+
+    /// Returns the tool attributes of the function with the given definition ID.
+    /// The attributes are parsed only the first time the function is seen.
+    fn tool_attributes(&mut self, function_def_id: rustc_hir::def_id::DefId) -> &[ToolAttribute] {
+        let tcx = self.tcx;
+        self.tool_attributes
+            .entry(function_def_id)
+            .or_insert_with(|| get_tool_attributes(function_def_id, tcx))
+    }
+
     /// Jumps from the current function on the top of the stack
     /// to a new function called inside the current function.
     ///
@@ -244,10 +493,15 @@ impl<'tcx> Translator<'tcx> {
     /// - Functions for mutexes: `std::sync::Mutex::new` and `std::sync::Mutex::lock`.
     /// - Functions for threads: `std::thread::spawn` and `std::thread::JoinHandle::<T>::join`.
     /// - Functions for condition variables: `std::sync::Condvar::new`, `std::sync::Condvar::wait` and `std::sync::Condvar::notify_one`.
+    /// - Functions for `tokio` primitives and tasks, e.g. `tokio::sync::Mutex::lock` and `tokio::spawn`.
+    /// - Functions for one-time initializations, e.g. `std::sync::Once::call_once`. The initializer is translated inline.
     /// - Functions from the Rust standard library or the Rust core library.
     ///
     /// This is the handler for the enum variant `TerminatorKind::Call` in the MIR Visitor.
     /// <https://doc.rust-lang.org/stable/nightly-rustc/rustc_middle/mir/enum.TerminatorKind.html#variant.Call>
+    ///
+    /// Calls through a function pointer cannot be resolved statically.
+    /// They are handled according to the `UnsupportedPolicy`.
     fn call_function(
         &mut self,
         func: &rustc_middle::mir::Operand<'tcx>,
@@ -255,11 +509,15 @@ impl<'tcx> Translator<'tcx> {
         destination: rustc_middle::mir::Place<'tcx>,
         target: Option<rustc_middle::mir::BasicBlock>,
         unwind: UnwindAction,
+        span: rustc_span::Span,
     ) {
         let current_function = self.call_stack.peek_mut();
         let function_def_id =
             extract_def_id_of_called_function_from_operand(func, current_function.def_id, self.tcx);
-        let function_name = self.tcx.def_path_str(function_def_id);
+        let function_name = function_def_id.map_or_else(
+            || FUNCTION_POINTER_NAME.to_string(),
+            |def_id| self.tcx.def_path_str(def_id),
+        );
         let start_place = current_function.get_start_place_for_function_call();
         info!("Encountered function call: {function_name}");
 
@@ -321,12 +579,14 @@ impl<'tcx> Translator<'tcx> {
                 // Non-recursive call for the translation process.
                 // `panic!`-related functions are a special case of this.
                 if is_panic_function(&function_name) {
-                    call_panic_function(
+                    let transition = call_panic_function(
                         &start_place,
                         &self.program_panic,
                         &current_function.name,
+                        self.panic_sites.len(),
                         &mut self.net,
                     );
+                    self.record_panic_site(transition, span);
                 } else {
                     call_diverging_function(&start_place, &function_name, &mut self.net);
                 }
@@ -356,7 +616,32 @@ impl<'tcx> Translator<'tcx> {
             }
         };
 
-        self.start_function_call(function_def_id, &function_name, args, destination, places);
+        if let Some(future_name) = coroutine::find_unsupported_tokio_future(func, self.tcx) {
+            self.handle_unsupported_call(
+                &format!("Awaiting `{future_name}`"),
+                &format!("Awaiting the unsupported future `{future_name}`"),
+                &function_name,
+                places,
+                span,
+            );
+        } else if let Some(function_def_id) = function_def_id {
+            self.start_function_call(
+                function_def_id,
+                &function_name,
+                args,
+                destination,
+                places,
+                span,
+            );
+        } else {
+            self.handle_unsupported_call(
+                "TyKind::FnPtr",
+                "Call through a function pointer",
+                &function_name,
+                places,
+                span,
+            );
+        }
         self.function_counter.increment(&function_name);
     }
 
@@ -372,47 +657,297 @@ impl<'tcx> Translator<'tcx> {
         args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
         destination: rustc_middle::mir::Place<'tcx>,
         places: Places,
+        span: rustc_span::Span,
     ) {
+        let places = if self.assumes_no_unwind(function_def_id) {
+            places.ignore_cleanup_place()
+        } else {
+            places
+        };
+        // User-supplied models take precedence over the default handling
+        if let Some(model) = self.models.get(function_name).cloned() {
+            self.call_modelled_function(function_name, &model, places);
+            return;
+        }
         // Special cases
         if function_name == "std::mem::drop" {
             self.call_mem_drop(function_name, args, destination, places);
             return;
         }
+        if once::is_new_function(function_name) {
+            self.call_once_new(function_name, args, destination, places, span);
+            return;
+        }
+        if once::is_init_function(function_name)
+            || (function_name == "std::ops::Deref::deref"
+                && self.is_self_ref_lazy_lock(function_name, args))
+        {
+            self.call_once_init(function_name, args, places, span);
+            return;
+        }
         if (function_name == "std::ops::Deref::deref"
             || function_name == "std::ops::DerefMut::deref_mut")
-            && self.is_self_ref_mutex(function_name, args)
+            && (self.is_self_ref_mutex(function_name, args)
+                || self.is_self_ref_atomic(function_name, args))
+        {
+            self.call_deref_mutex(function_name, args, destination, places, span);
+            return;
+        }
+        if matches!(
+            function_name,
+            "std::result::Result::<T, E>::is_err" | "std::result::Result::<T, E>::is_ok"
+        ) && self.is_self_ref_atomic_value(function_name, args)
         {
-            self.call_deref_mutex(function_name, args, destination, places);
+            self.call_result_check_atomic(function_name, args, destination, places);
             return;
         }
         if function_name == "std::result::Result::<T, E>::unwrap"
             && self.is_self_ref_mutex(function_name, args)
         {
-            self.call_unwrap_mutex(function_name, args, destination, places);
+            self.call_unwrap_mutex(function_name, args, destination, places, span);
+            return;
+        }
+        if function_name == "std::result::Result::<T, E>::unwrap"
+            && self.is_self_ref_join_result(function_name, args)
+        {
+            self.call_unwrap_join_result(function_name, args, destination, places, span);
             return;
         }
         if function_name == "std::thread::spawn" {
-            self.call_thread_spawn(function_name, args, destination, places);
+            self.call_thread_spawn(function_name, args, destination, places, span);
+            return;
+        }
+        if function_name == "std::thread::current" {
+            self.call_thread_current(function_name, args, destination, places);
+            return;
+        }
+        if function_name == "std::thread::JoinHandle::<T>::thread"
+            && self.is_self_ref_join_handle(function_name, args)
+        {
+            self.call_join_handle_thread(function_name, args, destination, places);
+            return;
+        }
+        if function_name == "std::thread::park" {
+            self.call_park(function_name, places);
+            return;
+        }
+        if function_name == "std::iter::IntoIterator::into_iter" && self.unroll_const_loops {
+            let body = self.tcx.optimized_mir(self.call_stack.peek().def_id);
+            if let (Some(length), Some(iterator)) = (
+                range::find_constant_range_into_iter_length(body, args, self.tcx),
+                destination.as_local(),
+            ) {
+                self.call_range_into_iter(function_name, iterator, length, places);
+                return;
+            }
+        }
+        if function_name == "std::iter::Iterator::next" && self.unroll_const_loops {
+            let body = self.tcx.optimized_mir(self.call_stack.peek().def_id);
+            match range::find_constant_range(body, args, self.tcx) {
+                Some((iterator, Ok(length))) => {
+                    self.call_range_next(function_name, iterator, length, destination, places);
+                    return;
+                }
+                Some((_, Err(length))) => {
+                    self.record_imprecise_model(
+                        &format!(
+                            "Loop over a range with {length} iterations, more than the {} modelled, runs any number of times",
+                            range::MAX_ITERATIONS
+                        ),
+                        span,
+                    );
+                }
+                None => {}
+            }
+        }
+        if function_name == "tokio::spawn" || function_name == "tokio::task::spawn" {
+            self.call_task_spawn(function_name, args, destination, places, span);
             return;
         }
         // Sync or multithreading function
         if sync::is_supported_function(function_name) {
+            let memory = &self.call_stack.peek().memory;
+            if let Some(reason) =
+                sync::find_unsupported_reason(function_name, args, memory, self.tcx)
+            {
+                self.handle_unsupported_call(&reason, &reason, function_name, places, span);
+                return;
+            }
             // Index for transition and place labels
             let index = self.function_counter.get_count(function_name);
+            if matches!(places, Places::WithCleanup { .. }) {
+                let (_, cleanup_transition) = foreign_call_transition_labels(function_name, index);
+                self.record_panic_site(cleanup_transition, span);
+            }
+            match function_name {
+                "parking_lot::Mutex::<T>::new"
+                | "parking_lot::RwLock::<T>::new"
+                | "std::sync::Mutex::<T>::new"
+                | "tokio::sync::Mutex::<T>::new"
+                | "tokio::sync::RwLock::<T>::new" => {
+                    self.record_primitive(PrimitiveKind::Mutex, index, span);
+                }
+                "tokio::sync::Notify::new" => {
+                    self.record_primitive(PrimitiveKind::Notify, index, span);
+                }
+                "tokio::sync::Semaphore::new" => {
+                    self.record_primitive(PrimitiveKind::Semaphore, index, span);
+                }
+                "crossbeam_channel::bounded"
+                | "crossbeam_channel::unbounded"
+                | "tokio::sync::mpsc::channel" => {
+                    self.record_primitive(PrimitiveKind::Channel, index, span);
+                }
+                "parking_lot::Condvar::new" | "std::sync::Condvar::new" => {
+                    self.record_primitive(PrimitiveKind::Condvar, index, span);
+                }
+                _ if sync::atomic::is_atomic_function(function_name)
+                    && function_name.ends_with("::new") =>
+                {
+                    self.record_primitive(PrimitiveKind::Atomic, index, span);
+                }
+                "parking_lot::Condvar::notify_all"
+                | "parking_lot::Condvar::notify_one"
+                | "std::sync::Condvar::notify_one" => {
+                    self.record_notify_call(function_name, index, args, span);
+                }
+                "tokio::sync::RwLock::<T>::read" => {
+                    self.record_imprecise_model(
+                        "Shared read of a `tokio::sync::RwLock` modelled as an exclusive lock",
+                        span,
+                    );
+                }
+                "parking_lot::RwLock::<T>::read" => {
+                    self.record_imprecise_model(
+                        "Shared read of a `parking_lot::RwLock` modelled as an exclusive lock",
+                        span,
+                    );
+                    self.record_lock_acquisition(function_name, index, args, span);
+                }
+                "parking_lot::Mutex::<T>::lock"
+                | "parking_lot::RwLock::<T>::write"
+                | "std::sync::Mutex::<T>::lock" => {
+                    self.record_lock_acquisition(function_name, index, args, span);
+                }
+                _ => {}
+            }
+            if self.mutex_values
+                && matches!(
+                    function_name,
+                    "parking_lot::Mutex::<T>::new" | "std::sync::Mutex::<T>::new"
+                )
+            {
+                match mutex::extract_initial_value(args, self.tcx) {
+                    Some(Ok((number_of_values, initial_value))) => {
+                        let memory = &mut self.call_stack.peek_mut().memory;
+                        let task = mutex::call_new_with_values(
+                            function_name,
+                            index,
+                            number_of_values,
+                            initial_value,
+                            destination,
+                            places,
+                            &mut self.net,
+                            memory,
+                        );
+                        self.postprocessing.push(task);
+                        return;
+                    }
+                    Some(Err(value)) => {
+                        self.record_imprecise_model(
+                            &format!("Mutex created with the value {value} outside the modelled values, its values are not modelled"),
+                            span,
+                        );
+                    }
+                    None => {}
+                }
+            }
             // A reference to the memory of the current function
             let current_function = self.call_stack.peek_mut();
             let memory = &mut current_function.memory;
             // A reference to the Petri net to add transitions and places
             let net = &mut self.net;
-            if let Some(task) =
-                sync::call_function(function_name, index, args, destination, places, net, memory)
-            {
+            if function_name == "std::sync::Condvar::wait_while" {
+                let def_id = current_function.def_id;
+                if let Some(predicate) =
+                    sync::condvar::evaluate_wait_while_condition(args, memory, def_id, self.tcx)
+                {
+                    sync::condvar::call_wait_while_with_values(
+                        function_name,
+                        args,
+                        &predicate,
+                        destination,
+                        places,
+                        net,
+                        memory,
+                    );
+                    return;
+                }
+            }
+            if self.model_panics && function_name == "std::thread::JoinHandle::<T>::join" {
+                sync::thread::call_join_with_panics(
+                    function_name,
+                    index,
+                    args,
+                    destination,
+                    places,
+                    net,
+                    memory,
+                );
+                return;
+            }
+            if let Some(task) = sync::call_function(
+                function_name,
+                index,
+                args,
+                destination,
+                places,
+                net,
+                memory,
+                self.tcx,
+            ) {
                 self.postprocessing.push(task);
             }
             return;
         }
+        // Futures implemented by a coroutine
+        if function_def_id.is_local() && self.tcx.asyncness(function_def_id).is_async() {
+            self.call_async_function(function_name, args, destination, places);
+            return;
+        }
+        if is_block_on(function_name) || function_name == POLL_FUNCTION_NAME {
+            if let Some(future) = self.find_future_in_arguments(args) {
+                self.call_future(&future, places);
+                return;
+            }
+        }
+        // Futures of asynchronous operations on `tokio` primitives
+        if function_name == POLL_FUNCTION_NAME
+            && sync::is_awaited_sync_variable(args, &self.call_stack.peek().memory)
+        {
+            self.call_await_sync_variable(function_name, args, destination, places, span);
+            return;
+        }
         // Default case for standard and core library calls
         if is_foreign_function(function_def_id, function_name, self.tcx) {
+            let index = self.function_counter.get_count(function_name);
+            let (call_transition, cleanup_transition) =
+                foreign_call_transition_labels(function_name, index);
+            if matches!(places, Places::WithCleanup { .. }) {
+                self.record_panic_site(cleanup_transition, span);
+            } else if is_panic_function(function_name) {
+                // A `panic!` with pending drops continues in a cleanup block instead of diverging.
+                self.record_panic_site(call_transition, span);
+            }
+            self.call_foreign_function(function_name, args, destination, places);
+            return;
+        }
+        // Functions annotated with `#[check_deadlock::ignore]` are not translated
+        if self
+            .tool_attributes(function_def_id)
+            .contains(&ToolAttribute::Ignore)
+        {
+            info!("Ignoring the body of {function_name} as requested by its attribute");
             self.call_foreign_function(function_name, args, destination, places);
             return;
         }
@@ -420,140 +955,530 @@ impl<'tcx> Translator<'tcx> {
         self.call_mir_function(function_def_id, function_name, places);
     }
 
-    /// Checks whether the first argument (the self reference) is a mutex or a mutex guard.
-    fn is_self_ref_mutex(
-        &self,
-        function_name: &str,
-        args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
-    ) -> bool {
-        let self_ref = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
-            panic!("BUG: `{function_name}` should receive a reference as a place")
-        });
-        let function = self.call_stack.peek();
-        check_substring_in_place_type(
-            &self_ref,
-            "std::sync::MutexGuard<",
-            function.def_id,
-            self.tcx,
-        ) || check_substring_in_place_type(
-            &self_ref,
-            "std::sync::Mutex<",
-            function.def_id,
-            self.tcx,
-        )
-    }
-
-    /// Call to a MIR function. It is the default for user-defined functions in the code.
-    /// It is a recursive call for the translation process.
+    /// Handles a call that cannot be modelled according to the `UnsupportedPolicy`.
+    /// - `Abort`: Stores an error with a message containing `construct`, see `Translator::abort_translation`.
+    /// - `Havoc`: The call may return, unwind or block forever.
+    /// - `Skip`: The call is translated as a foreign function call.
     ///
-    /// A separate counter is incremented every time that
-    /// the function is called to generate a unique label.
-    fn call_mir_function(
+    /// Records the approximation with the given `description` and the location of the call.
+    fn handle_unsupported_call(
         &mut self,
-        function_def_id: rustc_hir::def_id::DefId,
+        construct: &str,
+        description: &str,
         function_name: &str,
         places: Places,
+        span: rustc_span::Span,
     ) {
         let index = self.function_counter.get_count(function_name);
-
-        match places {
-            Places::WithCleanup {
-                start_place,
-                end_place,
-                cleanup_place,
-            } => {
-                connect_places(
+        match self.on_unsupported {
+            UnsupportedPolicy::Abort => {
+                self.abort_translation(construct, span);
+                call_foreign_function(function_name, index, places, &mut self.net);
+                return;
+            }
+            UnsupportedPolicy::Havoc => {
+                call_havoc_function(
+                    function_name,
+                    index,
+                    places,
+                    &self.program_panic,
                     &mut self.net,
-                    &start_place,
-                    &cleanup_place,
-                    &indexed_mir_function_cleanup_label(function_name, index),
                 );
-
-                self.call_stack.push(MirFunction::new(
-                    function_def_id,
-                    indexed_mir_function_name(function_name, index),
-                    start_place,
-                    end_place,
-                ));
             }
-            Places::Basic {
-                start_place,
-                end_place,
-            } => {
-                self.call_stack.push(MirFunction::new(
-                    function_def_id,
-                    indexed_mir_function_name(function_name, index),
-                    start_place,
-                    end_place,
-                ));
+            UnsupportedPolicy::Skip => {
+                call_foreign_function(function_name, index, places, &mut self.net);
             }
         }
-        info!("Pushed function {function_name} to the translation call stack");
-        self.translate_top_call_stack();
+        self.record_approximation(description, span);
     }
 
-    /// Call to a foreign function. It is the default for standard and core library calls.
-    /// It is a non-recursive call for the translation process.
-    /// It is also reused by other handlers since this is the basic case.
-    ///
-    /// Translates a call to a function with given function name using
-    /// the same representation as in `special_function::call_foreign_function`.
-    ///
-    /// A separate counter is incremented every time that
-    /// the function is called to generate a unique label.
-    ///
-    /// Performs a check to keep track of synchronization primitives.
-    /// In case the first argument is a mutex, mutex guard, join handle or condition variable,
-    /// it links the first argument of the function to its return value.
+    /// Handles a terminator that cannot be modelled according to the `UnsupportedPolicy`.
+    /// - `Abort`: Stores an error with a message containing `construct`, see `Translator::abort_translation`.
+    /// - `Havoc`: The terminator may jump to any of its successors, panic or block forever.
+    /// - `Skip`: The terminator is replaced by a jump to any of its successors.
     ///
-    /// Returns the transitions representing the function call.
-    fn call_foreign_function(
+    /// Records the approximation with the location of the terminator.
+    fn handle_unsupported_terminator(
         &mut self,
-        function_name: &str,
-        args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
-        destination: rustc_middle::mir::Place<'tcx>,
-        places: Places,
-    ) -> Transitions {
-        let index = self.function_counter.get_count(function_name);
-        let transitions = call_foreign_function(function_name, index, places, &mut self.net);
+        construct: &str,
+        targets: Vec<rustc_middle::mir::BasicBlock>,
+        span: rustc_span::Span,
+    ) {
+        if self.on_unsupported == UnsupportedPolicy::Abort {
+            self.abort_translation(construct, span);
+        }
+        let function = self.call_stack.peek_mut();
+        match self.on_unsupported {
+            UnsupportedPolicy::Abort => {
+                function.skip(targets, &mut self.net);
+                return;
+            }
+            UnsupportedPolicy::Havoc => {
+                function.havoc(targets, &self.program_panic, &mut self.net);
+            }
+            UnsupportedPolicy::Skip => {
+                function.skip(targets, &mut self.net);
+            }
+        }
+        self.record_approximation(construct, span);
+    }
 
-        let current_function = self.call_stack.peek_mut();
-        sync::link_return_value_if_sync_variable(
-            args,
-            destination,
-            &mut current_function.memory,
-            current_function.def_id,
-            self.tcx,
-        );
+    /// Stores the error for an unsupported construct found with `UnsupportedPolicy::Abort`
+    /// in the current function at the location given by `span`.
+    /// Only the first construct is reported. The translation goes on as with `UnsupportedPolicy::Skip`
+    /// so that the net stays consistent, but `Translator::run` returns the error at the end.
+    fn abort_translation(&mut self, construct: &str, span: rustc_span::Span) {
+        if self.unsupported_construct.is_some() {
+            return;
+        }
+        let function = self.call_stack.peek();
+        self.unsupported_construct = Some(format!(
+            "{construct} not implemented yet in `{}` at {}",
+            function.name,
+            format_span(span, self.tcx)
+        ));
+    }
 
-        transitions
+    /// Records an approximation of an unsupported construct in the current function
+    /// at the location given by `span`.
+    fn record_approximation(&mut self, construct: &str, span: rustc_span::Span) {
+        self.push_approximation(construct, span, Some(self.on_unsupported));
     }
 
-    /// Call to `std::mem::drop`.
-    /// Non-recursive call for the translation process.
-    fn call_mem_drop(
+    /// Records a supported construct whose model is imprecise in the current function
+    /// at the location given by `span`.
+    fn record_imprecise_model(&mut self, construct: &str, span: rustc_span::Span) {
+        self.push_approximation(construct, span, None);
+    }
+
+    /// Adds the approximation to the list of approximations found during the translation.
+    fn push_approximation(
         &mut self,
-        function_name: &str,
-        args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
-        destination: rustc_middle::mir::Place<'tcx>,
-        places: Places,
+        construct: &str,
+        span: rustc_span::Span,
+        policy: Option<UnsupportedPolicy>,
     ) {
-        let transitions = self.call_foreign_function(function_name, args, destination, places);
+        let function = self.call_stack.peek();
+        let approximation = Approximation {
+            construct: construct.to_string(),
+            function_name: function.name.clone(),
+            location: format_span(span, self.tcx),
+            policy,
+        };
+        info!("Approximated a construct: {approximation}");
+        self.approximations.push(approximation);
+    }
 
-        let dropped_place = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
-            panic!("BUG: `{function_name}` should receive the value to be dropped as a place")
+    /// Records a place where the current thread may start to panic at the location given by `span`.
+    /// `transition` is the label of the transition that starts the panic.
+    /// Spans inside macro expansions, e.g. `panic!` or `println!`, are mapped to the macro call.
+    pub fn record_panic_site(&mut self, transition: String, span: rustc_span::Span) {
+        self.panic_sites.push(PanicSite {
+            thread: self.current_thread,
+            transition,
+            location: format_span(span.source_callsite(), self.tcx),
         });
+    }
 
-        let function = self.call_stack.peek_mut();
+    /// Records a synchronization primitive created at the location given by `span`.
+    fn record_primitive(&mut self, kind: PrimitiveKind, index: usize, span: rustc_span::Span) {
+        self.primitives.push(Primitive {
+            kind,
+            index,
+            location: format_span(span, self.tcx),
+        });
+    }
+
+    /// Records a call to `std::sync::Mutex::<T>::lock` or an equivalent function at the location given by `span`.
+    /// The mutex is the one linked to the first argument (the self reference).
+    fn record_lock_acquisition(
+        &mut self,
+        function_name: &str,
+        index: usize,
+        args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
+        span: rustc_span::Span,
+    ) {
+        let self_ref = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+            panic!("BUG: `{function_name}` should receive the self reference as a place")
+        });
+        let mutex = self.call_stack.peek().memory.get_mutex(&self_ref).index();
+        let (transition, _) = foreign_call_transition_labels(function_name, index);
+        self.lock_acquisitions.push(LockAcquisition {
+            mutex,
+            transition,
+            location: format_span(span, self.tcx),
+            shared: function_name == "parking_lot::RwLock::<T>::read",
+        });
+    }
+
+    /// Records a call to `std::sync::Condvar::notify_one` or an equivalent function at the location given by `span`.
+    /// The condition variable is the one linked to the first argument (the self reference).
+    fn record_notify_call(
+        &mut self,
+        function_name: &str,
+        index: usize,
+        args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
+        span: rustc_span::Span,
+    ) {
+        let self_ref = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+            panic!("BUG: `{function_name}` should receive the self reference as a place")
+        });
+        let condvar = self.call_stack.peek().memory.get_condvar(&self_ref).index();
+        let (transition, _) = foreign_call_transition_labels(function_name, index);
+        self.notify_calls.push(NotifyCall {
+            condvar,
+            transition,
+            location: format_span(span, self.tcx),
+        });
+    }
+
+    /// Checks whether the first argument (the self reference) is linked to the join handle of a thread.
+    fn is_self_ref_join_handle(
+        &self,
+        function_name: &str,
+        args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
+    ) -> bool {
+        let self_ref = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+            panic!("BUG: `{function_name}` should receive a reference as a place")
+        });
+        self.call_stack.peek().memory.is_join_handle(&self_ref)
+    }
+
+    /// Checks whether the first argument (the self reference) is linked to the result of joining a thread.
+    /// This is only the case if the panics are modelled.
+    fn is_self_ref_join_result(
+        &self,
+        function_name: &str,
+        args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
+    ) -> bool {
+        let self_ref = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+            panic!("BUG: `{function_name}` should receive a reference as a place")
+        });
+        self.call_stack.peek().memory.is_join_result(&self_ref)
+    }
+
+    /// Checks whether the first argument (the self reference) is a mutex or a mutex guard
+    /// of the standard library or of `parking_lot`.
+    fn is_self_ref_mutex(
+        &self,
+        function_name: &str,
+        args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
+    ) -> bool {
+        let self_ref = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+            panic!("BUG: `{function_name}` should receive a reference as a place")
+        });
+        let function = self.call_stack.peek();
+        [
+            "std::sync::MutexGuard<",
+            "std::sync::Mutex<",
+            "parking_lot::MutexGuard<",
+            "parking_lot::Mutex<",
+            "parking_lot::RwLockReadGuard<",
+            "parking_lot::RwLockWriteGuard<",
+        ]
+        .iter()
+        .any(|expected_substring| {
+            check_substring_in_place_type(&self_ref, expected_substring, function.def_id, self.tcx)
+        })
+    }
+
+    /// Returns the mutex of the mutex guard passed as the first argument (the self reference)
+    /// if the value stored in the mutex is modelled.
+    fn find_mutex_with_values(
+        &self,
+        function_name: &str,
+        args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
+    ) -> Option<MutexRef> {
+        let self_ref = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+            panic!("BUG: `{function_name}` should receive a reference as a place")
+        });
+        let memory = &self.call_stack.peek().memory;
+        if !memory.is_mutex_guard(&self_ref) {
+            return None;
+        }
+        let mutex_ref = &memory.get_mutex_guard(&self_ref).mutex;
+        mutex_ref.has_values().then(|| mutex_ref.clone())
+    }
+
+    /// Checks whether the first argument (the self reference) contains an atomic variable,
+    /// e.g. an `std::sync::Arc<std::sync::atomic::AtomicBool>`.
+    fn is_self_ref_atomic(
+        &self,
+        function_name: &str,
+        args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
+    ) -> bool {
+        let self_ref = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+            panic!("BUG: `{function_name}` should receive a reference as a place")
+        });
+        let function = self.call_stack.peek();
+        check_substring_in_place_type(
+            &self_ref,
+            "std::sync::atomic::Atomic",
+            function.def_id,
+            self.tcx,
+        )
+    }
+
+    /// Checks whether the first argument (the self reference) is linked to a value
+    /// returned by a call on an atomic variable, e.g. the result of `compare_exchange`.
+    fn is_self_ref_atomic_value(
+        &self,
+        function_name: &str,
+        args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
+    ) -> bool {
+        let self_ref = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+            panic!("BUG: `{function_name}` should receive a reference as a place")
+        });
+        self.call_stack
+            .peek()
+            .memory
+            .find_atomic_value(&self_ref)
+            .is_some()
+    }
+
+    /// Checks whether the first argument (the self reference) is a reference to a `std::sync::LazyLock`
+    /// linked to a one-time initialization.
+    /// Dereferencing an `std::sync::Arc` that contains it does not force the initialization.
+    fn is_self_ref_lazy_lock(
+        &self,
+        function_name: &str,
+        args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
+    ) -> bool {
+        let self_ref = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+            panic!("BUG: `{function_name}` should receive a reference as a place")
+        });
+        let function = self.call_stack.peek();
+        function.memory.find_once(&self_ref).is_some()
+            && check_substring_in_place_type(
+                &self_ref,
+                "&std::sync::LazyLock<",
+                function.def_id,
+                self.tcx,
+            )
+    }
+
+    /// Call to a MIR function. It is the default for user-defined functions in the code.
+    /// It is a recursive call for the translation process.
+    ///
+    /// A separate counter is incremented every time that
+    /// the function is called to generate a unique label.
+    fn call_mir_function(
+        &mut self,
+        function_def_id: rustc_hir::def_id::DefId,
+        function_name: &str,
+        places: Places,
+    ) {
+        self.push_mir_function(function_def_id, function_name, places);
+        self.translate_top_call_stack();
+    }
+
+    /// Pushes a new function frame for a function with MIR representation to the call stack.
+    /// If a cleanup place is present, connects the start place to it to model the function unwinding.
+    fn push_mir_function(
+        &mut self,
+        function_def_id: rustc_hir::def_id::DefId,
+        function_name: &str,
+        places: Places,
+    ) {
+        let index = self.function_counter.get_count(function_name);
+        let tool_attributes = self.tool_attributes(function_def_id).to_vec();
+
+        match places {
+            Places::WithCleanup {
+                start_place,
+                end_place,
+                cleanup_place,
+            } => {
+                connect_places(
+                    &mut self.net,
+                    &start_place,
+                    &cleanup_place,
+                    &indexed_mir_function_cleanup_label(function_name, index),
+                );
+
+                self.call_stack.push(MirFunction::new(
+                    function_def_id,
+                    indexed_mir_function_name(function_name, index),
+                    start_place,
+                    end_place,
+                    tool_attributes,
+                ));
+            }
+            Places::Basic {
+                start_place,
+                end_place,
+            } => {
+                self.call_stack.push(MirFunction::new(
+                    function_def_id,
+                    indexed_mir_function_name(function_name, index),
+                    start_place,
+                    end_place,
+                    tool_attributes,
+                ));
+            }
+        }
+        info!("Pushed function {function_name} to the translation call stack");
+    }
+
+    /// Call to an `async fn`.
+    /// Non-recursive call for the translation process.
+    ///
+    /// The body of an `async fn` only creates the future, i.e. the coroutine that captures the arguments.
+    /// The destination is linked to the new future and its coroutine is translated when the future is polled.
+    /// Creating a future cannot panic, therefore the cleanup place is not modelled.
+    fn call_async_function(
+        &mut self,
+        function_name: &str,
+        args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
+        destination: rustc_middle::mir::Place<'tcx>,
+        places: Places,
+    ) {
+        let index = self.function_counter.get_count(function_name);
+        call_foreign_function(
+            function_name,
+            index,
+            places.ignore_cleanup_place(),
+            &mut self.net,
+        );
+        let current_function = self.call_stack.peek_mut();
+        let Some(def_id) =
+            extract_coroutine_def_id(&destination, current_function.def_id, self.tcx)
+        else {
+            return;
+        };
+        let operands = args.iter().map(|arg| &arg.node);
+        let future = create_future(def_id, operands, &current_function.memory);
+        current_function.memory.link_future(destination, future);
+        debug!("NEW FUTURE: {destination:?}");
+    }
+
+    /// Returns the first future linked to one of the arguments of the call.
+    fn find_future_in_arguments(
+        &self,
+        args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
+    ) -> Option<FutureRef> {
+        let memory = &self.call_stack.peek().memory;
+        args.iter()
+            .filter_map(|arg| arg.node.place())
+            .find_map(|place| memory.find_future(&place).cloned())
+    }
+
+    /// Call to a function that polls a future until it completes, i.e.
+    /// `std::future::Future::poll` when awaiting the future or an executor like `block_on`.
+    /// It is a recursive call for the translation process.
+    ///
+    /// Translates the body of the coroutine that implements the future like a MIR function
+    /// and links the values captured by the future in its memory.
+    /// See the `coroutine` submodule for the details of the executor model.
+    fn call_future(&mut self, future: &FutureRef, places: Places) {
+        let function_name = self.tcx.def_path_str(future.def_id);
+        self.push_mir_function(future.def_id, &function_name, places);
+        self.prepare_coroutine(future);
+
+        self.translate_top_call_stack();
+        self.function_counter.increment(&function_name);
+    }
+
+    /// Prepares the function on the top of the call stack, the coroutine that implements the future,
+    /// for translation: Finds the structure of its body and links the values captured by the future.
+    fn prepare_coroutine(&mut self, future: &FutureRef) {
+        let body = self.tcx.optimized_mir(future.def_id);
+        let coroutine = Coroutine::new(body, self.tcx);
+        let function = self.call_stack.peek_mut();
+        coroutine.link_upvars(future, &mut function.memory, body);
+        function.coroutine = Some(coroutine);
+    }
+
+    /// Call to a foreign function. It is the default for standard and core library calls.
+    /// It is a non-recursive call for the translation process.
+    /// It is also reused by other handlers since this is the basic case.
+    ///
+    /// Translates a call to a function with given function name using
+    /// the same representation as in `special_function::call_foreign_function`.
+    ///
+    /// A separate counter is incremented every time that
+    /// the function is called to generate a unique label.
+    ///
+    /// Performs a check to keep track of synchronization primitives.
+    /// In case the first argument is a mutex, mutex guard, join handle or condition variable,
+    /// it links the first argument of the function to its return value.
+    ///
+    /// Returns the transitions representing the function call.
+    fn call_foreign_function(
+        &mut self,
+        function_name: &str,
+        args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
+        destination: rustc_middle::mir::Place<'tcx>,
+        places: Places,
+    ) -> Transitions {
+        let index = self.function_counter.get_count(function_name);
+        let transitions = call_foreign_function(function_name, index, places, &mut self.net);
+
+        let current_function = self.call_stack.peek_mut();
+        sync::link_return_value_if_sync_variable(
+            args,
+            destination,
+            &mut current_function.memory,
+            current_function.def_id,
+            self.tcx,
+        );
+
+        transitions
+    }
+
+    /// Call to a function with a user-supplied model.
+    /// Non-recursive call for the translation process.
+    fn call_modelled_function(
+        &mut self,
+        function_name: &str,
+        model: &FunctionModel,
+        places: Places,
+    ) {
+        let index = self.function_counter.get_count(function_name);
+        let resource_place = model
+            .resource
+            .as_ref()
+            .and_then(|resource_name| self.resource_places.get(resource_name));
+        call_modelled_function(
+            function_name,
+            index,
+            model.behavior,
+            resource_place,
+            places,
+            &mut self.net,
+        );
+        info!("Translated the call to `{function_name}` with the user-supplied model");
+    }
+
+    /// Call to `std::mem::drop`.
+    /// Non-recursive call for the translation process.
+    fn call_mem_drop(
+        &mut self,
+        function_name: &str,
+        args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
+        destination: rustc_middle::mir::Place<'tcx>,
+        places: Places,
+    ) {
+        let transitions = self.call_foreign_function(function_name, args, destination, places);
+
+        let dropped_place = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+            panic!("BUG: `{function_name}` should receive the value to be dropped as a place")
+        });
+
+        let function = self.call_stack.peek_mut();
+        let def_id = function.def_id;
         let memory = &mut function.memory;
         let net = &mut self.net;
         match transitions {
             Transitions::Basic { default } => {
                 mutex::handle_mutex_guard_drop(dropped_place, &default, net, memory);
+                sync::handle_primitive_drop(dropped_place, &default, net, memory, def_id, self.tcx);
             }
             Transitions::WithCleanup { default, cleanup } => {
                 mutex::handle_mutex_guard_drop(dropped_place, &default, net, memory);
                 mutex::handle_mutex_guard_drop(dropped_place, &cleanup, net, memory);
+                sync::handle_primitive_drop(dropped_place, &default, net, memory, def_id, self.tcx);
+                sync::handle_primitive_drop(dropped_place, &cleanup, net, memory, def_id, self.tcx);
             }
         }
     }
@@ -562,21 +1487,59 @@ impl<'tcx> Translator<'tcx> {
     /// Non-recursive call for the translation process.
     ///
     /// If a mutex guard is dereferenced mutably, add the transition to set the mutex condition later.
+    /// If the value stored in the mutex is modelled, the dereference reads or writes the value instead,
+    /// see `mutex::call_deref_with_values`.
     ///
     /// In some cases, the `std::ops::Deref::deref` or `std::ops::DerefMut::deref_mut` functions contain a cleanup target.
     /// This target is not called in practice but creates trouble for lost signal detection.
     /// The reason is that any call may fail, which is equivalent to saying that the dereference operation
     /// was never present in the program, leading to a false lost signal.
     /// In conclusion: Ignore the cleanup place, do not model it.
-    /// Assume `deref` and `deref_mut` never unwind when dereferencing a variable linked to a mutex or a mutex guard.
+    /// Assume `deref` and `deref_mut` never unwind when dereferencing a variable linked to a mutex, a mutex guard
+    /// or an atomic variable.
     fn call_deref_mutex(
         &mut self,
         function_name: &str,
         args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
         destination: rustc_middle::mir::Place<'tcx>,
         places: Places,
+        span: rustc_span::Span,
     ) {
         let places = places.ignore_cleanup_place();
+        if let Some(mutex_ref) = self.find_mutex_with_values(function_name, args) {
+            let function = self.call_stack.peek_mut();
+            let write = match destination.as_local() {
+                _ if function_name == "std::ops::Deref::deref" => mutex::Write::None,
+                Some(reference) => {
+                    let body = self.tcx.optimized_mir(function.def_id);
+                    mutex::find_write(body, reference, self.tcx)
+                }
+                None => mutex::Write::Unknown,
+            };
+            if write.leaves_modelled_values(mutex_ref.number_of_values()) {
+                self.record_imprecise_model(
+                    &format!(
+                        "Write of a value outside the modelled values 0 to {} of mutex {}, modelled as any value",
+                        mutex_ref.number_of_values() - 1,
+                        mutex_ref.index()
+                    ),
+                    span,
+                );
+            }
+            let function = self.call_stack.peek_mut();
+            let index = self.function_counter.get_count(function_name);
+            mutex::call_deref_with_values(
+                function_name,
+                index,
+                &mutex_ref,
+                write,
+                destination,
+                places,
+                &mut self.net,
+                &mut function.memory,
+            );
+            return;
+        }
         let transitions = self.call_foreign_function(function_name, args, destination, places);
         let transition = transitions.default();
 
@@ -585,12 +1548,35 @@ impl<'tcx> Translator<'tcx> {
                 panic!("BUG: `{function_name}` should receive a reference as a place")
             });
             let function = self.call_stack.peek_mut();
+            if !function.memory.is_linked(&reference) {
+                // The mutex guard was not tracked, e.g. the call to `lock` was not modelled.
+                return;
+            }
             let mutex_guard_ref = function.memory.get_mutex_guard(&reference);
             mutex_guard_ref.mutex.add_deref_mut_transition(transition);
             info!("Encountered a mutable dereference of a mutex guard");
         }
     }
 
+    /// Call to `std::result::Result::<T, E>::is_err` or `std::result::Result::<T, E>::is_ok`
+    /// on the result of a compare-exchange operation on an atomic variable.
+    /// Non-recursive call for the translation process.
+    ///
+    /// Links the return value to the outcome of the operation, so that a loop on the result can be guarded.
+    /// Like in `call_deref_mutex`, ignore the cleanup place: Checking the variant of a `Result` never unwinds.
+    fn call_result_check_atomic(
+        &mut self,
+        function_name: &str,
+        args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
+        destination: rustc_middle::mir::Place<'tcx>,
+        places: Places,
+    ) {
+        let places = places.ignore_cleanup_place();
+        self.call_foreign_function(function_name, args, destination, places);
+        let function = self.call_stack.peek_mut();
+        sync::atomic::link_result_check(function_name, args, destination, &mut function.memory);
+    }
+
     /// Call to `std::result::Result::<T, E>::unwrap`.
     /// Non-recursive call for the translation process.
     ///
@@ -600,15 +1586,97 @@ impl<'tcx> Translator<'tcx> {
     /// was never present in the program, leading to a false lost signal.
     /// In conclusion: Ignore the cleanup place, do not model it.
     /// Assume `unwrap` never unwinds when applied to a variable linked to a mutex or a mutex guard.
+    ///
+    /// If the panics are modelled, `unwrap` panics if and only if the mutex is poisoned.
+    /// The panic goes to the cleanup place or to the panic place if there is no cleanup place.
     fn call_unwrap_mutex(
         &mut self,
         function_name: &str,
         args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
         destination: rustc_middle::mir::Place<'tcx>,
         places: Places,
+        span: rustc_span::Span,
     ) {
-        let places = places.ignore_cleanup_place();
-        self.call_foreign_function(function_name, args, destination, places);
+        let self_ref = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+            panic!("BUG: `{function_name}` should receive the self reference as a place")
+        });
+        if !self.model_panics || !self.call_stack.peek().memory.is_mutex_guard(&self_ref) {
+            let places = places.ignore_cleanup_place();
+            self.call_foreign_function(function_name, args, destination, places);
+            return;
+        }
+        let (start_place, panic_place) = self.get_start_and_panic_place(&places);
+        let index = self.function_counter.get_count(function_name);
+        let ok_transition = self
+            .call_foreign_function(
+                function_name,
+                args,
+                destination,
+                places.ignore_cleanup_place(),
+            )
+            .default();
+        let (_, err_label) = foreign_call_transition_labels(function_name, index);
+        let err_transition = connect_places(&mut self.net, &start_place, &panic_place, &err_label);
+
+        let memory = &self.call_stack.peek().memory;
+        let mutex_guard_ref = memory.get_mutex_guard(&self_ref);
+        mutex_guard_ref
+            .mutex
+            .add_poison_check_arcs(&ok_transition, &err_transition, &mut self.net);
+        self.record_panic_site(err_label, span);
+    }
+
+    /// Call to `std::result::Result::<T, E>::unwrap` on the result of joining a thread.
+    /// Non-recursive call for the translation process.
+    ///
+    /// The call returns if the thread ended normally and panics if the thread panicked.
+    /// The panic goes to the cleanup place or to the panic place if there is no cleanup place.
+    fn call_unwrap_join_result(
+        &mut self,
+        function_name: &str,
+        args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
+        destination: rustc_middle::mir::Place<'tcx>,
+        places: Places,
+        span: rustc_span::Span,
+    ) {
+        let self_ref = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+            panic!("BUG: `{function_name}` should receive the self reference as a place")
+        });
+        let (start_place, panic_place) = self.get_start_and_panic_place(&places);
+        let index = self.function_counter.get_count(function_name);
+        let ok_transition = self
+            .call_foreign_function(
+                function_name,
+                args,
+                destination,
+                places.ignore_cleanup_place(),
+            )
+            .default();
+        let (_, err_label) = foreign_call_transition_labels(function_name, index);
+        let err_transition = connect_places(&mut self.net, &start_place, &panic_place, &err_label);
+
+        let join_result_ref = self
+            .call_stack
+            .peek()
+            .memory
+            .get_join_result(&self_ref)
+            .clone();
+        add_arc_place_transition(&mut self.net, &join_result_ref.ok, &ok_transition);
+        add_arc_place_transition(&mut self.net, &join_result_ref.err, &err_transition);
+        self.record_panic_site(err_label, span);
+    }
+
+    /// Returns the start place of a function call and the place where a panic in the call goes,
+    /// i.e. the cleanup place or the panic place if there is no cleanup place.
+    fn get_start_and_panic_place(&self, places: &Places) -> (PlaceRef, PlaceRef) {
+        match places {
+            Places::Basic { start_place, .. } => (start_place.clone(), self.program_panic.clone()),
+            Places::WithCleanup {
+                start_place,
+                cleanup_place,
+                ..
+            } => (start_place.clone(), cleanup_place.clone()),
+        }
     }
 
     /// Call to `std::thread::spawn`.
@@ -625,21 +1693,31 @@ impl<'tcx> Translator<'tcx> {
         args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
         destination: rustc_middle::mir::Place<'tcx>,
         places: Places,
+        span: rustc_span::Span,
     ) {
-        let transitions = self.call_foreign_function(function_name, args, destination, places);
-        let transition = transitions.default();
-
         // Extract the definition ID of the thread function
-        let current_function = self.call_stack.peek_mut();
         let function_to_be_run = args.first().unwrap_or_else(|| {
             panic!("BUG: `{function_name}` should receive the function to be run")
         });
-        let thread_function_def_id = extract_def_id_of_called_function_from_operand(
+        let Some(thread_function_def_id) = extract_def_id_of_called_function_from_operand(
             &function_to_be_run.node,
-            current_function.def_id,
+            self.call_stack.peek().def_id,
             self.tcx,
-        );
+        ) else {
+            // A function pointer passed to `std::thread::spawn` cannot be resolved to the thread function.
+            self.handle_unsupported_call(
+                "TyKind::FnPtr",
+                "Function pointer passed to `std::thread::spawn`",
+                function_name,
+                places,
+                span,
+            );
+            return;
+        };
 
+        let transitions = self.call_foreign_function(function_name, args, destination, places);
+        let transition = transitions.default();
+        let current_function = self.call_stack.peek_mut();
         let closure = extract_closure(args);
         // The sync variables captured by the closure are aggregated together in a single value in memory
         // Get this vector of values that should be re-mapped in the new thread's memory.
@@ -657,6 +1735,373 @@ impl<'tcx> Translator<'tcx> {
 
         // Add the thread to the translator
         self.threads.push_back(thread_ref.clone());
+        self.record_primitive(PrimitiveKind::Thread, index, span);
         info!("Found thread {index} and pushed it to the back of the thread translation queue");
     }
+
+    /// Call to `std::sync::Once::new`, `std::sync::OnceLock::<T>::new` or `std::sync::LazyLock::<T, F>::new`.
+    /// Non-recursive call for the translation process.
+    ///
+    /// The closure passed to `std::sync::LazyLock::<T, F>::new` is stored as the initializer
+    /// together with the sync variables that it captures, see `once::call_new`.
+    fn call_once_new(
+        &mut self,
+        function_name: &str,
+        args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
+        destination: rustc_middle::mir::Place<'tcx>,
+        places: Places,
+        span: rustc_span::Span,
+    ) {
+        let function = self.call_stack.peek();
+        let initializer =
+            once::extract_initializer(args, 0, &function.memory, function.def_id, self.tcx);
+        if function_name == "std::sync::LazyLock::<T, F>::new" && initializer.is_none() {
+            let reason =
+                format!("Call to `{function_name}` with a function pointer as the initializer");
+            self.handle_unsupported_call(&reason, &reason, function_name, places, span);
+            return;
+        }
+        let index = self.function_counter.get_count(function_name);
+        if matches!(places, Places::WithCleanup { .. }) {
+            let (_, cleanup_transition) = foreign_call_transition_labels(function_name, index);
+            self.record_panic_site(cleanup_transition, span);
+        }
+        let memory = &mut self.call_stack.peek_mut().memory;
+        once::call_new(
+            function_name,
+            index,
+            initializer,
+            destination,
+            places,
+            &mut self.net,
+            memory,
+        );
+        self.record_primitive(PrimitiveKind::Once, index, span);
+    }
+
+    /// Call to a function that runs the initializer of a one-time initialization if it has not run yet,
+    /// i.e. `std::sync::Once::call_once`, `std::sync::OnceLock::<T>::get_or_init`,
+    /// `std::sync::LazyLock::<T, F>::force` or dereferencing a `std::sync::LazyLock`.
+    /// Recursive call for the translation process.
+    ///
+    /// - Retrieves the one-time initialization linked to the first argument (the self reference).
+    /// - Retrieves the initializer: The closure passed as the second argument
+    ///   or the closure passed to `std::sync::LazyLock::<T, F>::new`.
+    /// - Adds the transitions of the state machine, see `once::call_init`.
+    /// - Translates the body of the initializer inline between the start and the end of the initialization,
+    ///   with the sync variables that it captures linked in its memory.
+    fn call_once_init(
+        &mut self,
+        function_name: &str,
+        args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
+        places: Places,
+        span: rustc_span::Span,
+    ) {
+        let self_ref = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+            panic!("BUG: `{function_name}` should receive the self reference as a place")
+        });
+        let function = self.call_stack.peek();
+        let Some(once_ref) = function.memory.find_once(&self_ref).cloned() else {
+            let reason =
+                format!("Call to `{function_name}` with an untracked synchronization variable");
+            self.handle_unsupported_call(&reason, &reason, function_name, places, span);
+            return;
+        };
+        let initializer = once_ref.initializer().cloned().or_else(|| {
+            once::extract_initializer(args, 1, &function.memory, function.def_id, self.tcx)
+        });
+        let Some(initializer) = initializer else {
+            let reason =
+                format!("Call to `{function_name}` with a function pointer as the initializer");
+            self.handle_unsupported_call(&reason, &reason, function_name, places, span);
+            return;
+        };
+
+        let index = self.function_counter.get_count(function_name);
+        let (initializer_start, initializer_end, initializer_panic) = once::call_init(
+            function_name,
+            index,
+            &once_ref,
+            places,
+            &self.program_panic,
+            &mut self.net,
+        );
+        // The initializer may call the same function again, e.g. reentering the same `Once`.
+        // Increment the counter now so that the nested call gets different labels.
+        self.function_counter.increment(function_name);
+        let initializer_name = self.tcx.def_path_str(initializer.def_id);
+        self.push_mir_function(
+            initializer.def_id,
+            &initializer_name,
+            Places::Basic {
+                start_place: initializer_start,
+                end_place: initializer_end,
+            },
+        );
+        if !initializer.aggregate.is_empty() {
+            let new_function = self.call_stack.peek_mut();
+            sync::link_captured_sync_variables(
+                initializer.def_id,
+                initializer.aggregate,
+                &mut new_function.memory,
+                self.tcx,
+            );
+        }
+        // A panic in the initializer resets the initialization before it reaches the panic place.
+        let panic_place = std::mem::replace(&mut self.program_panic, initializer_panic);
+        self.translate_top_call_stack();
+        self.program_panic = panic_place;
+        self.function_counter.increment(&initializer_name);
+        info!(
+            "Translated the initializer of one-time initialization {} inline",
+            once_ref.index()
+        );
+    }
+
+    /// Links the place to the one-time initialization in a `static` item if the constant refers to one,
+    /// e.g. `_3 = const {alloc1: &std::sync::Once}` for `static INIT: Once = Once::new()`.
+    /// The one-time initialization is added to the net the first time that the static is referenced.
+    fn link_if_static_once(
+        &mut self,
+        place: &rustc_middle::mir::Place<'tcx>,
+        constant: &rustc_middle::mir::ConstOperand<'tcx>,
+    ) {
+        let Some((once_type, def_id)) = once::find_static_once(constant, self.tcx) else {
+            return;
+        };
+        let once_ref = if let Some(once_ref) = self.static_onces.get(&def_id) {
+            once_ref.clone()
+        } else {
+            let new_function = once::new_function(once_type);
+            let index = self.function_counter.get_count(new_function);
+            self.function_counter.increment(new_function);
+            let initializer = (once_type == "std::sync::LazyLock")
+                .then(|| once::extract_static_initializer(def_id, self.tcx))
+                .flatten();
+            let once_ref = Rc::new(Once::new(once_type, index, initializer, &mut self.net));
+            self.record_primitive(PrimitiveKind::Once, index, self.tcx.def_span(def_id));
+            info!(
+                "Found one-time initialization {index} in the static `{}`",
+                self.tcx.def_path_str(def_id)
+            );
+            self.static_onces.insert(def_id, once_ref.clone());
+            once_ref
+        };
+        let memory = &mut self.call_stack.peek_mut().memory;
+        memory.link_value(*place, Value::Once(once_ref));
+        debug!("STATIC ONCE: {place:?}");
+    }
+
+    /// Returns the park token of the thread with the given index or `None` for the main thread.
+    /// Adds it to the Petri net the first time that it is needed.
+    fn get_park_token(&mut self, thread: Option<usize>) -> ParkTokenRef {
+        self.park_tokens
+            .entry(thread)
+            .or_insert_with(|| Rc::new(ParkToken::new(thread, &mut self.net)))
+            .clone()
+    }
+
+    /// Call to `std::thread::current`.
+    /// Non-recursive call for the translation process.
+    ///
+    /// Links the return place to the park token of the thread being translated.
+    fn call_thread_current(
+        &mut self,
+        function_name: &str,
+        args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
+        destination: rustc_middle::mir::Place<'tcx>,
+        places: Places,
+    ) {
+        self.call_foreign_function(function_name, args, destination, places);
+        let park_token_ref = self.get_park_token(self.current_thread);
+        let function = self.call_stack.peek_mut();
+        function
+            .memory
+            .link_thread_handle(destination, park_token_ref);
+        debug!("NEW THREAD HANDLE FOR THE CURRENT THREAD: {destination:?}");
+    }
+
+    /// Call to `std::thread::JoinHandle::<T>::thread`.
+    /// Non-recursive call for the translation process.
+    ///
+    /// Links the return place to the park token of the thread of the join handle.
+    /// Getting the handle never unwinds, therefore the cleanup place is not modelled.
+    fn call_join_handle_thread(
+        &mut self,
+        function_name: &str,
+        args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
+        destination: rustc_middle::mir::Place<'tcx>,
+        places: Places,
+    ) {
+        let places = places.ignore_cleanup_place();
+        self.call_foreign_function(function_name, args, destination, places);
+        let self_ref = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+            panic!("BUG: `{function_name}` should receive the self reference as a place")
+        });
+        let thread_index = self
+            .call_stack
+            .peek()
+            .memory
+            .get_join_handle(&self_ref)
+            .index;
+        let park_token_ref = self.get_park_token(Some(thread_index));
+        let function = self.call_stack.peek_mut();
+        function
+            .memory
+            .link_thread_handle(destination, park_token_ref);
+        debug!("NEW THREAD HANDLE FOR THREAD {thread_index}: {destination:?}");
+    }
+
+    /// Call to `std::thread::park`.
+    /// Non-recursive call for the translation process.
+    ///
+    /// Consumes the park token of the thread being translated, see `park::call_park`.
+    fn call_park(&mut self, function_name: &str, places: Places) {
+        let index = self.function_counter.get_count(function_name);
+        let park_token_ref = self.get_park_token(self.current_thread);
+        park::call_park(function_name, index, &park_token_ref, places, &mut self.net);
+    }
+
+    /// Returns the range converted into the iterator stored in the given local.
+    /// The places of the range are created by the first call to `into_iter` or `next`
+    /// that is translated, since the MIR body does not guarantee which one comes first.
+    fn get_range(&mut self, iterator: rustc_middle::mir::Local, length: usize) -> RangeRef {
+        let memory = &mut self.call_stack.peek_mut().memory;
+        if let Some(range_ref) = memory.find_range(iterator) {
+            return range_ref.clone();
+        }
+        let index = self.function_counter.get_count(range::RANGE_TYPE);
+        self.function_counter.increment(range::RANGE_TYPE);
+        let range = Range::new(index, length, &mut self.net);
+        let memory = &mut self.call_stack.peek_mut().memory;
+        memory.link_range(iterator, range).clone()
+    }
+
+    /// Call to `std::iter::IntoIterator::into_iter` on a range with constant bounds.
+    /// Non-recursive call for the translation process.
+    ///
+    /// The loop starts again from the first iteration, see `range::call_into_iter`.
+    fn call_range_into_iter(
+        &mut self,
+        function_name: &str,
+        iterator: rustc_middle::mir::Local,
+        length: usize,
+        places: Places,
+    ) {
+        let index = self.function_counter.get_count(function_name);
+        let range_ref = self.get_range(iterator, length);
+        range::call_into_iter(function_name, index, &range_ref, places, &mut self.net);
+    }
+
+    /// Call to `std::iter::Iterator::next` on a range with constant bounds.
+    /// Non-recursive call for the translation process.
+    ///
+    /// The loop runs exactly `length` times, see `range::call_next`.
+    fn call_range_next(
+        &mut self,
+        function_name: &str,
+        iterator: rustc_middle::mir::Local,
+        length: usize,
+        destination: rustc_middle::mir::Place<'tcx>,
+        places: Places,
+    ) {
+        let index = self.function_counter.get_count(function_name);
+        let range_ref = self.get_range(iterator, length);
+        let function = self.call_stack.peek_mut();
+        range::call_next(
+            function_name,
+            index,
+            &range_ref,
+            destination,
+            places,
+            &mut self.net,
+            &mut function.memory,
+        );
+    }
+
+    /// Call to `tokio::spawn`.
+    /// Non-recursive call for the translation process.
+    ///
+    /// The spawned task is modelled as a thread that runs the coroutine of the future
+    /// passed as the first argument, since the tasks of the runtime may run in parallel.
+    /// Awaiting the returned `tokio::task::JoinHandle` joins the thread.
+    ///
+    /// - Retrieves the future linked to the first argument.
+    /// - Adds the thread to the `ThreadManager`.
+    /// - Links the return place to the `ThreadRef`.
+    fn call_task_spawn(
+        &mut self,
+        function_name: &str,
+        args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
+        destination: rustc_middle::mir::Place<'tcx>,
+        places: Places,
+        span: rustc_span::Span,
+    ) {
+        let Some(future) = self.find_future_in_arguments(args) else {
+            let reason = format!("Call to `{function_name}` with an untracked future");
+            self.handle_unsupported_call(&reason, &reason, function_name, places, span);
+            return;
+        };
+        let transitions = self.call_foreign_function(function_name, args, destination, places);
+        let transition = transitions.default();
+
+        // Create a new thread for the task
+        let index = self.threads.len();
+        let thread = sync::thread::Thread::new_task(transition, future, index);
+
+        // The return value contains a new join handle. Link the local variable to it.
+        let memory = &mut self.call_stack.peek_mut().memory;
+        let thread_ref = memory.link_join_handle(destination, thread);
+        debug!("NEW JOIN HANDLE FOR TASK: {destination:?}");
+
+        // Add the thread to the translator
+        self.threads.push_back(thread_ref.clone());
+        self.record_primitive(PrimitiveKind::Thread, index, span);
+        info!("Found task {index} and pushed it to the back of the thread translation queue");
+    }
+
+    /// Call to `std::future::Future::poll` on a future linked to a synchronization variable, i.e.
+    /// awaiting an asynchronous operation on a `tokio` primitive or a `tokio::task::JoinHandle`.
+    /// Non-recursive call for the translation process.
+    ///
+    /// Awaiting a join handle is translated like a call to `std::thread::JoinHandle::<T>::join`.
+    /// The other operations are translated by the handlers in the `sync` submodule.
+    /// The operation blocks until it completes, so the future never returns `Poll::Pending`.
+    fn call_await_sync_variable(
+        &mut self,
+        function_name: &str,
+        args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
+        destination: rustc_middle::mir::Place<'tcx>,
+        places: Places,
+        span: rustc_span::Span,
+    ) {
+        let index = self.function_counter.get_count(function_name);
+        let future_ref = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+            panic!("BUG: `{function_name}` should receive the pinned future as a place")
+        });
+        let memory = &self.call_stack.peek().memory;
+        let is_mutex = memory.find_mutex(&future_ref).is_some();
+        let is_join_handle = memory.is_join_handle(&future_ref);
+        if is_mutex {
+            self.record_lock_acquisition(function_name, index, args, span);
+        }
+
+        let memory = &mut self.call_stack.peek_mut().memory;
+        let net = &mut self.net;
+        if is_join_handle && self.model_panics {
+            sync::thread::call_join_with_panics(
+                function_name,
+                index,
+                args,
+                destination,
+                places,
+                net,
+                memory,
+            );
+        } else if is_join_handle {
+            sync::thread::call_join(function_name, index, args, places, net, memory);
+        } else {
+            sync::call_await(function_name, index, args, destination, places, net, memory);
+        }
+    }
 }
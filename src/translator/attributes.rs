@@ -0,0 +1,94 @@
+//! Submodule for reading the tool attributes that annotate the source code.
+//!
+//! The compiler is configured to register the tool `check_deadlock`,
+//! so the attributes can be written as `#[check_deadlock::<name>]`.
+//! The `cfg` option `check_deadlock` is also set, which allows writing
+//! `#[cfg_attr(check_deadlock, check_deadlock::<name>)]` to keep the code compiling with a regular build.
+//!
+//! The supported attributes on functions are:
+//! - `ignore`: Calls to the function are translated as calls to a foreign function.
+//!   Its body is not translated.
+//! - `assume_no_unwind`: Calls to the function and calls inside the function never unwind.
+//! - `lock_order(a, b, ...)`: Documents that inside the function the mutex in the variable `a`
+//!   is always acquired before the mutex in the variable `b`, and so on.
+
+use log::warn;
+
+/// The name of the tool under which the attributes are registered.
+pub const TOOL_NAME: &str = "check_deadlock";
+
+/// A tool attribute found in the source code.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ToolAttribute {
+    /// `#[check_deadlock::ignore]`
+    Ignore,
+    /// `#[check_deadlock::assume_no_unwind]`
+    AssumeNoUnwind,
+    /// `#[check_deadlock::lock_order(a, b, ...)]`
+    LockOrder {
+        /// The names of the variables containing the mutexes, in the order of acquisition.
+        variables: Vec<String>,
+        /// The location of the attribute in the source code.
+        span: rustc_span::Span,
+    },
+}
+
+/// A declared order of acquisition between two mutexes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LockOrder {
+    /// The index of the mutex that must be acquired first.
+    pub first: usize,
+    /// The index of the mutex that must be acquired second.
+    pub second: usize,
+    /// The name of the function where the order was declared.
+    pub function_name: String,
+    /// The location of the declaration in the source code.
+    pub location: String,
+}
+
+impl std::fmt::Display for LockOrder {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "mutex {} before mutex {} in `{}` at {}",
+            self.first, self.second, self.function_name, self.location
+        )
+    }
+}
+
+/// Returns the tool attributes of the item with the given definition ID.
+/// Unknown attributes in the tool namespace are reported with a warning and ignored.
+pub fn get_tool_attributes(
+    def_id: rustc_hir::def_id::DefId,
+    tcx: rustc_middle::ty::TyCtxt,
+) -> Vec<ToolAttribute> {
+    let mut tool_attributes = Vec::new();
+    for attribute in tcx.get_attrs_unchecked(def_id) {
+        let path = attribute.path();
+        let [tool, name] = path.as_slice() else {
+            continue;
+        };
+        if tool.as_str() != TOOL_NAME {
+            continue;
+        }
+        match name.as_str() {
+            "ignore" => tool_attributes.push(ToolAttribute::Ignore),
+            "assume_no_unwind" => tool_attributes.push(ToolAttribute::AssumeNoUnwind),
+            "lock_order" => {
+                let variables = attribute
+                    .meta_item_list()
+                    .unwrap_or_default()
+                    .iter()
+                    .filter_map(|item| item.ident())
+                    .map(|ident| ident.to_string())
+                    .collect();
+                tool_attributes.push(ToolAttribute::LockOrder {
+                    variables,
+                    span: attribute.span,
+                });
+            }
+            _ => warn!("Ignoring the unknown attribute `{TOOL_NAME}::{name}`"),
+        }
+    }
+    tool_attributes
+}
@@ -0,0 +1,389 @@
+//! Submodule for the translation of coroutines, i.e. the bodies of `async fn` and `async` blocks.
+//!
+//! Before the optimized MIR is built, the compiler lowers every coroutine to a state machine.
+//! The `Yield` terminators (one for each `.await`) disappear in the process.
+//! The state is the discriminant of the coroutine, which is reached through the first argument of its body.
+//! The first basic block dispatches on the state:
+//! - Variant 0: The coroutine was not resumed yet, jump to the start of the body.
+//! - Variants 1 and 2: The coroutine returned or panicked before, which leads to a panic.
+//! - Variants 3 and higher: The coroutine is suspended at one of its suspension points.
+//!
+//! A suspension point sets the state and returns `Poll::Pending`.
+//! The next poll jumps from the dispatch to the block following the suspension point.
+//! Data living across suspension points, e.g. a mutex guard, is stored in the fields of the coroutine.
+//!
+//! The translation follows a simple single-threaded executor that polls a future until it completes:
+//! - The dispatch only jumps to the start of the body.
+//! - A suspension point is modelled as a place between the block that suspends and the block that resumes.
+//!   A mutex guard held across the suspension point keeps the mutex locked while the coroutine is suspended.
+//! - Polling an awaited future translates the body of its coroutine in place, so the awaited future
+//!   always completes and the caller only continues with `Poll::Ready`.
+//! - Awaiting an operation on a `tokio` primitive blocks until the operation completes,
+//!   so the caller only continues with `Poll::Ready` as well.
+//!
+//! More info on the lowering of coroutines:
+//! <https://doc.rust-lang.org/stable/nightly-rustc/rustc_mir_transform/coroutine/index.html>
+
+use rustc_middle::mir::visit::{MutVisitor, PlaceContext, Visitor};
+use std::collections::{HashMap, HashSet};
+
+use crate::translator::mir_function::memory::{Memory, Value};
+use crate::translator::sync::is_async_operation;
+
+/// The name of the function that polls a future.
+pub const POLL_FUNCTION_NAME: &str = "std::future::Future::poll";
+
+/// The first variant of the state of a coroutine that corresponds to a suspension point.
+/// The previous ones are the states unresumed, returned and poisoned.
+const FIRST_SUSPENSION_STATE: u128 = 3;
+
+/// A future created by calling an `async fn` or evaluating an `async` block.
+#[derive(PartialEq)]
+pub struct Future {
+    /// The definition ID of the coroutine that implements the future.
+    pub def_id: rustc_hir::def_id::DefId,
+    /// The values captured by the coroutine (its upvars) in the order of the fields of the coroutine.
+    /// Values that are not synchronization variables are `None`.
+    upvars: Vec<Option<Value>>,
+}
+
+impl Future {
+    /// Creates a new future for the coroutine with the given captured values.
+    pub const fn new(def_id: rustc_hir::def_id::DefId, upvars: Vec<Option<Value>>) -> Self {
+        Self { def_id, upvars }
+    }
+}
+
+/// The structure of the optimized MIR body of a coroutine that is relevant for the translation.
+pub struct Coroutine {
+    /// The local that refers to the coroutine itself.
+    /// Every copy of the first argument in the body is replaced by this local.
+    self_local: rustc_middle::mir::Local,
+    /// The locals that contain a copy of the first argument, i.e. a reference to the coroutine.
+    self_aliases: HashSet<rustc_middle::mir::Local>,
+    /// The only target of the switch statements whose outcome is known in advance:
+    /// The dispatch on the state always starts the body
+    /// and the result of polling an awaited future is always `Poll::Ready`.
+    switch_targets: HashMap<rustc_middle::mir::BasicBlock, rustc_middle::mir::BasicBlock>,
+    /// The suspension points as a mapping from the block that suspends the coroutine
+    /// to the block where the coroutine resumes.
+    suspensions: HashMap<rustc_middle::mir::BasicBlock, rustc_middle::mir::BasicBlock>,
+}
+
+impl Coroutine {
+    /// Finds the self references, the dispatch on the state, the awaited futures and the suspension points
+    /// in the optimized MIR body of a coroutine.
+    pub fn new<'tcx>(
+        body: &rustc_middle::mir::Body<'tcx>,
+        tcx: rustc_middle::ty::TyCtxt<'tcx>,
+    ) -> Self {
+        let self_argument = rustc_middle::mir::Local::from_usize(1);
+        let mut self_aliases = HashSet::new();
+        let mut polls = HashSet::new();
+        for data in body.basic_blocks.iter() {
+            for statement in &data.statements {
+                if let Some((place, rustc_middle::mir::Rvalue::CopyForDeref(rhs))) =
+                    statement.kind.as_assign()
+                {
+                    if rhs.local == self_argument {
+                        self_aliases.insert(place.local);
+                    }
+                }
+            }
+            if let rustc_middle::mir::TerminatorKind::Call {
+                ref func,
+                destination,
+                ..
+            } = data.terminator().kind
+            {
+                if is_poll_of_modelled_future(func, tcx) {
+                    polls.insert(destination.local);
+                }
+            }
+        }
+
+        let mut states = HashSet::new();
+        let mut poll_discriminants = HashSet::new();
+        let mut set_states = HashMap::new();
+        for (block, data) in body.basic_blocks.iter_enumerated() {
+            for statement in &data.statements {
+                match &statement.kind {
+                    rustc_middle::mir::StatementKind::Assign(assignment) => {
+                        let (place, rvalue) = &**assignment;
+                        let rustc_middle::mir::Rvalue::Discriminant(rhs) = rvalue else {
+                            continue;
+                        };
+                        if is_coroutine_state(rhs, &self_aliases) {
+                            states.insert(place.local);
+                        } else if polls.contains(&rhs.local) {
+                            poll_discriminants.insert(place.local);
+                        }
+                    }
+                    rustc_middle::mir::StatementKind::SetDiscriminant {
+                        place,
+                        variant_index,
+                    } if is_coroutine_state(place, &self_aliases) => {
+                        set_states.insert(block, u128::from(variant_index.as_u32()));
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let mut switch_targets = HashMap::new();
+        let mut suspensions = HashMap::new();
+        for (block, data) in body.basic_blocks.iter_enumerated() {
+            let rustc_middle::mir::TerminatorKind::SwitchInt {
+                discr: rustc_middle::mir::Operand::Move(ref discr),
+                ref targets,
+            } = data.terminator().kind
+            else {
+                continue;
+            };
+            if states.contains(&discr.local) {
+                // The dispatch on the state of the coroutine.
+                switch_targets.insert(block, targets.target_for_value(0));
+                for (suspending_block, state) in &set_states {
+                    if *state < FIRST_SUSPENSION_STATE {
+                        continue;
+                    }
+                    let suspends = matches!(
+                        body.basic_blocks[*suspending_block].terminator().kind,
+                        rustc_middle::mir::TerminatorKind::Return
+                    );
+                    if suspends {
+                        suspensions.insert(*suspending_block, targets.target_for_value(*state));
+                    }
+                }
+            } else if poll_discriminants.contains(&discr.local) {
+                // `Poll::Ready` is the variant 0 of `std::task::Poll`.
+                switch_targets.insert(block, targets.target_for_value(0));
+            }
+        }
+
+        let self_local = self_aliases.iter().min().copied().unwrap_or(self_argument);
+        Self {
+            self_local,
+            self_aliases,
+            switch_targets,
+            suspensions,
+        }
+    }
+
+    /// Returns a copy of the body where every copy of the self reference is replaced by the same local.
+    /// This way the fields of the coroutine are always accessed through the same place,
+    /// e.g. the mutex guard stored in the coroutine before a suspension point
+    /// and dropped after the coroutine resumes.
+    pub fn unify_self_references<'tcx>(
+        &self,
+        body: &rustc_middle::mir::Body<'tcx>,
+        tcx: rustc_middle::ty::TyCtxt<'tcx>,
+    ) -> rustc_middle::mir::Body<'tcx> {
+        let mut body = body.clone();
+        let mut renamer = SelfReferenceRenamer {
+            tcx,
+            aliases: &self.self_aliases,
+            self_local: self.self_local,
+        };
+        renamer.visit_body_preserves_cfg(&mut body);
+        body
+    }
+
+    /// Links the fields of the coroutine to the values captured by the future in the memory of the coroutine.
+    /// The fields are accessed through the unified self reference, see `unify_self_references`.
+    pub fn link_upvars<'tcx>(
+        &self,
+        future: &Future,
+        memory: &mut Memory<'tcx>,
+        body: &rustc_middle::mir::Body<'tcx>,
+    ) {
+        let mut collector = UpvarCollector {
+            self_local: self.self_local,
+            self_aliases: &self.self_aliases,
+            places: Vec::new(),
+        };
+        collector.visit_body(body);
+        for place in collector.places {
+            let Some(rustc_middle::mir::ProjectionElem::Field(field, _)) = place.projection.get(1)
+            else {
+                continue;
+            };
+            if let Some(Some(value)) = future.upvars.get(field.as_usize()) {
+                memory.link_value(place, value.clone());
+            }
+        }
+    }
+
+    /// Returns the only possible target of the switch statement at the end of the given block.
+    /// Returns `None` if every target is possible.
+    pub fn switch_target(
+        &self,
+        block: rustc_middle::mir::BasicBlock,
+    ) -> Option<rustc_middle::mir::BasicBlock> {
+        self.switch_targets.get(&block).copied()
+    }
+
+    /// Returns the block where the coroutine resumes if the given block suspends it.
+    pub fn resume_target(
+        &self,
+        block: rustc_middle::mir::BasicBlock,
+    ) -> Option<rustc_middle::mir::BasicBlock> {
+        self.suspensions.get(&block).copied()
+    }
+}
+
+/// Creates a future for the coroutine from the operands that it captures,
+/// i.e. the arguments of an `async fn` or the operands of the aggregate that creates an `async` block.
+/// The sync variables among the operands are copied into the future.
+pub fn create_future<'a, 'tcx: 'a>(
+    def_id: rustc_hir::def_id::DefId,
+    operands: impl Iterator<Item = &'a rustc_middle::mir::Operand<'tcx>>,
+    memory: &Memory<'tcx>,
+) -> Future {
+    let upvars = operands
+        .map(|operand| {
+            operand
+                .place()
+                .and_then(|place| memory.find_value(&place).cloned())
+        })
+        .collect();
+    Future::new(def_id, upvars)
+}
+
+/// Checks whether the function runs a future to completion on the current thread,
+/// e.g. `futures::executor::block_on` or `pollster::block_on`.
+/// Any function named `block_on` is treated as such an executor, including a user-defined one.
+pub fn is_block_on(function_name: &str) -> bool {
+    function_name == "block_on" || function_name.ends_with("::block_on")
+}
+
+/// Checks whether the place is the state of the coroutine, i.e. `(*_X)` where `_X` is a self reference.
+fn is_coroutine_state(
+    place: &rustc_middle::mir::Place,
+    self_aliases: &HashSet<rustc_middle::mir::Local>,
+) -> bool {
+    self_aliases.contains(&place.local)
+        && matches!(
+            place.projection.as_slice(),
+            [rustc_middle::mir::ProjectionElem::Deref]
+        )
+}
+
+/// Checks whether the called function is `std::future::Future::poll` for a future whose completion is modelled:
+/// A future implemented by a coroutine outside of `tokio` or one of the futures in `is_modelled_tokio_future`.
+/// The operation of a `tokio` future blocks until it completes, see `sync::call_await`.
+fn is_poll_of_modelled_future<'tcx>(
+    func: &rustc_middle::mir::Operand<'tcx>,
+    tcx: rustc_middle::ty::TyCtxt<'tcx>,
+) -> bool {
+    polled_future_type(func, tcx).is_some_and(|self_ty| match tokio_future_name(self_ty, tcx) {
+        Some(future_name) => is_modelled_tokio_future(&future_name),
+        None => matches!(self_ty.kind(), rustc_middle::ty::TyKind::Coroutine(..)),
+    })
+}
+
+/// Checks whether the called function is `std::future::Future::poll` for a `tokio` future
+/// whose operation is not modelled. Returns the name of the future in that case.
+pub fn find_unsupported_tokio_future<'tcx>(
+    func: &rustc_middle::mir::Operand<'tcx>,
+    tcx: rustc_middle::ty::TyCtxt<'tcx>,
+) -> Option<String> {
+    polled_future_type(func, tcx)
+        .and_then(|self_ty| tokio_future_name(self_ty, tcx))
+        .filter(|future_name| !is_modelled_tokio_future(future_name))
+}
+
+/// Returns the type of the future polled by the called function if it is `std::future::Future::poll`.
+fn polled_future_type<'tcx>(
+    func: &rustc_middle::mir::Operand<'tcx>,
+    tcx: rustc_middle::ty::TyCtxt<'tcx>,
+) -> Option<rustc_middle::ty::Ty<'tcx>> {
+    let (def_id, args) = func.const_fn_def()?;
+    if tcx.def_path_str(def_id) != POLL_FUNCTION_NAME {
+        return None;
+    }
+    args.types().next()
+}
+
+/// Returns the name of the future if it is defined in `tokio`.
+/// The future returned by an `async fn` is named after the function.
+fn tokio_future_name<'tcx>(
+    self_ty: rustc_middle::ty::Ty<'tcx>,
+    tcx: rustc_middle::ty::TyCtxt<'tcx>,
+) -> Option<String> {
+    let future_name = match self_ty.kind() {
+        rustc_middle::ty::TyKind::Adt(adt_def, _) => tcx.def_path_str(adt_def.did()),
+        rustc_middle::ty::TyKind::Coroutine(def_id, _) => tcx.def_path_str(tcx.parent(*def_id)),
+        _ => return None,
+    };
+    future_name.starts_with("tokio::").then_some(future_name)
+}
+
+/// Checks whether the `tokio` future with the given name is modelled:
+/// The futures returned by the asynchronous operations in `sync::is_async_operation`,
+/// `tokio::sync::futures::Notified` and `tokio::task::JoinHandle`.
+fn is_modelled_tokio_future(future_name: &str) -> bool {
+    is_async_operation(future_name)
+        || matches!(
+            future_name,
+            "tokio::sync::futures::Notified" | "tokio::task::JoinHandle"
+        )
+}
+
+/// Replaces the copies of the self reference in a coroutine body by the same local.
+struct SelfReferenceRenamer<'a, 'tcx> {
+    tcx: rustc_middle::ty::TyCtxt<'tcx>,
+    aliases: &'a HashSet<rustc_middle::mir::Local>,
+    self_local: rustc_middle::mir::Local,
+}
+
+impl<'tcx> MutVisitor<'tcx> for SelfReferenceRenamer<'_, 'tcx> {
+    fn tcx(&self) -> rustc_middle::ty::TyCtxt<'tcx> {
+        self.tcx
+    }
+
+    fn visit_local(
+        &mut self,
+        local: &mut rustc_middle::mir::Local,
+        _context: PlaceContext,
+        _location: rustc_middle::mir::Location,
+    ) {
+        if self.aliases.contains(local) {
+            *local = self.self_local;
+        }
+    }
+}
+
+/// Collects the places of the form `(*_X).N` where `_X` is a copy of the self reference,
+/// i.e. the accesses to the values captured by the coroutine.
+/// The places are collected with the unified self reference.
+struct UpvarCollector<'a, 'tcx> {
+    self_local: rustc_middle::mir::Local,
+    self_aliases: &'a HashSet<rustc_middle::mir::Local>,
+    places: Vec<rustc_middle::mir::Place<'tcx>>,
+}
+
+impl<'tcx> Visitor<'tcx> for UpvarCollector<'_, 'tcx> {
+    fn visit_place(
+        &mut self,
+        place: &rustc_middle::mir::Place<'tcx>,
+        _context: PlaceContext,
+        _location: rustc_middle::mir::Location,
+    ) {
+        let is_upvar = self.self_aliases.contains(&place.local)
+            && matches!(
+                place.projection.as_slice(),
+                [
+                    rustc_middle::mir::ProjectionElem::Deref,
+                    rustc_middle::mir::ProjectionElem::Field(..)
+                ]
+            );
+        let place = rustc_middle::mir::Place {
+            local: self.self_local,
+            projection: place.projection,
+        };
+        if is_upvar && !self.places.contains(&place) {
+            self.places.push(place);
+        }
+    }
+}
@@ -13,12 +13,15 @@
 //! But the terminators may refer to blocks previously seen or new blocks. Each basic block is visited only once.
 
 mod basic_block;
+mod constants;
 pub mod memory;
 mod terminator;
 
 use std::collections::HashMap;
 
 use crate::data_structures::petri_net_interface::{PetriNet, PlaceRef};
+use crate::translator::attributes::ToolAttribute;
+use crate::translator::coroutine::Coroutine;
 use basic_block::BasicBlock;
 use memory::Memory;
 
@@ -38,6 +41,14 @@ pub struct MirFunction<'tcx> {
     basic_blocks: HashMap<rustc_middle::mir::BasicBlock, BasicBlock>,
     /// A representation of the memory of the function.
     pub memory: Memory<'tcx>,
+    /// The structure of the body if the function is a coroutine, i.e. the body of an `async fn` or an `async` block.
+    pub coroutine: Option<Coroutine>,
+    /// The only feasible target of the switch int terminators whose discriminant is a known constant.
+    constant_switch_targets: HashMap<rustc_middle::mir::BasicBlock, rustc_middle::mir::BasicBlock>,
+    /// The tool attributes of the function, parsed once when the function is pushed.
+    pub tool_attributes: Vec<ToolAttribute>,
+    /// Whether the function is annotated with `#[check_deadlock::assume_no_unwind]`.
+    pub assume_no_unwind: bool,
 }
 
 impl MirFunction<'_> {
@@ -48,7 +59,9 @@ impl MirFunction<'_> {
         function_name: String,
         start_place: PlaceRef,
         end_place: PlaceRef,
+        tool_attributes: Vec<ToolAttribute>,
     ) -> Self {
+        let assume_no_unwind = tool_attributes.contains(&ToolAttribute::AssumeNoUnwind);
         Self {
             def_id,
             name: function_name,
@@ -57,9 +70,32 @@ impl MirFunction<'_> {
             active_block: None,
             basic_blocks: HashMap::new(),
             memory: Memory::default(),
+            coroutine: None,
+            constant_switch_targets: HashMap::new(),
+            tool_attributes,
+            assume_no_unwind,
         }
     }
 
+    /// Propagates the constants assigned to the locals of the body to find the switch int terminators
+    /// that can only take one branch. Must be called before visiting the body.
+    pub fn propagate_constants<'a>(
+        &mut self,
+        body: &rustc_middle::mir::Body<'a>,
+        tcx: rustc_middle::ty::TyCtxt<'a>,
+    ) {
+        self.constant_switch_targets = constants::find_constant_switch_targets(body, tcx);
+    }
+
+    /// Returns the only feasible target of the switch statement at the end of the given block.
+    /// Returns `None` if the discriminant is not a known constant.
+    pub fn constant_switch_target(
+        &self,
+        block: rustc_middle::mir::BasicBlock,
+    ) -> Option<rustc_middle::mir::BasicBlock> {
+        self.constant_switch_targets.get(&block).copied()
+    }
+
     /// Prepares the start place for the next block.
     /// If it is the first block, then the start place of the function
     /// is the same as the start place of the block.
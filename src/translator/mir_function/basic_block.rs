@@ -9,8 +9,10 @@ use crate::data_structures::petri_net_interface::{
 };
 use crate::naming::basic_block::{
     assert_cleanup_transition_label, assert_transition_label, drop_cleanup_transition_label,
-    drop_transition_label, goto_transition_label, place_label, switch_int_transition_label,
-    unreachable_transition_label, unwind_transition_label,
+    drop_transition_label, goto_transition_label, havoc_blocked_place_label,
+    havoc_transition_label, havoc_transition_labels, place_label, skip_return_transition_label,
+    skip_transition_label, suspend_transition_labels, suspended_place_label,
+    switch_int_transition_label, unreachable_transition_label, unwind_transition_label,
 };
 
 pub struct BasicBlock {
@@ -49,9 +51,15 @@ impl BasicBlock {
     }
 
     /// Connects the end place of this block to the start place of the `target` basic block.
-    pub fn switch_int(&self, target: &Self, target_index: usize, net: &mut PetriNet) {
+    /// Returns the new transition created to connect the two basic blocks.
+    pub fn switch_int(
+        &self,
+        target: &Self,
+        target_index: usize,
+        net: &mut PetriNet,
+    ) -> TransitionRef {
         let label = switch_int_transition_label(&self.function_name, self.index, target_index);
-        connect_places(net, &self.place, &target.place, &label);
+        connect_places(net, &self.place, &target.place, &label)
     }
 
     /// Connects the end place of this block to the unwind place.
@@ -76,15 +84,17 @@ impl BasicBlock {
     }
 
     /// Connects the end place of this block to the start place of the `assert` basic block.
-    pub fn assert(&self, target: &Self, net: &mut PetriNet) {
+    /// Returns the new transition created to connect the two basic blocks.
+    pub fn assert(&self, target: &Self, net: &mut PetriNet) -> TransitionRef {
         let label = assert_transition_label(&self.function_name, self.index);
-        connect_places(net, &self.place, &target.place, &label);
+        connect_places(net, &self.place, &target.place, &label)
     }
 
     /// Connects the end place of this block to the start place of the `cleanup` basic block.
-    pub fn assert_cleanup(&self, cleanup: &Self, net: &mut PetriNet) {
+    /// Returns the new transition created to connect the two basic blocks.
+    pub fn assert_cleanup(&self, cleanup: &Self, net: &mut PetriNet) -> TransitionRef {
         let label = assert_cleanup_transition_label(&self.function_name, self.index);
-        connect_places(net, &self.place, &cleanup.place, &label);
+        connect_places(net, &self.place, &cleanup.place, &label)
     }
 
     /// Connects the end place of this block to the end place.
@@ -92,4 +102,57 @@ impl BasicBlock {
         let label = unreachable_transition_label(&self.function_name, self.index);
         connect_places(net, &self.place, end_place, &label);
     }
+
+    /// Connects the end place of this block to the start place of the `target` basic block,
+    /// skipping an unsupported terminator.
+    pub fn skip(&self, target: &Self, target_index: usize, net: &mut PetriNet) {
+        let label = skip_transition_label(&self.function_name, self.index, target_index);
+        connect_places(net, &self.place, &target.place, &label);
+    }
+
+    /// Connects the end place of this block to the end place of the function,
+    /// skipping an unsupported terminator that has no successors.
+    pub fn skip_return(&self, end_place: &PlaceRef, net: &mut PetriNet) {
+        let label = skip_return_transition_label(&self.function_name, self.index);
+        connect_places(net, &self.place, end_place, &label);
+    }
+
+    /// Connects the end place of this block to the start place of the `target` basic block
+    /// as one of the possible outcomes of an unsupported terminator.
+    pub fn havoc(&self, target: &Self, target_index: usize, net: &mut PetriNet) {
+        let label = havoc_transition_label(&self.function_name, self.index, target_index);
+        connect_places(net, &self.place, &target.place, &label);
+    }
+
+    /// Adds the remaining possible outcomes of an unsupported terminator:
+    /// - Optionally, returning to the end place of the function if the terminator has no successors.
+    /// - Panicking, i.e. connecting the end place of this block to the panic place.
+    /// - Blocking forever, i.e. connecting the end place of this block to a new place without outgoing arcs.
+    pub fn havoc_exits(
+        &self,
+        end_place: Option<&PlaceRef>,
+        panic_place: &PlaceRef,
+        net: &mut PetriNet,
+    ) {
+        let (return_label, panic_label, block_label) =
+            havoc_transition_labels(&self.function_name, self.index);
+        if let Some(end_place) = end_place {
+            connect_places(net, &self.place, end_place, &return_label);
+        }
+        connect_places(net, &self.place, panic_place, &panic_label);
+        let blocked_place =
+            net.add_place(&havoc_blocked_place_label(&self.function_name, self.index));
+        connect_places(net, &self.place, &blocked_place, &block_label);
+    }
+
+    /// Connects the end place of this block to the start place of the `resume` basic block
+    /// through a new place that models the coroutine being suspended in between.
+    pub fn suspend(&self, resume: &Self, net: &mut PetriNet) {
+        let (suspend_label, resume_label) =
+            suspend_transition_labels(&self.function_name, self.index);
+        let suspended_place =
+            net.add_place(&suspended_place_label(&self.function_name, self.index));
+        connect_places(net, &self.place, &suspended_place, &suspend_label);
+        connect_places(net, &suspended_place, &resume.place, &resume_label);
+    }
 }
@@ -0,0 +1,259 @@
+//! Submodule for a lightweight constant propagation over the locals of a MIR function.
+//!
+//! A switch int terminator on a value known at compile time can only take one branch,
+//! e.g. `let debug = false; if debug { ... }` or a `match` on an enum variant assigned in the same function.
+//! The other branches are infeasible and translating them may introduce deadlocks that can never happen.
+//!
+//! The analysis is a forward dataflow analysis built on the framework of the compiler:
+//! <https://rustc-dev-guide.rust-lang.org/mir/dataflow.html>
+//!
+//! Every local is mapped to an element of a flat lattice:
+//! - `Bottom`: The local has no value yet, i.e. the block is unreachable.
+//! - `Elem(bits)`: The local always holds the same scalar value. Enum variants are stored as their discriminant.
+//! - `Top`: The value of the local is unknown.
+//!
+//! Only assignments of constants, copies or moves of other locals, comparisons, bitwise operations,
+//! boolean negations, enum variants and their discriminants are interpreted.
+//! Locals whose address is taken are always `Top`, since they may be modified through a reference.
+
+use rustc_index::bit_set::BitSet;
+use rustc_index::IndexVec;
+use rustc_middle::mir::{
+    BasicBlock, BinOp, Body, Local, Operand, Rvalue, Statement, StatementKind, Terminator,
+    TerminatorEdges, TerminatorKind, UnOp,
+};
+use rustc_middle::ty::{TyCtxt, TypingEnv};
+use rustc_mir_dataflow::fmt::DebugWithContext;
+use rustc_mir_dataflow::lattice::{FlatSet, JoinSemiLattice};
+use rustc_mir_dataflow::Analysis;
+use std::collections::HashMap;
+
+/// The value of every local in the function at a given program point.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConstantLocals(IndexVec<Local, FlatSet<u128>>);
+
+impl JoinSemiLattice for ConstantLocals {
+    fn join(&mut self, other: &Self) -> bool {
+        self.0.join(&other.0)
+    }
+}
+
+impl<C> DebugWithContext<C> for ConstantLocals {}
+
+impl ConstantLocals {
+    /// Returns the value of the local if it is known.
+    fn get(&self, local: Local) -> Option<u128> {
+        match self.0[local] {
+            FlatSet::Elem(bits) => Some(bits),
+            FlatSet::Bottom | FlatSet::Top => None,
+        }
+    }
+}
+
+/// The constant propagation analysis for a single MIR body.
+struct ConstantPropagation<'a, 'tcx> {
+    body: &'a Body<'tcx>,
+    tcx: TyCtxt<'tcx>,
+    /// The locals whose address is taken somewhere in the body.
+    borrowed: BitSet<Local>,
+}
+
+impl<'tcx> ConstantPropagation<'_, 'tcx> {
+    /// Evaluates an operand in the given state.
+    /// Returns `None` if the value is not known.
+    fn evaluate_operand(&self, operand: &Operand<'tcx>, state: &ConstantLocals) -> Option<u128> {
+        match operand {
+            Operand::Copy(place) | Operand::Move(place) => {
+                let local = place.as_local()?;
+                state.get(local)
+            }
+            Operand::Constant(constant) => {
+                // The scalar representation of an enum constant is not always its discriminant.
+                let ty = constant.const_.ty();
+                if !(ty.is_integral() || ty.is_bool() || ty.is_char()) {
+                    return None;
+                }
+                let scalar = constant
+                    .const_
+                    .try_eval_scalar_int(self.tcx, TypingEnv::fully_monomorphized())?;
+                Some(scalar.to_bits_unchecked())
+            }
+        }
+    }
+
+    /// Evaluates the right-hand side of an assignment in the given state.
+    /// Returns `None` if the value is not known.
+    fn evaluate_rvalue(&self, rvalue: &Rvalue<'tcx>, state: &ConstantLocals) -> Option<u128> {
+        match rvalue {
+            Rvalue::Use(operand) => self.evaluate_operand(operand, state),
+            Rvalue::UnaryOp(UnOp::Not, operand) if operand.ty(self.body, self.tcx).is_bool() => {
+                let value = self.evaluate_operand(operand, state)?;
+                Some(value ^ 1)
+            }
+            Rvalue::BinaryOp(operation, operands) => {
+                let (left, right) = operands.as_ref();
+                let left_value = self.evaluate_operand(left, state)?;
+                let right_value = self.evaluate_operand(right, state)?;
+                let ty = left.ty(self.body, self.tcx);
+                let ordering = if ty.is_signed() {
+                    let size = ty.primitive_size(self.tcx);
+                    size.sign_extend(left_value)
+                        .cmp(&size.sign_extend(right_value))
+                } else {
+                    left_value.cmp(&right_value)
+                };
+                let result = match operation {
+                    BinOp::Eq => u128::from(ordering.is_eq()),
+                    BinOp::Ne => u128::from(ordering.is_ne()),
+                    BinOp::Lt => u128::from(ordering.is_lt()),
+                    BinOp::Le => u128::from(ordering.is_le()),
+                    BinOp::Gt => u128::from(ordering.is_gt()),
+                    BinOp::Ge => u128::from(ordering.is_ge()),
+                    BinOp::BitAnd => left_value & right_value,
+                    BinOp::BitOr => left_value | right_value,
+                    BinOp::BitXor => left_value ^ right_value,
+                    _ => return None,
+                };
+                Some(result)
+            }
+            Rvalue::Aggregate(kind, _) => {
+                let rustc_middle::mir::AggregateKind::Adt(def_id, variant_index, ..) = **kind
+                else {
+                    return None;
+                };
+                let adt_def = self.tcx.adt_def(def_id);
+                if !adt_def.is_enum() {
+                    return None;
+                }
+                Some(
+                    adt_def
+                        .discriminant_for_variant(self.tcx, variant_index)
+                        .val,
+                )
+            }
+            Rvalue::Discriminant(place) => {
+                let local = place.as_local()?;
+                state.get(local)
+            }
+            _ => None,
+        }
+    }
+
+    /// Updates the value of the local in the given state.
+    /// Borrowed locals are always unknown.
+    fn assign(&self, local: Local, value: Option<u128>, state: &mut ConstantLocals) {
+        state.0[local] = match value {
+            Some(bits) if !self.borrowed.contains(local) => FlatSet::Elem(bits),
+            _ => FlatSet::Top,
+        };
+    }
+}
+
+impl<'tcx> Analysis<'tcx> for ConstantPropagation<'_, 'tcx> {
+    type Domain = ConstantLocals;
+
+    const NAME: &'static str = "constant_propagation";
+
+    fn bottom_value(&self, body: &Body<'tcx>) -> Self::Domain {
+        ConstantLocals(IndexVec::from_elem(FlatSet::Bottom, &body.local_decls))
+    }
+
+    fn initialize_start_block(&self, body: &Body<'tcx>, state: &mut Self::Domain) {
+        // The arguments are unknown. The other locals are not initialized yet.
+        *state = ConstantLocals(IndexVec::from_elem(FlatSet::Top, &body.local_decls));
+    }
+
+    fn apply_statement_effect(
+        &mut self,
+        state: &mut Self::Domain,
+        statement: &Statement<'tcx>,
+        _location: rustc_middle::mir::Location,
+    ) {
+        match &statement.kind {
+            StatementKind::Assign(assignment) => {
+                let (place, rvalue) = assignment.as_ref();
+                // An assignment to a field or through a reference makes the whole local unknown.
+                let value = if place.projection.is_empty() {
+                    self.evaluate_rvalue(rvalue, state)
+                } else {
+                    None
+                };
+                self.assign(place.local, value, state);
+            }
+            StatementKind::SetDiscriminant {
+                place,
+                variant_index,
+            } => {
+                let ty = place.ty(self.body, self.tcx).ty;
+                let value = ty
+                    .ty_adt_def()
+                    .filter(|adt_def| adt_def.is_enum() && place.projection.is_empty())
+                    .map(|adt_def| {
+                        adt_def
+                            .discriminant_for_variant(self.tcx, *variant_index)
+                            .val
+                    });
+                self.assign(place.local, value, state);
+            }
+            StatementKind::StorageLive(local) | StatementKind::StorageDead(local) => {
+                self.assign(*local, None, state);
+            }
+            StatementKind::Deinit(place) => self.assign(place.local, None, state),
+            _ => {}
+        }
+    }
+
+    fn apply_terminator_effect<'mir>(
+        &mut self,
+        state: &mut Self::Domain,
+        terminator: &'mir Terminator<'tcx>,
+        _location: rustc_middle::mir::Location,
+    ) -> TerminatorEdges<'mir, 'tcx> {
+        match &terminator.kind {
+            TerminatorKind::SwitchInt { discr, targets } => {
+                // Only the feasible branch is reachable from a switch on a known value.
+                if let Some(value) = self.evaluate_operand(discr, state) {
+                    return TerminatorEdges::Single(targets.target_for_value(value));
+                }
+            }
+            TerminatorKind::Call { destination, .. } => self.assign(destination.local, None, state),
+            TerminatorKind::Yield { resume_arg, .. } => self.assign(resume_arg.local, None, state),
+            TerminatorKind::InlineAsm { .. } => {
+                // The assembly may write to any of its operands.
+                for value in state.0.iter_mut() {
+                    *value = FlatSet::Top;
+                }
+            }
+            _ => {}
+        }
+        terminator.edges()
+    }
+}
+
+/// Runs the constant propagation on the body and returns the only feasible target
+/// of every switch int terminator whose discriminant is known.
+pub fn find_constant_switch_targets<'tcx>(
+    body: &Body<'tcx>,
+    tcx: TyCtxt<'tcx>,
+) -> HashMap<BasicBlock, BasicBlock> {
+    let analysis = ConstantPropagation {
+        body,
+        tcx,
+        borrowed: rustc_mir_dataflow::impls::borrowed_locals(body),
+    };
+    let mut cursor = analysis
+        .iterate_to_fixpoint(tcx, body, None)
+        .into_results_cursor(body);
+    let mut switch_targets = HashMap::new();
+    for (block, block_data) in body.basic_blocks.iter_enumerated() {
+        let TerminatorKind::SwitchInt { discr, targets } = &block_data.terminator().kind else {
+            continue;
+        };
+        cursor.seek_before_primary_effect(body.terminator_loc(block));
+        let value = cursor.analysis().evaluate_operand(discr, cursor.get());
+        if let Some(value) = value {
+            switch_targets.insert(block, targets.target_for_value(value));
+        }
+    }
+    switch_targets
+}
@@ -4,7 +4,9 @@
 //! memory places (`rustc_middle::mir::Place`) and a variant of `Value`.
 //!
 //! It is used to keep track of the sync variables
-//! (mutexes, mutex guards, join handles and condition variables)
+//! (mutexes, mutex guards, join handles, join results, condition variables, futures,
+//! semaphores, semaphore permits, notifications, the ends of channels,
+//! atomic variables and the values loaded from them, thread handles, one-time initializations)
 //! in every MIR function.
 //!
 //! The idea is to mark (link) a place
@@ -20,10 +22,15 @@
 //! <https://rustc-dev-guide.rust-lang.org/mir/index.html#mir-data-types>
 
 use log::debug;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
 
-use crate::translator::sync::{Condvar, Mutex, MutexGuard, Thread};
+use crate::translator::coroutine::Future;
+use crate::translator::range::Range;
+use crate::translator::sync::{
+    Atomic, AtomicValue, Channel, Condvar, JoinResult, Mutex, MutexGuard, Notify, Once, ParkToken,
+    Select, Semaphore, SemaphorePermit, Thread,
+};
 
 /// A mutex reference is just a shared pointer to the mutex.
 pub type MutexRef = std::rc::Rc<Mutex>;
@@ -37,6 +44,44 @@ pub type CondvarRef = std::rc::Rc<Condvar>;
 /// A thread reference is just a shared pointer to the thread.
 pub type ThreadRef = std::rc::Rc<Thread>;
 
+/// A join result reference is just a shared pointer to the result of joining a thread.
+pub type JoinResultRef = std::rc::Rc<JoinResult>;
+
+/// A future reference is just a shared pointer to the future.
+pub type FutureRef = std::rc::Rc<Future>;
+
+/// A semaphore reference is just a shared pointer to the semaphore.
+pub type SemaphoreRef = std::rc::Rc<Semaphore>;
+
+/// A semaphore permit reference is just a shared pointer to the semaphore permit.
+pub type SemaphorePermitRef = std::rc::Rc<SemaphorePermit>;
+
+/// A notify reference is just a shared pointer to the notification primitive.
+pub type NotifyRef = std::rc::Rc<Notify>;
+
+/// A channel reference is just a shared pointer to the channel.
+/// It is shared by the sender and the receiver.
+pub type ChannelRef = std::rc::Rc<Channel>;
+
+/// A park token reference is just a shared pointer to the park token of a thread.
+/// It is shared by every handle (`std::thread::Thread`) to the same thread.
+pub type ParkTokenRef = std::rc::Rc<ParkToken>;
+
+/// An atomic reference is just a shared pointer to the atomic variable.
+pub type AtomicRef = std::rc::Rc<Atomic>;
+
+/// An atomic value reference is just a shared pointer to a value loaded from an atomic variable.
+pub type AtomicValueRef = std::rc::Rc<AtomicValue>;
+
+/// A once reference is just a shared pointer to the one-time initialization.
+pub type OnceRef = std::rc::Rc<Once>;
+
+/// A select reference is just a shared pointer to the operations registered in a `crossbeam_channel::Select`.
+pub type SelectRef = std::rc::Rc<Select>;
+
+/// A range reference is just a shared pointer to the places that count the iterations of a loop over a range.
+pub type RangeRef = std::rc::Rc<Range>;
+
 type Place<'tcx> = rustc_middle::mir::Place<'tcx>;
 
 /// Print a debug message about a place that was linked to the same value twice.
@@ -66,9 +111,38 @@ macro_rules! debug_different_type {
 #[derive(Default)]
 pub struct Memory<'tcx> {
     map: HashMap<Place<'tcx>, Value>,
+    /// The locals that are assigned in more than one statement or call of the body.
+    reassigned_locals: HashSet<rustc_middle::mir::Local>,
+    /// The loops over a range with constant bounds, indexed by the local returned by `into_iter`.
+    ranges: HashMap<rustc_middle::mir::Local, RangeRef>,
 }
 
 impl<'tcx> Memory<'tcx> {
+    /// Finds the locals that are assigned in more than one statement or call of the body.
+    /// Must be called before visiting the body, see `Memory::link_atomic_value`.
+    pub fn find_reassigned_locals(&mut self, body: &rustc_middle::mir::Body<'tcx>) {
+        let mut assigned_locals = HashSet::new();
+        self.reassigned_locals.clear();
+        for block_data in body.basic_blocks.iter() {
+            let statements = block_data
+                .statements
+                .iter()
+                .filter_map(|statement| match &statement.kind {
+                    rustc_middle::mir::StatementKind::Assign(assignment) => Some(assignment.0),
+                    _ => None,
+                });
+            let call = match &block_data.terminator().kind {
+                rustc_middle::mir::TerminatorKind::Call { destination, .. } => Some(*destination),
+                _ => None,
+            };
+            for place in statements.chain(call) {
+                if !assigned_locals.insert(place.local) {
+                    self.reassigned_locals.insert(place.local);
+                }
+            }
+        }
+    }
+
     /// Links a given place to a given mutex.
     /// Prints debug messages if the place was already linked.
     /// Returns a reference to the linked mutex.
@@ -142,6 +216,56 @@ impl<'tcx> Memory<'tcx> {
         self.map[&place].unpack_join_handle()
     }
 
+    /// Links a given place to a given join result.
+    /// Prints debug messages if the place was already linked.
+    /// Returns a reference to the linked join result.
+    pub fn link_join_result(
+        &mut self,
+        place: Place<'tcx>,
+        join_result: JoinResult,
+    ) -> &JoinResultRef {
+        let join_result_ref = Rc::new(join_result);
+        if let Some(old_value) = self.map.get(&place) {
+            let type_string = old_value.to_string();
+
+            if let Value::JoinResult(old_join_result_ref) = old_value {
+                if join_result_ref == *old_join_result_ref {
+                    debug_same_type_same_value!(place, type_string);
+                } else {
+                    debug_same_type_different_value!(place, type_string);
+                }
+            } else {
+                debug_different_type!(place, type_string);
+            }
+        }
+        let value = Value::JoinResult(join_result_ref);
+        self.map.insert(place, value);
+        self.map[&place].unpack_join_result()
+    }
+
+    /// Links a given place to a given future.
+    /// Prints debug messages if the place was already linked.
+    /// Returns a reference to the linked future.
+    pub fn link_future(&mut self, place: Place<'tcx>, future: Future) -> &FutureRef {
+        let future_ref = Rc::new(future);
+        if let Some(old_value) = self.map.get(&place) {
+            let type_string = old_value.to_string();
+
+            if let Value::Future(old_future_ref) = old_value {
+                if future_ref == *old_future_ref {
+                    debug_same_type_same_value!(place, type_string);
+                } else {
+                    debug_same_type_different_value!(place, type_string);
+                }
+            } else {
+                debug_different_type!(place, type_string);
+            }
+        }
+        let value = Value::Future(future_ref);
+        self.map.insert(place, value);
+        self.map[&place].unpack_future()
+    }
+
     /// Links a given place to a given condition variable.
     /// Prints debug messages if the place was already linked.
     /// Returns a reference to the linked condition variable.
@@ -165,6 +289,156 @@ impl<'tcx> Memory<'tcx> {
         self.map[&place].unpack_condvar()
     }
 
+    /// Links a given place to a given semaphore.
+    /// Prints debug messages if the place was already linked.
+    /// Returns a reference to the linked semaphore.
+    pub fn link_semaphore(&mut self, place: Place<'tcx>, semaphore: Semaphore) -> &SemaphoreRef {
+        let semaphore_ref = Rc::new(semaphore);
+        if let Some(old_value) = self.map.get(&place) {
+            let type_string = old_value.to_string();
+
+            if let Value::Semaphore(old_semaphore_ref) = old_value {
+                if semaphore_ref == *old_semaphore_ref {
+                    debug_same_type_same_value!(place, type_string);
+                } else {
+                    debug_same_type_different_value!(place, type_string);
+                }
+            } else {
+                debug_different_type!(place, type_string);
+            }
+        }
+        let value = Value::Semaphore(semaphore_ref);
+        self.map.insert(place, value);
+        self.map[&place].unpack_semaphore()
+    }
+
+    /// Links a given place to a given semaphore permit.
+    /// Prints debug messages if the place was already linked.
+    /// Returns a reference to the linked semaphore permit.
+    pub fn link_semaphore_permit(
+        &mut self,
+        place: Place<'tcx>,
+        semaphore_permit: SemaphorePermit,
+    ) -> &SemaphorePermitRef {
+        let semaphore_permit_ref = Rc::new(semaphore_permit);
+        if let Some(old_value) = self.map.get(&place) {
+            let type_string = old_value.to_string();
+
+            if let Value::SemaphorePermit(old_semaphore_permit_ref) = old_value {
+                if semaphore_permit_ref == *old_semaphore_permit_ref {
+                    debug_same_type_same_value!(place, type_string);
+                } else {
+                    debug_same_type_different_value!(place, type_string);
+                }
+            } else {
+                debug_different_type!(place, type_string);
+            }
+        }
+        let value = Value::SemaphorePermit(semaphore_permit_ref);
+        self.map.insert(place, value);
+        self.map[&place].unpack_semaphore_permit()
+    }
+
+    /// Links a given place to a given notify.
+    /// Prints debug messages if the place was already linked.
+    /// Returns a reference to the linked notify.
+    pub fn link_notify(&mut self, place: Place<'tcx>, notify: Notify) -> &NotifyRef {
+        let notify_ref = Rc::new(notify);
+        if let Some(old_value) = self.map.get(&place) {
+            let type_string = old_value.to_string();
+
+            if let Value::Notify(old_notify_ref) = old_value {
+                if notify_ref == *old_notify_ref {
+                    debug_same_type_same_value!(place, type_string);
+                } else {
+                    debug_same_type_different_value!(place, type_string);
+                }
+            } else {
+                debug_different_type!(place, type_string);
+            }
+        }
+        let value = Value::Notify(notify_ref);
+        self.map.insert(place, value);
+        self.map[&place].unpack_notify()
+    }
+
+    /// Links a given place to the handle of a thread, i.e. to the park token of the thread.
+    /// The park token is shared, so the place is linked to a reference to it.
+    pub fn link_thread_handle(&mut self, place: Place<'tcx>, park_token_ref: ParkTokenRef) {
+        if let Some(old_value) = self.map.insert(place, Value::ThreadHandle(park_token_ref)) {
+            debug_different_type!(place, old_value);
+        }
+    }
+
+    /// Links a given place to a given atomic variable.
+    /// Prints debug messages if the place was already linked.
+    /// Returns a reference to the linked atomic variable.
+    pub fn link_atomic(&mut self, place: Place<'tcx>, atomic: Atomic) -> &AtomicRef {
+        let atomic_ref = Rc::new(atomic);
+        if let Some(old_value) = self.map.get(&place) {
+            let type_string = old_value.to_string();
+
+            if let Value::Atomic(old_atomic_ref) = old_value {
+                if atomic_ref == *old_atomic_ref {
+                    debug_same_type_same_value!(place, type_string);
+                } else {
+                    debug_same_type_different_value!(place, type_string);
+                }
+            } else {
+                debug_different_type!(place, type_string);
+            }
+        }
+        let value = Value::Atomic(atomic_ref);
+        self.map.insert(place, value);
+        self.map[&place].unpack_atomic()
+    }
+
+    /// Links a given place to a given value loaded from an atomic variable.
+    /// The place is usually a temporary that is overwritten in every iteration of a loop,
+    /// therefore a previous value is replaced without debug messages.
+    ///
+    /// A local that is also assigned elsewhere in the body is not linked:
+    /// A switch int terminator that tests it may be reached without the call that computed the value.
+    pub fn link_atomic_value(&mut self, place: Place<'tcx>, atomic_value: AtomicValue) {
+        if self.reassigned_locals.contains(&place.local) {
+            debug!("PLACE {place:?} IS REASSIGNED, THE ATOMIC VALUE IS NOT TRACKED");
+            return;
+        }
+        self.map
+            .insert(place, Value::AtomicValue(Rc::new(atomic_value)));
+    }
+
+    /// Links a given place to a given one-time initialization.
+    /// Prints debug messages if the place was already linked.
+    /// Returns a reference to the linked one-time initialization.
+    pub fn link_once(&mut self, place: Place<'tcx>, once: Once) -> &OnceRef {
+        let once_ref = Rc::new(once);
+        if let Some(old_value) = self.map.get(&place) {
+            let type_string = old_value.to_string();
+
+            if let Value::Once(old_once_ref) = old_value {
+                if once_ref == *old_once_ref {
+                    debug_same_type_same_value!(place, type_string);
+                } else {
+                    debug_same_type_different_value!(place, type_string);
+                }
+            } else {
+                debug_different_type!(place, type_string);
+            }
+        }
+        let value = Value::Once(once_ref);
+        self.map.insert(place, value);
+        self.map[&place].unpack_once()
+    }
+
+    /// Links a given place to a given `crossbeam_channel::Select`.
+    /// Prints debug messages if the place was already linked.
+    pub fn link_select(&mut self, place: Place<'tcx>, select: Select) {
+        if let Some(old_value) = self.map.insert(place, Value::Select(Rc::new(select))) {
+            debug_different_type!(place, old_value);
+        }
+    }
+
     /// Links a given place to a given aggregate.
     ///
     /// # Panics
@@ -178,6 +452,14 @@ impl<'tcx> Memory<'tcx> {
         }
     }
 
+    /// Links a given place to a copy of a value from another memory,
+    /// e.g. a value captured by a future that is linked to a field of its coroutine.
+    pub fn link_value(&mut self, place: Place<'tcx>, value: Value) {
+        if let Some(old_value) = self.map.insert(place, value) {
+            debug_different_type!(place, old_value);
+        }
+    }
+
     /// Links two places to the same value.
     /// After this operation, both places point to the same value, i.e.
     /// the first place is an alias for the second place.
@@ -221,6 +503,15 @@ impl<'tcx> Memory<'tcx> {
         self.get_linked_value(place).unpack_mutex()
     }
 
+    /// Returns a reference to the mutex linked to the given place.
+    /// Returns `None` if the place is not linked to a mutex.
+    pub fn find_mutex(&self, place: &Place<'tcx>) -> Option<&MutexRef> {
+        match self.map.get(place) {
+            Some(Value::Mutex(mutex_ref)) => Some(mutex_ref),
+            _ => None,
+        }
+    }
+
     /// Returns a reference to the mutex guard linked to the given place.
     pub fn get_mutex_guard(&self, place: &Place<'tcx>) -> &MutexGuardRef {
         self.get_linked_value(place).unpack_mutex_guard()
@@ -231,11 +522,102 @@ impl<'tcx> Memory<'tcx> {
         self.get_linked_value(place).unpack_join_handle()
     }
 
+    /// Returns a reference to the join result linked to the given place.
+    pub fn get_join_result(&self, place: &Place<'tcx>) -> &JoinResultRef {
+        self.get_linked_value(place).unpack_join_result()
+    }
+
     /// Returns a reference to the condition variable linked to the given place.
     pub fn get_condvar(&self, place: &Place<'tcx>) -> &CondvarRef {
         self.get_linked_value(place).unpack_condvar()
     }
 
+    /// Returns a reference to the semaphore linked to the given place.
+    pub fn get_semaphore(&self, place: &Place<'tcx>) -> &SemaphoreRef {
+        self.get_linked_value(place).unpack_semaphore()
+    }
+
+    /// Returns a reference to the semaphore permit linked to the given place.
+    /// Returns `None` if the place is not linked to a semaphore permit.
+    pub fn find_semaphore_permit(&self, place: &Place<'tcx>) -> Option<&SemaphorePermitRef> {
+        match self.map.get(place) {
+            Some(Value::SemaphorePermit(permit_ref)) => Some(permit_ref),
+            _ => None,
+        }
+    }
+
+    /// Returns a reference to the notify linked to the given place.
+    pub fn get_notify(&self, place: &Place<'tcx>) -> &NotifyRef {
+        self.get_linked_value(place).unpack_notify()
+    }
+
+    /// Returns a reference to the channel linked to the given place,
+    /// which is either the sender or the receiver of the channel.
+    pub fn get_channel(&self, place: &Place<'tcx>) -> &ChannelRef {
+        self.get_linked_value(place).unpack_channel()
+    }
+
+    /// Returns a reference to the `crossbeam_channel::Select` linked to the given place.
+    pub fn get_select(&self, place: &Place<'tcx>) -> &SelectRef {
+        self.get_linked_value(place).unpack_select()
+    }
+
+    /// Returns a reference to the park token of the thread handle linked to the given place.
+    pub fn get_thread_handle(&self, place: &Place<'tcx>) -> &ParkTokenRef {
+        self.get_linked_value(place).unpack_thread_handle()
+    }
+
+    /// Returns a reference to the atomic variable linked to the given place.
+    pub fn get_atomic(&self, place: &Place<'tcx>) -> &AtomicRef {
+        self.get_linked_value(place).unpack_atomic()
+    }
+
+    /// Returns a reference to the value loaded from an atomic variable linked to the given place.
+    /// Returns `None` if the place is not linked to such a value.
+    pub fn find_atomic_value(&self, place: &Place<'tcx>) -> Option<&AtomicValueRef> {
+        match self.map.get(place) {
+            Some(Value::AtomicValue(atomic_value_ref)) => Some(atomic_value_ref),
+            _ => None,
+        }
+    }
+
+    /// Returns a reference to the one-time initialization linked to the given place.
+    /// Returns `None` if the place is not linked to a one-time initialization.
+    pub fn find_once(&self, place: &Place<'tcx>) -> Option<&OnceRef> {
+        match self.map.get(place) {
+            Some(Value::Once(once_ref)) => Some(once_ref),
+            _ => None,
+        }
+    }
+
+    /// Links the local returned by `std::iter::IntoIterator::into_iter` to the places of the range.
+    /// Returns a reference to the linked range.
+    pub fn link_range(&mut self, local: rustc_middle::mir::Local, range: Range) -> &RangeRef {
+        debug!("NEW RANGE: {local:?}");
+        self.ranges.entry(local).or_insert(Rc::new(range))
+    }
+
+    /// Returns a reference to the range linked to the given local.
+    /// Returns `None` if the local is not linked to a range.
+    pub fn find_range(&self, local: rustc_middle::mir::Local) -> Option<&RangeRef> {
+        self.ranges.get(&local)
+    }
+
+    /// Returns a reference to the value linked to the given place.
+    /// Returns `None` if the place is not linked to a value.
+    pub fn find_value(&self, place: &Place<'tcx>) -> Option<&Value> {
+        self.map.get(place)
+    }
+
+    /// Returns a reference to the future linked to the given place.
+    /// Returns `None` if the place is not linked to a future.
+    pub fn find_future(&self, place: &Place<'tcx>) -> Option<&FutureRef> {
+        match self.map.get(place) {
+            Some(Value::Future(future_ref)) => Some(future_ref),
+            _ => None,
+        }
+    }
+
     /// Returns the vector of values contained inside the aggregate linked to the given place.
     /// The vector is copied for the caller since the value may be used later by this function.
     ///
@@ -250,11 +632,31 @@ impl<'tcx> Memory<'tcx> {
             .clone()
     }
 
+    /// Checks whether the place is linked to any value.
+    pub fn is_linked(&self, place: &Place<'tcx>) -> bool {
+        self.map.contains_key(place)
+    }
+
     /// Checks whether the place is linked to a mutex guard.
     pub fn is_mutex_guard(&self, place: &Place<'tcx>) -> bool {
         self.map.contains_key(place) && matches!(self.get_linked_value(place), Value::MutexGuard(_))
     }
 
+    /// Checks whether the place is linked to an aggregate.
+    pub fn is_aggregate(&self, place: &Place<'tcx>) -> bool {
+        matches!(self.map.get(place), Some(Value::Aggregate(_)))
+    }
+
+    /// Checks whether the place is linked to a join handle.
+    pub fn is_join_handle(&self, place: &Place<'tcx>) -> bool {
+        matches!(self.map.get(place), Some(Value::JoinHandle(_)))
+    }
+
+    /// Checks whether the place is linked to a join result.
+    pub fn is_join_result(&self, place: &Place<'tcx>) -> bool {
+        matches!(self.map.get(place), Some(Value::JoinResult(_)))
+    }
+
     /// Creates a new aggregate value from the values linked to a vector of places.
     /// Links the new aggregate value to the given place.
     ///
@@ -313,7 +715,20 @@ pub enum Value {
     Mutex(MutexRef),
     MutexGuard(MutexGuardRef),
     JoinHandle(ThreadRef),
+    JoinResult(JoinResultRef),
     Condvar(CondvarRef),
+    Future(FutureRef),
+    Semaphore(SemaphoreRef),
+    SemaphorePermit(SemaphorePermitRef),
+    Notify(NotifyRef),
+    Sender(ChannelRef),
+    Receiver(ChannelRef),
+    ThreadHandle(ParkTokenRef),
+    Atomic(AtomicRef),
+    #[allow(clippy::enum_variant_names)]
+    AtomicValue(AtomicValueRef),
+    Once(OnceRef),
+    Select(SelectRef),
     Aggregate(Vec<Value>),
 }
 
@@ -339,6 +754,13 @@ impl Value {
         }
     }
 
+    fn unpack_join_result(&self) -> &JoinResultRef {
+        match self {
+            Self::JoinResult(join_result_ref) => join_result_ref,
+            _ => panic!("BUG: The value does not contain a join result, it contains a {self}."),
+        }
+    }
+
     fn unpack_condvar(&self) -> &CondvarRef {
         match self {
             Self::Condvar(condvar_ref) => condvar_ref,
@@ -348,6 +770,75 @@ impl Value {
         }
     }
 
+    fn unpack_future(&self) -> &FutureRef {
+        match self {
+            Self::Future(future_ref) => future_ref,
+            _ => panic!("BUG: The value does not contain a future, it contains a {self}."),
+        }
+    }
+
+    fn unpack_semaphore(&self) -> &SemaphoreRef {
+        match self {
+            Self::Semaphore(semaphore_ref) => semaphore_ref,
+            _ => panic!("BUG: The value does not contain a semaphore, it contains a {self}."),
+        }
+    }
+
+    fn unpack_semaphore_permit(&self) -> &SemaphorePermitRef {
+        match self {
+            Self::SemaphorePermit(permit_ref) => permit_ref,
+            _ => {
+                panic!("BUG: The value does not contain a semaphore permit, it contains a {self}.")
+            }
+        }
+    }
+
+    fn unpack_notify(&self) -> &NotifyRef {
+        match self {
+            Self::Notify(notify_ref) => notify_ref,
+            _ => panic!("BUG: The value does not contain a notify, it contains a {self}."),
+        }
+    }
+
+    fn unpack_channel(&self) -> &ChannelRef {
+        match self {
+            Self::Sender(channel_ref) | Self::Receiver(channel_ref) => channel_ref,
+            _ => panic!("BUG: The value does not contain a channel, it contains a {self}."),
+        }
+    }
+
+    fn unpack_thread_handle(&self) -> &ParkTokenRef {
+        match self {
+            Self::ThreadHandle(park_token_ref) => park_token_ref,
+            _ => panic!("BUG: The value does not contain a thread handle, it contains a {self}."),
+        }
+    }
+
+    fn unpack_atomic(&self) -> &AtomicRef {
+        match self {
+            Self::Atomic(atomic_ref) => atomic_ref,
+            _ => {
+                panic!("BUG: The value does not contain an atomic variable, it contains a {self}.")
+            }
+        }
+    }
+
+    fn unpack_once(&self) -> &OnceRef {
+        match self {
+            Self::Once(once_ref) => once_ref,
+            _ => panic!(
+                "BUG: The value does not contain a one-time initialization, it contains a {self}."
+            ),
+        }
+    }
+
+    fn unpack_select(&self) -> &SelectRef {
+        match self {
+            Self::Select(select_ref) => select_ref,
+            _ => panic!("BUG: The value does not contain a select, it contains a {self}."),
+        }
+    }
+
     fn unpack_aggregate(&self) -> &Vec<Self> {
         match self {
             Self::Aggregate(values) => values,
@@ -364,7 +855,19 @@ impl std::fmt::Display for Value {
             Self::Mutex(_) => write!(f, "mutex"),
             Self::MutexGuard(_) => write!(f, "mutex guard"),
             Self::JoinHandle(_) => write!(f, "join handle"),
+            Self::JoinResult(_) => write!(f, "join result"),
             Self::Condvar(_) => write!(f, "condition variable"),
+            Self::Future(_) => write!(f, "future"),
+            Self::Semaphore(_) => write!(f, "semaphore"),
+            Self::SemaphorePermit(_) => write!(f, "semaphore permit"),
+            Self::Notify(_) => write!(f, "notify"),
+            Self::Sender(_) => write!(f, "sender"),
+            Self::Receiver(_) => write!(f, "receiver"),
+            Self::ThreadHandle(_) => write!(f, "thread handle"),
+            Self::Atomic(_) => write!(f, "atomic variable"),
+            Self::AtomicValue(_) => write!(f, "atomic value"),
+            Self::Once(_) => write!(f, "one-time initialization"),
+            Self::Select(_) => write!(f, "select"),
             Self::Aggregate(_) => write!(f, "aggregate"),
         }
     }
@@ -376,7 +879,19 @@ impl std::fmt::Debug for Value {
             Self::Mutex(_) => write!(f, "MUTEX"),
             Self::MutexGuard(_) => write!(f, "MUTEX GUARD"),
             Self::JoinHandle(_) => write!(f, "JOIN HANDLE"),
+            Self::JoinResult(_) => write!(f, "JOIN RESULT"),
             Self::Condvar(_) => write!(f, "CONDITION VARIABLE"),
+            Self::Future(_) => write!(f, "FUTURE"),
+            Self::Semaphore(_) => write!(f, "SEMAPHORE"),
+            Self::SemaphorePermit(_) => write!(f, "SEMAPHORE PERMIT"),
+            Self::Notify(_) => write!(f, "NOTIFY"),
+            Self::Sender(_) => write!(f, "SENDER"),
+            Self::Receiver(_) => write!(f, "RECEIVER"),
+            Self::ThreadHandle(_) => write!(f, "THREAD HANDLE"),
+            Self::Atomic(_) => write!(f, "ATOMIC VARIABLE"),
+            Self::AtomicValue(_) => write!(f, "ATOMIC VALUE"),
+            Self::Once(_) => write!(f, "ONE-TIME INITIALIZATION"),
+            Self::Select(_) => write!(f, "SELECT"),
             Self::Aggregate(_) => write!(f, "AGGREGATE"),
         }
     }
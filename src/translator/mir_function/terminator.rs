@@ -22,17 +22,24 @@ impl MirFunction<'_> {
     /// Connects the active basic block to all the possible basic block targets in the switch int statement.
     /// This models the execution flow taking every possible path.
     /// Adds the corresponding block if it is not present already.
+    /// Returns the transitions that represent the switch int terminator, in the order of the targets.
     ///
     /// # Panics
     ///
     /// If there is no active basic block set, then the function panics.
-    pub fn switch_int(&mut self, targets: Vec<rustc_middle::mir::BasicBlock>, net: &mut PetriNet) {
+    pub fn switch_int(
+        &mut self,
+        targets: Vec<rustc_middle::mir::BasicBlock>,
+        net: &mut PetriNet,
+    ) -> Vec<TransitionRef> {
+        let mut transitions = Vec::new();
         for basic_block in targets {
             let (active_block, target_block) =
                 self.get_pair_active_block_target_block(basic_block, net);
             let index = basic_block.index();
-            active_block.switch_int(target_block, index, net);
+            transitions.push(active_block.switch_int(target_block, index, net));
         }
+        transitions
     }
 
     /// Connects the active basic block to a given unwind place that models a `panic!` scenario or similar.
@@ -74,6 +81,8 @@ impl MirFunction<'_> {
 
     /// Connects the active basic block to the next basic block identified as the argument `target`
     /// of the assert terminator.
+    /// Returns the pair of transitions that represent the assertion holding or failing.
+    ///
     /// Optionally, if a cleanup block is present, connects the active basic block
     /// to the next basic block identified as the argument `cleanup`.
     ///
@@ -85,15 +94,27 @@ impl MirFunction<'_> {
         target: rustc_middle::mir::BasicBlock,
         cleanup: Option<rustc_middle::mir::BasicBlock>,
         net: &mut PetriNet,
-    ) {
+    ) -> (TransitionRef, Option<TransitionRef>) {
         let (active_block, target_block) = self.get_pair_active_block_target_block(target, net);
-        active_block.assert(target_block, net);
-
-        if let Some(cleanup) = cleanup {
+        let assert_transition = active_block.assert(target_block, net);
+        let cleanup_transition = cleanup.map(|cleanup| {
             let (active_block, cleanup_block) =
                 self.get_pair_active_block_target_block(cleanup, net);
-            active_block.assert_cleanup(cleanup_block, net);
-        }
+            active_block.assert_cleanup(cleanup_block, net)
+        });
+        (assert_transition, cleanup_transition)
+    }
+
+    /// Connects the active basic block to the basic block where the coroutine resumes
+    /// after being suspended at a `.await` or a `yield`.
+    /// Adds the corresponding block if it is not present already.
+    ///
+    /// # Panics
+    ///
+    /// If there is no active basic block set, then the function panics.
+    pub fn suspend(&mut self, resume: rustc_middle::mir::BasicBlock, net: &mut PetriNet) {
+        let (active_block, resume_block) = self.get_pair_active_block_target_block(resume, net);
+        active_block.suspend(resume_block, net);
     }
 
     /// Connects the active basic block to the end place of the function.
@@ -116,4 +137,48 @@ impl MirFunction<'_> {
         let active_block = self.get_active_block();
         active_block.unreachable(end_place, net);
     }
+
+    /// Connects the active basic block to all the given successors, ignoring an unsupported terminator.
+    /// If the terminator has no successors, connects the active basic block to the end place of the function.
+    /// Adds the corresponding block if it is not present already.
+    ///
+    /// # Panics
+    ///
+    /// If there is no active basic block set, then the function panics.
+    pub fn skip(&mut self, targets: Vec<rustc_middle::mir::BasicBlock>, net: &mut PetriNet) {
+        if targets.is_empty() {
+            let end_place = self.end_place.clone();
+            let active_block = self.get_active_block();
+            active_block.skip_return(&end_place, net);
+        }
+        for basic_block in targets {
+            let (active_block, target_block) =
+                self.get_pair_active_block_target_block(basic_block, net);
+            active_block.skip(target_block, basic_block.index(), net);
+        }
+    }
+
+    /// Models an unsupported terminator as a nondeterministic choice between
+    /// jumping to any of the given successors, panicking or blocking forever.
+    /// If the terminator has no successors, it may return to the end place of the function instead.
+    /// Adds the corresponding block if it is not present already.
+    ///
+    /// # Panics
+    ///
+    /// If there is no active basic block set, then the function panics.
+    pub fn havoc(
+        &mut self,
+        targets: Vec<rustc_middle::mir::BasicBlock>,
+        panic_place: &PlaceRef,
+        net: &mut PetriNet,
+    ) {
+        let end_place = targets.is_empty().then(|| self.end_place.clone());
+        for basic_block in targets {
+            let (active_block, target_block) =
+                self.get_pair_active_block_target_block(basic_block, net);
+            active_block.havoc(target_block, basic_block.index(), net);
+        }
+        let active_block = self.get_active_block();
+        active_block.havoc_exits(end_place.as_ref(), panic_place, net);
+    }
 }
@@ -13,7 +13,14 @@ use rustc_middle::mir::TerminatorKind::{
 };
 use rustc_middle::mir::UnwindAction;
 
-use super::sync::{handle_aggregate_assignment, link_if_sync_variable, mutex};
+use crate::naming::basic_block::{assert_cleanup_transition_label, drop_cleanup_transition_label};
+use crate::utils::check_if_block_reachable;
+
+use super::coroutine::create_future;
+use super::sync::atomic::{handle_atomic_value_assignment, is_spin_loop_body};
+use super::sync::{
+    handle_aggregate_assignment, handle_primitive_drop, link_if_sync_variable, mutex,
+};
 use super::Translator;
 
 impl<'tcx> Visitor<'tcx> for Translator<'tcx> {
@@ -31,7 +38,7 @@ impl<'tcx> Visitor<'tcx> for Translator<'tcx> {
     }
 
     /// Keep track of synchronization variables in assignments
-    /// (mutexes, mutex guards, join handles and condition variables).
+    /// (mutexes, mutex guards, join handles, condition variables and futures).
     /// The idea is to link the right-hand side with the left-hand side of the assignment
     /// if a synchronization variable is involved.
     fn visit_assign(
@@ -48,19 +55,50 @@ impl<'tcx> Visitor<'tcx> for Translator<'tcx> {
                 let function = self.call_stack.peek_mut();
                 link_if_sync_variable(place, rhs, &mut function.memory, function.def_id, self.tcx);
             }
-            rustc_middle::mir::Rvalue::Aggregate(_, operands) => {
+            // A reference to a one-time initialization in a `static` item.
+            rustc_middle::mir::Rvalue::Use(rustc_middle::mir::Operand::Constant(constant)) => {
+                self.link_if_static_once(place, constant);
+            }
+            rustc_middle::mir::Rvalue::CopyForDeref(rhs) => {
+                // Copies of the values captured by a coroutine, e.g. a reference to a mutex,
+                // or of the references captured by a closure, which are fields of its aggregate.
                 let function = self.call_stack.peek_mut();
-                handle_aggregate_assignment(
-                    place,
-                    &operands.raw,
-                    &mut function.memory,
-                    function.def_id,
-                    self.tcx,
-                );
+                if function.memory.is_linked(rhs)
+                    || function
+                        .memory
+                        .is_aggregate(&rustc_middle::mir::Place::from(rhs.local))
+                {
+                    link_if_sync_variable(
+                        place,
+                        rhs,
+                        &mut function.memory,
+                        function.def_id,
+                        self.tcx,
+                    );
+                }
+            }
+            rustc_middle::mir::Rvalue::Aggregate(kind, operands) => {
+                let function = self.call_stack.peek_mut();
+                if let rustc_middle::mir::AggregateKind::Coroutine(def_id, _) = **kind {
+                    // An `async` block creates a future that captures the operands.
+                    let future = create_future(def_id, operands.iter(), &function.memory);
+                    function.memory.link_future(*place, future);
+                } else {
+                    handle_aggregate_assignment(
+                        place,
+                        &operands.raw,
+                        &mut function.memory,
+                        function.def_id,
+                        self.tcx,
+                    );
+                }
             }
             // No need to do anything for the other cases for now.
             _ => {}
         }
+        // Values computed from a value loaded from an atomic variable, e.g. its negation.
+        let function = self.call_stack.peek_mut();
+        handle_atomic_value_assignment(place, rvalue, &mut function.memory, self.tcx);
 
         self.super_assign(place, rvalue, location);
     }
@@ -71,24 +109,79 @@ impl<'tcx> Visitor<'tcx> for Translator<'tcx> {
         location: rustc_middle::mir::Location,
     ) {
         let function = self.call_stack.peek_mut();
+        let span = terminator.source_info.span;
 
         match terminator.kind {
             Goto { target } => {
                 function.goto(target, &mut self.net);
             }
             SwitchInt {
-                discr: _,
+                ref discr,
                 ref targets,
             } => {
-                // Convert the specific type for the targets vector into a `std::collections::Vec`
+                // Some switches in coroutines have a single possible target, see `Coroutine::switch_target`.
+                // Switches on a known constant also have a single feasible target.
+                // Otherwise convert the specific type for the targets vector into a `std::collections::Vec`
                 // <rustc_middle::mir::terminator::SwitchTargets>
-                function.switch_int(targets.all_targets().to_vec(), &mut self.net);
+                let single_target = function
+                    .coroutine
+                    .as_ref()
+                    .and_then(|coroutine| coroutine.switch_target(location.block))
+                    .or_else(|| function.constant_switch_target(location.block));
+                let all_targets = single_target
+                    .map_or_else(|| targets.all_targets().to_vec(), |target| vec![target]);
+                let transitions = function.switch_int(all_targets, &mut self.net);
+                // A switch on a value loaded from an atomic variable only takes the branch for the loaded value.
+                let atomic_value = discr
+                    .place()
+                    .and_then(|place| function.memory.find_atomic_value(&place));
+                if let (None, Some(atomic_value)) = (single_target, atomic_value) {
+                    // The branches that only spin until they loop back to the switch are the body of a spin loop,
+                    // unless every branch loops back, e.g. in an outer loop, or the value cannot change while looping.
+                    let body = self.tcx.optimized_mir(function.def_id);
+                    let looping_targets = targets
+                        .all_targets()
+                        .iter()
+                        .filter(|target| check_if_block_reachable(**target, location.block, body))
+                        .count();
+                    let mut spin_targets: Vec<rustc_middle::mir::BasicBlock> = targets
+                        .all_targets()
+                        .iter()
+                        .filter(|target| {
+                            check_if_block_reachable(**target, location.block, body)
+                                && is_spin_loop_body(**target, location.block, body, self.tcx)
+                        })
+                        .copied()
+                        .collect();
+                    if looping_targets == targets.all_targets().len()
+                        || !atomic_value.allows_spin_loops()
+                    {
+                        spin_targets.clear();
+                    }
+                    atomic_value.guard_switch(
+                        &function.name,
+                        location.block,
+                        targets,
+                        &spin_targets,
+                        &transitions,
+                        &mut self.net,
+                    );
+                }
             }
             UnwindResume | UnwindTerminate(..) => {
                 function.unwind(&self.program_panic, &mut self.net);
             }
             Return => {
-                function.return_statement(&mut self.net);
+                // A coroutine returning `Poll::Pending` at a suspension point resumes later.
+                let resume = function
+                    .coroutine
+                    .as_ref()
+                    .and_then(|coroutine| coroutine.resume_target(location.block));
+                if let Some(resume) = resume {
+                    function.suspend(resume, &mut self.net);
+                } else {
+                    function.return_statement(&mut self.net);
+                }
             }
             Unreachable => {
                 function.unreachable(&self.program_end, &mut self.net);
@@ -114,11 +207,31 @@ impl<'tcx> Visitor<'tcx> for Translator<'tcx> {
                     }
                 };
 
+                // A mutex guard dropped in a cleanup block is dropped during a panic and poisons the mutex.
+                let is_cleanup_block =
+                    self.tcx.optimized_mir(function.def_id).basic_blocks[location.block].is_cleanup;
                 let memory = &mut function.memory;
                 let net = &mut self.net;
-                mutex::handle_mutex_guard_drop(place, &transition, net, memory);
+                if self.model_panics && is_cleanup_block {
+                    mutex::handle_mutex_guard_drop_during_panic(place, &transition, net, memory);
+                } else {
+                    mutex::handle_mutex_guard_drop(place, &transition, net, memory);
+                }
+                let def_id = function.def_id;
+                handle_primitive_drop(place, &transition, net, memory, def_id, self.tcx);
                 if let Some(cleanup_transition) = cleanup_transition {
                     mutex::handle_mutex_guard_drop(place, &cleanup_transition, net, memory);
+                    handle_primitive_drop(
+                        place,
+                        &cleanup_transition,
+                        net,
+                        memory,
+                        def_id,
+                        self.tcx,
+                    );
+                    let transition =
+                        drop_cleanup_transition_label(&function.name, location.block.index());
+                    self.record_panic_site(transition, span);
                 }
             }
             Call {
@@ -127,21 +240,45 @@ impl<'tcx> Visitor<'tcx> for Translator<'tcx> {
                 destination,
                 target,
                 unwind,
-                fn_span: _,
+                fn_span,
                 call_source: _,
             } => {
-                self.call_function(func, args, destination, target, unwind);
+                self.call_function(func, args, destination, target, unwind, fn_span);
             }
             Assert {
-                cond: _,
-                expected: _,
+                ref cond,
+                expected,
                 msg: _,
                 target,
                 unwind,
             } => {
                 match unwind {
                     UnwindAction::Cleanup(cleanup) => {
-                        function.assert(target, Some(cleanup), &mut self.net);
+                        let (assert_transition, cleanup_transition) =
+                            function.assert(target, Some(cleanup), &mut self.net);
+                        // An overflow check on a value stored in a mutex only fails if the value overflows.
+                        let atomic_value = cond
+                            .place()
+                            .and_then(|place| function.memory.find_atomic_value(&place));
+                        if let (Some(atomic_value), Some(cleanup_transition)) =
+                            (atomic_value, cleanup_transition)
+                        {
+                            atomic_value.guard_switch(
+                                &function.name,
+                                location.block,
+                                &rustc_middle::mir::SwitchTargets::static_if(
+                                    u128::from(expected),
+                                    target,
+                                    cleanup,
+                                ),
+                                &[],
+                                &[assert_transition, cleanup_transition],
+                                &mut self.net,
+                            );
+                        }
+                        let transition =
+                            assert_cleanup_transition_label(&function.name, location.block.index());
+                        self.record_panic_site(transition, span);
                     }
                     // Do NOT model the `Terminate` case.
                     // It is not relevant for deadlock detection and makes the Petri nets unnecessarily bigger.
@@ -154,27 +291,37 @@ impl<'tcx> Visitor<'tcx> for Translator<'tcx> {
                     }
                 }
             }
-            Yield { .. } => {
-                unimplemented!("TerminatorKind::Yield not implemented yet")
+            // Only present in coroutines that were not lowered to a state machine yet.
+            // The executor never drops a suspended coroutine, so the drop target is not modelled.
+            Yield { resume, .. } => {
+                function.suspend(resume, &mut self.net);
             }
             CoroutineDrop => {
-                unimplemented!("TerminatorKind::CoroutineDrop not implemented yet")
+                function.return_statement(&mut self.net);
             }
-            FalseEdge { .. } => {
-                unimplemented!("TerminatorKind::FalseEdge not implemented yet")
+            FalseEdge { real_target, .. } => {
+                self.handle_unsupported_terminator(
+                    "TerminatorKind::FalseEdge",
+                    vec![real_target],
+                    span,
+                );
             }
-            FalseUnwind { .. } => {
-                unimplemented!("TerminatorKind::FalseUnwind not implemented yet")
+            FalseUnwind { real_target, .. } => {
+                self.handle_unsupported_terminator(
+                    "TerminatorKind::FalseUnwind",
+                    vec![real_target],
+                    span,
+                );
             }
-            InlineAsm { .. } => {
-                unimplemented!("TerminatorKind::InlineAsm not implemented yet")
+            InlineAsm { ref targets, .. } => {
+                self.handle_unsupported_terminator(
+                    "TerminatorKind::InlineAsm",
+                    targets.to_vec(),
+                    span,
+                );
             }
-            TailCall {
-                func: _,
-                args: _,
-                fn_span: _,
-            } => {
-                unimplemented!("TerminatorKind::TailCall not implemented yet")
+            TailCall { .. } => {
+                self.handle_unsupported_terminator("TerminatorKind::TailCall", vec![], span);
             }
         }
 
@@ -0,0 +1,85 @@
+//! Submodule for keeping track of the synchronization primitives modelled in the Petri net.
+//!
+//! Every mutex, condition variable, thread, semaphore, notify, channel, atomic variable
+//! and one-time initialization found during the translation is recorded
+//! together with its index and its location in the source code.
+//! The index is the same one used in the labels of the Petri net, e.g. `MUTEX_0`.
+//! The calls that notify a condition variable are recorded as well to locate lost signals.
+//! The places where a thread may start to panic are recorded to locate reachable panics.
+
+/// The kinds of synchronization primitives that are modelled in the Petri net.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PrimitiveKind {
+    /// A `std::sync::Mutex`
+    Mutex,
+    /// A `std::sync::Condvar`
+    Condvar,
+    /// A thread created with `std::thread::spawn` or a task created with `tokio::spawn`
+    Thread,
+    /// A `tokio::sync::Semaphore`
+    Semaphore,
+    /// A `tokio::sync::Notify`
+    Notify,
+    /// A channel created with `tokio::sync::mpsc::channel`, `crossbeam_channel::bounded` or `crossbeam_channel::unbounded`
+    Channel,
+    /// An atomic variable, e.g. a `std::sync::atomic::AtomicBool`
+    Atomic,
+    /// A one-time initialization, i.e. a `std::sync::Once`, `std::sync::OnceLock` or `std::sync::LazyLock`
+    Once,
+}
+
+impl std::fmt::Display for PrimitiveKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Mutex => write!(f, "mutex"),
+            Self::Condvar => write!(f, "condition variable"),
+            Self::Thread => write!(f, "thread"),
+            Self::Semaphore => write!(f, "semaphore"),
+            Self::Notify => write!(f, "notify"),
+            Self::Channel => write!(f, "channel"),
+            Self::Atomic => write!(f, "atomic variable"),
+            Self::Once => write!(f, "one-time initialization"),
+        }
+    }
+}
+
+/// A synchronization primitive modelled in the Petri net.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Primitive {
+    /// The kind of synchronization primitive.
+    pub kind: PrimitiveKind,
+    /// The index used in the labels of the Petri net.
+    pub index: usize,
+    /// The location in the source code where the primitive is created.
+    pub location: String,
+}
+
+impl std::fmt::Display for Primitive {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} {} at {}", self.kind, self.index, self.location)
+    }
+}
+
+/// A call to `std::sync::Condvar::notify_one` modelled in the Petri net.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NotifyCall {
+    /// The index of the condition variable that is notified.
+    pub condvar: usize,
+    /// The label of the transition that models the call.
+    pub transition: String,
+    /// The location of the call in the source code.
+    pub location: String,
+}
+
+/// A place in the source code where a thread may start to panic, modelled in the Petri net.
+/// This is a call to `panic!`, an assertion inserted by the compiler, e.g. a bounds check,
+/// or a call to a foreign function that may unwind.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PanicSite {
+    /// The index of the thread that executes the code or `None` for the main thread.
+    pub thread: Option<usize>,
+    /// The label of the transition that starts the panic.
+    pub transition: String,
+    /// The location in the source code.
+    pub location: String,
+}
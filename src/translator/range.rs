@@ -0,0 +1,329 @@
+//! Representation of a loop over a range with constant bounds (`for i in 0..N`) in the Petri net.
+//!
+//! A `for` loop over a range is translated to calls to `std::iter::Iterator::next`
+//! followed by a switch int terminator on the discriminant of the returned `Option`.
+//! Without further information, both branches of the switch are possible after every call,
+//! so the loop may run any number of times.
+//!
+//! If the bounds of the range are constants, the loop stores one place for each number of iterations
+//! from 0 to N. Exactly one of them has a token at any time.
+//! Each call to `next` is translated as one transition for each iteration that moves the token
+//! to the next place and returns `Some`, plus one transition that exits the loop after the last iteration,
+//! returns `None` and moves the token back to the first place, so that the loop can run again.
+//! The return value is linked to an `AtomicValue` that guards the switch on the discriminant.
+//!
+//! A loop may also be left before the last iteration, e.g. with `break`, `return` or `?`.
+//! The call to `into_iter` that starts the loop is therefore translated as one transition
+//! for each iteration that moves the token back to the first place.
+//!
+//! The range is found by following the argument of `next` back to its definition in the MIR body:
+//! `_R = Range { start: const A, end: const B }`, `_I = into_iter(move _R)`, `_J = move _I`
+//! and `_K = &mut _J`. Every local must be assigned exactly once and the iterator must be borrowed
+//! exactly once, otherwise the loop is translated as before.
+//! The places are stored in the `Memory` of the function under the local `_I`.
+
+use log::debug;
+use rustc_middle::mir::visit::{MutatingUseContext, PlaceContext, Visitor};
+use rustc_middle::mir::{Body, Local, Operand, Rvalue, StatementKind, TerminatorKind};
+
+use crate::data_structures::petri_net_interface::PlaceRef;
+use crate::data_structures::petri_net_interface::{
+    add_arc_place_transition, add_arc_transition_place, connect_places, PetriNet,
+};
+use crate::naming::range::{
+    exit_transition_label, iteration_place_label, iteration_transition_label,
+    reset_transition_label,
+};
+use crate::translator::function::Places;
+use crate::translator::mir_function::memory::Memory;
+use crate::translator::sync::atomic::AtomicValue;
+use crate::utils::evaluate_constant_as_integer;
+
+/// The maximum number of iterations modelled for a loop over a range.
+/// Longer loops are translated as unbounded loops to keep the state space small
+/// and recorded as approximations.
+pub const MAX_ITERATIONS: usize = 16;
+
+/// The type of the ranges with constant bounds.
+/// It also indexes the places of every range in the net.
+pub const RANGE_TYPE: &str = "std::ops::Range";
+
+/// The places that count the iterations of a loop over a range.
+pub struct Range {
+    iterations: Vec<PlaceRef>,
+}
+
+impl Range {
+    /// Creates the places for a range with `length` iterations.
+    /// The token starts in the place for zero iterations.
+    pub fn new(index: usize, length: usize, net: &mut PetriNet) -> Self {
+        let iterations: Vec<_> = (0..=length)
+            .map(|iteration| net.add_place(&iteration_place_label(index, iteration)))
+            .collect();
+        net.add_token(&iterations[0], 1).expect(
+            "BUG: Adding the initial token to the first iteration should not cause an overflow",
+        );
+        Self { iterations }
+    }
+
+    /// Returns the number of iterations of the range.
+    pub fn length(&self) -> usize {
+        self.iterations.len() - 1
+    }
+}
+
+/// The definition of a local in the MIR body.
+enum Definition<'a, 'tcx> {
+    /// An assignment statement.
+    Assign(&'a Rvalue<'tcx>),
+    /// The return value of a function call.
+    Call(
+        &'a Operand<'tcx>,
+        &'a [rustc_span::source_map::Spanned<Operand<'tcx>>],
+    ),
+}
+
+/// Visitor that collects the definitions and borrows of a local.
+struct LocalUses<'a, 'tcx> {
+    local: Local,
+    definitions: Vec<Definition<'a, 'tcx>>,
+    borrows: usize,
+    other_uses: usize,
+}
+
+impl<'tcx> Visitor<'tcx> for LocalUses<'_, 'tcx> {
+    fn visit_local(
+        &mut self,
+        local: Local,
+        context: PlaceContext,
+        _location: rustc_middle::mir::Location,
+    ) {
+        if local != self.local {
+            return;
+        }
+        match context {
+            // The definitions are collected separately, see `find_uses`.
+            PlaceContext::MutatingUse(MutatingUseContext::Store | MutatingUseContext::Call) => {}
+            PlaceContext::MutatingUse(MutatingUseContext::Borrow) => self.borrows += 1,
+            PlaceContext::NonUse(_) => {}
+            _ => self.other_uses += 1,
+        }
+    }
+}
+
+/// Collects the definitions and borrows of the local in the body.
+fn find_uses<'a, 'tcx>(body: &'a Body<'tcx>, local: Local) -> LocalUses<'a, 'tcx> {
+    let mut uses = LocalUses {
+        local,
+        definitions: Vec::new(),
+        borrows: 0,
+        other_uses: 0,
+    };
+    uses.visit_body(body);
+    for block in body.basic_blocks.iter() {
+        for statement in &block.statements {
+            if let StatementKind::Assign(assignment) = &statement.kind {
+                let (place, rvalue) = assignment.as_ref();
+                if place.local == local {
+                    if place.projection.is_empty() {
+                        uses.definitions.push(Definition::Assign(rvalue));
+                    } else {
+                        uses.other_uses += 1;
+                    }
+                }
+            }
+        }
+        if let TerminatorKind::Call {
+            func,
+            args,
+            destination,
+            ..
+        } = &block.terminator().kind
+        {
+            if destination.local == local {
+                uses.definitions.push(Definition::Call(func, args));
+            }
+        }
+    }
+    uses
+}
+
+/// Returns the only definition of the local, if it is a local without projections.
+fn find_only_definition<'a, 'tcx>(
+    body: &'a Body<'tcx>,
+    operand: &Operand<'tcx>,
+) -> Option<Definition<'a, 'tcx>> {
+    let local = operand.place()?.as_local()?;
+    let mut uses = find_uses(body, local);
+    if uses.definitions.len() != 1 {
+        return None;
+    }
+    uses.definitions.pop()
+}
+
+/// Returns the iterator returned by `std::iter::IntoIterator::into_iter` and the number of iterations
+/// of the range passed to `std::iter::Iterator::next` if its bounds are constants
+/// and the iterator is only used by this call.
+/// The number of iterations is `Err(length)` if the range has more than `MAX_ITERATIONS` iterations.
+/// Returns `None` if the range cannot be found.
+pub fn find_constant_range<'tcx>(
+    body: &Body<'tcx>,
+    args: &[rustc_span::source_map::Spanned<Operand<'tcx>>],
+    tcx: rustc_middle::ty::TyCtxt<'tcx>,
+) -> Option<(Local, Result<usize, usize>)> {
+    // `_K = &mut _J`
+    let Definition::Assign(Rvalue::Ref(_, _, iterator)) =
+        find_only_definition(body, &args.first()?.node)?
+    else {
+        return None;
+    };
+    let mut iterator = iterator.as_local()?;
+    let uses = find_uses(body, iterator);
+    if uses.borrows != 1 || uses.other_uses != 0 {
+        return None;
+    }
+    let [mut definition] = <[Definition; 1]>::try_from(uses.definitions).ok()?;
+    loop {
+        definition = match definition {
+            // `_J = move _I`
+            Definition::Assign(Rvalue::Use(operand)) => {
+                iterator = operand.place()?.as_local()?;
+                find_only_definition(body, operand)?
+            }
+            // `_I = into_iter(move _R)`
+            Definition::Call(func, call_args) => {
+                let (def_id, _) = func.const_fn_def()?;
+                if tcx.def_path_str(def_id) != "std::iter::IntoIterator::into_iter" {
+                    return None;
+                }
+                let Definition::Assign(rvalue) =
+                    find_only_definition(body, &call_args.first()?.node)?
+                else {
+                    return None;
+                };
+                return Some((iterator, constant_range_length(rvalue, tcx)?));
+            }
+            // `_J = Range { start: const A, end: const B }` without `into_iter`
+            Definition::Assign(rvalue) => {
+                return Some((iterator, constant_range_length(rvalue, tcx)?))
+            }
+        };
+    }
+}
+
+/// Returns the number of iterations of the range built by the assignment
+/// `_R = Range { start: const A, end: const B }`.
+/// Returns `Some(Err(length))` if the range has more than `MAX_ITERATIONS` iterations.
+/// Returns `None` if the bounds are not constants.
+fn constant_range_length<'tcx>(
+    rvalue: &Rvalue<'tcx>,
+    tcx: rustc_middle::ty::TyCtxt<'tcx>,
+) -> Option<Result<usize, usize>> {
+    let Rvalue::Aggregate(kind, fields) = rvalue else {
+        return None;
+    };
+    let rustc_middle::mir::AggregateKind::Adt(def_id, ..) = **kind else {
+        return None;
+    };
+    if tcx.def_path_str(def_id) != RANGE_TYPE {
+        return None;
+    }
+    let start = evaluate_constant_as_integer(fields.iter().next()?, tcx)?;
+    let end = evaluate_constant_as_integer(fields.iter().nth(1)?, tcx)?;
+    let length = usize::try_from((end - start).max(0)).ok()?;
+    Some(if length <= MAX_ITERATIONS {
+        Ok(length)
+    } else {
+        Err(length)
+    })
+}
+
+/// Returns the number of iterations of the range passed to `std::iter::IntoIterator::into_iter`
+/// if its bounds are constants and it has at most `MAX_ITERATIONS` iterations.
+/// Converting a range into an iterator returns the range itself, so the call never unwinds.
+pub fn find_constant_range_into_iter_length<'tcx>(
+    body: &Body<'tcx>,
+    args: &[rustc_span::source_map::Spanned<Operand<'tcx>>],
+    tcx: rustc_middle::ty::TyCtxt<'tcx>,
+) -> Option<usize> {
+    match find_only_definition(body, &args.first()?.node)? {
+        Definition::Assign(rvalue) => constant_range_length(rvalue, tcx)?.ok(),
+        Definition::Call(..) => None,
+    }
+}
+
+/// Call to `std::iter::IntoIterator::into_iter` on a range.
+/// Non-recursive call for the translation process.
+///
+/// Connects the start place and the end place through one transition for each iteration
+/// that moves the token back to the first place, so that the loop starts from the beginning
+/// even if it was left before the last iteration.
+///
+/// Converting a range never unwinds, therefore the cleanup place is not modelled.
+pub fn call_into_iter(
+    function_name: &str,
+    index: usize,
+    range: &Range,
+    places: Places,
+    net: &mut PetriNet,
+) {
+    let (start_place, end_place) = places.ignore_cleanup_place().get_start_end_place();
+    for (iteration, iteration_place) in range.iterations.iter().enumerate() {
+        let label = reset_transition_label(function_name, index, iteration);
+        let transition = connect_places(net, &start_place, &end_place, &label);
+        add_arc_place_transition(net, iteration_place, &transition);
+        add_arc_transition_place(net, &transition, &range.iterations[0]);
+    }
+    debug!(
+        "{function_name} ON RANGE WITH {} ITERATIONS",
+        range.length()
+    );
+}
+
+/// Call to `std::iter::Iterator::next` on a range.
+/// Non-recursive call for the translation process.
+///
+/// - Connects the start place and the end place through one transition for each iteration
+///   and one transition that exits the loop.
+/// - Links the return place to an `AtomicValue` with the discriminant of the returned `Option`.
+///
+/// Advancing a range never unwinds, therefore the cleanup place is not modelled.
+pub fn call_next<'tcx>(
+    function_name: &str,
+    index: usize,
+    range: &Range,
+    destination: rustc_middle::mir::Place<'tcx>,
+    places: Places,
+    net: &mut PetriNet,
+    memory: &mut Memory<'tcx>,
+) {
+    let (start_place, end_place) = places.ignore_cleanup_place().get_start_end_place();
+    let iterations = &range.iterations;
+    let length = range.length();
+
+    let mut transitions = Vec::new();
+    let mut outcomes = Vec::new();
+    for iteration in 0..length {
+        let label = iteration_transition_label(function_name, index, iteration);
+        let transition = connect_places(net, &start_place, &end_place, &label);
+        add_arc_place_transition(net, &iterations[iteration], &transition);
+        add_arc_transition_place(net, &transition, &iterations[iteration + 1]);
+        transitions.push(transition);
+        // The discriminant of `Some`
+        outcomes.push(1);
+    }
+    let label = exit_transition_label(function_name, index);
+    let transition = connect_places(net, &start_place, &end_place, &label);
+    add_arc_place_transition(net, &iterations[length], &transition);
+    add_arc_transition_place(net, &transition, &iterations[0]);
+    transitions.push(transition);
+    // The discriminant of `None`
+    outcomes.push(0);
+
+    // The loop is not a spin loop, the switch always takes the branch selected by the iteration.
+    memory.link_atomic_value(
+        destination,
+        AtomicValue::new_without_spin_loops(transitions, outcomes),
+    );
+    debug!("{function_name} ON RANGE WITH {length} ITERATIONS: {destination:?}");
+}
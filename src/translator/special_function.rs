@@ -6,23 +6,35 @@
 //! For example: Calls to standard library methods, iterators, etc.
 
 use crate::data_structures::petri_net_interface::{
-    add_arc_place_transition, connect_places, PetriNet, PlaceRef,
+    add_arc_place_transition, add_arc_transition_place, connect_places, PetriNet, PlaceRef,
 };
+use crate::models::Behavior;
 use crate::naming::function::{
-    diverging_call_transition_label, foreign_call_transition_labels, panic_transition_label,
+    diverging_call_transition_label, foreign_call_transition_labels,
+    havoc_call_blocked_place_label, havoc_call_transition_labels, panic_transition_label,
 };
+use crate::naming::model::diverging_call_transition_label as modelled_diverging_call_transition_label;
 use crate::translator::function::{Places, Transitions};
 
 /// Checks whether the function name corresponds to one of the functions
 /// that starts a panic, i.e. an unwind of the stack.
+///
+/// Since edition 2021, `panic!` expands to a call to a helper function defined in the
+/// calling function, e.g. `main::panic_cold_explicit`. These helpers are also panic functions.
 pub fn is_panic_function(function_name: &str) -> bool {
     matches!(
         function_name,
         "core::panicking::assert_failed"
             | "core::panicking::panic"
+            | "core::panicking::panic_display"
+            | "core::panicking::panic_explicit"
             | "core::panicking::panic_fmt"
             | "std::rt::begin_panic"
             | "std::rt::begin_panic_fmt"
+            | "std::rt::panic_display"
+    ) || matches!(
+        function_name.rsplit("::").next(),
+        Some("panic_cold_explicit" | "panic_cold_display")
     )
 }
 
@@ -92,12 +104,90 @@ pub fn call_diverging_function(start_place: &PlaceRef, function_name: &str, net:
 /// Creates an abridged Petri net representation of a function call
 /// that starts a panic, i.e. an unwind of the stack.
 /// Connects the start place to the panic place through a new transition.
+/// Returns the label of the new transition.
 pub fn call_panic_function(
     start_place: &PlaceRef,
     unwind_place: &PlaceRef,
     function_name: &str,
+    index: usize,
     net: &mut PetriNet,
-) {
-    let label = panic_transition_label(function_name);
+) -> String {
+    let label = panic_transition_label(function_name, index);
     connect_places(net, start_place, unwind_place, &label);
+    label
+}
+
+/// Creates a nondeterministic Petri net representation of a function call
+/// that cannot be modelled exactly.
+/// The call may return normally, unwind or block forever:
+/// - Connects the start place and the end place through a new transition.
+/// - Connects the start place and the cleanup place through a second new transition.
+///   If no cleanup place is provided, the `panic_place` is used instead.
+/// - Connects the start place to a new place without outgoing arcs through a third new transition.
+///
+/// The labels from the transitions are generated from the function name and the index.
+pub fn call_havoc_function(
+    function_name: &str,
+    index: usize,
+    places: Places,
+    panic_place: &PlaceRef,
+    net: &mut PetriNet,
+) {
+    let (return_label, panic_label, block_label) =
+        havoc_call_transition_labels(function_name, index);
+    let (start_place, end_place, cleanup_place) = match places {
+        Places::Basic {
+            start_place,
+            end_place,
+        } => (start_place, end_place, panic_place.clone()),
+        Places::WithCleanup {
+            start_place,
+            end_place,
+            cleanup_place,
+        } => (start_place, end_place, cleanup_place),
+    };
+    connect_places(net, &start_place, &end_place, &return_label);
+    connect_places(net, &start_place, &cleanup_place, &panic_label);
+    let blocked_place = net.add_place(&havoc_call_blocked_place_label(function_name, index));
+    connect_places(net, &start_place, &blocked_place, &block_label);
+}
+
+/// Creates a Petri net representation of a call to a function with a user-supplied model.
+/// The cleanup place is ignored, i.e. the call never unwinds.
+/// - `Diverge`: Connects the start place to a new transition without output arcs.
+/// - `NoUnwind`: Connects the start place and the end place through a new transition.
+/// - Otherwise: Connects the start place and the end place through a new transition
+///   that takes a token from or puts a token into the `resource_place`.
+///
+/// # Panics
+///
+/// If the behaviour uses a resource but no resource place is provided, then the function panics.
+pub fn call_modelled_function(
+    function_name: &str,
+    index: usize,
+    behavior: Behavior,
+    resource_place: Option<&PlaceRef>,
+    places: Places,
+    net: &mut PetriNet,
+) {
+    if behavior == Behavior::Diverge {
+        let (start_place, _) = places.get_start_end_place();
+        let label = modelled_diverging_call_transition_label(function_name, index);
+        let transition = net.add_transition(&label);
+        add_arc_place_transition(net, &start_place, &transition);
+        return;
+    }
+    let transition =
+        call_foreign_function(function_name, index, places.ignore_cleanup_place(), net).default();
+    if !behavior.uses_resource() {
+        return;
+    }
+    let resource_place = resource_place.unwrap_or_else(|| {
+        panic!("BUG: The model for `{function_name}` should have a resource place")
+    });
+    if behavior.takes_token() {
+        add_arc_place_transition(net, resource_place, &transition);
+    } else {
+        add_arc_transition_place(net, &transition, resource_place);
+    }
 }
@@ -1,43 +1,162 @@
 //! Submodule for implementing the translation of synchronization primitives
 //! and the translation of thread primitives.
+//!
+//! Besides the primitives of the standard library, the primitives of `parking_lot`, `tokio::sync`
+//! and the channels of `crossbeam_channel` are supported.
+//! The primitives of `parking_lot` are modelled like the ones of the standard library.
+//! They are never poisoned, so `lock`, `read` and `write` return the guard directly.
+//! A `parking_lot::RwLock` is modelled like a mutex, i.e. readers exclude each other.
+//! Every `read` on a `parking_lot::RwLock` is recorded as an approximation,
+//! but a read while holding a read lock adds no edge to the lock graph.
+//!
+//! Their blocking operations are asynchronous: The call only creates a future, which is linked to
+//! the primitive, and the operation happens when the future is awaited, i.e. polled.
+//! A `tokio::sync::Mutex` is modelled like a `std::sync::Mutex`.
+//! A `tokio::sync::RwLock` is modelled like a mutex as well, i.e. readers exclude each other.
+//! This is an over-approximation since the Petri net cannot express
+//! taking every read permit at once without weighted arcs.
+//! Every `read` on a `tokio::sync::RwLock` is therefore recorded as an approximation.
 
+pub mod atomic;
+pub mod channel;
 pub mod condvar;
 pub mod mutex;
+pub mod notify;
+pub mod once;
+pub mod park;
+pub mod select;
+pub mod semaphore;
 pub mod thread;
 
 use log::debug;
 
-use crate::data_structures::petri_net_interface::PetriNet;
+use crate::data_structures::petri_net_interface::{PetriNet, TransitionRef};
 use crate::translator::function::{Places, PostprocessingTask};
-use crate::translator::mir_function::memory::Memory;
+use crate::translator::mir_function::memory::{Memory, Value};
+use crate::translator::special_function::call_foreign_function;
 use crate::utils::{
-    check_substring_in_place_type, extract_nth_argument_as_place, get_field_number_in_projection,
+    check_substring_in_place_type, extract_nth_argument_as_place, extract_nth_argument_as_usize,
+    get_field_number_in_projection,
 };
 
 // Re-export the types that the module contains.
 // It does not make assumptions about how they are stored.
 // That is the responsibility of the memory.
+pub use atomic::{Atomic, AtomicValue};
+pub use channel::Channel;
 pub use condvar::Condvar;
 pub use mutex::{Guard as MutexGuard, Mutex};
-pub use thread::Thread;
+pub use notify::Notify;
+pub use once::Once;
+pub use park::ParkToken;
+pub use select::Select;
+pub use semaphore::{Permit as SemaphorePermit, Semaphore};
+pub use thread::{JoinResult, Thread};
+
+/// The substrings in the type of a place that contains a synchronization variable.
+/// The type of a coroutine is printed as `{async fn body of ...}` or `{async block@...}`.
+const SYNC_VARIABLE_TYPES: [&str; 30] = [
+    "std::sync::MutexGuard<",
+    "std::sync::Mutex<",
+    "std::thread::JoinHandle<",
+    "std::thread::Thread",
+    "std::sync::Condvar",
+    "std::sync::atomic::Atomic",
+    "std::sync::Once",
+    "std::sync::LazyLock<",
+    "{async ",
+    "crossbeam_channel::Sender<",
+    "crossbeam_channel::Receiver<",
+    "crossbeam_channel::Select<",
+    "parking_lot::MutexGuard<",
+    "parking_lot::Mutex<",
+    "parking_lot::RwLockReadGuard<",
+    "parking_lot::RwLockWriteGuard<",
+    "parking_lot::RwLock<",
+    "parking_lot::Condvar",
+    "tokio::sync::MutexGuard<",
+    "tokio::sync::Mutex<",
+    "tokio::sync::RwLockReadGuard<",
+    "tokio::sync::RwLockWriteGuard<",
+    "tokio::sync::RwLock<",
+    "tokio::sync::Notify",
+    "tokio::sync::futures::Notified<",
+    "tokio::sync::Semaphore",
+    "tokio::sync::mpsc::Sender<",
+    "tokio::sync::mpsc::Receiver<",
+    "tokio::task::JoinHandle<",
+    "tokio::task::JoinError",
+];
 
 /// Checks whether the function name corresponds to one of the
 /// supported synchronization or multithreading functions.
 pub fn is_supported_function(function_name: &str) -> bool {
+    atomic::is_atomic_function(function_name)
+        || matches!(
+            function_name,
+            "crossbeam_channel::Receiver::<T>::recv"
+                | "crossbeam_channel::Select::<'a>::new"
+                | "crossbeam_channel::Select::<'a>::recv"
+                | "crossbeam_channel::Select::<'a>::select"
+                | "crossbeam_channel::Select::<'a>::send"
+                | "crossbeam_channel::SelectedOperation::<'a>::index"
+                | "crossbeam_channel::SelectedOperation::<'a>::recv"
+                | "crossbeam_channel::SelectedOperation::<'a>::send"
+                | "crossbeam_channel::Sender::<T>::send"
+                | "crossbeam_channel::bounded"
+                | "crossbeam_channel::unbounded"
+                | "parking_lot::Condvar::new"
+                | "parking_lot::Condvar::notify_all"
+                | "parking_lot::Condvar::notify_one"
+                | "parking_lot::Condvar::wait"
+                | "parking_lot::Mutex::<T>::lock"
+                | "parking_lot::Mutex::<T>::new"
+                | "parking_lot::RwLock::<T>::new"
+                | "parking_lot::RwLock::<T>::read"
+                | "parking_lot::RwLock::<T>::write"
+                | "std::sync::Condvar::new"
+                | "std::sync::Condvar::notify_one"
+                | "std::sync::Condvar::wait"
+                | "std::sync::Condvar::wait_while"
+                | "std::sync::Mutex::<T>::lock"
+                | "std::sync::Mutex::<T>::new"
+                | "std::thread::spawn"
+                | "std::thread::JoinHandle::<T>::join"
+                | "std::thread::Thread::unpark"
+                | "tokio::sync::Mutex::<T>::lock"
+                | "tokio::sync::Mutex::<T>::new"
+                | "tokio::sync::Notify::new"
+                | "tokio::sync::Notify::notified"
+                | "tokio::sync::Notify::notify_one"
+                | "tokio::sync::RwLock::<T>::new"
+                | "tokio::sync::RwLock::<T>::read"
+                | "tokio::sync::RwLock::<T>::write"
+                | "tokio::sync::Semaphore::acquire"
+                | "tokio::sync::Semaphore::new"
+                | "tokio::sync::mpsc::Receiver::<T>::recv"
+                | "tokio::sync::mpsc::Sender::<T>::send"
+                | "tokio::sync::mpsc::channel"
+        )
+}
+
+/// Checks whether the function creates the future of an asynchronous operation
+/// on one of the supported `tokio` primitives.
+/// The operation happens when the future is awaited, see `call_await`.
+pub fn is_async_operation(function_name: &str) -> bool {
     matches!(
         function_name,
-        "std::sync::Condvar::new"
-            | "std::sync::Condvar::notify_one"
-            | "std::sync::Condvar::wait"
-            | "std::sync::Condvar::wait_while"
-            | "std::sync::Mutex::<T>::lock"
-            | "std::sync::Mutex::<T>::new"
-            | "std::thread::spawn"
-            | "std::thread::JoinHandle::<T>::join"
+        "tokio::sync::Mutex::<T>::lock"
+            | "tokio::sync::Notify::notified"
+            | "tokio::sync::RwLock::<T>::read"
+            | "tokio::sync::RwLock::<T>::write"
+            | "tokio::sync::Semaphore::acquire"
+            | "tokio::sync::mpsc::Receiver::<T>::recv"
+            | "tokio::sync::mpsc::Sender::<T>::send"
     )
 }
 
 /// Calls the corresponding handler for the supported synchronization or multithreading functions.
+#[allow(clippy::too_many_arguments)]
 pub fn call_function<'tcx>(
     function_name: &str,
     index: usize,
@@ -46,26 +165,97 @@ pub fn call_function<'tcx>(
     places: Places,
     net: &mut PetriNet,
     memory: &mut Memory<'tcx>,
+    tcx: rustc_middle::ty::TyCtxt<'tcx>,
 ) -> Option<PostprocessingTask> {
+    if is_async_operation(function_name) {
+        call_async_operation(function_name, index, args, destination, places, net, memory);
+        return None;
+    }
+    if atomic::is_atomic_function(function_name) {
+        call_atomic_function(
+            function_name,
+            index,
+            args,
+            destination,
+            places,
+            net,
+            memory,
+            tcx,
+        );
+        return None;
+    }
     match function_name {
-        "std::sync::Condvar::new" => {
+        "crossbeam_channel::Receiver::<T>::recv" => {
+            channel::call_recv(function_name, index, args, places, net, memory);
+            None
+        }
+        "crossbeam_channel::Select::<'a>::new" => {
+            select::call_new(function_name, index, destination, places, net, memory);
+            None
+        }
+        "crossbeam_channel::Select::<'a>::recv" | "crossbeam_channel::Select::<'a>::send" => {
+            select::call_register(function_name, index, args, places, net, memory);
+            None
+        }
+        "crossbeam_channel::Select::<'a>::select" => {
+            select::call_select(function_name, index, args, destination, places, net, memory);
+            None
+        }
+        "crossbeam_channel::SelectedOperation::<'a>::index" => {
+            select::call_index(function_name, index, args, destination, places, net, memory);
+            None
+        }
+        "crossbeam_channel::SelectedOperation::<'a>::recv"
+        | "crossbeam_channel::SelectedOperation::<'a>::send" => {
+            select::call_complete(function_name, index, places, net);
+            None
+        }
+        "crossbeam_channel::Sender::<T>::send" => {
+            channel::call_send(function_name, index, args, places, net, memory);
+            None
+        }
+        "crossbeam_channel::bounded" | "crossbeam_channel::unbounded" => {
+            let capacity = extract_nth_argument_as_usize(args, 0, tcx);
+            channel::call_channel(
+                function_name,
+                index,
+                capacity,
+                destination,
+                places,
+                net,
+                memory,
+            );
+            None
+        }
+        "parking_lot::Condvar::new" | "std::sync::Condvar::new" => {
             condvar::call_new(function_name, index, destination, places, net, memory);
             None
         }
-        "std::sync::Condvar::notify_one" => {
+        "parking_lot::Condvar::notify_all"
+        | "parking_lot::Condvar::notify_one"
+        | "std::sync::Condvar::notify_one" => {
             condvar::call_notify_one(function_name, index, args, places, net, memory);
             None
         }
-        "std::sync::Condvar::wait" | "std::sync::Condvar::wait_while" => {
+        "parking_lot::Condvar::wait"
+        | "std::sync::Condvar::wait"
+        | "std::sync::Condvar::wait_while" => {
             let task =
                 condvar::call_wait(function_name, index, args, destination, places, net, memory);
             Some(task)
         }
-        "std::sync::Mutex::<T>::lock" => {
+        "parking_lot::Mutex::<T>::lock"
+        | "parking_lot::RwLock::<T>::read"
+        | "parking_lot::RwLock::<T>::write"
+        | "std::sync::Mutex::<T>::lock" => {
             mutex::call_lock(function_name, index, args, destination, places, net, memory);
             None
         }
-        "std::sync::Mutex::<T>::new" => {
+        "parking_lot::Mutex::<T>::new"
+        | "parking_lot::RwLock::<T>::new"
+        | "std::sync::Mutex::<T>::new"
+        | "tokio::sync::Mutex::<T>::new"
+        | "tokio::sync::RwLock::<T>::new" => {
             let task = mutex::call_new(function_name, index, destination, places, net, memory);
             Some(task)
         }
@@ -73,26 +263,297 @@ pub fn call_function<'tcx>(
             thread::call_join(function_name, index, args, places, net, memory);
             None
         }
+        "std::thread::Thread::unpark" => {
+            park::call_unpark(function_name, index, args, places, net, memory);
+            None
+        }
+        "tokio::sync::Notify::new" => {
+            notify::call_new(function_name, index, destination, places, net, memory);
+            None
+        }
+        "tokio::sync::Notify::notify_one" => {
+            notify::call_notify_one(function_name, index, args, places, net, memory);
+            None
+        }
+        "tokio::sync::Semaphore::new" => {
+            let permits = extract_nth_argument_as_usize(args, 0, tcx).unwrap_or_else(|| {
+                panic!("BUG: `{function_name}` should receive a constant number of permits")
+            });
+            semaphore::call_new(
+                function_name,
+                index,
+                permits,
+                destination,
+                places,
+                net,
+                memory,
+            );
+            None
+        }
+        "tokio::sync::mpsc::channel" => {
+            let capacity = extract_nth_argument_as_usize(args, 0, tcx).unwrap_or_else(|| {
+                panic!("BUG: `{function_name}` should receive a constant capacity")
+            });
+            channel::call_channel(
+                function_name,
+                index,
+                Some(capacity),
+                destination,
+                places,
+                net,
+                memory,
+            );
+            None
+        }
         _ => panic!("BUG: Call handler for {function_name} is not defined"),
     }
 }
 
+/// Checks whether a call to one of the supported synchronization or multithreading functions
+/// can be translated with the information available in the memory.
+/// Returns a description of the problem if it cannot be translated:
+/// - A synchronization variable passed as an argument is not linked in the memory.
+/// - The condition variable was already linked to another call to `wait` or `wait_while`.
+/// - The number of permits of a semaphore or the capacity of a channel is not a constant.
+/// - A value written to an atomic variable is not a constant in the range of modelled values.
+///
+/// Otherwise returns `None`.
+pub fn find_unsupported_reason<'tcx>(
+    function_name: &str,
+    args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
+    memory: &Memory<'tcx>,
+    tcx: rustc_middle::ty::TyCtxt<'tcx>,
+) -> Option<String> {
+    if matches!(
+        function_name,
+        "crossbeam_channel::bounded" | "tokio::sync::Semaphore::new" | "tokio::sync::mpsc::channel"
+    ) && extract_nth_argument_as_usize(args, 0, tcx).is_none()
+    {
+        return Some(format!(
+            "Call to `{function_name}` with a size that is not a constant"
+        ));
+    }
+    let is_atomic_function = atomic::is_atomic_function(function_name);
+    if is_atomic_function
+        && atomic::value_arguments(function_name)
+            .iter()
+            .any(|index| atomic::extract_value(function_name, args, *index, tcx).is_none())
+    {
+        return Some(format!(
+            "Call to `{function_name}` with a value that is not a constant between 0 and {}",
+            atomic::number_of_values(function_name) - 1
+        ));
+    }
+    let sync_arguments: &[usize] = match function_name {
+        "crossbeam_channel::Receiver::<T>::recv"
+        | "crossbeam_channel::Select::<'a>::select"
+        | "crossbeam_channel::SelectedOperation::<'a>::index"
+        | "crossbeam_channel::Sender::<T>::send"
+        | "parking_lot::Condvar::notify_all"
+        | "parking_lot::Condvar::notify_one"
+        | "parking_lot::Mutex::<T>::lock"
+        | "parking_lot::RwLock::<T>::read"
+        | "parking_lot::RwLock::<T>::write"
+        | "std::sync::Condvar::notify_one"
+        | "std::sync::Mutex::<T>::lock"
+        | "std::thread::JoinHandle::<T>::join"
+        | "std::thread::Thread::unpark"
+        | "tokio::sync::Notify::notify_one" => &[0],
+        _ if is_async_operation(function_name) => &[0],
+        _ if is_atomic_function && !function_name.ends_with("::new") => &[0],
+        "crossbeam_channel::Select::<'a>::recv"
+        | "crossbeam_channel::Select::<'a>::send"
+        | "parking_lot::Condvar::wait"
+        | "std::sync::Condvar::wait"
+        | "std::sync::Condvar::wait_while" => &[0, 1],
+        _ => &[],
+    };
+    for index in sync_arguments {
+        let Some(place) = extract_nth_argument_as_place(args, *index) else {
+            return Some(format!(
+                "Call to `{function_name}` with a constant synchronization variable"
+            ));
+        };
+        if !memory.is_linked(&place) {
+            return Some(format!(
+                "Call to `{function_name}` with an untracked synchronization variable"
+            ));
+        }
+    }
+    if matches!(
+        function_name,
+        "parking_lot::Condvar::wait"
+            | "std::sync::Condvar::wait"
+            | "std::sync::Condvar::wait_while"
+    ) {
+        let self_ref = extract_nth_argument_as_place(args, 0)?;
+        if memory.get_condvar(&self_ref).is_linked_to_wait_call() {
+            return Some(
+                "Multiple calls to `wait` or `wait_while` on the same condition variable"
+                    .to_string(),
+            );
+        }
+    }
+    None
+}
+
 /// Checks whether a place contains a sync variable
-/// (mutex, mutex guard, join handle or condition variable)
+/// (mutex, mutex guard, join handle, condition variable, a future implemented by a coroutine
+/// or one of the supported `tokio` and `crossbeam_channel` primitives), see `SYNC_VARIABLE_TYPES`.
 pub fn check_if_sync_variable<'tcx>(
     place: &rustc_middle::mir::Place<'tcx>,
     caller_function_def_id: rustc_hir::def_id::DefId,
     tcx: rustc_middle::ty::TyCtxt<'tcx>,
 ) -> bool {
-    check_substring_in_place_type(place, "std::sync::MutexGuard<", caller_function_def_id, tcx)
-        || check_substring_in_place_type(place, "std::sync::Mutex<", caller_function_def_id, tcx)
-        || check_substring_in_place_type(
-            place,
-            "std::thread::JoinHandle<",
-            caller_function_def_id,
-            tcx,
-        )
-        || check_substring_in_place_type(place, "std::sync::Condvar", caller_function_def_id, tcx)
+    SYNC_VARIABLE_TYPES.iter().any(|expected_substring| {
+        check_substring_in_place_type(place, expected_substring, caller_function_def_id, tcx)
+    })
+}
+
+/// Calls the corresponding handler for a call on an atomic variable.
+/// The values written to the variable must be constants, see `find_unsupported_reason`.
+#[allow(clippy::too_many_arguments)]
+fn call_atomic_function<'tcx>(
+    function_name: &str,
+    index: usize,
+    args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
+    destination: rustc_middle::mir::Place<'tcx>,
+    places: Places,
+    net: &mut PetriNet,
+    memory: &mut Memory<'tcx>,
+    tcx: rustc_middle::ty::TyCtxt<'tcx>,
+) {
+    let values: Vec<usize> = atomic::value_arguments(function_name)
+        .iter()
+        .map(|argument| {
+            atomic::extract_value(function_name, args, *argument, tcx).unwrap_or_else(|| {
+                panic!(
+                    "BUG: `{function_name}` should receive a constant value in the modelled range"
+                )
+            })
+        })
+        .collect();
+    if function_name.ends_with("::new") {
+        atomic::call_new(
+            function_name,
+            index,
+            values[0],
+            destination,
+            places,
+            net,
+            memory,
+        );
+        return;
+    }
+    atomic::call_operation(
+        function_name,
+        index,
+        args,
+        &values,
+        destination,
+        places,
+        net,
+        memory,
+    );
+}
+
+/// Call to a function that creates the future of an asynchronous operation on a `tokio` primitive,
+/// e.g. `tokio::sync::Mutex::<T>::lock`.
+/// Non-recursive call for the translation process.
+///
+/// Creating the future does not block and cannot panic, therefore the cleanup place is not modelled.
+/// The destination is linked to the primitive linked to the first argument (the self reference),
+/// so that the operation can be translated when the future is awaited.
+fn call_async_operation<'tcx>(
+    function_name: &str,
+    index: usize,
+    args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
+    destination: rustc_middle::mir::Place<'tcx>,
+    places: Places,
+    net: &mut PetriNet,
+    memory: &mut Memory<'tcx>,
+) {
+    call_foreign_function(function_name, index, places.ignore_cleanup_place(), net);
+    let self_ref = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+        panic!("BUG: `{function_name}` should receive the self reference as a place")
+    });
+    memory.link_place_to_same_value(destination, self_ref);
+    debug!("NEW FUTURE {destination:?} FOR AN OPERATION ON {self_ref:?}");
+}
+
+/// Checks whether the future polled by `std::future::Future::poll` is linked to a synchronization variable,
+/// i.e. it is the future of an asynchronous operation on a `tokio` primitive or a `tokio::task::JoinHandle`.
+pub fn is_awaited_sync_variable<'tcx>(
+    args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
+    memory: &Memory<'tcx>,
+) -> bool {
+    extract_nth_argument_as_place(args, 0)
+        .and_then(|future_ref| memory.find_value(&future_ref))
+        .is_some_and(|value| {
+            matches!(
+                value,
+                Value::Mutex(_)
+                    | Value::JoinHandle(_)
+                    | Value::Semaphore(_)
+                    | Value::Notify(_)
+                    | Value::Sender(_)
+                    | Value::Receiver(_)
+            )
+        })
+}
+
+/// Calls the corresponding handler for awaiting the future of an asynchronous operation
+/// on a `tokio` primitive, i.e. the call to `std::future::Future::poll`.
+/// The operation depends on the primitive linked to the future, see `is_awaited_sync_variable`.
+/// Awaiting a `tokio::task::JoinHandle` is handled by the caller like a call to `join`.
+pub fn call_await<'tcx>(
+    function_name: &str,
+    index: usize,
+    args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
+    destination: rustc_middle::mir::Place<'tcx>,
+    places: Places,
+    net: &mut PetriNet,
+    memory: &mut Memory<'tcx>,
+) {
+    let future_ref = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+        panic!("BUG: `{function_name}` should receive the pinned future as a place")
+    });
+    match memory.find_value(&future_ref) {
+        Some(Value::Mutex(_)) => {
+            mutex::call_lock(function_name, index, args, destination, places, net, memory);
+        }
+        Some(Value::Semaphore(_)) => {
+            semaphore::call_acquire(function_name, index, args, destination, places, net, memory);
+        }
+        Some(Value::Notify(_)) => {
+            notify::call_notified(function_name, index, args, places, net, memory);
+        }
+        Some(Value::Sender(_)) => {
+            channel::call_send(function_name, index, args, places, net, memory);
+        }
+        Some(Value::Receiver(_)) => {
+            channel::call_recv(function_name, index, args, places, net, memory);
+        }
+        _ => panic!("BUG: The future {future_ref:?} awaited in `{function_name}` should be linked to a supported primitive"),
+    }
+}
+
+/// Checks whether the variable to be dropped releases one of the supported `tokio` or `crossbeam_channel` primitives:
+/// Dropping a semaphore permit gives the permit back.
+/// Dropping a sender or a receiver marks its end of the channel as dropped.
+/// The futures returned by `send` and `recv` in `tokio` are linked to the channel too, but dropping them does nothing.
+pub fn handle_primitive_drop<'tcx>(
+    place: rustc_middle::mir::Place<'tcx>,
+    transition: &TransitionRef,
+    net: &mut PetriNet,
+    memory: &Memory<'tcx>,
+    caller_function_def_id: rustc_hir::def_id::DefId,
+    tcx: rustc_middle::ty::TyCtxt<'tcx>,
+) {
+    semaphore::handle_permit_drop(place, transition, net, memory);
+    if !check_substring_in_place_type(&place, "{async ", caller_function_def_id, tcx) {
+        channel::handle_end_drop(place, transition, net, memory);
+    }
 }
 
 /// Handles MIR assignments of the form: `_X = { copy_data: move _Y }`.
@@ -143,6 +604,13 @@ pub fn handle_aggregate_assignment<'tcx>(
 ///
 /// It also works for checking if a function argument is a sync variable
 /// and then linking the return value to the argument.
+///
+/// If `place_linked` itself is linked to a value, e.g. a field of a coroutine,
+/// `place_to_link` becomes an alias for it without looking into the base place.
+///
+/// It also works for a field of a tuple without a deref, e.g. `_X = move (_Y.0)`,
+/// and for the content of an enum variant, e.g. `_X = move ((_Y as Ready).0)`
+/// where `_Y` is the result of polling a future and is linked to the output of the future.
 pub fn link_if_sync_variable<'tcx>(
     place_to_link: &rustc_middle::mir::Place<'tcx>,
     place_linked: &rustc_middle::mir::Place<'tcx>,
@@ -153,7 +621,9 @@ pub fn link_if_sync_variable<'tcx>(
     if !check_if_sync_variable(place_to_link, caller_function_def_id, tcx) {
         return;
     }
-    if place_linked.is_indirect() {
+    if memory.is_linked(place_linked) {
+        memory.link_place_to_same_value(*place_to_link, *place_linked);
+    } else if place_linked.is_indirect() {
         // Checks if the place has a `ProjectionElem::Deref`
         let field_number = get_field_number_in_projection(place_linked);
         // Create a new place without the projections
@@ -162,11 +632,61 @@ pub fn link_if_sync_variable<'tcx>(
 
         debug!("ACCESS FIELD {field_number} AFTER DEREF IN BASE PLACE {base_place:?}");
         memory.link_field_in_aggregate(*place_to_link, base_place, field_number);
+    } else if !place_linked.projection.is_empty() {
+        let base_place = rustc_middle::mir::Place::from(place_linked.local);
+        if memory.is_aggregate(&base_place) {
+            let field_number = get_field_number_in_projection(place_linked);
+            debug!("ACCESS FIELD {field_number} IN BASE PLACE {base_place:?}");
+            memory.link_field_in_aggregate(*place_to_link, base_place, field_number);
+        } else if memory.is_linked(&base_place) {
+            debug!("ACCESS VARIANT IN BASE PLACE {base_place:?}");
+            memory.link_place_to_same_value(*place_to_link, base_place);
+        }
     } else {
         memory.link_place_to_same_value(*place_to_link, *place_linked);
     }
 }
 
+/// Links the aggregated value containing the sync variables captured by a closure
+/// to the memory of the closure, e.g. the function run by a new thread.
+/// Checks the debug info to detect places containing a synchronization variable captured by the closure.
+/// We are only interested in places of the form `_1.X` since the closure itself is the first argument.
+/// <https://doc.rust-lang.org/stable/nightly-rustc/rustc_middle/mir/struct.VarDebugInfo.html>
+///
+/// # Examples
+///
+/// The following line in the MIR output indicates that `_1.0` contains a mutex.
+/// `debug copy_data => (_1.0: std::sync::Arc<std::sync::Mutex<i32>>)`
+pub fn link_captured_sync_variables<'tcx>(
+    closure_def_id: rustc_hir::def_id::DefId,
+    aggregate: Vec<Value>,
+    memory: &mut Memory<'tcx>,
+    tcx: rustc_middle::ty::TyCtxt<'tcx>,
+) {
+    // Link the aggregate itself, the local _1
+    let base_place = rustc_middle::mir::Place {
+        local: rustc_middle::mir::Local::from_usize(1),
+        projection: rustc_middle::ty::List::empty(),
+    };
+    memory.link_aggregate(base_place, aggregate);
+    debug!("LINKED AGGREGATE VALUE {base_place:?} WITH THE CAPTURED SYNC VARIABLES");
+
+    let body = tcx.optimized_mir(closure_def_id);
+    for debug_info in &body.var_debug_info {
+        let rustc_middle::mir::VarDebugInfoContents::Place(place) = debug_info.value else {
+            // Not interested in the other variants of `VarDebugInfoContents`
+            continue;
+        };
+        if place.local != rustc_middle::mir::Local::from(1u32) {
+            // Not interested in locals other that `_1.X`
+            continue;
+        }
+        let field_number = get_field_number_in_projection(&place);
+        memory.link_field_in_aggregate(place, base_place, field_number);
+        debug!("LINKED FIELD {place:?} IN AGGREGATE",);
+    }
+}
+
 /// Checks if the first argument for a function call contains a mutex, a mutex guard,
 /// a join handle or a condition variable, i.e. a synchronization variable.
 /// If the first argument contains a synchronization variable, links it to the return value.
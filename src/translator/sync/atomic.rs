@@ -0,0 +1,493 @@
+//! Representation of an atomic variable (`std::sync::atomic::AtomicBool` or an atomic integer) in the Petri net.
+//!
+//! The atomic variable stores one place for each value that it may hold.
+//! Exactly one of them has a token at any time.
+//! An `AtomicBool` holds two values, an atomic integer holds the values from 0 to `INTEGER_VALUES - 1`.
+//! The initial value and the values written to the variable must be constants in this range.
+//!
+//! A call to `load`, `store`, `swap`, `compare_exchange` or `compare_exchange_weak` is translated
+//! as one transition for each value that the call may find in the variable.
+//! All of them connect the start place and the end place of the call, i.e. exactly one of them fires.
+//! `compare_exchange_weak` is modelled like `compare_exchange`, it never fails spuriously.
+//!
+//! The value returned by `load`, `swap` and the compare-exchange operations is linked to these transitions,
+//! see `AtomicValue`. When a switch int terminator tests the value directly,
+//! each transition passes a token to the target that corresponds to the value it found.
+//! A branch that leads back to the switch while another branch does not is the body of a spin loop
+//! if it only spins, i.e. the blocks on its path back to the switch do nothing but call `std::hint::spin_loop`
+//! or `std::thread::yield_now` and read the variable again, see `is_spin_loop_body`.
+//! Instead of spinning, the call waits until the variable holds a value that leaves the loop.
+//! This turns a thread that waits forever for a flag that nobody sets into a deadlock.
+//! Any other branch that loops back is translated like a normal branch guarded by the value.
+
+use log::debug;
+use std::cell::OnceCell;
+use std::rc::Rc;
+
+use crate::data_structures::petri_net_interface::{
+    add_arc_place_transition, add_arc_transition_place, connect_places,
+};
+use crate::data_structures::petri_net_interface::{PetriNet, PlaceRef, TransitionRef};
+use crate::naming::atomic::{tested_value_place_label, value_place_label, value_transition_label};
+use crate::translator::function::Places;
+use crate::translator::mir_function::memory::{AtomicRef, AtomicValueRef, Memory};
+use crate::translator::special_function::call_foreign_function;
+use crate::utils::{evaluate_constant_as_integer, extract_nth_argument_as_place};
+
+/// The number of values modelled for an atomic integer.
+pub const INTEGER_VALUES: usize = 4;
+
+/// The types of the supported atomic variables.
+pub const ATOMIC_TYPES: [&str; 11] = [
+    "std::sync::atomic::AtomicBool",
+    "std::sync::atomic::AtomicI8",
+    "std::sync::atomic::AtomicI16",
+    "std::sync::atomic::AtomicI32",
+    "std::sync::atomic::AtomicI64",
+    "std::sync::atomic::AtomicIsize",
+    "std::sync::atomic::AtomicU8",
+    "std::sync::atomic::AtomicU16",
+    "std::sync::atomic::AtomicU32",
+    "std::sync::atomic::AtomicU64",
+    "std::sync::atomic::AtomicUsize",
+];
+
+/// The supported methods of the atomic variables.
+const ATOMIC_METHODS: [&str; 6] = [
+    "new",
+    "load",
+    "store",
+    "swap",
+    "compare_exchange",
+    "compare_exchange_weak",
+];
+
+#[derive(PartialEq, Eq)]
+pub struct Atomic {
+    index: usize,
+    values: Vec<PlaceRef>,
+}
+
+impl Atomic {
+    /// Creates a new atomic variable of the given type whose label is based on `index`.
+    /// Adds one place for each of the `number_of_values` to the Petri net.
+    /// The place of the `initial_value` gets a token.
+    pub fn new(
+        atomic_type: &str,
+        index: usize,
+        number_of_values: usize,
+        initial_value: usize,
+        net: &mut PetriNet,
+    ) -> Self {
+        let values: Vec<PlaceRef> = (0..number_of_values)
+            .map(|value| net.add_place(&value_place_label(atomic_type, index, value)))
+            .collect();
+        net.add_token(&values[initial_value], 1).expect(
+            "BUG: Adding the initial token to the atomic variable should not cause an overflow",
+        );
+
+        Self { index, values }
+    }
+
+    /// Returns the index of the atomic variable used in the labels of the Petri net.
+    pub const fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Adds the arcs for a transition that finds `old_value` in the variable and writes `new_value`.
+    fn add_write_arcs(
+        &self,
+        old_value: usize,
+        new_value: usize,
+        transition: &TransitionRef,
+        net: &mut PetriNet,
+    ) {
+        add_arc_place_transition(net, &self.values[old_value], transition);
+        add_arc_transition_place(net, transition, &self.values[new_value]);
+    }
+}
+
+/// A value returned by a call on an atomic variable, or a value computed directly from it,
+/// e.g. its negation or its comparison with a constant.
+///
+/// It keeps the transitions of the call, one for each value found in the variable,
+/// and the outcome of each transition, i.e. the value that a switch int terminator sees.
+/// Only the first switch int terminator that tests the value is guarded.
+///
+/// The index of the operation selected by `crossbeam_channel::Select::<'a>::select` is represented the same way.
+/// Looping back to the switch does not change it, therefore it never guards a spin loop.
+#[derive(PartialEq, Eq)]
+pub struct AtomicValue {
+    transitions: Rc<Vec<TransitionRef>>,
+    outcomes: Vec<u128>,
+    guarded: Rc<OnceCell<()>>,
+    spin_loops: bool,
+}
+
+impl AtomicValue {
+    /// Creates a new value computed by the given `transitions` with the given `outcomes`.
+    fn new(transitions: Vec<TransitionRef>, outcomes: Vec<u128>) -> Self {
+        Self {
+            transitions: Rc::new(transitions),
+            outcomes,
+            guarded: Rc::new(OnceCell::new()),
+            spin_loops: true,
+        }
+    }
+
+    /// Creates a new value computed by the given `transitions` with the given `outcomes`
+    /// that does not change while looping, i.e. a loop that tests it is never a spin loop.
+    pub fn new_without_spin_loops(transitions: Vec<TransitionRef>, outcomes: Vec<u128>) -> Self {
+        Self {
+            spin_loops: false,
+            ..Self::new(transitions, outcomes)
+        }
+    }
+
+    /// Checks whether a loop that tests this value may spin until the value changes.
+    pub const fn allows_spin_loops(&self) -> bool {
+        self.spin_loops
+    }
+
+    /// Creates a new value computed from this value by applying `operation` to each outcome.
+    /// The new value shares the transitions with this value.
+    pub fn map(&self, operation: impl Fn(u128) -> u128) -> Self {
+        Self {
+            transitions: Rc::clone(&self.transitions),
+            outcomes: self
+                .outcomes
+                .iter()
+                .map(|outcome| operation(*outcome))
+                .collect(),
+            guarded: Rc::clone(&self.guarded),
+            spin_loops: self.spin_loops,
+        }
+    }
+
+    /// Guards the transitions of a switch int terminator that tests this value.
+    ///
+    /// - Creates one place for each target of the switch and connects it to the transition of the target.
+    /// - Connects each transition of the call to the place of the target that its outcome selects.
+    /// - If the target is one of the `spin_targets`, i.e. the body of a spin loop, the place never gets a token
+    ///   and disables the transition of the call instead.
+    ///
+    /// Does nothing if the value was already guarded.
+    pub fn guard_switch(
+        &self,
+        function_name: &str,
+        block: rustc_middle::mir::BasicBlock,
+        targets: &rustc_middle::mir::SwitchTargets,
+        spin_targets: &[rustc_middle::mir::BasicBlock],
+        transitions: &[TransitionRef],
+        net: &mut PetriNet,
+    ) {
+        if self.guarded.set(()).is_err() {
+            return;
+        }
+        let mut tested_value_places: Vec<(rustc_middle::mir::BasicBlock, PlaceRef)> = Vec::new();
+        for (target, transition) in targets.all_targets().iter().zip(transitions) {
+            if tested_value_places.iter().any(|(other, _)| other == target) {
+                continue;
+            }
+            let label = tested_value_place_label(function_name, block.index(), target.index());
+            let place = net.add_place(&label);
+            add_arc_place_transition(net, &place, transition);
+            tested_value_places.push((*target, place));
+        }
+        for (transition, outcome) in self.transitions.iter().zip(&self.outcomes) {
+            let target = targets.target_for_value(*outcome);
+            let (_, place) = tested_value_places
+                .iter()
+                .find(|(other, _)| *other == target)
+                .expect("BUG: Every target of the switch should have a place for the tested value");
+            if spin_targets.contains(&target) {
+                debug!("SPIN LOOP: {transition} WAITS INSTEAD OF JUMPING BACK TO {target:?}");
+                add_arc_place_transition(net, place, transition);
+            } else {
+                add_arc_transition_place(net, transition, place);
+            }
+        }
+    }
+}
+
+/// Checks whether the function is one of the supported methods of the atomic variables,
+/// e.g. `std::sync::atomic::AtomicBool::load`.
+pub fn is_atomic_function(function_name: &str) -> bool {
+    function_name
+        .rsplit_once("::")
+        .is_some_and(|(atomic_type, method)| {
+            ATOMIC_TYPES.contains(&atomic_type) && ATOMIC_METHODS.contains(&method)
+        })
+}
+
+/// Checks whether the branch starting at `target` is the body of a spin loop on the switch in `switch_block`:
+/// Every block on the path from `target` back to the switch only calls `std::hint::spin_loop`,
+/// `std::thread::yield_now` or reads the atomic variable again, e.g. with `load` or `compare_exchange`.
+/// The cleanup blocks for unwinding out of these calls are not part of the loop.
+pub fn is_spin_loop_body(
+    target: rustc_middle::mir::BasicBlock,
+    switch_block: rustc_middle::mir::BasicBlock,
+    body: &rustc_middle::mir::Body,
+    tcx: rustc_middle::ty::TyCtxt,
+) -> bool {
+    let mut visited = std::collections::HashSet::new();
+    let mut pending = vec![target];
+    while let Some(block) = pending.pop() {
+        if block == switch_block || !visited.insert(block) {
+            continue;
+        }
+        let data = &body.basic_blocks[block];
+        if data.is_cleanup {
+            continue;
+        }
+        let only_spins = match &data.terminator().kind {
+            rustc_middle::mir::TerminatorKind::Goto { .. } => true,
+            rustc_middle::mir::TerminatorKind::Call { func, .. } => func
+                .const_fn_def()
+                .is_some_and(|(def_id, _)| is_spin_loop_call(&tcx.def_path_str(def_id))),
+            _ => false,
+        };
+        if !only_spins {
+            return false;
+        }
+        pending.extend(data.terminator().successors());
+    }
+    true
+}
+
+/// Checks whether the function may be called in the body of a spin loop:
+/// A hint to the processor or the scheduler, or an operation that reads the atomic variable again,
+/// including the dereference of the `std::sync::Arc` that holds it.
+fn is_spin_loop_call(function_name: &str) -> bool {
+    matches!(
+        function_name,
+        "std::hint::spin_loop"
+            | "std::thread::yield_now"
+            | "std::ops::Deref::deref"
+            | "std::result::Result::<T, E>::is_err"
+            | "std::result::Result::<T, E>::is_ok"
+    ) || (is_atomic_function(function_name)
+        && !matches!(function_name.rsplit_once("::"), Some((_, "new" | "store"))))
+}
+
+/// Returns the type of the atomic variable on which the function is called,
+/// e.g. `std::sync::atomic::AtomicBool` for `std::sync::atomic::AtomicBool::load`.
+fn atomic_type(function_name: &str) -> &str {
+    function_name
+        .rsplit_once("::")
+        .map_or(function_name, |(atomic_type, _)| atomic_type)
+}
+
+/// Returns the number of values modelled for the atomic variable on which the function is called.
+pub fn number_of_values(function_name: &str) -> usize {
+    if function_name.starts_with("std::sync::atomic::AtomicBool::") {
+        2
+    } else {
+        INTEGER_VALUES
+    }
+}
+
+/// Returns the positions of the arguments that are written to the atomic variable.
+/// The compare-exchange operations receive the expected value first and then the new value.
+pub fn value_arguments(function_name: &str) -> &'static [usize] {
+    match function_name.rsplit_once("::").map(|(_, method)| method) {
+        Some("new") => &[0],
+        Some("store" | "swap") => &[1],
+        Some("compare_exchange" | "compare_exchange_weak") => &[1, 2],
+        _ => &[],
+    }
+}
+
+/// Extracts the value passed to a function on an atomic variable as the n-th argument.
+/// Returns `None` if the argument is not a constant in the range of modelled values.
+pub fn extract_value<'tcx>(
+    function_name: &str,
+    args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
+    index: usize,
+    tcx: rustc_middle::ty::TyCtxt<'tcx>,
+) -> Option<usize> {
+    let value = evaluate_constant_as_integer(&args.get(index)?.node, tcx)?;
+    usize::try_from(value)
+        .ok()
+        .filter(|value| *value < number_of_values(function_name))
+}
+
+/// Call to `new` on an atomic variable.
+/// Non-recursive call for the translation process.
+///
+/// - Creates a new `Atomic` that holds the initial value.
+/// - Links the return place to the `Atomic`.
+pub fn call_new<'tcx>(
+    function_name: &str,
+    index: usize,
+    initial_value: usize,
+    destination: rustc_middle::mir::Place<'tcx>,
+    places: Places,
+    net: &mut PetriNet,
+    memory: &mut Memory<'tcx>,
+) {
+    call_foreign_function(function_name, index, places, net);
+    let atomic = Atomic::new(
+        atomic_type(function_name),
+        index,
+        number_of_values(function_name),
+        initial_value,
+        net,
+    );
+    memory.link_atomic(destination, atomic);
+    debug!("NEW ATOMIC WITH VALUE {initial_value}: {destination:?}");
+}
+
+/// Call to `load`, `store`, `swap`, `compare_exchange` or `compare_exchange_weak` on an atomic variable.
+/// Non-recursive call for the translation process.
+///
+/// - Retrieves the atomic variable linked to the first argument (the self reference).
+/// - Connects the start place and the end place through one transition for each value
+///   that the call may find in the variable.
+/// - Adds the arcs that read the value and write the new value (if any) to each transition.
+/// - Links the return place to an `AtomicValue`, except for `store`.
+///
+/// `values` are the constants passed to the function, see `value_arguments`.
+/// Atomic operations never unwind, therefore the cleanup place is not modelled.
+#[allow(clippy::too_many_arguments)]
+pub fn call_operation<'tcx>(
+    function_name: &str,
+    index: usize,
+    args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
+    values: &[usize],
+    destination: rustc_middle::mir::Place<'tcx>,
+    places: Places,
+    net: &mut PetriNet,
+    memory: &mut Memory<'tcx>,
+) {
+    let (start_place, end_place) = places.ignore_cleanup_place().get_start_end_place();
+    let self_ref = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+        panic!("BUG: `{function_name}` should receive the self reference as a place")
+    });
+    let atomic_ref: AtomicRef = memory.get_atomic(&self_ref).clone();
+    let method = function_name
+        .rsplit_once("::")
+        .map_or(function_name, |(_, method)| method);
+
+    let mut transitions = Vec::new();
+    let mut outcomes = Vec::new();
+    for old_value in 0..atomic_ref.values.len() {
+        let label = value_transition_label(function_name, index, old_value);
+        let transition = connect_places(net, &start_place, &end_place, &label);
+        // The value written and the value returned, where the result of
+        // a compare-exchange operation is the discriminant of `Ok` or `Err`.
+        let (new_value, outcome) = match (method, values) {
+            ("store" | "swap", [new_value]) => (*new_value, old_value as u128),
+            (_, [current, new_value]) if *current == old_value => (*new_value, 0),
+            (_, [_, _]) => (old_value, 1),
+            _ => (old_value, old_value as u128),
+        };
+        atomic_ref.add_write_arcs(old_value, new_value, &transition, net);
+        transitions.push(transition);
+        outcomes.push(outcome);
+    }
+    if method != "store" {
+        memory.link_atomic_value(destination, AtomicValue::new(transitions, outcomes));
+    }
+    debug!(
+        "{} ON ATOMIC {} WITH VALUES {values:?}",
+        method.to_uppercase(),
+        atomic_ref.index()
+    );
+}
+
+/// Handles MIR assignments that compute a new value directly from the value returned by a call on an atomic variable:
+/// - `_X = _Y`, `_X = &_Y` and `_X = discriminant(_Y)` keep the value.
+/// - `_X = Not(_Y)` negates a boolean.
+/// - `_X = Eq(_Y, const C)` and the other comparisons with a constant compute a boolean.
+///
+/// Other assignments are not tracked, a switch int terminator that tests the result is not guarded.
+pub fn handle_atomic_value_assignment<'tcx>(
+    place: &rustc_middle::mir::Place<'tcx>,
+    rvalue: &rustc_middle::mir::Rvalue<'tcx>,
+    memory: &mut Memory<'tcx>,
+    tcx: rustc_middle::ty::TyCtxt<'tcx>,
+) {
+    use rustc_middle::mir::{BinOp, Operand, Rvalue, UnOp};
+
+    let find = |operand: &Operand<'tcx>, memory: &Memory<'tcx>| -> Option<AtomicValueRef> {
+        let rhs = operand.place()?;
+        memory.find_atomic_value(&rhs).cloned().or_else(|| {
+            // The value stored in a mutex is read through the reference returned by `deref`.
+            (rhs.projection.as_slice() == [rustc_middle::mir::ProjectionElem::Deref])
+                .then(|| memory.find_atomic_value(&rhs.local.into()).cloned())
+                .flatten()
+        })
+    };
+    let value = match rvalue {
+        Rvalue::Use(operand) => find(operand, memory).map(|value| value.map(|outcome| outcome)),
+        Rvalue::Ref(_, _, rhs) | Rvalue::Discriminant(rhs) => memory
+            .find_atomic_value(rhs)
+            .map(|value| value.map(|outcome| outcome)),
+        Rvalue::UnaryOp(UnOp::Not, operand) => find(operand, memory)
+            .filter(|value| value.outcomes.iter().all(|outcome| *outcome <= 1))
+            .map(|value| value.map(|outcome| outcome ^ 1)),
+        Rvalue::BinaryOp(operation, operands) => {
+            let (lhs, rhs) = &**operands;
+            let (value, constant, swapped) = match (find(lhs, memory), find(rhs, memory)) {
+                (Some(value), None) => (value, evaluate_constant_as_integer(rhs, tcx), false),
+                (None, Some(value)) => (value, evaluate_constant_as_integer(lhs, tcx), true),
+                _ => return,
+            };
+            let Some(constant) = constant else {
+                return;
+            };
+            let compare: fn(&i128, &i128) -> bool = match operation {
+                BinOp::Eq => i128::eq,
+                BinOp::Ne => i128::ne,
+                BinOp::Lt => i128::lt,
+                BinOp::Le => i128::le,
+                BinOp::Gt => i128::gt,
+                BinOp::Ge => i128::ge,
+                _ => return,
+            };
+            Some(value.map(|outcome| {
+                let outcome = outcome as i128;
+                let result = if swapped {
+                    compare(&constant, &outcome)
+                } else {
+                    compare(&outcome, &constant)
+                };
+                u128::from(result)
+            }))
+        }
+        _ => None,
+    };
+    if let Some(value) = value {
+        memory.link_atomic_value(*place, value);
+        debug!("ATOMIC VALUE COMPUTED IN {place:?}");
+    }
+}
+
+/// Call to `std::result::Result::<T, E>::is_ok` or `std::result::Result::<T, E>::is_err`
+/// on the result of a compare-exchange operation.
+/// Links the return place to a boolean computed from the discriminant of the result.
+///
+/// # Panics
+///
+/// If the first argument is not linked to a value returned by a call on an atomic variable,
+/// then the function panics.
+pub fn link_result_check<'tcx>(
+    function_name: &str,
+    args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
+    destination: rustc_middle::mir::Place<'tcx>,
+    memory: &mut Memory<'tcx>,
+) {
+    let value = extract_nth_argument_as_place(args, 0)
+        .and_then(|self_ref| memory.find_atomic_value(&self_ref).cloned())
+        .unwrap_or_else(|| {
+            panic!("BUG: `{function_name}` should receive a value loaded from an atomic variable")
+        });
+    let value = if function_name == "std::result::Result::<T, E>::is_ok" {
+        value.map(|outcome| u128::from(outcome == 0))
+    } else {
+        value.map(|outcome| u128::from(outcome != 0))
+    };
+    memory.link_atomic_value(destination, value);
+    debug!("{function_name} ON ATOMIC VALUE: {destination:?}");
+}
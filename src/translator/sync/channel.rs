@@ -0,0 +1,423 @@
+//! Representation of a channel (`tokio::sync::mpsc` or `crossbeam_channel`) in the Petri net.
+//!
+//! The channel is modelled with four places:
+//! - `capacity` has one token for each free slot in the buffer. It starts with the capacity of the channel,
+//!   which must be a constant. An unbounded channel does not have this place.
+//! - `messages` has one token for each message in the buffer.
+//! - `sender_dropped` and `receiver_dropped` receive a token when a sender or the receiver is dropped.
+//!
+//! Sending moves a token from `capacity` to `messages`, i.e. it blocks while the buffer is full.
+//! Receiving moves a token from `messages` back to `capacity`, i.e. it blocks while the buffer is empty.
+//! Both have a second transition that returns immediately once the other end of the channel was dropped.
+//! In `tokio` this happens when the future returned by `send` or `recv` is awaited,
+//! in `crossbeam_channel` the calls themselves block.
+//!
+//! A zero-capacity channel (`crossbeam_channel::bounded(0)`) hands each message over directly.
+//! Whichever end comes first waits for the other one:
+//! - A waiting sender puts the message in `messages` and waits until a receiver takes it,
+//!   which puts a token in the place `received`.
+//! - A waiting receiver puts a token in `waiting_receivers` and waits until a sender consumes it,
+//!   which puts the message in the place `delivered`.
+//!
+//! If the other end is dropped in the meantime, the waiting end withdraws its token and returns.
+//! An operation inside `crossbeam_channel::Select` never waits in a place of its own,
+//! it completes in a single transition with an end that is already waiting, see `Channel::connect_selected_send`.
+//!
+//! The receiver returns `None` or an error once every sender has been dropped.
+//! Counting the senders requires a test for zero, which Petri nets cannot express.
+//! Instead, the receiver may observe a closed channel after any sender was dropped.
+//! This is exact for a channel with a single sender.
+//!
+//! The sender and the receiver simply contain a reference to the corresponding channel.
+
+use log::debug;
+
+use crate::data_structures::petri_net_interface::{
+    add_arc_place_transition, add_arc_transition_place, connect_places,
+};
+use crate::data_structures::petri_net_interface::{PetriNet, PlaceRef, TransitionRef};
+use crate::naming::channel::{
+    closed_transition_label, delivered_transition_label, dropped_place_labels,
+    offer_transition_label, operation_label, place_labels, received_place_label,
+    receiver_waiting_place_label, rendezvous_transition_labels, transfer_transition_label,
+    waiting_place_label, waiting_receivers_place_labels,
+};
+use crate::translator::function::Places;
+use crate::translator::mir_function::memory::{ChannelRef, Memory, Value};
+use crate::translator::special_function::call_foreign_function;
+use crate::utils::extract_nth_argument_as_place;
+
+/// The functions that create a channel.
+pub const CHANNEL_CONSTRUCTORS: [&str; 3] = [
+    "crossbeam_channel::bounded",
+    "crossbeam_channel::unbounded",
+    "tokio::sync::mpsc::channel",
+];
+
+/// The places of a zero-capacity channel that model the direct handover of the messages.
+#[derive(PartialEq, Eq)]
+struct Rendezvous {
+    received: PlaceRef,
+    waiting_receivers: PlaceRef,
+    delivered: PlaceRef,
+}
+
+#[derive(PartialEq, Eq)]
+pub struct Channel {
+    index: usize,
+    capacity: Option<PlaceRef>,
+    messages: PlaceRef,
+    rendezvous: Option<Rendezvous>,
+    sender_dropped: PlaceRef,
+    receiver_dropped: PlaceRef,
+}
+
+impl Channel {
+    /// Creates a new channel created by `constructor` whose label is based on `index`.
+    /// Adds the places of the channel to the Petri net.
+    /// A capacity of `None` creates an unbounded channel, a capacity of zero a zero-capacity channel.
+    pub fn new(
+        constructor: &str,
+        index: usize,
+        capacity: Option<usize>,
+        net: &mut PetriNet,
+    ) -> Self {
+        let (capacity_label, messages_label) = place_labels(constructor, index);
+        let (sender_dropped_label, receiver_dropped_label) =
+            dropped_place_labels(constructor, index);
+        let capacity_place = capacity.filter(|capacity| *capacity > 0).map(|capacity| {
+            let capacity_place = net.add_place(&capacity_label);
+            net.add_token(&capacity_place, capacity).expect(
+                "BUG: Adding the initial capacity to the channel should not cause an overflow",
+            );
+            capacity_place
+        });
+        let messages = net.add_place(&messages_label);
+        let rendezvous = (capacity == Some(0)).then(|| {
+            let (waiting_receivers_label, delivered_label) =
+                waiting_receivers_place_labels(constructor, index);
+            Rendezvous {
+                received: net.add_place(&received_place_label(constructor, index)),
+                waiting_receivers: net.add_place(&waiting_receivers_label),
+                delivered: net.add_place(&delivered_label),
+            }
+        });
+
+        Self {
+            index,
+            capacity: capacity_place,
+            messages,
+            rendezvous,
+            sender_dropped: net.add_place(&sender_dropped_label),
+            receiver_dropped: net.add_place(&receiver_dropped_label),
+        }
+    }
+
+    /// Returns the index of the channel used in the labels of the Petri net.
+    pub const fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Connects the start place and the end place through the transitions that send a message:
+    /// - The transitions that send without waiting, see `Channel::connect_selected_send`.
+    /// - On a zero-capacity channel, the transition that offers the message if no receiver is waiting.
+    ///   It leads to a place where the sender waits until a receiver takes the message or the receiver is dropped.
+    ///
+    /// Returns the transitions that end the operation.
+    pub fn connect_send(
+        &self,
+        operation: &str,
+        start_place: &PlaceRef,
+        end_place: &PlaceRef,
+        net: &mut PetriNet,
+    ) -> Vec<TransitionRef> {
+        let mut transitions = self.connect_selected_send(operation, start_place, end_place, net);
+        let Some(rendezvous) = &self.rendezvous else {
+            return transitions;
+        };
+        let waiting_place = net.add_place(&waiting_place_label(operation));
+        let offer_transition = connect_places(
+            net,
+            start_place,
+            &waiting_place,
+            &offer_transition_label(operation),
+        );
+        add_arc_transition_place(net, &offer_transition, &self.messages);
+
+        let (received_label, waiting_closed_label) = rendezvous_transition_labels(operation);
+        let received_transition = connect_places(net, &waiting_place, end_place, &received_label);
+        add_arc_place_transition(net, &rendezvous.received, &received_transition);
+        let waiting_closed_transition =
+            connect_places(net, &waiting_place, end_place, &waiting_closed_label);
+        add_arc_place_transition(net, &self.messages, &waiting_closed_transition);
+        add_arc_place_transition(net, &self.receiver_dropped, &waiting_closed_transition);
+        add_arc_transition_place(net, &waiting_closed_transition, &self.receiver_dropped);
+        transitions.extend([received_transition, waiting_closed_transition]);
+        transitions
+    }
+
+    /// Connects the start place and the end place through the transitions that send a message without waiting,
+    /// which is how a send registered in a `crossbeam_channel::Select` completes:
+    /// - The transition that takes a free slot, if the channel is bounded, and puts a message in the buffer.
+    ///   On a zero-capacity channel, it consumes a waiting receiver and hands the message over to it instead.
+    /// - The transition that may only fire if the receiver was dropped and regenerates the token.
+    ///
+    /// Returns the transitions that end the operation.
+    pub fn connect_selected_send(
+        &self,
+        operation: &str,
+        start_place: &PlaceRef,
+        end_place: &PlaceRef,
+        net: &mut PetriNet,
+    ) -> Vec<TransitionRef> {
+        let send_transition = connect_places(
+            net,
+            start_place,
+            end_place,
+            &transfer_transition_label(operation),
+        );
+        let closed_transition = connect_places(
+            net,
+            start_place,
+            end_place,
+            &closed_transition_label(operation),
+        );
+        if let Some(capacity) = &self.capacity {
+            add_arc_place_transition(net, capacity, &send_transition);
+        }
+        if let Some(rendezvous) = &self.rendezvous {
+            add_arc_place_transition(net, &rendezvous.waiting_receivers, &send_transition);
+            add_arc_transition_place(net, &send_transition, &rendezvous.delivered);
+        } else {
+            add_arc_transition_place(net, &send_transition, &self.messages);
+        }
+        add_arc_place_transition(net, &self.receiver_dropped, &closed_transition);
+        add_arc_transition_place(net, &closed_transition, &self.receiver_dropped);
+        vec![send_transition, closed_transition]
+    }
+
+    /// Connects the start place and the end place through the transitions that receive a message:
+    /// - The transitions that receive without waiting, see `Channel::connect_selected_receive`.
+    /// - On a zero-capacity channel, the transition that announces a waiting receiver if no sender is waiting.
+    ///   It leads to a place where the receiver waits until a sender hands over a message,
+    ///   a sender offers a message or every sender is dropped.
+    ///
+    /// Returns the transitions that end the operation.
+    pub fn connect_receive(
+        &self,
+        operation: &str,
+        start_place: &PlaceRef,
+        end_place: &PlaceRef,
+        net: &mut PetriNet,
+    ) -> Vec<TransitionRef> {
+        let mut transitions = self.connect_selected_receive(operation, start_place, end_place, net);
+        let Some(rendezvous) = &self.rendezvous else {
+            return transitions;
+        };
+        let waiting_place = net.add_place(&receiver_waiting_place_label(operation));
+        let offer_transition = connect_places(
+            net,
+            start_place,
+            &waiting_place,
+            &offer_transition_label(operation),
+        );
+        add_arc_transition_place(net, &offer_transition, &rendezvous.waiting_receivers);
+
+        let delivered_transition = connect_places(
+            net,
+            &waiting_place,
+            end_place,
+            &delivered_transition_label(operation),
+        );
+        add_arc_place_transition(net, &rendezvous.delivered, &delivered_transition);
+        // A sender that started waiting after this receiver: Withdraw the waiting receiver and take the message.
+        let (received_label, waiting_closed_label) = rendezvous_transition_labels(operation);
+        let received_transition = connect_places(net, &waiting_place, end_place, &received_label);
+        add_arc_place_transition(net, &rendezvous.waiting_receivers, &received_transition);
+        add_arc_place_transition(net, &self.messages, &received_transition);
+        add_arc_transition_place(net, &received_transition, &rendezvous.received);
+        let waiting_closed_transition =
+            connect_places(net, &waiting_place, end_place, &waiting_closed_label);
+        add_arc_place_transition(
+            net,
+            &rendezvous.waiting_receivers,
+            &waiting_closed_transition,
+        );
+        add_arc_place_transition(net, &self.sender_dropped, &waiting_closed_transition);
+        add_arc_transition_place(net, &waiting_closed_transition, &self.sender_dropped);
+        transitions.extend([
+            delivered_transition,
+            received_transition,
+            waiting_closed_transition,
+        ]);
+        transitions
+    }
+
+    /// Connects the start place and the end place through the transitions that receive a message without waiting,
+    /// which is how a receive registered in a `crossbeam_channel::Select` completes:
+    /// - The transition that takes a message from the buffer and frees the slot, if the channel is bounded.
+    ///   On a zero-capacity channel, it lets the waiting sender return instead.
+    /// - The transition that may only fire if a sender was dropped and regenerates the token.
+    ///
+    /// Returns the transitions that end the operation.
+    pub fn connect_selected_receive(
+        &self,
+        operation: &str,
+        start_place: &PlaceRef,
+        end_place: &PlaceRef,
+        net: &mut PetriNet,
+    ) -> Vec<TransitionRef> {
+        let receive_transition = connect_places(
+            net,
+            start_place,
+            end_place,
+            &transfer_transition_label(operation),
+        );
+        let closed_transition = connect_places(
+            net,
+            start_place,
+            end_place,
+            &closed_transition_label(operation),
+        );
+        add_arc_place_transition(net, &self.messages, &receive_transition);
+        if let Some(capacity) = &self.capacity {
+            add_arc_transition_place(net, &receive_transition, capacity);
+        }
+        if let Some(rendezvous) = &self.rendezvous {
+            add_arc_transition_place(net, &receive_transition, &rendezvous.received);
+        }
+        add_arc_place_transition(net, &self.sender_dropped, &closed_transition);
+        add_arc_transition_place(net, &closed_transition, &self.sender_dropped);
+        vec![receive_transition, closed_transition]
+    }
+}
+
+/// Call to `tokio::sync::mpsc::channel`, `crossbeam_channel::bounded` or `crossbeam_channel::unbounded`.
+/// Non-recursive call for the translation process.
+///
+/// - Creates a new `Channel` with the given capacity, `None` for an unbounded channel.
+/// - Links the return place to an aggregate containing the sender and the receiver.
+pub fn call_channel<'tcx>(
+    function_name: &str,
+    index: usize,
+    capacity: Option<usize>,
+    destination: rustc_middle::mir::Place<'tcx>,
+    places: Places,
+    net: &mut PetriNet,
+    memory: &mut Memory<'tcx>,
+) {
+    call_foreign_function(function_name, index, places, net);
+    let channel_ref = std::rc::Rc::new(Channel::new(function_name, index, capacity, net));
+    // The return value is the tuple `(Sender<T>, Receiver<T>)`.
+    memory.link_aggregate(
+        destination,
+        vec![
+            Value::Sender(channel_ref.clone()),
+            Value::Receiver(channel_ref),
+        ],
+    );
+    debug!("NEW CHANNEL WITH CAPACITY {capacity:?}: {destination:?}");
+}
+
+/// Call to `crossbeam_channel::Sender::<T>::send` or to `std::future::Future::poll`
+/// on the future returned by `tokio::sync::mpsc::Sender::<T>::send`, i.e. awaiting the message to be sent.
+/// Non-recursive call for the translation process.
+///
+/// - Retrieves the channel linked to the first argument (the sender or the pinned future).
+/// - Connects the start place and the end place through the transitions that send the message,
+///   see `Channel::connect_send`.
+///
+/// Sending never unwinds, therefore the cleanup place is not modelled.
+pub fn call_send<'tcx>(
+    function_name: &str,
+    index: usize,
+    args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
+    places: Places,
+    net: &mut PetriNet,
+    memory: &Memory<'tcx>,
+) {
+    let channel_ref = get_channel_from_first_argument(function_name, args, memory);
+    let (start_place, end_place) = places.get_start_end_place();
+    let operation = operation_label(function_name, index);
+    channel_ref.connect_send(&operation, &start_place, &end_place, net);
+    debug!(
+        "SEND ON CHANNEL {} DUE TO CALL {operation}",
+        channel_ref.index()
+    );
+}
+
+/// Call to `crossbeam_channel::Receiver::<T>::recv` or to `std::future::Future::poll`
+/// on the future returned by `tokio::sync::mpsc::Receiver::<T>::recv`, i.e. awaiting the next message.
+/// Non-recursive call for the translation process.
+///
+/// - Retrieves the channel linked to the first argument (the receiver or the pinned future).
+/// - Connects the start place and the end place through the transitions that receive the message,
+///   see `Channel::connect_receive`.
+///
+/// Receiving never unwinds, therefore the cleanup place is not modelled.
+pub fn call_recv<'tcx>(
+    function_name: &str,
+    index: usize,
+    args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
+    places: Places,
+    net: &mut PetriNet,
+    memory: &Memory<'tcx>,
+) {
+    let channel_ref = get_channel_from_first_argument(function_name, args, memory);
+    let (start_place, end_place) = places.get_start_end_place();
+    let operation = operation_label(function_name, index);
+    channel_ref.connect_receive(&operation, &start_place, &end_place, net);
+    debug!(
+        "RECEIVE ON CHANNEL {} DUE TO CALL {operation}",
+        channel_ref.index()
+    );
+}
+
+/// Returns the channel linked to the first argument of a call on one end of the channel.
+fn get_channel_from_first_argument<'tcx>(
+    function_name: &str,
+    args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
+    memory: &Memory<'tcx>,
+) -> ChannelRef {
+    let self_ref = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+        panic!("BUG: `{function_name}` should receive the end of the channel as a place")
+    });
+    memory.get_channel(&self_ref).clone()
+}
+
+/// Checks whether the variable to be dropped is a sender or a receiver.
+/// If that is the case, adds an arc to the place that marks the end as dropped.
+/// Dropping an aggregate, e.g. a closure, drops the senders and receivers that it contains.
+/// Otherwise do nothing.
+///
+/// The futures returned by `send` and `recv` in `tokio` are linked to the same channel,
+/// therefore the caller must make sure that the place contains the end of the channel itself.
+pub fn handle_end_drop<'tcx>(
+    place: rustc_middle::mir::Place<'tcx>,
+    drop_transition: &TransitionRef,
+    net: &mut PetriNet,
+    memory: &Memory<'tcx>,
+) {
+    if let Some(value) = memory.find_value(&place) {
+        add_end_drop_arcs(value, drop_transition, net);
+        debug!("DROP CHANNEL ENDS IN {place:?} DUE TO TRANSITION {drop_transition}");
+    }
+}
+
+/// Adds an arc to the place that marks the end as dropped for every sender or receiver in the value.
+fn add_end_drop_arcs(value: &Value, drop_transition: &TransitionRef, net: &mut PetriNet) {
+    match value {
+        Value::Sender(channel_ref) => {
+            add_arc_transition_place(net, drop_transition, &channel_ref.sender_dropped);
+        }
+        Value::Receiver(channel_ref) => {
+            add_arc_transition_place(net, drop_transition, &channel_ref.receiver_dropped);
+        }
+        Value::Aggregate(values) => {
+            for value in values {
+                add_end_drop_arcs(value, drop_transition, net);
+            }
+        }
+        _ => {}
+    }
+}
@@ -1,4 +1,4 @@
-//! Representation of a condition variable in the Petri net.
+//! Representation of a condition variable (`std::sync::Condvar` or `parking_lot::Condvar`) in the Petri net.
 //!
 //! The behavior of the condition variable is modelled using
 //! two places and three transitions, plus some additional places for the mutex
@@ -13,27 +13,44 @@
 //! preventing `lost_signal` from firing and ensuring that an output token is set,
 //! which will allow the waiting thread to continue.
 //!
+//! The first lost signal moves the token from `no_signal_lost` to `signal_lost`.
+//! Later lost signals fire `lost_signal_again` instead, which keeps the token in `signal_lost`.
+//! This marks the states after a lost signal without making the net unbounded.
+//!
 //! This Petri net model is a modified version of the one presented in the paper
 //! "Modelling Multithreaded Applications Using Petri Nets" by Kavi, Moshtaghi and Chen.
 //! <https://www.researchgate.net/publication/220091454_Modeling_Multithreaded_Applications_Using_Petri_Nets>
 //! The model was extended to model the condition on which the condition variable waits
 //! and unnecessary intermediate places were removed.
+//!
+//! If the value stored in the mutex is modelled, the condition of `std::sync::Condvar::wait_while`
+//! is evaluated for every value that the mutex may hold. A transition for each value checks the condition:
+//! If it holds, the thread waits for a notification and checks the condition again afterwards.
+//! Otherwise the call returns.
 
 use log::debug;
 use std::cell::OnceCell;
+use std::collections::HashMap;
 
 use crate::data_structures::petri_net_interface::{
-    add_arc_place_transition, add_arc_transition_place,
+    add_arc_place_transition, add_arc_transition_place, connect_places,
 };
 use crate::data_structures::petri_net_interface::{PetriNet, PlaceRef, TransitionRef};
-use crate::naming::condvar::{place_labels, transition_labels};
+use crate::naming::condvar::{
+    lost_signal_again_label, lost_signal_place_labels, place_labels, transition_labels,
+    wait_while_check_label, wait_while_place_labels,
+};
 use crate::translator::function::{Places, PostprocessingTask};
 use crate::translator::mir_function::memory::{Memory, MutexGuardRef};
 use crate::translator::special_function::call_foreign_function;
-use crate::utils::extract_nth_argument_as_place;
+use crate::utils::{
+    evaluate_constant_as_integer, extract_def_id_of_called_function_from_operand,
+    extract_nth_argument_as_place,
+};
 
 #[derive(PartialEq, Eq)]
 pub struct Condvar {
+    index: usize,
     wait_start: TransitionRef,
     notify: PlaceRef,
     notify_received: TransitionRef,
@@ -56,10 +73,26 @@ impl Condvar {
         let lost_signal = net.add_transition(&t2);
         let notify_received = net.add_transition(&t3);
 
+        let (p3, p4) = lost_signal_place_labels(index);
+        let no_signal_lost = net.add_place(&p3);
+        let signal_lost = net.add_place(&p4);
+        net.add_token(&no_signal_lost, 1)
+            .expect("BUG: Adding initial token to `no_signal_lost` should not cause an overflow");
+        let lost_signal_again = net.add_transition(&lost_signal_again_label(index));
+
         // Loop for consuming the token in `notify` when `wait()` has not been called yet.
         add_arc_place_transition(net, &wait_enabled, &lost_signal);
         add_arc_place_transition(net, &notify, &lost_signal);
         add_arc_transition_place(net, &lost_signal, &wait_enabled);
+        // Mark that a notification was lost
+        add_arc_place_transition(net, &no_signal_lost, &lost_signal);
+        add_arc_transition_place(net, &lost_signal, &signal_lost);
+        // Same loop once the mark is set
+        add_arc_place_transition(net, &wait_enabled, &lost_signal_again);
+        add_arc_place_transition(net, &notify, &lost_signal_again);
+        add_arc_transition_place(net, &lost_signal_again, &wait_enabled);
+        add_arc_place_transition(net, &signal_lost, &lost_signal_again);
+        add_arc_transition_place(net, &lost_signal_again, &signal_lost);
         // Start the wait only if the wait is enabled
         add_arc_place_transition(net, &wait_enabled, &wait_start);
         // Exit the wait only if the notify was received
@@ -68,6 +101,7 @@ impl Condvar {
         add_arc_transition_place(net, &notify_received, &wait_enabled);
 
         Self {
+            index,
             wait_start,
             notify,
             notify_received,
@@ -75,6 +109,17 @@ impl Condvar {
         }
     }
 
+    /// Returns the index of the condition variable used in the labels of the Petri net.
+    pub const fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Checks whether the condition variable was already linked to a call to
+    /// `std::sync::Condvar::wait` or `std::sync::Condvar::wait_while`.
+    pub fn is_linked_to_wait_call(&self) -> bool {
+        self.already_linked_to_call.get().is_some()
+    }
+
     /// Links the Petri net model of the condition variable to the representation of
     /// a call to `std::sync::Condvar::wait`.
     /// Connects the `start_place` place to the `wait_start` transition.
@@ -139,6 +184,10 @@ pub fn call_new<'tcx>(
 /// Call to `std::sync::Condvar::notify_one`.
 /// Non-recursive call for the translation process.
 ///
+/// Calls to `parking_lot::Condvar::notify_one` and `parking_lot::Condvar::notify_all` are translated the same way.
+/// Only one call to `wait` per condition variable is supported, so waking up all the waiting threads
+/// is the same as waking up one.
+///
 /// - Retrieves the condvar linked to the first argument (the self reference).
 /// - Creates an arc from the transition of this function call to the `notify`
 ///   in the Petri net model of the condvar.
@@ -166,7 +215,7 @@ pub fn call_notify_one<'tcx>(
     condvar_ref.link_to_notify_one_call(transitions.get_default(), net);
 }
 
-/// Call to `std::sync::Condvar::wait`.
+/// Call to `std::sync::Condvar::wait` or `parking_lot::Condvar::wait`.
 /// Non-recursive call for the translation process.
 ///
 /// - Retrieves the condvar linked to the first argument (the self reference).
@@ -174,7 +223,7 @@ pub fn call_notify_one<'tcx>(
 /// - Connects the start and end place to the condition variable.
 /// - Adds the arc for the unlocking of the mutex at the start of the `wait`.
 /// - Adds the arc for the locking of the mutex at the end of the `wait`.
-/// - Links the return place to the mutex guard.
+/// - Links the return place to the mutex guard, except for `parking_lot`.
 /// - Returns a postprocessing task to link the mutex to the condition variable.
 ///
 /// In some cases, the `std::sync::Condvar::wait` function contains a cleanup target.
@@ -209,9 +258,175 @@ pub fn call_wait<'tcx>(
     let wait_start = condvar_ref.wait_start.clone();
 
     // The return value contains the mutex guard passed to the function. Link the local variable to it.
-    memory.link_place_to_same_value(destination, mutex_guard);
+    // `parking_lot::Condvar::wait` receives a mutable reference to the guard instead and returns nothing.
+    if function_name != "parking_lot::Condvar::wait" {
+        memory.link_place_to_same_value(destination, mutex_guard);
+    }
 
     // Create a postprocessing task to link the mutex to the condvar.
     // This creates the condition and skip logic.
     PostprocessingTask::link_mutex_to_condvar(index, start_place, end_place, wait_start)
 }
+
+/// Evaluates the condition passed to `std::sync::Condvar::wait_while` for every value
+/// that the mutex of the mutex guard may hold.
+/// Returns whether the thread waits for each value, i.e. whether the condition holds.
+///
+/// Returns `None` if the value stored in the mutex is not modelled
+/// or if the condition cannot be evaluated, see `evaluate_condition`.
+pub fn evaluate_wait_while_condition<'tcx>(
+    args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
+    memory: &Memory<'tcx>,
+    caller_function_def_id: rustc_hir::def_id::DefId,
+    tcx: rustc_middle::ty::TyCtxt<'tcx>,
+) -> Option<Vec<bool>> {
+    let mutex_guard = extract_nth_argument_as_place(args, 1)?;
+    if !memory.is_mutex_guard(&mutex_guard) {
+        return None;
+    }
+    let mutex_ref = &memory.get_mutex_guard(&mutex_guard).mutex;
+    let condition = extract_def_id_of_called_function_from_operand(
+        &args.get(2)?.node,
+        caller_function_def_id,
+        tcx,
+    )?;
+    let body = tcx.optimized_mir(condition);
+    (0..mutex_ref.number_of_values())
+        .map(|value| evaluate_condition(body, value as i128, tcx))
+        .collect::<Option<Vec<bool>>>()
+        .filter(|predicate| !predicate.is_empty())
+}
+
+/// Evaluates the body of the closure passed as the condition to `std::sync::Condvar::wait_while`
+/// when the mutex holds `value`. The closure receives a mutable reference to the value as its second argument.
+///
+/// Follows the control flow from the first basic block and interprets assignments of constants,
+/// reads of the value, comparisons and boolean negations.
+/// Returns `None` if the closure contains anything else, e.g. a function call.
+fn evaluate_condition<'tcx>(
+    body: &rustc_middle::mir::Body<'tcx>,
+    value: i128,
+    tcx: rustc_middle::ty::TyCtxt<'tcx>,
+) -> Option<bool> {
+    use rustc_middle::mir::{
+        BinOp, Operand, ProjectionElem, Rvalue, StatementKind, TerminatorKind, UnOp,
+    };
+
+    let value_reference = rustc_middle::mir::Local::from_usize(2);
+    let mut locals: HashMap<rustc_middle::mir::Local, i128> = HashMap::new();
+    let evaluate_operand = |operand: &Operand<'tcx>, locals: &HashMap<_, i128>| match operand {
+        Operand::Constant(_) => evaluate_constant_as_integer(operand, tcx),
+        Operand::Copy(place) | Operand::Move(place) => match place.projection.as_slice() {
+            [] => locals.get(&place.local).copied(),
+            [ProjectionElem::Deref] if place.local == value_reference => Some(value),
+            _ => None,
+        },
+    };
+    let mut block = rustc_middle::mir::START_BLOCK;
+    // The body of the condition has no loops, each block is visited at most once.
+    for _ in 0..body.basic_blocks.len() {
+        let block_data = &body.basic_blocks[block];
+        for statement in &block_data.statements {
+            match &statement.kind {
+                StatementKind::Assign(assignment) => {
+                    let (place, rvalue) = assignment.as_ref();
+                    let result = match rvalue {
+                        Rvalue::Use(operand) => evaluate_operand(operand, &locals)?,
+                        Rvalue::UnaryOp(UnOp::Not, operand) if operand.ty(body, tcx).is_bool() => {
+                            evaluate_operand(operand, &locals)? ^ 1
+                        }
+                        Rvalue::BinaryOp(operation, operands) => {
+                            let (lhs, rhs) = operands.as_ref();
+                            let lhs = evaluate_operand(lhs, &locals)?;
+                            let rhs = evaluate_operand(rhs, &locals)?;
+                            let result = match operation {
+                                BinOp::Eq => lhs == rhs,
+                                BinOp::Ne => lhs != rhs,
+                                BinOp::Lt => lhs < rhs,
+                                BinOp::Le => lhs <= rhs,
+                                BinOp::Gt => lhs > rhs,
+                                BinOp::Ge => lhs >= rhs,
+                                _ => return None,
+                            };
+                            i128::from(result)
+                        }
+                        _ => return None,
+                    };
+                    locals.insert(place.as_local()?, result);
+                }
+                StatementKind::StorageLive(_)
+                | StatementKind::StorageDead(_)
+                | StatementKind::Nop => {}
+                _ => return None,
+            }
+        }
+        match &block_data.terminator().kind {
+            TerminatorKind::Goto { target } => block = *target,
+            TerminatorKind::SwitchInt { discr, targets } => {
+                let discriminant = evaluate_operand(discr, &locals)?;
+                block = targets.target_for_value(u128::try_from(discriminant).ok()?);
+            }
+            TerminatorKind::Return => {
+                let result = locals.get(&rustc_middle::mir::RETURN_PLACE)?;
+                return Some(*result != 0);
+            }
+            _ => return None,
+        }
+    }
+    None
+}
+
+/// Call to `std::sync::Condvar::wait_while` if the value stored in the mutex is modelled
+/// and the condition was evaluated for every value, see `evaluate_wait_while_condition`.
+/// Non-recursive call for the translation process.
+///
+/// - Retrieves the condvar linked to the first argument (the self reference).
+/// - Retrieves the mutex guard linked to the second argument.
+/// - Connects the start place through one transition for each value to a new place `condition_holds`
+///   if the condition holds for the value or to the end place otherwise.
+/// - Connects `condition_holds` to the condition variable, which goes back to the start place
+///   to check the condition again after the notification.
+///   The new place `waiting` ensures that the notification is only received after the wait started.
+/// - Links the return place to the mutex guard.
+///
+/// Like `call_wait`, the cleanup place is not modelled.
+pub fn call_wait_while_with_values<'tcx>(
+    function_name: &str,
+    args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
+    predicate: &[bool],
+    destination: rustc_middle::mir::Place<'tcx>,
+    places: Places,
+    net: &mut PetriNet,
+    memory: &mut Memory<'tcx>,
+) {
+    let self_ref = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+        panic!("BUG: `{function_name}` should receive the self reference as a place")
+    });
+    let condvar_ref = memory.get_condvar(&self_ref);
+    let mutex_guard = extract_nth_argument_as_place(args, 1).unwrap_or_else(|| {
+        panic!("BUG: `{function_name}` should receive the first argument as a place")
+    });
+    let mutex_guard_ref = memory.get_mutex_guard(&mutex_guard);
+
+    let (start_place, end_place) = places.ignore_cleanup_place().get_start_end_place();
+    let (condition_holds, waiting) = wait_while_place_labels(condvar_ref.index());
+    let condition_holds = net.add_place(&condition_holds);
+    let waiting = net.add_place(&waiting);
+    for (value, waits) in predicate.iter().enumerate() {
+        let target = if *waits { &condition_holds } else { &end_place };
+        let label = wait_while_check_label(condvar_ref.index(), value);
+        let transition = connect_places(net, &start_place, target, &label);
+        mutex_guard_ref
+            .mutex
+            .add_value_arcs(value, value, &transition, net);
+    }
+    condvar_ref.link_to_wait_call(&condition_holds, &start_place, mutex_guard_ref, net);
+    // The condition is checked again after the notification,
+    // so the notification must only be received while the thread is waiting.
+    add_arc_transition_place(net, &condvar_ref.wait_start, &waiting);
+    add_arc_place_transition(net, &waiting, &condvar_ref.notify_received);
+
+    let index = condvar_ref.index();
+    memory.link_place_to_same_value(destination, mutex_guard);
+    debug!("WAIT WHILE ON CONDVAR {index} WITH CONDITION {predicate:?}");
+}
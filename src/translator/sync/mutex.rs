@@ -11,25 +11,68 @@
 //! If the place does not have a token, the mutex is locked.
 //!
 //! A mutex guard simply contains a reference to the corresponding mutex.
+//!
+//! If the panics are modelled, the mutex also tracks whether it is poisoned.
+//! A mutex guard dropped during a panic does not put the token back directly.
+//! Instead, the token goes through a transition that marks the mutex as poisoned.
+//! Calling `unwrap` on the result of `lock` then panics if and only if the mutex is poisoned.
+//! The call to `lock` itself does not need to branch: The mutex cannot become poisoned
+//! between `lock` and `unwrap` because the thread holds the lock in between.
+//!
+//! If the values stored in mutexes are modelled, a `Mutex<bool>` or a mutex of an integer type
+//! created with a constant stores one place for each value that it may hold, like an atomic variable.
+//! A mutex of an integer type holds the values from 0 to `INTEGER_VALUES - 1`.
+//! A mutex created with a value outside this range does not model its values.
+//! A write that may leave a value outside this range, e.g. incrementing the largest value,
+//! is modelled as a write of any value. Both cases are recorded as approximations.
+//! The condition places are not used for these mutexes.
+//!
+//! A dereference of a mutex guard is translated as one transition for each value found in the mutex:
+//! - `std::ops::Deref::deref` keeps the value. The returned reference is linked to an `AtomicValue`,
+//!   so a switch int terminator that tests the value only takes the branch for the value found.
+//! - `std::ops::DerefMut::deref_mut` writes the new value right away, since no other thread
+//!   can see the value before the guard is dropped. The writes through the returned reference
+//!   are recognized in the MIR body, see `Write`.
 
 use log::debug;
-use std::cell::RefCell;
+use rustc_middle::mir::visit::Visitor;
+use std::cell::{OnceCell, RefCell};
 
 use crate::data_structures::petri_net_interface::{
     add_arc_place_transition, add_arc_transition_place, connect_places,
 };
 use crate::data_structures::petri_net_interface::{PetriNet, PlaceRef, TransitionRef};
 use crate::naming::condvar::wait_skip_label;
-use crate::naming::mutex::{condition_place_labels, place_label};
+use crate::naming::mutex::{
+    condition_place_labels, place_label, poison_place_labels, poison_transition_labels,
+    unlocked_by_panic_place_label, value_place_label, value_transition_label,
+};
 use crate::translator::function::{Places, PostprocessingTask};
 use crate::translator::mir_function::memory::{Memory, MutexRef};
 use crate::translator::special_function::call_foreign_function;
-use crate::utils::extract_nth_argument_as_place;
+use crate::translator::sync::atomic::INTEGER_VALUES;
+use crate::translator::sync::AtomicValue;
+use crate::utils::{evaluate_constant_as_integer, extract_nth_argument_as_place};
 
 #[derive(PartialEq, Eq)]
 pub struct Mutex {
+    index: usize,
     mutex: PlaceRef,
     deref_mut: RefCell<Vec<TransitionRef>>,
+    poison: OnceCell<Poison>,
+    values: Option<Vec<PlaceRef>>,
+}
+
+/// The places that model whether the mutex is poisoned.
+/// They are added to the Petri net the first time that they are needed.
+#[derive(PartialEq, Eq)]
+struct Poison {
+    /// Has a token if no thread panicked while holding the lock.
+    not_poisoned: PlaceRef,
+    /// Has a token if a thread panicked while holding the lock.
+    poisoned: PlaceRef,
+    /// Receives the token of the mutex when a mutex guard is dropped during a panic.
+    unlocked_by_panic: PlaceRef,
 }
 
 impl Mutex {
@@ -42,11 +85,70 @@ impl Mutex {
             .expect("BUG: Adding initial token to mutex place should not cause an overflow");
 
         Self {
+            index,
             mutex,
             deref_mut: RefCell::new(Vec::new()),
+            poison: OnceCell::new(),
+            values: None,
         }
     }
 
+    /// Creates a new mutex whose label is based on `index` and that stores one of `number_of_values` values.
+    /// Adds the place of the mutex and one place for each value to the Petri Net.
+    /// The place of the `initial_value` gets a token.
+    pub fn new_with_values(
+        index: usize,
+        number_of_values: usize,
+        initial_value: usize,
+        net: &mut PetriNet,
+    ) -> Self {
+        let mut mutex = Self::new(index, net);
+        let values: Vec<PlaceRef> = (0..number_of_values)
+            .map(|value| net.add_place(&value_place_label(index, value)))
+            .collect();
+        net.add_token(&values[initial_value], 1).expect(
+            "BUG: Adding the initial token to the mutex value should not cause an overflow",
+        );
+        mutex.values = Some(values);
+        mutex
+    }
+
+    /// Returns the index of the mutex used in the labels of the Petri net.
+    pub const fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Checks whether the value stored in the mutex is modelled.
+    pub const fn has_values(&self) -> bool {
+        self.values.is_some()
+    }
+
+    /// Returns the number of values modelled for the value stored in the mutex.
+    /// Returns 0 if the value is not modelled.
+    pub fn number_of_values(&self) -> usize {
+        self.values.as_ref().map_or(0, Vec::len)
+    }
+
+    /// Adds the arcs for a transition that finds `old_value` in the mutex and leaves `new_value` in it.
+    ///
+    /// # Panics
+    ///
+    /// If the value stored in the mutex is not modelled, then the function panics.
+    pub fn add_value_arcs(
+        &self,
+        old_value: usize,
+        new_value: usize,
+        transition: &TransitionRef,
+        net: &mut PetriNet,
+    ) {
+        let values = self
+            .values
+            .as_ref()
+            .expect("BUG: The value stored in the mutex should be modelled");
+        add_arc_place_transition(net, &values[old_value], transition);
+        add_arc_transition_place(net, transition, &values[new_value]);
+    }
+
     /// Adds a lock arc for this mutex.
     /// Connects the mutex's place to the transition, then the transition will only
     /// fire if the mutex is unlocked.
@@ -61,6 +163,65 @@ impl Mutex {
         add_arc_transition_place(net, unlock_transition, &self.mutex);
     }
 
+    /// Adds an unlock arc for a mutex guard dropped during a panic.
+    /// Connects the transition to the place `unlocked_by_panic`, from where
+    /// the token goes back to the mutex's place while marking the mutex as poisoned.
+    pub fn add_unwind_unlock_arc(&self, unlock_transition: &TransitionRef, net: &mut PetriNet) {
+        let unlocked_by_panic = self.poison(net).unlocked_by_panic.clone();
+        add_arc_transition_place(net, unlock_transition, &unlocked_by_panic);
+    }
+
+    /// Adds the arcs that check whether the mutex is poisoned.
+    /// The `ok_transition` may only fire if the mutex is not poisoned,
+    /// the `err_transition` may only fire if the mutex is poisoned.
+    /// Both transitions regenerate the token they take.
+    pub fn add_poison_check_arcs(
+        &self,
+        ok_transition: &TransitionRef,
+        err_transition: &TransitionRef,
+        net: &mut PetriNet,
+    ) {
+        let poison = self.poison(net);
+        let (not_poisoned, poisoned) = (poison.not_poisoned.clone(), poison.poisoned.clone());
+        add_arc_place_transition(net, &not_poisoned, ok_transition);
+        add_arc_transition_place(net, ok_transition, &not_poisoned);
+        add_arc_place_transition(net, &poisoned, err_transition);
+        add_arc_transition_place(net, err_transition, &poisoned);
+    }
+
+    /// Returns the places that model whether the mutex is poisoned.
+    /// The first call adds them to the Petri net:
+    /// - Creates the places `not_poisoned` (with a token), `poisoned` and `unlocked_by_panic`.
+    /// - Connects `unlocked_by_panic` and `not_poisoned` to a new transition `poison`
+    ///   that puts the token back in the mutex's place and in `poisoned`.
+    /// - Connects `unlocked_by_panic` to a new transition `remain_poisoned`
+    ///   that puts the token back in the mutex's place if the mutex is already poisoned.
+    fn poison(&self, net: &mut PetriNet) -> &Poison {
+        self.poison.get_or_init(|| {
+            let (not_poisoned_label, poisoned_label) = poison_place_labels(self.index);
+            let not_poisoned = net.add_place(&not_poisoned_label);
+            let poisoned = net.add_place(&poisoned_label);
+            let unlocked_by_panic = net.add_place(&unlocked_by_panic_place_label(self.index));
+            net.add_token(&not_poisoned, 1)
+                .expect("BUG: Adding initial token to `not_poisoned` should not cause an overflow");
+
+            let (poison_label, remain_poisoned_label) = poison_transition_labels(self.index);
+            let poison = connect_places(net, &unlocked_by_panic, &self.mutex, &poison_label);
+            add_arc_place_transition(net, &not_poisoned, &poison);
+            add_arc_transition_place(net, &poison, &poisoned);
+            let remain_poisoned =
+                connect_places(net, &unlocked_by_panic, &self.mutex, &remain_poisoned_label);
+            add_arc_place_transition(net, &poisoned, &remain_poisoned);
+            add_arc_transition_place(net, &remain_poisoned, &poisoned);
+
+            Poison {
+                not_poisoned,
+                poisoned,
+                unlocked_by_panic,
+            }
+        })
+    }
+
     /// Adds a transition of a call to `std::ops::DerefMut::deref_mut`.
     /// This transition has set a value for a mutex and must be used to disable the condition variable later.
     pub fn add_deref_mut_transition(&self, transition: TransitionRef) {
@@ -209,3 +370,358 @@ pub fn handle_mutex_guard_drop<'tcx>(
         debug!("DROP MUTEX GUARD {place:?} DUE TO TRANSITION {unlock_transition}");
     }
 }
+
+/// Checks whether the variable dropped during a panic is a mutex guard.
+/// If that is the case, adds an unlock arc that poisons the mutex corresponding to the mutex guard.
+/// Otherwise do nothing.
+pub fn handle_mutex_guard_drop_during_panic<'tcx>(
+    place: rustc_middle::mir::Place<'tcx>,
+    unlock_transition: &TransitionRef,
+    net: &mut PetriNet,
+    memory: &Memory<'tcx>,
+) {
+    if memory.is_mutex_guard(&place) {
+        let mutex_guard_ref = memory.get_mutex_guard(&place);
+        mutex_guard_ref
+            .mutex
+            .add_unwind_unlock_arc(unlock_transition, net);
+        debug!("DROP MUTEX GUARD {place:?} DURING A PANIC DUE TO TRANSITION {unlock_transition}");
+    }
+}
+
+/// A write to the value stored in a mutex through the reference returned by `std::ops::DerefMut::deref_mut`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Write<'tcx> {
+    /// The value is not written, e.g. the reference is only read.
+    None,
+    /// A constant is assigned, e.g. `*guard = true`.
+    Constant(i128),
+    /// A constant is added or subtracted, e.g. `*guard += 1` or `*guard -= 1`.
+    /// For a checked operation, the place of the flag that indicates whether the operation overflowed.
+    Add(i128, Option<rustc_middle::mir::Place<'tcx>>),
+    /// Any other write. The mutex may hold any value afterwards.
+    Unknown,
+}
+
+impl Write<'_> {
+    /// Returns the values that the mutex may hold after the write if it held `old_value` before.
+    /// A result outside the range of modelled values is modelled as any value.
+    fn new_values(self, old_value: usize, number_of_values: usize) -> Vec<usize> {
+        if self == Self::Unknown {
+            return (0..number_of_values).collect();
+        }
+        self.written_value(old_value).map_or_else(
+            || vec![old_value],
+            |new_value| {
+                to_modelled_value(new_value, number_of_values)
+                    .map_or_else(|| (0..number_of_values).collect(), |value| vec![value])
+            },
+        )
+    }
+
+    /// Checks whether the write may leave a value outside the range of modelled values in the mutex,
+    /// i.e. whether it is modelled as a write of any value for some old value.
+    pub fn leaves_modelled_values(self, number_of_values: usize) -> bool {
+        (0..number_of_values).any(|old_value| {
+            self.written_value(old_value)
+                .is_some_and(|new_value| to_modelled_value(new_value, number_of_values).is_none())
+        })
+    }
+
+    /// Returns the constant value written if the mutex held `old_value` before.
+    /// Returns `None` if the value does not change or is unknown.
+    /// A checked decrement below zero panics before the write, so the value does not change.
+    fn written_value(self, old_value: usize) -> Option<i128> {
+        match self {
+            Self::Constant(constant) => Some(constant),
+            Self::Add(constant, overflow) => {
+                let new_value = old_value as i128 + constant;
+                (new_value >= 0 || overflow.is_none()).then_some(new_value)
+            }
+            Self::None | Self::Unknown => None,
+        }
+    }
+}
+
+/// Converts the value to the index of its place in a mutex with `number_of_values` values.
+/// Returns `None` if the value is outside the range of modelled values.
+fn to_modelled_value(value: i128, number_of_values: usize) -> Option<usize> {
+    usize::try_from(value)
+        .ok()
+        .filter(|value| *value < number_of_values)
+}
+
+/// Finds the writes through the reference returned by a call to `std::ops::DerefMut::deref_mut` in the MIR body.
+/// The reference is the local where the call stores its return value.
+///
+/// Recognizes a single assignment `(*_X) = const C` or an increment or decrement by a constant,
+/// i.e. `(*_X) = Add(copy (*_X), const C)` or the checked version `AddWithOverflow` followed by
+/// `(*_X) = move (_Y.0)`, and the same for `Sub`.
+/// Reading the value through the reference is allowed.
+/// Any other use of the reference, e.g. passing it to a function, is an unknown write.
+pub fn find_write<'tcx>(
+    body: &rustc_middle::mir::Body<'tcx>,
+    reference: rustc_middle::mir::Local,
+    tcx: rustc_middle::ty::TyCtxt<'tcx>,
+) -> Write<'tcx> {
+    let mut finder = WriteFinder {
+        body,
+        reference,
+        tcx,
+        writes: Vec::new(),
+        unknown_use: false,
+    };
+    finder.visit_body(body);
+    match finder.writes.as_slice() {
+        _ if finder.unknown_use => Write::Unknown,
+        [] => Write::None,
+        [write] => *write,
+        _ => Write::Unknown,
+    }
+}
+
+/// Visitor that collects the writes through a reference to the value stored in a mutex.
+struct WriteFinder<'a, 'tcx> {
+    body: &'a rustc_middle::mir::Body<'tcx>,
+    reference: rustc_middle::mir::Local,
+    tcx: rustc_middle::ty::TyCtxt<'tcx>,
+    writes: Vec<Write<'tcx>>,
+    unknown_use: bool,
+}
+
+impl<'tcx> WriteFinder<'_, 'tcx> {
+    /// Checks whether the place is the value behind the reference, i.e. `(*_X)`.
+    fn is_value(&self, place: &rustc_middle::mir::Place<'tcx>) -> bool {
+        place.local == self.reference
+            && place.projection.as_slice() == [rustc_middle::mir::ProjectionElem::Deref]
+    }
+
+    /// Checks whether the operand reads the value behind the reference.
+    fn reads_value(&self, operand: &rustc_middle::mir::Operand<'tcx>) -> bool {
+        operand.place().is_some_and(|place| self.is_value(&place))
+    }
+
+    /// Returns the constant added to the value behind the reference by a binary operation, if any.
+    /// Subtracting a constant is adding its negation.
+    fn find_addition(
+        &self,
+        operation: rustc_middle::mir::BinOp,
+        operands: &(
+            rustc_middle::mir::Operand<'tcx>,
+            rustc_middle::mir::Operand<'tcx>,
+        ),
+    ) -> Option<i128> {
+        use rustc_middle::mir::BinOp;
+        let (lhs, rhs) = operands;
+        if !self.reads_value(lhs) {
+            return None;
+        }
+        let constant = evaluate_constant_as_integer(rhs, self.tcx)?;
+        match operation {
+            BinOp::Add | BinOp::AddWithOverflow => Some(constant),
+            BinOp::Sub | BinOp::SubWithOverflow => Some(-constant),
+            _ => None,
+        }
+    }
+
+    /// Returns the constant added to the value behind the reference by the checked operation
+    /// whose result is stored in `local`, e.g. `_Y = AddWithOverflow(copy (*_X), const 1)`.
+    fn find_checked_addition(&self, local: rustc_middle::mir::Local) -> Option<i128> {
+        self.body
+            .basic_blocks
+            .iter()
+            .flat_map(|block| &block.statements)
+            .find_map(|statement| match &statement.kind {
+                rustc_middle::mir::StatementKind::Assign(assignment) => {
+                    let (place, rvalue) = assignment.as_ref();
+                    match rvalue {
+                        rustc_middle::mir::Rvalue::BinaryOp(operation, operands)
+                            if place.as_local() == Some(local) =>
+                        {
+                            self.find_addition(*operation, operands)
+                        }
+                        _ => None,
+                    }
+                }
+                _ => None,
+            })
+    }
+
+    /// Classifies the value assigned to the value behind the reference.
+    fn classify(&self, rvalue: &rustc_middle::mir::Rvalue<'tcx>) -> Write<'tcx> {
+        use rustc_middle::mir::{Operand, ProjectionElem, Rvalue};
+        let write = match rvalue {
+            Rvalue::Use(operand @ Operand::Constant(_)) => {
+                evaluate_constant_as_integer(operand, self.tcx).map(Write::Constant)
+            }
+            Rvalue::Use(Operand::Copy(place) | Operand::Move(place)) => {
+                match place.projection.as_slice() {
+                    [ProjectionElem::Field(field, _)] if field.as_usize() == 0 => {
+                        // The second field of the result of the checked operation is the overflow flag.
+                        let overflow = self.tcx.mk_place_field(
+                            place.local.into(),
+                            rustc_abi::FieldIdx::from_u32(1),
+                            self.tcx.types.bool,
+                        );
+                        self.find_checked_addition(place.local)
+                            .map(|constant| Write::Add(constant, Some(overflow)))
+                    }
+                    _ => None,
+                }
+            }
+            Rvalue::BinaryOp(operation, operands) => self
+                .find_addition(*operation, operands)
+                .map(|constant| Write::Add(constant, None)),
+            _ => None,
+        };
+        write.unwrap_or(Write::Unknown)
+    }
+}
+
+impl<'tcx> Visitor<'tcx> for WriteFinder<'_, 'tcx> {
+    fn visit_assign(
+        &mut self,
+        place: &rustc_middle::mir::Place<'tcx>,
+        rvalue: &rustc_middle::mir::Rvalue<'tcx>,
+        location: rustc_middle::mir::Location,
+    ) {
+        if self.is_value(place) {
+            let write = self.classify(rvalue);
+            self.writes.push(write);
+            self.visit_rvalue(rvalue, location);
+        } else {
+            self.super_assign(place, rvalue, location);
+        }
+    }
+
+    fn visit_place(
+        &mut self,
+        place: &rustc_middle::mir::Place<'tcx>,
+        context: rustc_middle::mir::visit::PlaceContext,
+        location: rustc_middle::mir::Location,
+    ) {
+        use rustc_middle::mir::visit::{MutatingUseContext, NonMutatingUseContext, PlaceContext};
+        if place.local == self.reference {
+            let allowed = match context {
+                PlaceContext::NonMutatingUse(NonMutatingUseContext::Copy) => self.is_value(place),
+                PlaceContext::MutatingUse(MutatingUseContext::Call) => place.as_local().is_some(),
+                PlaceContext::NonUse(_) => true,
+                _ => false,
+            };
+            self.unknown_use |= !allowed;
+        }
+        self.super_place(place, context, location);
+    }
+}
+
+/// Extracts the initial value passed to `std::sync::Mutex::<T>::new` or `parking_lot::Mutex::<T>::new`.
+/// Returns the number of values modelled for the type and the initial value.
+/// Returns `Some(Err(value))` if the initial value is outside the range of modelled values.
+/// Returns `None` if the argument is not a constant boolean or integer.
+pub fn extract_initial_value<'tcx>(
+    args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
+    tcx: rustc_middle::ty::TyCtxt<'tcx>,
+) -> Option<Result<(usize, usize), i128>> {
+    let operand = &args.first()?.node;
+    let ty = operand.constant()?.const_.ty();
+    let number_of_values = if ty.is_bool() {
+        2
+    } else if ty.is_integral() && !ty.is_signed() {
+        INTEGER_VALUES
+    } else {
+        return None;
+    };
+    let value = evaluate_constant_as_integer(operand, tcx)?;
+    Some(
+        to_modelled_value(value, number_of_values)
+            .map(|initial_value| (number_of_values, initial_value))
+            .ok_or(value),
+    )
+}
+
+/// Call to `std::sync::Mutex::<T>::new` or `parking_lot::Mutex::<T>::new` with a constant
+/// if the values stored in mutexes are modelled.
+/// Non-recursive call for the translation process.
+///
+/// - Creates a new `Mutex` that holds the initial value.
+/// - Links the return place to the `Mutex`.
+/// - Returns a postprocessing task to notify the creation of this mutex.
+#[allow(clippy::too_many_arguments)]
+pub fn call_new_with_values<'tcx>(
+    function_name: &str,
+    index: usize,
+    number_of_values: usize,
+    initial_value: usize,
+    destination: rustc_middle::mir::Place<'tcx>,
+    places: Places,
+    net: &mut PetriNet,
+    memory: &mut Memory<'tcx>,
+) -> PostprocessingTask {
+    call_foreign_function(function_name, index, places, net);
+    let mutex = Mutex::new_with_values(index, number_of_values, initial_value, net);
+    let mutex_ref = memory.link_mutex(destination, mutex);
+    debug!("NEW MUTEX WITH VALUE {initial_value}: {destination:?}");
+    PostprocessingTask::new_mutex(mutex_ref.clone())
+}
+
+/// Call to `std::ops::Deref::deref` or `std::ops::DerefMut::deref_mut` on a mutex guard
+/// whose mutex stores a modelled value.
+/// Non-recursive call for the translation process.
+///
+/// - Connects the start place and the end place through one transition for each value
+///   that the call may find in the mutex and each value that the `write` may leave in it.
+/// - If the value is not written, links the return place to an `AtomicValue` with the value found.
+/// - If the value is incremented or decremented with an overflow check, links the overflow flag
+///   to an `AtomicValue` that is set only when decrementing zero.
+///   A write that leaves the range of modelled values may leave any value, see `Write::new_values`.
+///
+/// Like the other dereferences of a mutex guard, the cleanup place is not modelled.
+#[allow(clippy::too_many_arguments)]
+pub fn call_deref_with_values<'tcx>(
+    function_name: &str,
+    index: usize,
+    mutex_ref: &MutexRef,
+    write: Write<'tcx>,
+    destination: rustc_middle::mir::Place<'tcx>,
+    places: Places,
+    net: &mut PetriNet,
+    memory: &mut Memory<'tcx>,
+) {
+    let (start_place, end_place) = places.ignore_cleanup_place().get_start_end_place();
+    let number_of_values = mutex_ref.number_of_values();
+    let mut transitions = Vec::new();
+    let mut outcomes = Vec::new();
+    let mut overflows = Vec::new();
+    for old_value in 0..number_of_values {
+        for new_value in write.new_values(old_value, number_of_values) {
+            let label = value_transition_label(function_name, index, old_value, new_value);
+            let transition = connect_places(net, &start_place, &end_place, &label);
+            mutex_ref.add_value_arcs(old_value, new_value, &transition, net);
+            transitions.push(transition);
+            outcomes.push(old_value as u128);
+            if let Write::Add(constant, _) = write {
+                overflows.push(u128::from(old_value as i128 + constant < 0));
+            }
+        }
+    }
+    match write {
+        Write::None => {
+            // The value cannot change while the guard is held, a loop that tests it is not a spin loop.
+            memory.link_atomic_value(
+                destination,
+                AtomicValue::new_without_spin_loops(transitions, outcomes),
+            );
+        }
+        Write::Add(_, Some(overflow)) => {
+            memory.link_atomic_value(
+                overflow,
+                AtomicValue::new_without_spin_loops(transitions, overflows),
+            );
+        }
+        _ => {}
+    }
+    debug!(
+        "{function_name} ON MUTEX {} WITH VALUES, {write:?}",
+        mutex_ref.index()
+    );
+}
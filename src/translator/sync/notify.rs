@@ -0,0 +1,168 @@
+//! Representation of a notification primitive (`tokio::sync::Notify`) in the Petri net.
+//!
+//! A `Notify` stores at most one permit.
+//! Calling `notify_one` wakes up a task waiting for a notification,
+//! or stores the permit so that the next task that waits returns immediately.
+//! Awaiting `notified` consumes the permit.
+//!
+//! The permit is modelled with two complementary places: `permit` and `no_permit`.
+//! Exactly one of them has a token.
+//! A waiting task simply blocks on the `permit` place, so waking it up is the same as storing the permit.
+//! `notify_one` is translated as two transitions:
+//! One stores the permit if there is none, the other keeps the permit that is already stored.
+//! Unlike with a condition variable, a notification is never lost.
+
+use log::debug;
+
+use crate::data_structures::petri_net_interface::{
+    add_arc_place_transition, add_arc_transition_place, connect_places,
+};
+use crate::data_structures::petri_net_interface::{PetriNet, PlaceRef, TransitionRef};
+use crate::naming::notify::{keep_permit_transition_label, place_labels};
+use crate::translator::function::Places;
+use crate::translator::mir_function::memory::Memory;
+use crate::translator::special_function::call_foreign_function;
+use crate::utils::extract_nth_argument_as_place;
+
+#[derive(PartialEq, Eq)]
+pub struct Notify {
+    index: usize,
+    permit: PlaceRef,
+    no_permit: PlaceRef,
+}
+
+impl Notify {
+    /// Creates a new `Notify` whose label is based on `index`.
+    /// Adds the places `permit` and `no_permit` (with a token) to the Petri net.
+    pub fn new(index: usize, net: &mut PetriNet) -> Self {
+        let (permit_label, no_permit_label) = place_labels(index);
+        let permit = net.add_place(&permit_label);
+        let no_permit = net.add_place(&no_permit_label);
+        net.add_token(&no_permit, 1)
+            .expect("BUG: Adding initial token to `no_permit` should not cause an overflow");
+
+        Self {
+            index,
+            permit,
+            no_permit,
+        }
+    }
+
+    /// Returns the index of the `Notify` used in the labels of the Petri net.
+    pub const fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Adds the arcs for a call to `notify_one`.
+    /// The `store_transition` may only fire if there is no permit and stores it.
+    /// The `keep_transition` may only fire if the permit is already stored and regenerates it.
+    fn add_notify_arcs(
+        &self,
+        store_transition: &TransitionRef,
+        keep_transition: &TransitionRef,
+        net: &mut PetriNet,
+    ) {
+        add_arc_place_transition(net, &self.no_permit, store_transition);
+        add_arc_transition_place(net, store_transition, &self.permit);
+        add_arc_place_transition(net, &self.permit, keep_transition);
+        add_arc_transition_place(net, keep_transition, &self.permit);
+    }
+
+    /// Adds the arcs for awaiting a notification.
+    /// The transition may only fire if the permit is stored and consumes it.
+    fn add_wait_arcs(&self, wait_transition: &TransitionRef, net: &mut PetriNet) {
+        add_arc_place_transition(net, &self.permit, wait_transition);
+        add_arc_transition_place(net, wait_transition, &self.no_permit);
+    }
+}
+
+/// Call to `tokio::sync::Notify::new`.
+/// Non-recursive call for the translation process.
+///
+/// - Creates a new `Notify`.
+/// - Links the return place to the `Notify`.
+pub fn call_new<'tcx>(
+    function_name: &str,
+    index: usize,
+    destination: rustc_middle::mir::Place<'tcx>,
+    places: Places,
+    net: &mut PetriNet,
+    memory: &mut Memory<'tcx>,
+) {
+    call_foreign_function(function_name, index, places, net);
+    // Create a new notify
+    let notify = Notify::new(index, net);
+    // The return value contains a new notify. Link the local variable to it.
+    memory.link_notify(destination, notify);
+    debug!("NEW NOTIFY: {destination:?}");
+}
+
+/// Call to `tokio::sync::Notify::notify_one`.
+/// Non-recursive call for the translation process.
+///
+/// - Retrieves the `Notify` linked to the first argument (the self reference).
+/// - Connects the start place and the end place through a second transition
+///   for the case that the permit is already stored.
+/// - Adds the arcs that store or keep the permit.
+///
+/// Notifying never unwinds, therefore the cleanup place is not modelled.
+pub fn call_notify_one<'tcx>(
+    function_name: &str,
+    index: usize,
+    args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
+    places: Places,
+    net: &mut PetriNet,
+    memory: &Memory<'tcx>,
+) {
+    let (start_place, end_place) = places.get_start_end_place();
+    let places = Places::Basic {
+        start_place: start_place.clone(),
+        end_place: end_place.clone(),
+    };
+    let transitions = call_foreign_function(function_name, index, places, net);
+    let store_transition = transitions.get_default();
+    let keep_transition = connect_places(
+        net,
+        &start_place,
+        &end_place,
+        &keep_permit_transition_label(function_name, index),
+    );
+
+    // Retrieve the notify from the local variable passed to the function as an argument.
+    let self_ref = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+        panic!("BUG: `{function_name}` should receive the self reference as a place")
+    });
+    let notify_ref = memory.get_notify(&self_ref);
+    notify_ref.add_notify_arcs(store_transition, &keep_transition, net);
+}
+
+/// Call to `std::future::Future::poll` on the future returned by `tokio::sync::Notify::notified`,
+/// i.e. awaiting a notification.
+/// Non-recursive call for the translation process.
+///
+/// - Retrieves the `Notify` linked to the first argument (the pinned future).
+/// - Adds the arcs that consume the permit.
+///
+/// Waiting for a notification never unwinds, therefore the cleanup place is not modelled.
+pub fn call_notified<'tcx>(
+    function_name: &str,
+    index: usize,
+    args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
+    places: Places,
+    net: &mut PetriNet,
+    memory: &Memory<'tcx>,
+) {
+    let places = places.ignore_cleanup_place();
+    let transitions = call_foreign_function(function_name, index, places, net);
+    let wait_transition = transitions.get_default();
+
+    let future_ref = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+        panic!("BUG: `{function_name}` should receive the pinned future as a place")
+    });
+    let notify_ref = memory.get_notify(&future_ref);
+    notify_ref.add_wait_arcs(wait_transition, net);
+    debug!(
+        "WAIT FOR NOTIFY {} DUE TO TRANSITION {wait_transition}",
+        notify_ref.index()
+    );
+}
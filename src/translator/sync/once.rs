@@ -0,0 +1,323 @@
+//! Representation of a one-time initialization (`std::sync::Once`, `std::sync::OnceLock`
+//! and `std::sync::LazyLock`) in the Petri net.
+//!
+//! The state of the initialization is modelled with three places: `uninit`, `running` and `done`.
+//! Exactly one of them has a token.
+//! A call that initializes (`call_once`, `get_or_init`, `force` or dereferencing a `LazyLock`)
+//! is translated as three transitions:
+//! - One returns immediately if the initialization is done.
+//! - One starts the initializer if the initialization has not started yet.
+//! - One finishes the initialization when the initializer returns.
+//!
+//! The body of the initializer (the closure) is translated inline between the last two transitions.
+//! A concurrent caller blocks while the initialization is running.
+//! A call that reenters the same initialization from its initializer blocks forever, as it does in practice.
+//!
+//! The initializer of a `std::sync::LazyLock` is the closure passed to `new`.
+//! It is stored together with the sync variables that it captures.
+//! A panic in the initializer resets the initialization, so that the next caller runs the initializer again.
+//! Poisoning is not modelled, see `call_init`.
+//!
+//! A one-time initialization in a `static` item is created the first time that a function references the static.
+//! Every reference to the same static shares it. The initializer of a `static` `LazyLock` is read from the body of the static.
+
+use log::debug;
+
+use crate::data_structures::petri_net_interface::{
+    add_arc_place_transition, add_arc_transition_place, connect_places,
+};
+use crate::data_structures::petri_net_interface::{PetriNet, PlaceRef};
+use crate::naming::once::{
+    already_done_transition_label, init_transition_labels, initializer_place_labels, place_labels,
+};
+use crate::translator::function::Places;
+use crate::translator::mir_function::memory::{Memory, Value};
+use crate::translator::special_function::call_foreign_function;
+use crate::utils::{extract_def_id_of_called_function_from_operand, extract_nth_argument_as_place};
+
+/// The types that implement a one-time initialization.
+pub const ONCE_TYPES: [&str; 3] = [
+    "std::sync::Once",
+    "std::sync::OnceLock",
+    "std::sync::LazyLock",
+];
+
+/// The closure that initializes the value
+/// together with the sync variables that it captures.
+#[derive(PartialEq, Clone)]
+pub struct Initializer {
+    /// The definition ID of the closure.
+    pub def_id: rustc_hir::def_id::DefId,
+    /// The aggregate value containing the sync variables captured by the closure.
+    pub aggregate: Vec<Value>,
+}
+
+#[derive(PartialEq)]
+pub struct Once {
+    index: usize,
+    uninit: PlaceRef,
+    running: PlaceRef,
+    done: PlaceRef,
+    initializer: Option<Initializer>,
+}
+
+impl Once {
+    /// Creates a new one-time initialization of the given type whose label is based on `index`.
+    /// Adds the places `uninit` (with a token), `running` and `done` to the Petri net.
+    /// The initializer is only known at this point for a `std::sync::LazyLock`.
+    pub fn new(
+        once_type: &str,
+        index: usize,
+        initializer: Option<Initializer>,
+        net: &mut PetriNet,
+    ) -> Self {
+        let (uninit_label, running_label, done_label) = place_labels(once_type, index);
+        let uninit = net.add_place(&uninit_label);
+        let running = net.add_place(&running_label);
+        let done = net.add_place(&done_label);
+        net.add_token(&uninit, 1)
+            .expect("BUG: Adding initial token to `uninit` should not cause an overflow");
+
+        Self {
+            index,
+            uninit,
+            running,
+            done,
+            initializer,
+        }
+    }
+
+    /// Returns the index of the one-time initialization used in the labels of the Petri net.
+    pub const fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the initializer passed to `std::sync::LazyLock::<T, F>::new`, if any.
+    pub const fn initializer(&self) -> Option<&Initializer> {
+        self.initializer.as_ref()
+    }
+}
+
+/// Returns the type of the one-time initialization on which the function is called,
+/// e.g. `std::sync::OnceLock` for `std::sync::OnceLock::<T>::get_or_init`.
+fn once_type(function_name: &str) -> &str {
+    ONCE_TYPES
+        .iter()
+        .find(|once_type| {
+            function_name
+                .strip_prefix(*once_type)
+                .is_some_and(|method| method.starts_with("::"))
+        })
+        .unwrap_or_else(|| panic!("BUG: `{function_name}` should be a function of a supported one-time initialization type"))
+}
+
+/// Returns the function that creates a one-time initialization of the given type,
+/// whose counter gives the index of the one-time initialization.
+pub fn new_function(once_type: &str) -> &'static str {
+    match once_type {
+        "std::sync::LazyLock" => "std::sync::LazyLock::<T, F>::new",
+        "std::sync::OnceLock" => "std::sync::OnceLock::<T>::new",
+        _ => "std::sync::Once::new",
+    }
+}
+
+/// Returns the type of the one-time initialization and the definition ID of the `static` item
+/// that the constant refers to, e.g. `const {alloc1: &std::sync::Once}` for `static INIT: Once = Once::new()`.
+/// Returns `None` if the constant is not a reference to a `static` one-time initialization.
+pub fn find_static_once<'tcx>(
+    constant: &rustc_middle::mir::ConstOperand<'tcx>,
+    tcx: rustc_middle::ty::TyCtxt<'tcx>,
+) -> Option<(&'static str, rustc_hir::def_id::DefId)> {
+    let rustc_middle::mir::Const::Val(
+        rustc_middle::mir::ConstValue::Scalar(rustc_middle::mir::interpret::Scalar::Ptr(
+            pointer,
+            _,
+        )),
+        ty,
+    ) = constant.const_
+    else {
+        return None;
+    };
+    let rustc_middle::ty::TyKind::Adt(adt_def, _) = ty.builtin_deref(true)?.kind() else {
+        return None;
+    };
+    let type_name = tcx.def_path_str(adt_def.did());
+    let once_type = ONCE_TYPES
+        .iter()
+        .find(|once_type| **once_type == type_name)?;
+    match tcx.try_get_global_alloc(pointer.provenance.alloc_id())? {
+        rustc_middle::mir::interpret::GlobalAlloc::Static(def_id) => Some((once_type, def_id)),
+        _ => None,
+    }
+}
+
+/// Extracts the initializer of a `static` `std::sync::LazyLock` from the body of the static,
+/// i.e. the closure or the function coerced to the function pointer passed to `new`.
+/// The closure cannot capture any sync variables.
+///
+/// Returns `None` if no closure or function is coerced in the body.
+pub fn extract_static_initializer(
+    def_id: rustc_hir::def_id::DefId,
+    tcx: rustc_middle::ty::TyCtxt<'_>,
+) -> Option<Initializer> {
+    use rustc_middle::mir::{CastKind, Rvalue, StatementKind};
+    use rustc_middle::ty::adjustment::PointerCoercion;
+
+    let body = tcx.mir_for_ctfe(def_id);
+    body.basic_blocks
+        .iter()
+        .flat_map(|block_data| &block_data.statements)
+        .find_map(|statement| {
+            let StatementKind::Assign(assignment) = &statement.kind else {
+                return None;
+            };
+            let Rvalue::Cast(
+                CastKind::PointerCoercion(
+                    PointerCoercion::ClosureFnPointer(_) | PointerCoercion::ReifyFnPointer,
+                    _,
+                ),
+                operand,
+                _,
+            ) = &assignment.1
+            else {
+                return None;
+            };
+            match operand.ty(body, tcx).kind() {
+                rustc_middle::ty::TyKind::Closure(def_id, _)
+                | rustc_middle::ty::TyKind::FnDef(def_id, _) => Some(Initializer {
+                    def_id: *def_id,
+                    aggregate: Vec::new(),
+                }),
+                _ => None,
+            }
+        })
+}
+
+/// Checks whether the function creates a one-time initialization.
+pub fn is_new_function(function_name: &str) -> bool {
+    matches!(
+        function_name,
+        "std::sync::LazyLock::<T, F>::new"
+            | "std::sync::Once::new"
+            | "std::sync::OnceLock::<T>::new"
+    )
+}
+
+/// Checks whether the function runs the initializer of a one-time initialization if it has not run yet.
+pub fn is_init_function(function_name: &str) -> bool {
+    matches!(
+        function_name,
+        "std::sync::LazyLock::<T, F>::force"
+            | "std::sync::Once::call_once"
+            | "std::sync::OnceLock::<T>::get_or_init"
+    )
+}
+
+/// Extracts the initializer passed as the n-th argument to the function.
+/// The sync variables captured by the closure are copied from the aggregate linked to it.
+///
+/// Returns `None` if the initializer is a function pointer, which cannot be resolved statically.
+pub fn extract_initializer<'tcx>(
+    args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
+    index: usize,
+    memory: &Memory<'tcx>,
+    caller_function_def_id: rustc_hir::def_id::DefId,
+    tcx: rustc_middle::ty::TyCtxt<'tcx>,
+) -> Option<Initializer> {
+    let operand = &args.get(index)?.node;
+    let def_id =
+        extract_def_id_of_called_function_from_operand(operand, caller_function_def_id, tcx)?;
+    let aggregate = extract_nth_argument_as_place(args, index)
+        .filter(|place| memory.is_aggregate(place))
+        .map_or_else(Vec::new, |place| memory.copy_aggregate(&place));
+    Some(Initializer { def_id, aggregate })
+}
+
+/// Call to `std::sync::Once::new`, `std::sync::OnceLock::<T>::new` or `std::sync::LazyLock::<T, F>::new`.
+/// Non-recursive call for the translation process.
+///
+/// - Creates a new `Once` with the given initializer.
+/// - Links the return place to the `Once`.
+pub fn call_new<'tcx>(
+    function_name: &str,
+    index: usize,
+    initializer: Option<Initializer>,
+    destination: rustc_middle::mir::Place<'tcx>,
+    places: Places,
+    net: &mut PetriNet,
+    memory: &mut Memory<'tcx>,
+) {
+    call_foreign_function(function_name, index, places, net);
+    // Create a new one-time initialization
+    let once = Once::new(once_type(function_name), index, initializer, net);
+    // The return value contains a new one-time initialization. Link the local variable to it.
+    memory.link_once(destination, once);
+    debug!("NEW ONCE: {destination:?}");
+}
+
+/// Call to a function that runs the initializer if it has not run yet, see `is_init_function`.
+/// Non-recursive call for the translation process.
+///
+/// - Connects the start place and the end place through a transition that requires the state `done`.
+/// - Adds the places where the initializer starts, ends and panics.
+/// - Connects the start place to the start of the initializer through a transition
+///   that changes the state from `uninit` to `running`.
+/// - Connects the end of the initializer to the end place through a transition
+///   that changes the state from `running` to `done`.
+/// - Connects the panic place of the initializer to the given `panic_place` through a transition
+///   that changes the state from `running` back to `uninit`.
+///
+/// Returns the start place, the end place and the panic place of the initializer,
+/// so that the caller can translate its body in between.
+///
+/// Waiting for the initialization never unwinds, therefore the cleanup place is not modelled.
+/// A panic in the initializer unwinds from the body of the initializer itself.
+/// After the panic, the next caller runs the initializer again. This is the behavior of `std::sync::OnceLock`.
+/// `std::sync::Once` and `std::sync::LazyLock` are poisoned instead and the next caller panics,
+/// which is over-approximated by running the initializer again.
+pub fn call_init(
+    function_name: &str,
+    index: usize,
+    once: &Once,
+    places: Places,
+    panic_place: &PlaceRef,
+    net: &mut PetriNet,
+) -> (PlaceRef, PlaceRef, PlaceRef) {
+    let (start_place, end_place) = places.get_start_end_place();
+    let already_done_transition = connect_places(
+        net,
+        &start_place,
+        &end_place,
+        &already_done_transition_label(function_name, index),
+    );
+    add_arc_place_transition(net, &once.done, &already_done_transition);
+    add_arc_transition_place(net, &already_done_transition, &once.done);
+
+    let (initializer_start_label, initializer_end_label, initializer_panic_label) =
+        initializer_place_labels(function_name, index);
+    let initializer_start = net.add_place(&initializer_start_label);
+    let initializer_end = net.add_place(&initializer_end_label);
+    let initializer_panic = net.add_place(&initializer_panic_label);
+    let (init_start_label, init_end_label, init_panic_label) =
+        init_transition_labels(function_name, index);
+
+    let init_start_transition =
+        connect_places(net, &start_place, &initializer_start, &init_start_label);
+    add_arc_place_transition(net, &once.uninit, &init_start_transition);
+    add_arc_transition_place(net, &init_start_transition, &once.running);
+
+    let init_end_transition = connect_places(net, &initializer_end, &end_place, &init_end_label);
+    add_arc_place_transition(net, &once.running, &init_end_transition);
+    add_arc_transition_place(net, &init_end_transition, &once.done);
+
+    let init_panic_transition =
+        connect_places(net, &initializer_panic, panic_place, &init_panic_label);
+    add_arc_place_transition(net, &once.running, &init_panic_transition);
+    add_arc_transition_place(net, &init_panic_transition, &once.uninit);
+    debug!(
+        "INITIALIZE ONCE {} DUE TO TRANSITION {init_start_transition}",
+        once.index
+    );
+
+    (initializer_start, initializer_end, initializer_panic)
+}
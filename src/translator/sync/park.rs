@@ -0,0 +1,147 @@
+//! Representation of the park token of a thread (`std::thread::park` and `std::thread::Thread::unpark`)
+//! in the Petri net.
+//!
+//! Every thread stores at most one token.
+//! Calling `unpark` on the handle of a thread (`std::thread::Thread`) stores the token,
+//! so an `unpark` before the `park` is not lost.
+//! Calling `park` consumes the token, i.e. it blocks until the token is available.
+//! Spurious wake-ups of `park` are not modelled.
+//!
+//! The token is modelled with two complementary places: `token` and `no_token`.
+//! Exactly one of them has a token.
+//! `unpark` is translated as two transitions:
+//! One stores the token if there is none, the other keeps the token that is already stored.
+//!
+//! The handles returned by `std::thread::current` and `std::thread::JoinHandle::<T>::thread`
+//! are linked to the park token of the corresponding thread.
+//! The park token of a thread is created the first time that it is needed.
+
+use log::debug;
+
+use crate::data_structures::petri_net_interface::{
+    add_arc_place_transition, add_arc_transition_place, connect_places,
+};
+use crate::data_structures::petri_net_interface::{PetriNet, PlaceRef, TransitionRef};
+use crate::naming::thread::{keep_park_token_transition_label, park_token_place_labels};
+use crate::translator::function::Places;
+use crate::translator::mir_function::memory::Memory;
+use crate::translator::special_function::call_foreign_function;
+use crate::utils::extract_nth_argument_as_place;
+
+#[derive(PartialEq, Eq)]
+pub struct ParkToken {
+    thread: Option<usize>,
+    token: PlaceRef,
+    no_token: PlaceRef,
+}
+
+impl ParkToken {
+    /// Creates a new park token for the thread with the given index (`None` for the main thread).
+    /// Adds the places `token` and `no_token` (with a token) to the Petri net.
+    pub fn new(thread: Option<usize>, net: &mut PetriNet) -> Self {
+        let (token_label, no_token_label) = park_token_place_labels(thread);
+        let token = net.add_place(&token_label);
+        let no_token = net.add_place(&no_token_label);
+        net.add_token(&no_token, 1)
+            .expect("BUG: Adding initial token to `no_token` should not cause an overflow");
+
+        Self {
+            thread,
+            token,
+            no_token,
+        }
+    }
+
+    /// Returns the index of the thread that owns the park token or `None` for the main thread.
+    pub const fn thread(&self) -> Option<usize> {
+        self.thread
+    }
+
+    /// Adds the arcs for a call to `unpark`.
+    /// The `store_transition` may only fire if there is no token and stores it.
+    /// The `keep_transition` may only fire if the token is already stored and regenerates it.
+    fn add_unpark_arcs(
+        &self,
+        store_transition: &TransitionRef,
+        keep_transition: &TransitionRef,
+        net: &mut PetriNet,
+    ) {
+        add_arc_place_transition(net, &self.no_token, store_transition);
+        add_arc_transition_place(net, store_transition, &self.token);
+        add_arc_place_transition(net, &self.token, keep_transition);
+        add_arc_transition_place(net, keep_transition, &self.token);
+    }
+
+    /// Adds the arcs for a call to `park`.
+    /// The transition may only fire if the token is stored and consumes it.
+    fn add_park_arcs(&self, park_transition: &TransitionRef, net: &mut PetriNet) {
+        add_arc_place_transition(net, &self.token, park_transition);
+        add_arc_transition_place(net, park_transition, &self.no_token);
+    }
+}
+
+/// Call to `std::thread::park` in the thread that owns the given park token.
+/// Non-recursive call for the translation process.
+///
+/// - Adds the arcs that consume the token.
+///
+/// Parking never unwinds, therefore the cleanup place is not modelled.
+pub fn call_park(
+    function_name: &str,
+    index: usize,
+    park_token: &ParkToken,
+    places: Places,
+    net: &mut PetriNet,
+) {
+    let places = places.ignore_cleanup_place();
+    let transitions = call_foreign_function(function_name, index, places, net);
+    let park_transition = transitions.get_default();
+    park_token.add_park_arcs(park_transition, net);
+    debug!(
+        "PARK THREAD {:?} DUE TO TRANSITION {park_transition}",
+        park_token.thread()
+    );
+}
+
+/// Call to `std::thread::Thread::unpark`.
+/// Non-recursive call for the translation process.
+///
+/// - Retrieves the park token linked to the first argument (the thread handle).
+/// - Connects the start place and the end place through a second transition
+///   for the case that the token is already stored.
+/// - Adds the arcs that store or keep the token.
+///
+/// Unparking never unwinds, therefore the cleanup place is not modelled.
+pub fn call_unpark<'tcx>(
+    function_name: &str,
+    index: usize,
+    args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
+    places: Places,
+    net: &mut PetriNet,
+    memory: &Memory<'tcx>,
+) {
+    let (start_place, end_place) = places.get_start_end_place();
+    let places = Places::Basic {
+        start_place: start_place.clone(),
+        end_place: end_place.clone(),
+    };
+    let transitions = call_foreign_function(function_name, index, places, net);
+    let store_transition = transitions.get_default();
+    let keep_transition = connect_places(
+        net,
+        &start_place,
+        &end_place,
+        &keep_park_token_transition_label(function_name, index),
+    );
+
+    // Retrieve the park token from the local variable passed to the function as an argument.
+    let self_ref = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+        panic!("BUG: `{function_name}` should receive the self reference as a place")
+    });
+    let park_token_ref = memory.get_thread_handle(&self_ref);
+    park_token_ref.add_unpark_arcs(store_transition, &keep_transition, net);
+    debug!(
+        "UNPARK THREAD {:?} DUE TO TRANSITION {store_transition}",
+        park_token_ref.thread()
+    );
+}
@@ -0,0 +1,181 @@
+//! Representation of a `crossbeam_channel::Select` in the Petri net.
+//!
+//! The `Select` only collects the operations registered with `send` and `recv` during the translation.
+//! It does not add places to the Petri net.
+//!
+//! The call to `select` blocks until one of the registered operations is ready and selects it.
+//! It is translated as the transitions of every operation, see `Channel::connect_selected_send`
+//! and `Channel::connect_selected_receive`, all of them connecting the start place and the end place of the call.
+//! The first one that is enabled may fire, i.e. the choice between ready operations is nondeterministic.
+//! An operation on a zero-capacity channel is only ready if the other end is already waiting.
+//! The selected operation happens in the call to `select` itself,
+//! completing it with `SelectedOperation::send` or `SelectedOperation::recv` does nothing.
+//!
+//! The index returned by `SelectedOperation::index` is linked to these transitions like a value
+//! loaded from an atomic variable, see `AtomicValue`. The switch int terminator that matches it
+//! therefore only takes the branch of the operation that was selected.
+//! The `select!` macro of the stand-in in `examples/stubs` expands to this `match`.
+//! The real `crossbeam_channel::select!` expands to internal functions of the crate instead, which are not supported.
+
+use log::debug;
+use std::cell::RefCell;
+
+use crate::data_structures::petri_net_interface::PetriNet;
+use crate::naming::channel::selected_operation_label;
+use crate::translator::function::Places;
+use crate::translator::mir_function::memory::{ChannelRef, Memory};
+use crate::translator::special_function::call_foreign_function;
+use crate::translator::sync::AtomicValue;
+use crate::utils::extract_nth_argument_as_place;
+
+/// An operation registered in a `Select`.
+#[derive(PartialEq, Eq)]
+enum Operation {
+    Send(ChannelRef),
+    Receive(ChannelRef),
+}
+
+#[derive(PartialEq, Eq, Default)]
+pub struct Select {
+    operations: RefCell<Vec<Operation>>,
+}
+
+/// Call to `crossbeam_channel::Select::<'a>::new`.
+/// Non-recursive call for the translation process.
+///
+/// - Creates a new `Select` without operations.
+/// - Links the return place to the `Select`.
+pub fn call_new<'tcx>(
+    function_name: &str,
+    index: usize,
+    destination: rustc_middle::mir::Place<'tcx>,
+    places: Places,
+    net: &mut PetriNet,
+    memory: &mut Memory<'tcx>,
+) {
+    call_foreign_function(function_name, index, places, net);
+    memory.link_select(destination, Select::default());
+    debug!("NEW SELECT: {destination:?}");
+}
+
+/// Call to `crossbeam_channel::Select::<'a>::send` or `crossbeam_channel::Select::<'a>::recv`.
+/// Non-recursive call for the translation process.
+///
+/// - Retrieves the `Select` linked to the first argument and the channel linked to the second argument.
+/// - Registers the operation on the channel. Its index is the number of operations registered before.
+///
+/// Registering an operation does not block and never unwinds, therefore the cleanup place is not modelled.
+pub fn call_register<'tcx>(
+    function_name: &str,
+    index: usize,
+    args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
+    places: Places,
+    net: &mut PetriNet,
+    memory: &Memory<'tcx>,
+) {
+    call_foreign_function(function_name, index, places.ignore_cleanup_place(), net);
+    let self_ref = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+        panic!("BUG: `{function_name}` should receive the self reference as a place")
+    });
+    let end_ref = extract_nth_argument_as_place(args, 1).unwrap_or_else(|| {
+        panic!("BUG: `{function_name}` should receive the end of the channel as a place")
+    });
+    let channel_ref = memory.get_channel(&end_ref).clone();
+    let operation = if function_name.ends_with("::send") {
+        Operation::Send(channel_ref)
+    } else {
+        Operation::Receive(channel_ref)
+    };
+    let mut operations = memory.get_select(&self_ref).operations.borrow_mut();
+    operations.push(operation);
+    debug!(
+        "REGISTER OPERATION {} IN {self_ref:?}",
+        operations.len() - 1
+    );
+}
+
+/// Call to `crossbeam_channel::Select::<'a>::select`.
+/// Non-recursive call for the translation process.
+///
+/// - Retrieves the `Select` linked to the first argument.
+/// - Connects the start place and the end place through the transitions of every registered operation.
+/// - Links the return place (the `SelectedOperation`) to these transitions,
+///   with the index of the operation as the outcome of each transition.
+///
+/// Without registered operations the call blocks forever, as it does in practice.
+/// Selecting never unwinds, therefore the cleanup place is not modelled.
+pub fn call_select<'tcx>(
+    function_name: &str,
+    index: usize,
+    args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
+    destination: rustc_middle::mir::Place<'tcx>,
+    places: Places,
+    net: &mut PetriNet,
+    memory: &mut Memory<'tcx>,
+) {
+    let self_ref = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+        panic!("BUG: `{function_name}` should receive the self reference as a place")
+    });
+    let (start_place, end_place) = places.get_start_end_place();
+    let mut transitions = Vec::new();
+    let mut outcomes = Vec::new();
+    for (position, operation) in memory
+        .get_select(&self_ref)
+        .operations
+        .borrow()
+        .iter()
+        .enumerate()
+    {
+        let label = selected_operation_label(function_name, index, position);
+        let operation_transitions = match operation {
+            Operation::Send(channel_ref) => {
+                channel_ref.connect_selected_send(&label, &start_place, &end_place, net)
+            }
+            Operation::Receive(channel_ref) => {
+                channel_ref.connect_selected_receive(&label, &start_place, &end_place, net)
+            }
+        };
+        outcomes.extend(std::iter::repeat(position as u128).take(operation_transitions.len()));
+        transitions.extend(operation_transitions);
+    }
+    memory.link_atomic_value(
+        destination,
+        AtomicValue::new_without_spin_loops(transitions, outcomes),
+    );
+    debug!("SELECT OPERATION FROM {self_ref:?}: {destination:?}");
+}
+
+/// Call to `crossbeam_channel::SelectedOperation::<'a>::index`.
+/// Non-recursive call for the translation process.
+///
+/// Links the return place to the transitions of the `select` call that returned the selected operation.
+/// The call never unwinds, therefore the cleanup place is not modelled.
+pub fn call_index<'tcx>(
+    function_name: &str,
+    index: usize,
+    args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
+    destination: rustc_middle::mir::Place<'tcx>,
+    places: Places,
+    net: &mut PetriNet,
+    memory: &mut Memory<'tcx>,
+) {
+    call_foreign_function(function_name, index, places.ignore_cleanup_place(), net);
+    let self_ref = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+        panic!("BUG: `{function_name}` should receive the self reference as a place")
+    });
+    let selected = memory.find_atomic_value(&self_ref).unwrap_or_else(|| {
+        panic!("BUG: The selected operation {self_ref:?} should be linked to the transitions of `select`")
+    });
+    let selected_index = selected.map(|outcome| outcome);
+    memory.link_atomic_value(destination, selected_index);
+    debug!("INDEX OF SELECTED OPERATION {self_ref:?}: {destination:?}");
+}
+
+/// Call to `crossbeam_channel::SelectedOperation::<'a>::send` or `crossbeam_channel::SelectedOperation::<'a>::recv`.
+/// Non-recursive call for the translation process.
+///
+/// The operation already happened in the call to `select`, therefore completing it does nothing.
+/// The call never unwinds, therefore the cleanup place is not modelled.
+pub fn call_complete(function_name: &str, index: usize, places: Places, net: &mut PetriNet) {
+    call_foreign_function(function_name, index, places.ignore_cleanup_place(), net);
+}
@@ -0,0 +1,154 @@
+//! Representation of a semaphore and a semaphore permit in the Petri net.
+//!
+//! The semaphore stores one reference to the place in the Petri net
+//! whose tokens are the available permits.
+//! The place starts with as many tokens as the initial number of permits,
+//! which must be a constant.
+//!
+//! Awaiting `acquire` takes a token from the place, i.e. it blocks while no permit is available.
+//! A semaphore permit simply contains a reference to the corresponding semaphore.
+//! Dropping the permit puts the token back.
+
+use log::debug;
+
+use crate::data_structures::petri_net_interface::{
+    add_arc_place_transition, add_arc_transition_place,
+};
+use crate::data_structures::petri_net_interface::{PetriNet, PlaceRef, TransitionRef};
+use crate::naming::semaphore::place_label;
+use crate::translator::function::Places;
+use crate::translator::mir_function::memory::{Memory, SemaphoreRef};
+use crate::translator::special_function::call_foreign_function;
+use crate::utils::extract_nth_argument_as_place;
+
+#[derive(PartialEq, Eq)]
+pub struct Semaphore {
+    index: usize,
+    permits: PlaceRef,
+}
+
+impl Semaphore {
+    /// Creates a new semaphore whose label is based on `index`.
+    /// Adds a place with one token for each permit to the Petri Net.
+    pub fn new(index: usize, permits: usize, net: &mut PetriNet) -> Self {
+        let label = place_label(index);
+        let place = net.add_place(&label);
+        net.add_token(&place, permits).expect(
+            "BUG: Adding the initial permits to the semaphore place should not cause an overflow",
+        );
+
+        Self {
+            index,
+            permits: place,
+        }
+    }
+
+    /// Returns the index of the semaphore used in the labels of the Petri net.
+    pub const fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Adds an acquire arc for this semaphore.
+    /// Connects the semaphore's place to the transition, then the transition will only
+    /// fire if a permit is available.
+    pub fn add_acquire_arc(&self, acquire_transition: &TransitionRef, net: &mut PetriNet) {
+        add_arc_place_transition(net, &self.permits, acquire_transition);
+    }
+
+    /// Adds a release arc for this semaphore.
+    /// Connects the transition to the semaphore's place, then the transition will
+    /// give the permit back when it fires.
+    pub fn add_release_arc(&self, release_transition: &TransitionRef, net: &mut PetriNet) {
+        add_arc_transition_place(net, release_transition, &self.permits);
+    }
+}
+
+#[derive(PartialEq, Eq)]
+pub struct Permit {
+    pub semaphore: SemaphoreRef,
+}
+
+impl Permit {
+    /// Creates a new semaphore permit for a given semaphore reference.
+    pub const fn new(semaphore: SemaphoreRef) -> Self {
+        Self { semaphore }
+    }
+}
+
+/// Call to `tokio::sync::Semaphore::new`.
+/// Non-recursive call for the translation process.
+///
+/// - Creates a new `Semaphore` with the given number of permits.
+/// - Links the return place to the `Semaphore`.
+pub fn call_new<'tcx>(
+    function_name: &str,
+    index: usize,
+    permits: usize,
+    destination: rustc_middle::mir::Place<'tcx>,
+    places: Places,
+    net: &mut PetriNet,
+    memory: &mut Memory<'tcx>,
+) {
+    call_foreign_function(function_name, index, places, net);
+    // Create a new semaphore
+    let semaphore = Semaphore::new(index, permits, net);
+    // The return value contains a new semaphore. Link the local variable to it.
+    memory.link_semaphore(destination, semaphore);
+    debug!("NEW SEMAPHORE WITH {permits} PERMITS: {destination:?}");
+}
+
+/// Call to `std::future::Future::poll` on the future returned by `tokio::sync::Semaphore::acquire`,
+/// i.e. awaiting the permit.
+/// Non-recursive call for the translation process.
+///
+/// - Retrieves the semaphore linked to the first argument (the pinned future).
+/// - Adds an arc from the place of the semaphore to the transition of this function call.
+/// - Creates a new `SemaphorePermit`.
+/// - Links the return place to the `SemaphorePermit`.
+///
+/// Waiting for a permit never unwinds, therefore the cleanup place is not modelled.
+pub fn call_acquire<'tcx>(
+    function_name: &str,
+    index: usize,
+    args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
+    destination: rustc_middle::mir::Place<'tcx>,
+    places: Places,
+    net: &mut PetriNet,
+    memory: &mut Memory<'tcx>,
+) {
+    let places = places.ignore_cleanup_place();
+    let transitions = call_foreign_function(function_name, index, places, net);
+    let acquire_transition = transitions.get_default();
+
+    // Retrieve the semaphore from the local variable passed to the function as an argument.
+    let future_ref = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+        panic!("BUG: `{function_name}` should receive the pinned future as a place")
+    });
+    let semaphore_ref = memory.get_semaphore(&future_ref);
+    semaphore_ref.add_acquire_arc(acquire_transition, net);
+
+    // The return value contains a new permit. Link the local variable to it.
+    let permit = Permit::new(semaphore_ref.clone());
+    let index = semaphore_ref.index();
+    memory.link_semaphore_permit(destination, permit);
+    debug!(
+        "NEW PERMIT OF SEMAPHORE {index} {destination:?} DUE TO TRANSITION {acquire_transition}"
+    );
+}
+
+/// Checks whether the variable to be dropped is a semaphore permit.
+/// If that is the case, adds a release arc for the semaphore corresponding to the permit.
+/// Otherwise do nothing.
+pub fn handle_permit_drop<'tcx>(
+    place: rustc_middle::mir::Place<'tcx>,
+    release_transition: &TransitionRef,
+    net: &mut PetriNet,
+    memory: &Memory<'tcx>,
+) {
+    if let Some(permit_ref) = memory.find_semaphore_permit(&place) {
+        permit_ref
+            .semaphore
+            .add_release_arc(release_transition, net);
+        debug!("DROP SEMAPHORE PERMIT {place:?} DUE TO TRANSITION {release_transition}");
+    }
+}
@@ -19,19 +19,32 @@
 //! Therefore we store the basic information we need
 //! to translate the thread function and defer the translation.
 //! The function executed by the thread is translated to a Petri net just as any other.
+//!
+//! A task spawned with `tokio::spawn` is modelled as a thread as well.
+//! It runs the coroutine of the spawned future instead of a closure.
+//!
+//! If the panics are modelled, a panicking thread ends in its own panic place instead of the end place.
+//! The call to `std::thread::JoinHandle::<T>::join` then has two transitions:
+//! One takes the token from the end place and returns `Ok`,
+//! the other takes the token from the panic place and returns `Err`.
+//! The result is stored in one of two places, so that calling `unwrap` on it can branch accordingly.
 
 use log::{debug, info};
 use std::cell::OnceCell;
 
 use crate::data_structures::petri_net_interface::{
-    add_arc_place_transition, add_arc_transition_place,
+    add_arc_place_transition, add_arc_transition_place, connect_places,
 };
 use crate::data_structures::petri_net_interface::{PetriNet, PlaceRef, TransitionRef};
-use crate::naming::thread::{end_place_label, start_place_label};
+use crate::naming::thread::{
+    end_place_label, join_panicked_transition_label, join_result_place_labels, panic_place_label,
+    start_place_label,
+};
 use crate::translator::function::Places;
-use crate::translator::mir_function::memory::{Memory, Value};
+use crate::translator::mir_function::memory::{FutureRef, Memory, Value};
 use crate::translator::special_function::call_foreign_function;
-use crate::utils::{extract_nth_argument_as_place, get_field_number_in_projection};
+use crate::translator::sync::link_captured_sync_variables;
+use crate::utils::extract_nth_argument_as_place;
 
 pub struct Thread {
     /// The transition from which the thread branches off at the start.
@@ -40,8 +53,13 @@ pub struct Thread {
     def_id: rustc_hir::def_id::DefId,
     /// The aggregate value containing the sync variables passed to the thread.
     aggregate: Vec<Value>,
+    /// The future run by the thread if it is a task spawned with `tokio::spawn`.
+    future: Option<FutureRef>,
     /// The transition to which the thread joins in at the end.
     join_transition: OnceCell<TransitionRef>,
+    /// The transition to which the thread joins in after a panic.
+    /// It is only set if the panics are modelled.
+    join_panicked_transition: OnceCell<TransitionRef>,
     /// An index to identify the thread.
     pub index: usize,
 }
@@ -67,11 +85,32 @@ impl Thread {
             spawn_transition,
             def_id: thread_function_def_id,
             aggregate,
+            future: None,
+            join_transition: OnceCell::new(),
+            join_panicked_transition: OnceCell::new(),
+            index,
+        }
+    }
+
+    /// Creates a new thread for a task that runs the given future, without a join transition.
+    /// The join transition must be set later.
+    pub fn new_task(spawn_transition: TransitionRef, future: FutureRef, index: usize) -> Self {
+        Self {
+            spawn_transition,
+            def_id: future.def_id,
+            aggregate: Vec::new(),
+            future: Some(future),
             join_transition: OnceCell::new(),
+            join_panicked_transition: OnceCell::new(),
             index,
         }
     }
 
+    /// Returns the future run by the thread if it is a task.
+    pub const fn future(&self) -> Option<&FutureRef> {
+        self.future.as_ref()
+    }
+
     /// Sets the transition that models joining this thread.
     pub fn set_join_transition(&self, join_transition: TransitionRef) {
         let result = self.join_transition.set(join_transition);
@@ -100,45 +139,38 @@ impl Thread {
         (self.def_id, thread_start_place, thread_end_place)
     }
 
+    /// Sets the transition that models joining this thread after it panicked.
+    pub fn set_join_panicked_transition(&self, join_panicked_transition: TransitionRef) {
+        let result = self.join_panicked_transition.set(join_panicked_transition);
+        assert!(
+            result.is_ok(),
+            "BUG: The join transition for a panicked thread may only be set once"
+        );
+    }
+
+    /// Adds a panic place for the thread to the Petri net.
+    /// Connects the panic place to the transition that joins the panicked thread (if available).
+    /// Returns the panic place.
+    pub fn add_panic_place(&self, net: &mut PetriNet) -> PlaceRef {
+        let thread_panic_place = net.add_place(&panic_place_label(self.index));
+        if let Some(join_panicked_transition) = self.join_panicked_transition.get() {
+            add_arc_place_transition(net, &thread_panic_place, join_panicked_transition);
+        }
+        thread_panic_place
+    }
+
     /// Moves the aggregated value containing the sync variables to the new function's memory.
-    /// Checks the debug info to detect places containing a synchronization variable passed to the new thread.
-    /// We are only interested in places of the form `_1.X` since `std::thread::spawn` only receives one argument.
-    /// <https://doc.rust-lang.org/stable/nightly-rustc/rustc_middle/mir/struct.VarDebugInfo.html>
-    ///
-    /// # Examples
-    ///
-    /// The following line in the MIR output indicates that `_1.0` contains a mutex.
-    /// `debug copy_data => (_1.0: std::sync::Arc<std::sync::Mutex<i32>>)`
+    /// See `link_captured_sync_variables` for details.
     pub fn move_sync_variables<'tcx>(
         &self,
         memory: &mut Memory<'tcx>,
         tcx: rustc_middle::ty::TyCtxt<'tcx>,
     ) {
-        // Link the aggregate itself, the local _1
-        let base_place = rustc_middle::mir::Place {
-            local: rustc_middle::mir::Local::from_usize(1),
-            projection: rustc_middle::ty::List::empty(),
-        };
-        memory.link_aggregate(base_place, self.aggregate.clone());
+        link_captured_sync_variables(self.def_id, self.aggregate.clone(), memory, tcx);
         debug!(
-            "MOVED AGGREGATE VALUE {base_place:?} WITH SYNC VARIABLES TO THE THREAD {}",
+            "MOVED AGGREGATE VALUE WITH SYNC VARIABLES TO THE THREAD {}",
             self.index
         );
-
-        let body = tcx.optimized_mir(self.def_id);
-        for debug_info in &body.var_debug_info {
-            let rustc_middle::mir::VarDebugInfoContents::Place(place) = debug_info.value else {
-                // Not interested in the other variants of `VarDebugInfoContents`
-                continue;
-            };
-            if place.local != rustc_middle::mir::Local::from(1u32) {
-                // Not interested in locals other that `_1.X`
-                continue;
-            }
-            let field_number = get_field_number_in_projection(&place);
-            memory.link_field_in_aggregate(place, base_place, field_number);
-            debug!("LINKED FIELD {place:?} IN AGGREGATE",);
-        }
     }
 }
 
@@ -172,3 +204,66 @@ pub fn call_join<'tcx>(
     thread_ref.set_join_transition(transition);
     info!("Found join call for thread {}", thread_ref.index);
 }
+
+/// The result of a call to `std::thread::JoinHandle::<T>::join` if the panics are modelled.
+#[derive(PartialEq, Eq)]
+pub struct JoinResult {
+    /// Has a token if the thread ended normally.
+    pub ok: PlaceRef,
+    /// Has a token if the thread panicked.
+    pub err: PlaceRef,
+}
+
+/// Call to `std::thread::JoinHandle::<T>::join` if the panics are modelled.
+/// Non-recursive call for the translation process.
+///
+/// - Translates the call as in `call_join`.
+/// - Connects the start place and the end place through a second transition
+///   that joins the thread after it panicked.
+/// - Creates two places for the result and connects each transition to one of them.
+/// - Links the return place to the `JoinResult`.
+pub fn call_join_with_panics<'tcx>(
+    function_name: &str,
+    index: usize,
+    args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
+    destination: rustc_middle::mir::Place<'tcx>,
+    places: Places,
+    net: &mut PetriNet,
+    memory: &mut Memory<'tcx>,
+) {
+    let (start_place, end_place) = places.get_start_end_place();
+    let places = Places::Basic {
+        start_place: start_place.clone(),
+        end_place: end_place.clone(),
+    };
+    call_join(function_name, index, args, places, net, memory);
+
+    let self_ref = extract_nth_argument_as_place(args, 0).unwrap_or_else(|| {
+        panic!("BUG: `{function_name}` should receive the self reference as a place")
+    });
+    let thread_ref = memory.get_join_handle(&self_ref).clone();
+    let join_panicked_transition = connect_places(
+        net,
+        &start_place,
+        &end_place,
+        &join_panicked_transition_label(thread_ref.index),
+    );
+
+    let (ok_label, err_label) = join_result_place_labels(thread_ref.index);
+    let result = JoinResult {
+        ok: net.add_place(&ok_label),
+        err: net.add_place(&err_label),
+    };
+    let join_transition = thread_ref.join_transition.get().unwrap_or_else(|| {
+        panic!(
+            "BUG: The join transition of thread {} should be set",
+            thread_ref.index
+        )
+    });
+    add_arc_transition_place(net, join_transition, &result.ok);
+    add_arc_transition_place(net, &join_panicked_transition, &result.err);
+    thread_ref.set_join_panicked_transition(join_panicked_transition);
+
+    memory.link_join_result(destination, result);
+    debug!("NEW JOIN RESULT: {destination:?}");
+}
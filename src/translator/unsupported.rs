@@ -0,0 +1,79 @@
+//! Submodule for handling constructs that the translator cannot model.
+//!
+//! Examples are unknown MIR terminators, calls through function pointers
+//! or synchronization primitives whose variable could not be tracked in the memory.
+//!
+//! The behavior is controlled by the `UnsupportedPolicy`:
+//! - `Abort` stops the translation with an error that describes the first unsupported construct.
+//! - `Havoc` models the construct as a nondeterministic subnet that may block, return or panic.
+//!   This is a sound over-approximation of the real behavior.
+//! - `Skip` treats the construct as if it were not present in the code.
+//!
+//! Every construct modelled with `Havoc` or `Skip` is recorded as an `Approximation`,
+//! so that the user knows how much to trust the verdict of the model checker.
+//! Supported constructs whose model is imprecise are recorded as an `Approximation` without a policy.
+
+/// Possible policies for dealing with unsupported constructs during the translation.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum UnsupportedPolicy {
+    /// Stop the translation with an error.
+    #[default]
+    Abort,
+    /// Model the construct as a nondeterministic subnet that may block, return or panic.
+    Havoc,
+    /// Ignore the construct and continue with the next statement.
+    Skip,
+}
+
+impl std::str::FromStr for UnsupportedPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "abort" => Ok(Self::Abort),
+            "havoc" => Ok(Self::Havoc),
+            "skip" => Ok(Self::Skip),
+            _ => Err(format!(
+                "invalid value '{s}', possible values are: abort, havoc, skip"
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for UnsupportedPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Abort => write!(f, "abort"),
+            Self::Havoc => write!(f, "havoc"),
+            Self::Skip => write!(f, "skip"),
+        }
+    }
+}
+
+/// A construct that could not be modelled exactly and was approximated instead.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Approximation {
+    /// A short description of the construct.
+    pub construct: String,
+    /// The name of the function where the construct was found.
+    pub function_name: String,
+    /// The location of the construct in the source code.
+    pub location: String,
+    /// The policy used to model the construct.
+    /// `None` if the construct is supported, but its model is imprecise.
+    pub policy: Option<UnsupportedPolicy>,
+}
+
+impl std::fmt::Display for Approximation {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{} in `{}` at {}",
+            self.construct, self.function_name, self.location
+        )?;
+        if let Some(policy) = self.policy {
+            write!(f, " (modelled with `{policy}`)")?;
+        }
+        Ok(())
+    }
+}
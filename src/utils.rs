@@ -12,13 +12,20 @@
 /// or a closure (`rustc_middle::ty::TyKind::Closure`)
 /// <https://doc.rust-lang.org/stable/nightly-rustc/rustc_middle/ty/enum.TyKind.html>
 ///
+/// If the type is a function pointer (`rustc_middle::ty::TyKind::FnPtr`),
+/// the called function cannot be resolved statically and `None` is returned.
+///
 /// This method is used to know which function will be called as part of the `Call` MIR Terminator.
 /// <https://doc.rust-lang.org/stable/nightly-rustc/rustc_middle/mir/syntax/enum.TerminatorKind.html#variant.Call>
+///
+/// # Panics
+///
+/// If the type of the operand is not a function definition, a closure or a function pointer, then the function panics.
 pub fn extract_def_id_of_called_function_from_operand<'tcx>(
     operand: &rustc_middle::mir::Operand<'tcx>,
     caller_function_def_id: rustc_hir::def_id::DefId,
     tcx: rustc_middle::ty::TyCtxt<'tcx>,
-) -> rustc_hir::def_id::DefId {
+) -> Option<rustc_hir::def_id::DefId> {
     let function_type = match operand {
         rustc_middle::mir::Operand::Copy(place) | rustc_middle::mir::Operand::Move(place) => {
             // Find the type through the local declarations of the caller function.
@@ -30,19 +37,32 @@ pub fn extract_def_id_of_called_function_from_operand<'tcx>(
         rustc_middle::mir::Operand::Constant(constant) => constant.ty(),
     };
     match function_type.kind() {
-        rustc_middle::ty::TyKind::FnPtr(_, _) => {
-            unimplemented!(
-                "TyKind::FnPtr not implemented yet. Function pointers are present in the MIR"
-            );
-        }
+        rustc_middle::ty::TyKind::FnPtr(_, _) => None,
         rustc_middle::ty::TyKind::FnDef(def_id, _)
-        | rustc_middle::ty::TyKind::Closure(def_id, _) => *def_id,
+        | rustc_middle::ty::TyKind::Closure(def_id, _) => Some(*def_id),
         _ => {
             panic!("TyKind::FnDef, a function definition, but got: {function_type:?}");
         }
     }
 }
 
+/// Extracts the definition ID of the coroutine from the type of a place,
+/// e.g. the future returned by an `async fn`.
+/// <https://doc.rust-lang.org/stable/nightly-rustc/rustc_middle/ty/enum.TyKind.html#variant.Coroutine>
+///
+/// Returns `None` if the type of the place is not a coroutine.
+pub fn extract_coroutine_def_id<'tcx>(
+    place: &rustc_middle::mir::Place<'tcx>,
+    caller_function_def_id: rustc_hir::def_id::DefId,
+    tcx: rustc_middle::ty::TyCtxt<'tcx>,
+) -> Option<rustc_hir::def_id::DefId> {
+    let body = tcx.optimized_mir(caller_function_def_id);
+    match place.ty(body, tcx).ty.kind() {
+        rustc_middle::ty::TyKind::Coroutine(def_id, _) => Some(*def_id),
+        _ => None,
+    }
+}
+
 /// Extracts the n-th argument from the arguments for the function call.
 /// Returns the place corresponding to that argument.
 ///
@@ -67,6 +87,43 @@ pub fn extract_nth_argument_as_place<'tcx>(
     }
 }
 
+/// Extracts the n-th argument from the arguments for the function call as a constant `usize`,
+/// e.g. the number of permits passed to `tokio::sync::Semaphore::new`.
+///
+/// If the argument can not be found or it is not a constant that can be evaluated,
+/// then the function returns `None`.
+pub fn extract_nth_argument_as_usize<'tcx>(
+    args: &[rustc_span::source_map::Spanned<rustc_middle::mir::Operand<'tcx>>],
+    index: usize,
+    tcx: rustc_middle::ty::TyCtxt<'tcx>,
+) -> Option<usize> {
+    let constant = args.get(index)?.node.constant()?;
+    let value = constant
+        .const_
+        .try_eval_target_usize(tcx, rustc_middle::ty::TypingEnv::fully_monomorphized())?;
+    usize::try_from(value).ok()
+}
+
+/// Evaluates a constant operand as an integer, e.g. the value passed to `std::sync::atomic::AtomicBool::store`.
+/// Booleans are evaluated as 0 or 1. Signed integers keep their sign.
+///
+/// If the operand is not a constant that can be evaluated as a scalar,
+/// then the function returns `None`.
+pub fn evaluate_constant_as_integer<'tcx>(
+    operand: &rustc_middle::mir::Operand<'tcx>,
+    tcx: rustc_middle::ty::TyCtxt<'tcx>,
+) -> Option<i128> {
+    let constant = operand.constant()?;
+    let scalar = constant
+        .const_
+        .try_eval_scalar_int(tcx, rustc_middle::ty::TypingEnv::fully_monomorphized())?;
+    if constant.const_.ty().is_signed() {
+        Some(scalar.to_int(scalar.size()))
+    } else {
+        i128::try_from(scalar.to_uint(scalar.size())).ok()
+    }
+}
+
 /// Extracts the closure passed as the 0-th argument to `std::thread::spawn`.
 /// Returns the place corresponding to that argument.
 ///
@@ -141,3 +198,55 @@ pub fn get_field_number_in_projection(place: &rustc_middle::mir::Place) -> usize
     }
     panic!("BUG: A field number was not found in the place {place:?}");
 }
+
+/// Checks whether the basic block `to` can be reached from the basic block `from`
+/// by following the successors of the terminators in the MIR body, e.g. through the back edge of a loop.
+pub fn check_if_block_reachable(
+    from: rustc_middle::mir::BasicBlock,
+    to: rustc_middle::mir::BasicBlock,
+    body: &rustc_middle::mir::Body,
+) -> bool {
+    let mut visited = std::collections::HashSet::new();
+    let mut pending = vec![from];
+    while let Some(block) = pending.pop() {
+        if block == to {
+            return true;
+        }
+        if visited.insert(block) {
+            pending.extend(body.basic_blocks[block].terminator().successors());
+        }
+    }
+    false
+}
+
+/// Formats the location of a span in the source code as `file:line:column`.
+/// Uses the source map of the current session to find the file.
+/// <https://doc.rust-lang.org/stable/nightly-rustc/rustc_span/source_map/struct.SourceMap.html>
+pub fn format_span(span: rustc_span::Span, tcx: rustc_middle::ty::TyCtxt) -> String {
+    let source_map = tcx.sess.source_map();
+    let (source_file, line, column, _, _) = source_map.span_to_location_info(span);
+    source_file.map_or_else(
+        || "unknown location".to_string(),
+        |source_file| format!("{}:{line}:{column}", source_file.name.prefer_local()),
+    )
+}
+
+/// Finds the definition ID of a function defined in the local crate given its path,
+/// e.g. `main` or `module::function`.
+/// The path is compared against the output of `rustc_middle::ty::TyCtxt::def_path_str`.
+///
+/// Returns `None` if no function with the given path exists in the local crate.
+pub fn find_local_function_by_path(
+    path: &str,
+    tcx: rustc_middle::ty::TyCtxt,
+) -> Option<rustc_hir::def_id::DefId> {
+    tcx.hir()
+        .body_owners()
+        .map(rustc_hir::def_id::LocalDefId::to_def_id)
+        .find(|def_id| {
+            matches!(
+                tcx.def_kind(*def_id),
+                rustc_hir::def::DefKind::Fn | rustc_hir::def::DefKind::AssocFn
+            ) && tcx.def_path_str(*def_id) == path
+        })
+}
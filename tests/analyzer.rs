@@ -0,0 +1,286 @@
+// Linking to the library requires access to the compiler's `rustc_driver` library.
+#![feature(rustc_private)]
+
+use cargo_check_deadlock::{
+    Analyzer, Checker, Comparison, Limits, PrimitiveKind, Property, PropertyVerdict, Verdict,
+};
+
+#[test]
+fn detects_double_lock_deadlock() {
+    let report = Analyzer::from_file("./examples/programs/mutex/double_lock_deadlock.rs")
+        .analyze()
+        .expect("The analysis should succeed");
+
+    assert!(report.is_deadlock());
+    assert!(!report.is_deadlock_free());
+    assert!(
+        !report.witness_path.is_empty(),
+        "A witness path should be reported for the deadlock"
+    );
+}
+
+#[test]
+fn reports_statistics_of_the_deadlock_analysis() {
+    let report = Analyzer::from_file("./examples/programs/mutex/double_lock_deadlock.rs")
+        .properties([Property::Panic])
+        .analyze()
+        .expect("The analysis should succeed");
+
+    let statistics = report
+        .statistics
+        .expect("The statistics of the deadlock analysis should be reported");
+    assert_eq!(statistics.states, 3);
+    assert_eq!(statistics.edges, 2);
+    assert!(report.property_results[0].statistics.is_some());
+}
+
+#[test]
+fn two_threads_sharing_mutex_are_deadlock_free() {
+    let report =
+        cargo_check_deadlock::analyze("./examples/programs/mutex/two_threads_sharing_mutex.rs")
+            .expect("The analysis should succeed");
+
+    assert!(report.is_deadlock_free());
+    assert!(report.witness_path.is_empty());
+}
+
+#[test]
+fn reports_modelled_primitives() {
+    let report = Analyzer::from_file("./examples/programs/condvar/infinite_wait_deadlock.rs")
+        .analyze()
+        .expect("The analysis should succeed");
+
+    let kinds: Vec<PrimitiveKind> = report
+        .primitives
+        .iter()
+        .map(|primitive| primitive.kind)
+        .collect();
+    assert_eq!(kinds, vec![PrimitiveKind::Mutex, PrimitiveKind::Condvar]);
+    assert!(report.primitives[0]
+        .location
+        .ends_with("infinite_wait_deadlock.rs:2:17"));
+}
+
+#[test]
+fn analyzes_source_code_from_string() {
+    let report = Analyzer::from_code(
+        r"
+        fn main() {
+            let mutex = std::sync::Mutex::new(0);
+            let _first = mutex.lock().unwrap();
+            let _second = mutex.lock().unwrap();
+        }
+        ",
+    )
+    .analyze()
+    .expect("The analysis should succeed");
+
+    assert!(report.is_deadlock());
+}
+
+#[test]
+fn source_locations_use_virtual_filename() {
+    let report = Analyzer::from_named_code(
+        "generated.rs",
+        "fn main() {
+    let mutex = std::sync::Mutex::new(0);
+}",
+    )
+    .analyze()
+    .expect("The analysis should succeed");
+
+    assert_eq!(report.primitives.len(), 1);
+    assert_eq!(report.primitives[0].location, "generated.rs:2:17");
+}
+
+#[test]
+fn starts_translation_at_entry_point() {
+    let report = Analyzer::from_code(
+        r"
+        fn deadlock() {
+            let mutex = std::sync::Mutex::new(0);
+            let _first = mutex.lock().unwrap();
+            let _second = mutex.lock().unwrap();
+        }
+
+        fn main() {}
+        ",
+    )
+    .entry_point("deadlock")
+    .analyze()
+    .expect("The analysis should succeed");
+
+    assert!(report.is_deadlock());
+}
+
+#[test]
+fn missing_entry_point_is_an_error() {
+    let result = Analyzer::from_code("fn main() {}")
+        .entry_point("does_not_exist")
+        .translate();
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn passes_rustc_args_to_the_compiler() {
+    let report = Analyzer::from_code(
+        r"
+        fn main() {
+            #[cfg(deadlock)]
+            {
+                let mutex = std::sync::Mutex::new(0);
+                let _first = mutex.lock().unwrap();
+                let _second = mutex.lock().unwrap();
+            }
+        }
+        ",
+    )
+    .rustc_args(["--cfg", "deadlock"])
+    .analyze()
+    .expect("The analysis should succeed");
+
+    assert!(report.is_deadlock());
+}
+
+#[test]
+fn time_limit_gives_inconclusive_verdict() {
+    let report = Analyzer::from_file("./examples/programs/thread/dining_philosophers.rs")
+        .limits(Limits {
+            time_limit: Some(0),
+            marking_limit: None,
+            timeout: None,
+        })
+        .analyze()
+        .expect("The analysis should succeed");
+
+    assert_eq!(
+        report.verdict,
+        Verdict::Inconclusive(
+            "`lola` did not produce a result: time limit reached - aborting".to_string()
+        )
+    );
+    assert!(!report.is_deadlock());
+    assert!(!report.is_deadlock_free());
+}
+
+#[test]
+fn timeout_kills_model_checker() {
+    let report = Analyzer::from_file("./examples/programs/thread/dining_philosophers.rs")
+        .limits(Limits {
+            timeout: Some(0),
+            ..Limits::default()
+        })
+        .analyze()
+        .expect("The analysis should succeed");
+
+    assert_eq!(
+        report.verdict,
+        Verdict::Inconclusive("`lola` was killed after the timeout of 0 seconds".to_string())
+    );
+}
+
+#[test]
+fn missing_lola_executable_gives_inconclusive_verdict() {
+    let report = Analyzer::from_file("./examples/programs/mutex/double_lock_deadlock.rs")
+        .lola_path("./does_not_exist/lola")
+        .analyze()
+        .expect("The analysis should succeed");
+
+    let Verdict::Inconclusive(reason) = report.verdict else {
+        panic!("The verdict should be inconclusive");
+    };
+    assert!(reason.starts_with("`lola` could not be executed at ./does_not_exist/lola"));
+}
+
+#[test]
+fn lola_backend_checks_property_on_translated_net() {
+    let translation = Analyzer::from_file("./examples/programs/mutex/double_lock_deadlock.rs")
+        .translate()
+        .expect("The translation should succeed");
+    let backend = Checker::Lola.backend(Limits::default());
+
+    assert_eq!(backend.name(), "LoLA");
+    assert_eq!(
+        backend.check(&translation.net, &Property::Deadlock),
+        PropertyVerdict::Satisfied
+    );
+    assert_eq!(
+        backend.check(&translation.net, &Property::Panic),
+        PropertyVerdict::NotSatisfied
+    );
+}
+
+#[test]
+fn comparison_of_checkers_is_consistent() {
+    let translation = Analyzer::from_file("./examples/programs/mutex/two_threads_sharing_mutex.rs")
+        .translate()
+        .expect("The translation should succeed");
+    let checkers = [
+        Checker::Lola.backend(Limits::default()),
+        Checker::Verifypn.backend(Limits::default()),
+    ];
+    let comparison = Comparison::run(&translation.net, &Property::Deadlock, &checkers);
+
+    assert_eq!(comparison.verdicts.len(), 2);
+    assert_eq!(
+        comparison.verdicts[0],
+        ("LoLA", PropertyVerdict::NotSatisfied)
+    );
+    assert!(comparison.is_consistent());
+}
+
+#[test]
+fn compares_verdicts_of_several_checkers() {
+    let report = Analyzer::from_file("./examples/programs/mutex/two_threads_sharing_mutex.rs")
+        .compare_with([Checker::Verifypn])
+        .properties([Property::Panic])
+        .analyze()
+        .expect("The analysis should succeed");
+
+    assert_eq!(report.comparisons.len(), 2);
+    assert_eq!(report.comparisons[0].property, Property::Deadlock);
+    assert_eq!(report.comparisons[1].property, Property::Panic);
+    assert_eq!(
+        report.comparisons[0].verdicts[0],
+        ("LoLA", PropertyVerdict::NotSatisfied)
+    );
+    for comparison in &report.comparisons {
+        assert_eq!(comparison.verdicts.len(), 2);
+        assert!(comparison.is_consistent());
+    }
+}
+
+#[test]
+fn disabled_deadlock_analysis_only_checks_properties() {
+    let report = Analyzer::from_file("./examples/programs/mutex/double_lock_deadlock.rs")
+        .deadlock_analysis(false)
+        .properties([Property::Panic])
+        .analyze()
+        .expect("The analysis should succeed");
+
+    assert!(!report.is_deadlock());
+    assert!(report.statistics.is_none());
+    assert_eq!(
+        report.property_results[0].verdict,
+        PropertyVerdict::NotSatisfied
+    );
+}
+
+#[test]
+fn verifypn_does_not_support_raw_formulas() {
+    let report = Analyzer::from_file("./examples/programs/mutex/two_threads_sharing_mutex.rs")
+        .checker(Checker::Verifypn)
+        .properties(["EF PROGRAM_END > 0"
+            .parse()
+            .expect("The formula should be valid")])
+        .analyze()
+        .expect("The analysis should succeed");
+
+    assert_eq!(
+        report.property_results[0].verdict,
+        PropertyVerdict::Inconclusive(
+            "The property `EF PROGRAM_END > 0` is not supported by `verifypn`".to_string()
+        )
+    );
+}
@@ -0,0 +1,29 @@
+mod utils;
+
+mod block_on {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/async_await/block_on.rs",
+        "./examples/results/async_await/block_on/"
+    );
+}
+
+mod guard_dropped_before_await {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/async_await/guard_dropped_before_await.rs",
+        "./examples/results/async_await/guard_dropped_before_await/"
+    );
+}
+
+mod guard_held_across_await {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/async_await/guard_held_across_await.rs",
+        "./examples/results/async_await/guard_held_across_await/"
+    );
+}
+
+mod guard_held_while_awaiting_thread {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/async_await/guard_held_while_awaiting_thread.rs",
+        "./examples/results/async_await/guard_held_while_awaiting_thread/"
+    );
+}
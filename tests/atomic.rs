@@ -0,0 +1,43 @@
+mod utils;
+
+mod compare_exchange_spinlock {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/atomic/compare_exchange_spinlock.rs",
+        "./examples/results/atomic/compare_exchange_spinlock/"
+    );
+}
+
+mod load_on_one_path {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/atomic/load_on_one_path.rs",
+        "./examples/results/atomic/load_on_one_path/"
+    );
+}
+
+mod spin_wait_flag_never_set {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/atomic/spin_wait_flag_never_set.rs",
+        "./examples/results/atomic/spin_wait_flag_never_set/"
+    );
+}
+
+mod spin_wait_flag_set {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/atomic/spin_wait_flag_set.rs",
+        "./examples/results/atomic/spin_wait_flag_set/"
+    );
+}
+
+mod spin_wait_with_lock_in_body {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/atomic/spin_wait_with_lock_in_body.rs",
+        "./examples/results/atomic/spin_wait_with_lock_in_body/"
+    );
+}
+
+mod swap_spinlock_held_while_joining {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/atomic/swap_spinlock_held_while_joining.rs",
+        "./examples/results/atomic/swap_spinlock_held_while_joining/"
+    );
+}
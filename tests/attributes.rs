@@ -0,0 +1,132 @@
+// Linking to the library requires access to the compiler's `rustc_driver` library.
+#![feature(rustc_private)]
+
+use cargo_check_deadlock::{Analyzer, LockOrder};
+
+#[test]
+fn ignored_function_is_not_translated() {
+    let report = Analyzer::from_code(
+        r"
+        use std::sync::Mutex;
+
+        #[cfg_attr(check_deadlock, check_deadlock::ignore)]
+        fn lock_twice(mutex: &Mutex<i32>) {
+            let _first = mutex.lock().unwrap();
+            let _second = mutex.lock().unwrap();
+        }
+
+        fn main() {
+            let mutex = Mutex::new(0);
+            lock_twice(&mutex);
+        }
+        ",
+    )
+    .analyze()
+    .expect("The analysis should succeed");
+
+    assert!(report.is_deadlock_free());
+}
+
+#[test]
+fn tool_registered_in_the_source_code_is_not_registered_again() {
+    let report = Analyzer::from_code(
+        r"
+        #![feature(register_tool)]
+        #![register_tool(check_deadlock)]
+
+        use std::sync::Mutex;
+
+        #[check_deadlock::ignore]
+        fn lock_twice(mutex: &Mutex<i32>) {
+            let _first = mutex.lock().unwrap();
+            let _second = mutex.lock().unwrap();
+        }
+
+        fn main() {
+            let mutex = Mutex::new(0);
+            lock_twice(&mutex);
+        }
+        ",
+    )
+    .analyze()
+    .expect("The analysis should succeed");
+
+    assert!(report.is_deadlock_free());
+}
+
+#[test]
+fn tool_registered_among_other_tools_is_not_registered_again() {
+    let translation = Analyzer::from_code(
+        r"
+        #![feature(never_type, register_tool)]
+        #![register_tool(other_tool, check_deadlock)]
+
+        #[check_deadlock::assume_no_unwind]
+        fn helper() {}
+
+        fn main() {
+            helper();
+        }
+        ",
+    )
+    .translate();
+
+    assert!(translation.is_ok());
+}
+
+#[test]
+fn function_assumed_not_to_unwind_has_no_unwind_transition() {
+    let code = r"
+        #[check_deadlock::assume_no_unwind]
+        fn helper() -> Vec<i32> {
+            vec![1, 2, 3]
+        }
+
+        fn main() {
+            let _values = helper();
+        }
+        ";
+    let translation = Analyzer::from_code(code)
+        .translate()
+        .expect("The translation should succeed");
+
+    let mut lola = Vec::new();
+    translation
+        .net
+        .to_lola(&mut lola)
+        .expect("The net should be written");
+    let lola = String::from_utf8(lola).expect("The output should be valid UTF-8");
+    assert!(!lola.contains("helper_0_CALL_UNWIND"));
+    // The calls inside the annotated function do not unwind either.
+    assert!(!lola.contains("CALL_UNWIND"));
+}
+
+#[test]
+fn declared_lock_order_is_resolved_to_mutexes() {
+    let report = Analyzer::from_named_code(
+        "lock_order.rs",
+        r"use std::sync::Mutex;
+
+#[check_deadlock::lock_order(first, second)]
+fn main() {
+    let second = Mutex::new(0);
+    let first = Mutex::new(0);
+    let _first_guard = first.lock().unwrap();
+    let _second_guard = second.lock().unwrap();
+}
+",
+    )
+    .analyze()
+    .expect("The analysis should succeed");
+
+    assert_eq!(
+        report.lock_orders,
+        vec![LockOrder {
+            first: 1,
+            second: 0,
+            function_name: "main".to_string(),
+            location: "lock_order.rs:3:1".to_string(),
+        }]
+    );
+    assert!(report.is_deadlock_free());
+}
@@ -0,0 +1,57 @@
+mod utils;
+
+mod bounded_channel_producer_consumer {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/crossbeam/bounded_channel_producer_consumer.rs",
+        "./examples/results/crossbeam/bounded_channel_producer_consumer/"
+    );
+}
+
+mod rendezvous_handoff_between_threads {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/crossbeam/rendezvous_handoff_between_threads.rs",
+        "./examples/results/crossbeam/rendezvous_handoff_between_threads/"
+    );
+}
+
+mod rendezvous_send_without_receiver {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/crossbeam/rendezvous_send_without_receiver.rs",
+        "./examples/results/crossbeam/rendezvous_send_without_receiver/"
+    );
+}
+
+mod select_in_loop_until_disconnected {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/crossbeam/select_in_loop_until_disconnected.rs",
+        "./examples/results/crossbeam/select_in_loop_until_disconnected/"
+    );
+}
+
+mod select_receives_from_ready_channel {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/crossbeam/select_receives_from_ready_channel.rs",
+        "./examples/results/crossbeam/select_receives_from_ready_channel/"
+    );
+}
+
+mod select_send_without_receiver {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/crossbeam/select_send_without_receiver.rs",
+        "./examples/results/crossbeam/select_send_without_receiver/"
+    );
+}
+
+mod select_without_ready_operation {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/crossbeam/select_without_ready_operation.rs",
+        "./examples/results/crossbeam/select_without_ready_operation/"
+    );
+}
+
+mod unbounded_receive_until_disconnected {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/crossbeam/unbounded_receive_until_disconnected.rs",
+        "./examples/results/crossbeam/unbounded_receive_until_disconnected/"
+    );
+}
@@ -0,0 +1,153 @@
+// Linking to the library requires access to the compiler's `rustc_driver` library.
+#![feature(rustc_private)]
+
+use cargo_check_deadlock::Analyzer;
+
+#[test]
+fn reports_lock_order_inversion_between_threads() {
+    let report = Analyzer::from_file("./examples/programs/thread/dating_philosophers.rs")
+        .analyze()
+        .expect("The analysis should succeed");
+
+    assert_eq!(report.lock_cycles.len(), 1);
+    let edges = &report.lock_cycles[0].edges;
+    assert_eq!(edges.len(), 2);
+    assert_eq!((edges[0].held, edges[0].acquired), (0, 1));
+    assert_eq!((edges[1].held, edges[1].acquired), (1, 0));
+    assert_eq!(edges[0].thread, Some(0));
+    assert_eq!(edges[1].thread, Some(1));
+    assert!(edges[0].held_at.ends_with("dating_philosophers.rs:20:35"));
+    assert!(edges[0]
+        .acquired_at
+        .ends_with("dating_philosophers.rs:21:37"));
+    assert!(report.is_deadlock());
+}
+
+#[test]
+fn reports_double_lock_as_self_loop() {
+    let report = Analyzer::from_file("./examples/programs/mutex/double_lock_deadlock.rs")
+        .precheck(true)
+        .analyze()
+        .expect("The analysis should succeed");
+
+    assert_eq!(report.lock_cycles.len(), 1);
+    assert_eq!(report.lock_cycles[0].to_string(), "mutex 0 -> mutex 0");
+    assert!(!report.skipped_model_checker);
+    assert!(report.is_deadlock());
+}
+
+#[test]
+fn inversion_in_a_single_thread_is_not_a_cycle() {
+    let translation = Analyzer::from_code(
+        r"
+        use std::sync::Mutex;
+
+        fn main() {
+            let first = Mutex::new(0);
+            let second = Mutex::new(0);
+            {
+                let _first_guard = first.lock().unwrap();
+                let _second_guard = second.lock().unwrap();
+            }
+            {
+                let _second_guard = second.lock().unwrap();
+                let _first_guard = first.lock().unwrap();
+            }
+        }
+        ",
+    )
+    .translate()
+    .expect("The translation should succeed");
+
+    assert_eq!(translation.lock_graph.edges().len(), 2);
+    assert!(translation.lock_graph.cycles().is_empty());
+    assert!(translation.lock_graph.rules_out_deadlocks());
+}
+
+#[test]
+fn read_while_holding_a_read_lock_is_not_an_edge() {
+    let translation =
+        Analyzer::from_file("./examples/programs/parking_lot/rwlock_read_while_reading.rs")
+            .translate()
+            .expect("The translation should succeed");
+
+    assert!(translation.lock_graph.edges().is_empty());
+    assert_eq!(translation.approximations.len(), 3);
+    assert!(translation
+        .approximations
+        .iter()
+        .all(|approximation| approximation.construct
+            == "Shared read of a `parking_lot::RwLock` modelled as an exclusive lock"));
+}
+
+#[test]
+fn precheck_skips_model_checker_for_consistent_lock_order() {
+    let report = Analyzer::from_file("./examples/programs/mutex/two_threads_sharing_mutex.rs")
+        .precheck(true)
+        .analyze()
+        .expect("The analysis should succeed");
+
+    assert!(report.skipped_model_checker);
+    assert!(report.is_deadlock_free());
+    assert!(report.lock_cycles.is_empty());
+}
+
+#[test]
+fn precheck_does_not_skip_model_checker_with_condvars() {
+    let report = Analyzer::from_file("./examples/programs/condvar/infinite_wait_deadlock.rs")
+        .precheck(true)
+        .analyze()
+        .expect("The analysis should succeed");
+
+    assert!(!report.skipped_model_checker);
+    assert!(report.is_deadlock());
+}
+
+#[test]
+fn precheck_does_not_skip_model_checker_for_join_while_holding_a_mutex() {
+    let report = Analyzer::from_code(
+        r"
+        use std::sync::{Arc, Mutex};
+
+        fn main() {
+            let mutex = Arc::new(Mutex::new(0));
+            let mutex_in_thread = mutex.clone();
+            let _guard = mutex.lock().unwrap();
+            let handle = std::thread::spawn(move || {
+                let _guard = mutex_in_thread.lock().unwrap();
+            });
+            handle.join().unwrap();
+        }
+        ",
+    )
+    .precheck(true)
+    .analyze()
+    .expect("The analysis should succeed");
+
+    assert!(report.lock_cycles.is_empty());
+    assert!(!report.skipped_model_checker);
+    assert!(report.is_deadlock());
+}
+
+#[test]
+fn reports_violations_of_declared_lock_order() {
+    let report = Analyzer::from_code(
+        r"
+        use std::sync::Mutex;
+
+        #[check_deadlock::lock_order(first, second)]
+        fn main() {
+            let first = Mutex::new(0);
+            let second = Mutex::new(0);
+            let _second_guard = second.lock().unwrap();
+            let _first_guard = first.lock().unwrap();
+        }
+        ",
+    )
+    .analyze()
+    .expect("The analysis should succeed");
+
+    assert_eq!(report.lock_order_violations.len(), 1);
+    assert_eq!(report.lock_order_violations[0].held, 1);
+    assert_eq!(report.lock_order_violations[0].acquired, 0);
+}
@@ -4,6 +4,86 @@
 
 mod utils;
 
+mod block_on {
+    super::utils::generate_lola_tests_for_example_program!(
+        "./examples/programs/async_await/block_on.rs",
+        "./examples/results/async_await/block_on/",
+        false
+    );
+}
+
+mod guard_dropped_before_await {
+    super::utils::generate_lola_tests_for_example_program!(
+        "./examples/programs/async_await/guard_dropped_before_await.rs",
+        "./examples/results/async_await/guard_dropped_before_await/",
+        false
+    );
+}
+
+mod guard_held_across_await {
+    super::utils::generate_lola_tests_for_example_program!(
+        "./examples/programs/async_await/guard_held_across_await.rs",
+        "./examples/results/async_await/guard_held_across_await/",
+        true
+    );
+}
+
+mod guard_held_while_awaiting_thread {
+    super::utils::generate_lola_tests_for_example_program!(
+        "./examples/programs/async_await/guard_held_while_awaiting_thread.rs",
+        "./examples/results/async_await/guard_held_while_awaiting_thread/",
+        true
+    );
+}
+
+mod compare_exchange_spinlock {
+    super::utils::generate_lola_tests_for_example_program!(
+        "./examples/programs/atomic/compare_exchange_spinlock.rs",
+        "./examples/results/atomic/compare_exchange_spinlock/",
+        false
+    );
+}
+
+mod load_on_one_path {
+    super::utils::generate_lola_tests_for_example_program!(
+        "./examples/programs/atomic/load_on_one_path.rs",
+        "./examples/results/atomic/load_on_one_path/",
+        false
+    );
+}
+
+mod spin_wait_flag_never_set {
+    super::utils::generate_lola_tests_for_example_program!(
+        "./examples/programs/atomic/spin_wait_flag_never_set.rs",
+        "./examples/results/atomic/spin_wait_flag_never_set/",
+        true
+    );
+}
+
+mod spin_wait_flag_set {
+    super::utils::generate_lola_tests_for_example_program!(
+        "./examples/programs/atomic/spin_wait_flag_set.rs",
+        "./examples/results/atomic/spin_wait_flag_set/",
+        false
+    );
+}
+
+mod spin_wait_with_lock_in_body {
+    super::utils::generate_lola_tests_for_example_program!(
+        "./examples/programs/atomic/spin_wait_with_lock_in_body.rs",
+        "./examples/results/atomic/spin_wait_with_lock_in_body/",
+        false
+    );
+}
+
+mod swap_spinlock_held_while_joining {
+    super::utils::generate_lola_tests_for_example_program!(
+        "./examples/programs/atomic/swap_spinlock_held_while_joining.rs",
+        "./examples/results/atomic/swap_spinlock_held_while_joining/",
+        true
+    );
+}
+
 mod calculator {
     super::utils::generate_lola_tests_for_example_program!(
         "./examples/programs/basic/calculator.rs",
@@ -76,6 +156,70 @@ mod wait_while {
     );
 }
 
+mod bounded_channel_producer_consumer {
+    super::utils::generate_lola_tests_for_example_program!(
+        "./examples/programs/crossbeam/bounded_channel_producer_consumer.rs",
+        "./examples/results/crossbeam/bounded_channel_producer_consumer/",
+        false
+    );
+}
+
+mod rendezvous_handoff_between_threads {
+    super::utils::generate_lola_tests_for_example_program!(
+        "./examples/programs/crossbeam/rendezvous_handoff_between_threads.rs",
+        "./examples/results/crossbeam/rendezvous_handoff_between_threads/",
+        false
+    );
+}
+
+mod rendezvous_send_without_receiver {
+    super::utils::generate_lola_tests_for_example_program!(
+        "./examples/programs/crossbeam/rendezvous_send_without_receiver.rs",
+        "./examples/results/crossbeam/rendezvous_send_without_receiver/",
+        true
+    );
+}
+
+mod select_in_loop_until_disconnected {
+    super::utils::generate_lola_tests_for_example_program!(
+        "./examples/programs/crossbeam/select_in_loop_until_disconnected.rs",
+        "./examples/results/crossbeam/select_in_loop_until_disconnected/",
+        false
+    );
+}
+
+mod select_receives_from_ready_channel {
+    super::utils::generate_lola_tests_for_example_program!(
+        "./examples/programs/crossbeam/select_receives_from_ready_channel.rs",
+        "./examples/results/crossbeam/select_receives_from_ready_channel/",
+        false
+    );
+}
+
+mod select_send_without_receiver {
+    super::utils::generate_lola_tests_for_example_program!(
+        "./examples/programs/crossbeam/select_send_without_receiver.rs",
+        "./examples/results/crossbeam/select_send_without_receiver/",
+        false
+    );
+}
+
+mod select_without_ready_operation {
+    super::utils::generate_lola_tests_for_example_program!(
+        "./examples/programs/crossbeam/select_without_ready_operation.rs",
+        "./examples/results/crossbeam/select_without_ready_operation/",
+        true
+    );
+}
+
+mod unbounded_receive_until_disconnected {
+    super::utils::generate_lola_tests_for_example_program!(
+        "./examples/programs/crossbeam/unbounded_receive_until_disconnected.rs",
+        "./examples/results/crossbeam/unbounded_receive_until_disconnected/",
+        false
+    );
+}
+
 mod diverging {
     super::utils::generate_lola_tests_for_example_program!(
         "./examples/programs/function_call/diverging.rs",
@@ -116,6 +260,22 @@ mod two_calls_same_function {
     );
 }
 
+mod double_lock_behind_disabled_flag {
+    super::utils::generate_lola_tests_for_example_program!(
+        "./examples/programs/mutex/double_lock_behind_disabled_flag.rs",
+        "./examples/results/mutex/double_lock_behind_disabled_flag/",
+        false
+    );
+}
+
+mod double_lock_behind_enabled_flag {
+    super::utils::generate_lola_tests_for_example_program!(
+        "./examples/programs/mutex/double_lock_behind_enabled_flag.rs",
+        "./examples/results/mutex/double_lock_behind_enabled_flag/",
+        true
+    );
+}
+
 mod double_lock_deadlock_in_function {
     super::utils::generate_lola_tests_for_example_program!(
         "./examples/programs/mutex/double_lock_deadlock_in_function.rs",
@@ -140,6 +300,14 @@ mod double_lock_deadlock {
     );
 }
 
+mod double_lock_in_constant_enum_match {
+    super::utils::generate_lola_tests_for_example_program!(
+        "./examples/programs/mutex/double_lock_in_constant_enum_match.rs",
+        "./examples/results/mutex/double_lock_in_constant_enum_match/",
+        false
+    );
+}
+
 mod drop_mutex_guard_in_arc_manually {
     super::utils::generate_lola_tests_for_example_program!(
         "./examples/programs/mutex/drop_mutex_guard_in_arc_manually.rs",
@@ -164,6 +332,118 @@ mod two_threads_sharing_mutex {
     );
 }
 
+mod call_once_blocked_by_lock_holder {
+    super::utils::generate_lola_tests_for_example_program!(
+        "./examples/programs/once/call_once_blocked_by_lock_holder.rs",
+        "./examples/results/once/call_once_blocked_by_lock_holder/",
+        true
+    );
+}
+
+mod call_once_in_two_threads {
+    super::utils::generate_lola_tests_for_example_program!(
+        "./examples/programs/once/call_once_in_two_threads.rs",
+        "./examples/results/once/call_once_in_two_threads/",
+        false
+    );
+}
+
+mod call_once_reentrant {
+    super::utils::generate_lola_tests_for_example_program!(
+        "./examples/programs/once/call_once_reentrant.rs",
+        "./examples/results/once/call_once_reentrant/",
+        true
+    );
+}
+
+mod lazy_lock_forced_while_holding_lock {
+    super::utils::generate_lola_tests_for_example_program!(
+        "./examples/programs/once/lazy_lock_forced_while_holding_lock.rs",
+        "./examples/results/once/lazy_lock_forced_while_holding_lock/",
+        true
+    );
+}
+
+mod once_lock_get_or_init {
+    super::utils::generate_lola_tests_for_example_program!(
+        "./examples/programs/once/once_lock_get_or_init.rs",
+        "./examples/results/once/once_lock_get_or_init/",
+        false
+    );
+}
+
+mod static_call_once_blocked_by_lock_holder {
+    super::utils::generate_lola_tests_for_example_program!(
+        "./examples/programs/once/static_call_once_blocked_by_lock_holder.rs",
+        "./examples/results/once/static_call_once_blocked_by_lock_holder/",
+        true
+    );
+}
+
+mod park_twice_with_one_stored_token {
+    super::utils::generate_lola_tests_for_example_program!(
+        "./examples/programs/park/park_twice_with_one_stored_token.rs",
+        "./examples/results/park/park_twice_with_one_stored_token/",
+        true
+    );
+}
+
+mod park_without_unpark {
+    super::utils::generate_lola_tests_for_example_program!(
+        "./examples/programs/park/park_without_unpark.rs",
+        "./examples/results/park/park_without_unpark/",
+        true
+    );
+}
+
+mod unpark_before_park {
+    super::utils::generate_lola_tests_for_example_program!(
+        "./examples/programs/park/unpark_before_park.rs",
+        "./examples/results/park/unpark_before_park/",
+        false
+    );
+}
+
+mod unpark_main_thread {
+    super::utils::generate_lola_tests_for_example_program!(
+        "./examples/programs/park/unpark_main_thread.rs",
+        "./examples/results/park/unpark_main_thread/",
+        false
+    );
+}
+
+mod double_lock_without_poisoning {
+    super::utils::generate_lola_tests_for_example_program!(
+        "./examples/programs/parking_lot/double_lock_without_poisoning.rs",
+        "./examples/results/parking_lot/double_lock_without_poisoning/",
+        true
+    );
+}
+
+mod rwlock_guard_dropped {
+    super::utils::generate_lola_tests_for_example_program!(
+        "./examples/programs/parking_lot/rwlock_guard_dropped.rs",
+        "./examples/results/parking_lot/rwlock_guard_dropped/",
+        false
+    );
+}
+
+mod rwlock_held_while_joining_writer {
+    super::utils::generate_lola_tests_for_example_program!(
+        "./examples/programs/parking_lot/rwlock_held_while_joining_writer.rs",
+        "./examples/results/parking_lot/rwlock_held_while_joining_writer/",
+        true
+    );
+}
+
+mod wait_with_guard_reference {
+    super::utils::generate_lola_tests_for_example_program!(
+        "./examples/programs/parking_lot/wait_with_guard_reference.rs",
+        "./examples/results/parking_lot/wait_with_guard_reference/",
+        false
+    );
+}
+
 mod abort {
     super::utils::generate_lola_tests_for_example_program!(
         "./examples/programs/statement/abort.rs",
@@ -259,3 +539,75 @@ mod spawn_with_empty_closure {
         false
     );
 }
+
+mod channel_receive_with_sender_alive {
+    super::utils::generate_lola_tests_for_example_program!(
+        "./examples/programs/tokio/channel_receive_with_sender_alive.rs",
+        "./examples/results/tokio/channel_receive_with_sender_alive/",
+        true
+    );
+}
+
+mod channel_send_and_receive {
+    super::utils::generate_lola_tests_for_example_program!(
+        "./examples/programs/tokio/channel_send_and_receive.rs",
+        "./examples/results/tokio/channel_send_and_receive/",
+        false
+    );
+}
+
+mod mutex_held_while_joining_task {
+    super::utils::generate_lola_tests_for_example_program!(
+        "./examples/programs/tokio/mutex_held_while_joining_task.rs",
+        "./examples/results/tokio/mutex_held_while_joining_task/",
+        true
+    );
+}
+
+mod mutex_released_before_joining_task {
+    super::utils::generate_lola_tests_for_example_program!(
+        "./examples/programs/tokio/mutex_released_before_joining_task.rs",
+        "./examples/results/tokio/mutex_released_before_joining_task/",
+        false
+    );
+}
+
+mod notified_without_notify {
+    super::utils::generate_lola_tests_for_example_program!(
+        "./examples/programs/tokio/notified_without_notify.rs",
+        "./examples/results/tokio/notified_without_notify/",
+        true
+    );
+}
+
+mod notify_wakes_up_task {
+    super::utils::generate_lola_tests_for_example_program!(
+        "./examples/programs/tokio/notify_wakes_up_task.rs",
+        "./examples/results/tokio/notify_wakes_up_task/",
+        false
+    );
+}
+
+mod rwlock_write_while_reading {
+    super::utils::generate_lola_tests_for_example_program!(
+        "./examples/programs/tokio/rwlock_write_while_reading.rs",
+        "./examples/results/tokio/rwlock_write_while_reading/",
+        true
+    );
+}
+
+mod semaphore_acquired_twice {
+    super::utils::generate_lola_tests_for_example_program!(
+        "./examples/programs/tokio/semaphore_acquired_twice.rs",
+        "./examples/results/tokio/semaphore_acquired_twice/",
+        true
+    );
+}
+
+mod semaphore_with_enough_permits {
+    super::utils::generate_lola_tests_for_example_program!(
+        "./examples/programs/tokio/semaphore_with_enough_permits.rs",
+        "./examples/results/tokio/semaphore_with_enough_permits/",
+        false
+    );
+}
@@ -0,0 +1,81 @@
+// Linking to the library requires access to the compiler's `rustc_driver` library.
+#![feature(rustc_private)]
+
+use cargo_check_deadlock::{Analyzer, LostSignal};
+
+#[test]
+fn reports_lost_signal_causing_deadlock() {
+    let report = Analyzer::from_file("./examples/programs/condvar/self_notify_lost_signal.rs")
+        .analyze()
+        .expect("The analysis should succeed");
+
+    assert!(report.is_deadlock());
+    assert_eq!(report.lost_signals.len(), 1);
+    let LostSignal {
+        condvar,
+        location,
+        causes_deadlock,
+    } = &report.lost_signals[0];
+    assert_eq!(*condvar, 0);
+    assert!(location.ends_with("self_notify_lost_signal.rs:5:10"));
+    assert!(causes_deadlock);
+    assert!(report.lock_cycles.is_empty());
+}
+
+#[test]
+fn infinite_wait_is_not_a_lost_signal() {
+    let report = Analyzer::from_file("./examples/programs/condvar/infinite_wait_deadlock.rs")
+        .analyze()
+        .expect("The analysis should succeed");
+
+    assert!(report.is_deadlock());
+    assert!(report.lost_signals.is_empty());
+}
+
+#[test]
+fn reports_harmless_lost_signal_without_deadlock() {
+    let report = Analyzer::from_file("./examples/programs/condvar/wait.rs")
+        .analyze()
+        .expect("The analysis should succeed");
+
+    assert!(report.is_deadlock_free());
+    assert_eq!(report.lost_signals.len(), 1);
+    assert!(!report.lost_signals[0].causes_deadlock);
+    assert!(report.lost_signals[0].location.ends_with("wait.rs:13:14"));
+}
+
+#[test]
+fn lost_signal_and_lock_cycle_are_reported_separately() {
+    let report = Analyzer::from_code(
+        r"
+        use std::sync::{Arc, Condvar, Mutex};
+
+        fn main() {
+            let first = Arc::new(Mutex::new(false));
+            let second = Arc::new(Mutex::new(0));
+            let condvar = Condvar::new();
+            let first_in_thread = first.clone();
+            let second_in_thread = second.clone();
+            let handle = std::thread::spawn(move || {
+                let _second_guard = second_in_thread.lock().unwrap();
+                let _first_guard = first_in_thread.lock().unwrap();
+            });
+            {
+                let _first_guard = first.lock().unwrap();
+                let _second_guard = second.lock().unwrap();
+            }
+            let guard = first.lock().unwrap();
+            condvar.notify_one();
+            let _guard = condvar.wait(guard).unwrap();
+            handle.join().unwrap();
+        }
+        ",
+    )
+    .analyze()
+    .expect("The analysis should succeed");
+
+    assert!(report.is_deadlock());
+    assert_eq!(report.lock_cycles.len(), 1);
+    assert_eq!(report.lost_signals.len(), 1);
+    assert!(report.lost_signals[0].causes_deadlock);
+}
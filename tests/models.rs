@@ -0,0 +1,203 @@
+// Linking to the library requires access to the compiler's `rustc_driver` library.
+#![feature(rustc_private)]
+
+use assert_cmd::prelude::*; // Add methods on commands
+use assert_fs::prelude::*; // Used for creating temp files
+use cargo_check_deadlock::{Analyzer, Behavior, Models};
+use predicates::prelude::*; // Used for writing assertions
+use std::process::Command; // Run programs
+
+const FFI_PROGRAM: &str = r#"
+mod ffi {
+    extern "C" {
+        pub fn acquire_device_lock();
+        pub fn release_device_lock();
+        pub fn wait_for_event();
+        pub fn signal_event();
+        pub fn abort_process();
+    }
+}
+
+fn main() {
+    unsafe {
+        #[cfg(double_acquire)]
+        ffi::acquire_device_lock();
+        ffi::acquire_device_lock();
+        ffi::release_device_lock();
+        #[cfg(signal)]
+        ffi::signal_event();
+        ffi::wait_for_event();
+        #[cfg(abort)]
+        ffi::abort_process();
+    }
+}
+"#;
+
+const MODELS: &str = r#"
+[resources]
+device = 1
+event = 0
+
+[functions]
+"ffi::acquire_device_lock" = { behavior = "lock", resource = "device" }
+"ffi::release_device_lock" = { behavior = "unlock", resource = "device" }
+"ffi::wait_for_event" = { behavior = "wait", resource = "event" }
+"ffi::signal_event" = { behavior = "notify", resource = "event" }
+"ffi::abort_process" = { behavior = "diverge" }
+"#;
+
+fn analyzer(cfgs: &[&str]) -> Analyzer {
+    let models: Models = MODELS.parse().expect("The models should be valid");
+    let rustc_args = cfgs.iter().flat_map(|cfg| ["--cfg", cfg]);
+    Analyzer::from_code(FFI_PROGRAM)
+        .rustc_args(rustc_args)
+        .models(models)
+}
+
+#[test]
+fn foreign_functions_are_not_blocking_without_models() {
+    let report = Analyzer::from_code(FFI_PROGRAM)
+        .analyze()
+        .expect("The analysis should succeed");
+
+    assert!(report.is_deadlock_free());
+}
+
+#[test]
+fn wait_without_notify_deadlocks() {
+    let report = analyzer(&[])
+        .analyze()
+        .expect("The analysis should succeed");
+
+    assert!(report.is_deadlock());
+}
+
+#[test]
+fn wait_after_notify_is_deadlock_free() {
+    let report = analyzer(&["signal"])
+        .analyze()
+        .expect("The analysis should succeed");
+
+    assert!(report.is_deadlock_free());
+}
+
+#[test]
+fn double_lock_deadlocks() {
+    let report = analyzer(&["signal", "double_acquire"])
+        .analyze()
+        .expect("The analysis should succeed");
+
+    assert!(report.is_deadlock());
+}
+
+#[test]
+fn diverging_function_deadlocks() {
+    let report = analyzer(&["signal", "abort"])
+        .analyze()
+        .expect("The analysis should succeed");
+
+    assert!(report.is_deadlock());
+}
+
+#[test]
+fn behaviours_of_other_primitives_are_semaphore_operations() {
+    let models: Models = r#"
+    [resources]
+    event = 0
+
+    [functions]
+    "ffi::wait_for_event" = { behavior = "wait", resource = "event" }
+    "ffi::join_worker" = { behavior = "join", resource = "event" }
+    "ffi::signal_event" = { behavior = "notify", resource = "event" }
+    "ffi::spawn_worker" = { behavior = "spawn", resource = "event" }
+    "#
+    .parse()
+    .expect("The models should be valid");
+
+    for function_name in ["ffi::wait_for_event", "ffi::join_worker"] {
+        let model = models.get(function_name).expect("The model should exist");
+        assert_eq!(model.behavior, Behavior::Acquire);
+    }
+    for function_name in ["ffi::signal_event", "ffi::spawn_worker"] {
+        let model = models.get(function_name).expect("The model should exist");
+        assert_eq!(model.behavior, Behavior::Release);
+    }
+}
+
+#[test]
+fn undeclared_resource_is_rejected() {
+    let result = r#"
+    [functions]
+    "ffi::acquire_device_lock" = { behavior = "lock", resource = "device" }
+    "#
+    .parse::<Models>();
+
+    assert_eq!(
+        result,
+        Err(
+            "The model for `ffi::acquire_device_lock` refers to the undeclared resource `device`"
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn missing_resource_is_rejected() {
+    let result = r#"
+    [functions]
+    "ffi::acquire_device_lock" = { behavior = "lock" }
+    "#
+    .parse::<Models>();
+
+    assert_eq!(
+        result,
+        Err("The model for `ffi::acquire_device_lock` requires a resource".to_string())
+    );
+}
+
+#[test]
+fn cli_reads_model_file() {
+    let folder = assert_fs::TempDir::new().expect("Could not create temporary folder for test");
+    let file = folder.child("ffi.rs");
+    file.write_str(FFI_PROGRAM)
+        .expect("Could not write test file contents");
+    let models = folder.child("models.toml");
+    models
+        .write_str(MODELS)
+        .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg(file.path())
+        .arg("--output-folder")
+        .arg(folder.path())
+        .arg("--models")
+        .arg(models.path());
+    cmd.assert().success().stdout(predicate::str::contains(
+        "Deadlock can be reached according to the model checker `LoLA`",
+    ));
+}
+
+#[test]
+fn cli_rejects_invalid_model_file() {
+    let folder = assert_fs::TempDir::new().expect("Could not create temporary folder for test");
+    let file = folder.child("ffi.rs");
+    file.write_str(FFI_PROGRAM)
+        .expect("Could not write test file contents");
+    let models = folder.child("models.toml");
+    models
+        .write_str("[functions]\n\"ffi::abort_process\" = { behavior = \"explode\" }\n")
+        .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg(file.path())
+        .arg("--output-folder")
+        .arg(folder.path())
+        .arg("--models")
+        .arg(models.path());
+    cmd.assert()
+        .failure()
+        .code(5)
+        .stderr(predicate::str::contains("unknown variant `explode`"));
+}
@@ -1,5 +1,19 @@
 mod utils;
 
+mod double_lock_behind_disabled_flag {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/mutex/double_lock_behind_disabled_flag.rs",
+        "./examples/results/mutex/double_lock_behind_disabled_flag/"
+    );
+}
+
+mod double_lock_behind_enabled_flag {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/mutex/double_lock_behind_enabled_flag.rs",
+        "./examples/results/mutex/double_lock_behind_enabled_flag/"
+    );
+}
+
 mod double_lock_deadlock_in_function {
     super::utils::generate_tests_for_example_program!(
         "./examples/programs/mutex/double_lock_deadlock_in_function.rs",
@@ -21,6 +35,13 @@ mod double_lock_deadlock {
     );
 }
 
+mod double_lock_in_constant_enum_match {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/mutex/double_lock_in_constant_enum_match.rs",
+        "./examples/results/mutex/double_lock_in_constant_enum_match/"
+    );
+}
+
 mod drop_mutex_guard_in_arc_manually {
     super::utils::generate_tests_for_example_program!(
         "./examples/programs/mutex/drop_mutex_guard_in_arc_manually.rs",
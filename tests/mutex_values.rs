@@ -0,0 +1,325 @@
+// Linking to the library requires access to the compiler's `rustc_driver` library.
+#![feature(rustc_private)]
+
+#[path = "utils/net.rs"]
+mod net;
+
+use cargo_check_deadlock::Analyzer;
+use net::has_place;
+
+#[test]
+fn counter_checked_in_loop_before_waiting() {
+    let report = Analyzer::from_code(
+        r"
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+
+fn main() {
+    let pair = Arc::new((Mutex::new(0usize), Condvar::new()));
+    let producer = Arc::clone(&pair);
+    thread::spawn(move || {
+        let (lock, cvar) = &*producer;
+        let mut count = lock.lock().unwrap();
+        *count += 1;
+        cvar.notify_one();
+    });
+    let (lock, cvar) = &*pair;
+    let mut count = lock.lock().unwrap();
+    while *count == 0 {
+        count = cvar.wait(count).unwrap();
+    }
+    *count -= 1;
+}
+",
+    )
+    .mutex_values(true)
+    .analyze()
+    .expect("The analysis should succeed");
+
+    assert!(report.is_deadlock_free());
+    assert!(has_place(&report.net, "MUTEX_0_VALUE_0"));
+    assert!(has_place(&report.net, "MUTEX_0_VALUE_3"));
+    assert!(!has_place(&report.net, "MUTEX_0_VALUE_4"));
+}
+
+#[test]
+fn counter_reset_instead_of_incremented() {
+    let report = Analyzer::from_code(
+        r"
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+
+fn main() {
+    let pair = Arc::new((Mutex::new(0usize), Condvar::new()));
+    let producer = Arc::clone(&pair);
+    thread::spawn(move || {
+        let (lock, cvar) = &*producer;
+        let mut count = lock.lock().unwrap();
+        *count = 0;
+        cvar.notify_one();
+    });
+    let (lock, cvar) = &*pair;
+    let mut count = lock.lock().unwrap();
+    while *count == 0 {
+        count = cvar.wait(count).unwrap();
+    }
+    *count -= 1;
+}
+",
+    )
+    .mutex_values(true)
+    .analyze()
+    .expect("The analysis should succeed");
+
+    assert!(!report.is_deadlock_free());
+}
+
+#[test]
+fn wait_while_flag_is_set() {
+    let report = Analyzer::from_code(
+        r"
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+
+fn main() {
+    let pair = Arc::new((Mutex::new(false), Condvar::new()));
+    let pair2 = Arc::clone(&pair);
+    thread::spawn(move || {
+        let (lock, cvar) = &*pair2;
+        let mut started = lock.lock().unwrap();
+        *started = true;
+        cvar.notify_one();
+    });
+    let (lock, cvar) = &*pair;
+    let guard = cvar.wait_while(lock.lock().unwrap(), |started| !*started).unwrap();
+    drop(guard);
+}
+",
+    )
+    .mutex_values(true)
+    .analyze()
+    .expect("The analysis should succeed");
+
+    assert!(report.is_deadlock_free());
+    assert!(has_place(&report.net, "MUTEX_0_VALUE_1"));
+    assert!(!has_place(&report.net, "MUTEX_0_VALUE_2"));
+}
+
+#[test]
+fn wait_while_flag_is_cleared() {
+    let report = Analyzer::from_code(
+        r"
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+
+fn main() {
+    let pair = Arc::new((Mutex::new(false), Condvar::new()));
+    let pair2 = Arc::clone(&pair);
+    thread::spawn(move || {
+        let (lock, cvar) = &*pair2;
+        let mut started = lock.lock().unwrap();
+        *started = false;
+        cvar.notify_one();
+    });
+    let (lock, cvar) = &*pair;
+    let guard = cvar.wait_while(lock.lock().unwrap(), |started| !*started).unwrap();
+    drop(guard);
+}
+",
+    )
+    .mutex_values(true)
+    .analyze()
+    .expect("The analysis should succeed");
+
+    assert!(!report.is_deadlock_free());
+}
+
+#[test]
+fn wait_while_counter_reaches_number_of_increments() {
+    let report = Analyzer::from_code(
+        r"
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+
+fn main() {
+    let pair = Arc::new((Mutex::new(0usize), Condvar::new()));
+    let first = Arc::clone(&pair);
+    let second = Arc::clone(&pair);
+    thread::spawn(move || {
+        let (lock, cvar) = &*first;
+        let mut count = lock.lock().unwrap();
+        *count += 1;
+        cvar.notify_one();
+    });
+    thread::spawn(move || {
+        let (lock, cvar) = &*second;
+        let mut count = lock.lock().unwrap();
+        *count += 1;
+        cvar.notify_one();
+    });
+    let (lock, cvar) = &*pair;
+    let guard = cvar.wait_while(lock.lock().unwrap(), |count| *count < 2).unwrap();
+    drop(guard);
+}
+",
+    )
+    .mutex_values(true)
+    .analyze()
+    .expect("The analysis should succeed");
+
+    assert!(report.is_deadlock_free());
+}
+
+#[test]
+fn wait_while_counter_exceeds_number_of_increments() {
+    let report = Analyzer::from_code(
+        r"
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+
+fn main() {
+    let pair = Arc::new((Mutex::new(0usize), Condvar::new()));
+    let first = Arc::clone(&pair);
+    let second = Arc::clone(&pair);
+    thread::spawn(move || {
+        let (lock, cvar) = &*first;
+        let mut count = lock.lock().unwrap();
+        *count += 1;
+        cvar.notify_one();
+    });
+    thread::spawn(move || {
+        let (lock, cvar) = &*second;
+        let mut count = lock.lock().unwrap();
+        *count += 1;
+        cvar.notify_one();
+    });
+    let (lock, cvar) = &*pair;
+    let guard = cvar.wait_while(lock.lock().unwrap(), |count| *count < 3).unwrap();
+    drop(guard);
+}
+",
+    )
+    .mutex_values(true)
+    .analyze()
+    .expect("The analysis should succeed");
+
+    assert!(!report.is_deadlock_free());
+}
+
+#[test]
+fn values_not_modelled_by_default() {
+    let report = Analyzer::from_code(
+        r"
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+
+fn main() {
+    let pair = Arc::new((Mutex::new(0usize), Condvar::new()));
+    let producer = Arc::clone(&pair);
+    thread::spawn(move || {
+        let (lock, cvar) = &*producer;
+        let mut count = lock.lock().unwrap();
+        *count += 1;
+        cvar.notify_one();
+    });
+    let (lock, cvar) = &*pair;
+    let mut count = lock.lock().unwrap();
+    while *count == 0 {
+        count = cvar.wait(count).unwrap();
+    }
+    *count -= 1;
+}
+",
+    )
+    .analyze()
+    .expect("The analysis should succeed");
+
+    assert!(!has_place(&report.net, "MUTEX_0_VALUE_0"));
+}
+
+#[test]
+fn signed_integers_are_not_modelled() {
+    let report = Analyzer::from_code(
+        r"
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+
+fn main() {
+    let pair = Arc::new((Mutex::new(0i32), Condvar::new()));
+    let producer = Arc::clone(&pair);
+    thread::spawn(move || {
+        let (lock, cvar) = &*producer;
+        let mut count = lock.lock().unwrap();
+        *count += 1;
+        cvar.notify_one();
+    });
+    let (lock, cvar) = &*pair;
+    let mut count = lock.lock().unwrap();
+    while *count == 0 {
+        count = cvar.wait(count).unwrap();
+    }
+    *count -= 1;
+}
+",
+    )
+    .mutex_values(true)
+    .analyze()
+    .expect("The analysis should succeed");
+
+    assert!(!has_place(&report.net, "MUTEX_0_VALUE_0"));
+}
+
+#[test]
+fn counter_incremented_past_the_largest_modelled_value() {
+    let report = Analyzer::from_code(
+        r"
+use std::sync::Mutex;
+
+fn main() {
+    let counter = Mutex::new(3usize);
+    *counter.lock().unwrap() += 1;
+    let value = *counter.lock().unwrap();
+    if value != 3 {
+        let _first = counter.lock().unwrap();
+        let _second = counter.lock().unwrap();
+    }
+}
+",
+    )
+    .mutex_values(true)
+    .analyze()
+    .expect("The analysis should succeed");
+
+    assert!(!report.is_deadlock_free());
+    assert!(report
+        .approximations
+        .iter()
+        .any(|approximation| approximation
+            .construct
+            .starts_with("Write of a value outside the modelled values 0 to 3 of mutex 0")));
+}
+
+#[test]
+fn initial_value_outside_the_modelled_values_is_not_modelled() {
+    let report = Analyzer::from_code(
+        r"
+use std::sync::Mutex;
+
+fn main() {
+    let counter = Mutex::new(7usize);
+    *counter.lock().unwrap() += 1;
+}
+",
+    )
+    .mutex_values(true)
+    .analyze()
+    .expect("The analysis should succeed");
+
+    assert!(!has_place(&report.net, "MUTEX_0_VALUE_0"));
+    assert!(report
+        .approximations
+        .iter()
+        .any(|approximation| approximation
+            .construct
+            .starts_with("Mutex created with the value 7 outside the modelled values")));
+}
@@ -0,0 +1,43 @@
+mod utils;
+
+mod call_once_blocked_by_lock_holder {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/once/call_once_blocked_by_lock_holder.rs",
+        "./examples/results/once/call_once_blocked_by_lock_holder/"
+    );
+}
+
+mod call_once_in_two_threads {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/once/call_once_in_two_threads.rs",
+        "./examples/results/once/call_once_in_two_threads/"
+    );
+}
+
+mod call_once_reentrant {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/once/call_once_reentrant.rs",
+        "./examples/results/once/call_once_reentrant/"
+    );
+}
+
+mod lazy_lock_forced_while_holding_lock {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/once/lazy_lock_forced_while_holding_lock.rs",
+        "./examples/results/once/lazy_lock_forced_while_holding_lock/"
+    );
+}
+
+mod once_lock_get_or_init {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/once/once_lock_get_or_init.rs",
+        "./examples/results/once/once_lock_get_or_init/"
+    );
+}
+
+mod static_call_once_blocked_by_lock_holder {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/once/static_call_once_blocked_by_lock_holder.rs",
+        "./examples/results/once/static_call_once_blocked_by_lock_holder/"
+    );
+}
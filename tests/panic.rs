@@ -0,0 +1,194 @@
+// Linking to the library requires access to the compiler's `rustc_driver` library.
+#![feature(rustc_private)]
+
+#[path = "utils/net.rs"]
+mod net;
+
+use cargo_check_deadlock::{AnalysisQuery, Analyzer, Property, PropertyVerdict, ReachablePanic};
+use net::has_place;
+
+#[test]
+fn reports_panic_in_main_thread() {
+    let report = Analyzer::from_file("./examples/programs/statement/panic.rs")
+        .panics(true)
+        .analyze()
+        .expect("The analysis should succeed");
+
+    assert_eq!(
+        report.panics,
+        vec![ReachablePanic {
+            thread: None,
+            location: "./examples/programs/statement/panic.rs:2:5".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn no_panic_in_hello_world() {
+    let report = Analyzer::from_file("./examples/programs/basic/hello_world.rs")
+        .panics(true)
+        .analyze()
+        .expect("The analysis should succeed");
+
+    assert!(report.panics.is_empty());
+}
+
+#[test]
+fn unanswered_panic_query_is_inconclusive() {
+    let report = Analyzer::from_file("./examples/programs/basic/hello_world.rs")
+        .panics(true)
+        .lola_path("./does_not_exist/lola")
+        .analyze()
+        .expect("The analysis should succeed");
+
+    assert!(report.panics.is_empty());
+    assert_eq!(report.inconclusive_queries.len(), 1);
+    assert_eq!(
+        report.inconclusive_queries[0].query,
+        AnalysisQuery::Panic(None)
+    );
+}
+
+#[test]
+fn several_panics_in_the_same_function() {
+    let report = Analyzer::from_code(
+        r"
+fn main() {
+    let count = std::env::args().count();
+    if count > 3 {
+        panic!();
+    }
+    if count > 5 {
+        panic!();
+    }
+}
+",
+    )
+    .panics(true)
+    .analyze()
+    .expect("The analysis should succeed");
+
+    assert!(report.is_deadlock_free());
+    assert_eq!(report.panics.len(), 1);
+    assert_eq!(report.panics[0].thread, None);
+    assert!(report.panics[0].location.starts_with("main.rs:"));
+}
+
+#[test]
+fn reports_panic_in_thread() {
+    let report = Analyzer::from_code(
+        r"
+fn main() {
+    let handle = std::thread::spawn(|| {
+        let values: Vec<u32> = Vec::new();
+        let _ = values[std::env::args().count()];
+    });
+    let _ = handle.join();
+}
+",
+    )
+    .panics(true)
+    .analyze()
+    .expect("The analysis should succeed");
+
+    assert!(report.is_deadlock_free());
+    assert!(has_place(&report.net, "THREAD_0_PANIC"));
+    assert_eq!(report.panics.len(), 1);
+    assert_eq!(report.panics[0].thread, Some(0));
+    assert!(report.panics[0].location.starts_with("main.rs:5:"));
+}
+
+#[test]
+fn thread_panics_end_in_the_thread_end_place_by_default() {
+    let report = Analyzer::from_file("./examples/programs/thread/shared_counter.rs")
+        .analyze()
+        .expect("The analysis should succeed");
+
+    assert!(!has_place(&report.net, "THREAD_0_PANIC"));
+    assert!(report.panics.is_empty());
+}
+
+#[test]
+fn join_on_a_thread_that_does_not_panic_succeeds() {
+    let report = Analyzer::from_code(
+        r"
+fn main() {
+    let handle = std::thread::spawn(|| {});
+    handle.join().unwrap();
+}
+",
+    )
+    .panics(true)
+    .analyze()
+    .expect("The analysis should succeed");
+
+    assert!(report.is_deadlock_free());
+    assert!(has_place(&report.net, "THREAD_0_JOIN_OK"));
+    assert!(has_place(&report.net, "THREAD_0_JOIN_ERR"));
+    assert!(report.panics.is_empty());
+}
+
+#[test]
+fn join_on_a_panicked_thread_returns_err() {
+    let report = Analyzer::from_code(
+        r"
+fn main() {
+    let handle = std::thread::spawn(|| panic!());
+    handle.join().unwrap();
+}
+",
+    )
+    .panics(true)
+    .analyze()
+    .expect("The analysis should succeed");
+
+    assert!(report.is_deadlock_free());
+    assert_eq!(report.panics.len(), 2);
+    assert_eq!(report.panics[0].thread, None);
+    assert!(report.panics[0].location.starts_with("main.rs:4:"));
+    assert_eq!(report.panics[1].thread, Some(0));
+}
+
+#[test]
+fn panic_while_holding_a_lock_poisons_the_mutex() {
+    let report = Analyzer::from_code(
+        r#"
+use std::sync::{Arc, Mutex};
+
+fn main() {
+    let data = Arc::new(Mutex::new(0));
+    let worker_data = Arc::clone(&data);
+    let handle = std::thread::spawn(move || {
+        let _guard = worker_data.lock().unwrap();
+        panic!("worker failed");
+    });
+    let _ = handle.join();
+    let _value = data.lock().unwrap();
+}
+"#,
+    )
+    .panics(true)
+    .properties([Property::Formula("EF MUTEX_0_POISONED > 0".to_string())])
+    .analyze()
+    .expect("The analysis should succeed");
+
+    assert!(report.is_deadlock_free());
+    assert!(has_place(&report.net, "MUTEX_0_NOT_POISONED"));
+    assert_eq!(
+        report.property_results[0].verdict,
+        PropertyVerdict::Satisfied
+    );
+    assert_eq!(report.panics.len(), 2);
+    assert_eq!(report.panics[1].thread, Some(0));
+    assert!(report.panics[1].location.starts_with("main.rs:9:"));
+}
+
+#[test]
+fn mutexes_are_never_poisoned_by_default() {
+    let report = Analyzer::from_file("./examples/programs/thread/shared_counter.rs")
+        .analyze()
+        .expect("The analysis should succeed");
+
+    assert!(!has_place(&report.net, "MUTEX_0_POISONED"));
+    assert!(!has_place(&report.net, "THREAD_0_JOIN_ERR"));
+}
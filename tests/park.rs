@@ -0,0 +1,29 @@
+mod utils;
+
+mod park_twice_with_one_stored_token {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/park/park_twice_with_one_stored_token.rs",
+        "./examples/results/park/park_twice_with_one_stored_token/"
+    );
+}
+
+mod park_without_unpark {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/park/park_without_unpark.rs",
+        "./examples/results/park/park_without_unpark/"
+    );
+}
+
+mod unpark_before_park {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/park/unpark_before_park.rs",
+        "./examples/results/park/unpark_before_park/"
+    );
+}
+
+mod unpark_main_thread {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/park/unpark_main_thread.rs",
+        "./examples/results/park/unpark_main_thread/"
+    );
+}
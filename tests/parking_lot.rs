@@ -0,0 +1,36 @@
+mod utils;
+
+mod double_lock_without_poisoning {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/parking_lot/double_lock_without_poisoning.rs",
+        "./examples/results/parking_lot/double_lock_without_poisoning/"
+    );
+}
+
+mod rwlock_guard_dropped {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/parking_lot/rwlock_guard_dropped.rs",
+        "./examples/results/parking_lot/rwlock_guard_dropped/"
+    );
+}
+
+mod rwlock_held_while_joining_writer {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/parking_lot/rwlock_held_while_joining_writer.rs",
+        "./examples/results/parking_lot/rwlock_held_while_joining_writer/"
+    );
+}
+
+mod rwlock_read_while_reading {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/parking_lot/rwlock_read_while_reading.rs",
+        "./examples/results/parking_lot/rwlock_read_while_reading/"
+    );
+}
+
+mod wait_with_guard_reference {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/parking_lot/wait_with_guard_reference.rs",
+        "./examples/results/parking_lot/wait_with_guard_reference/"
+    );
+}
@@ -0,0 +1,116 @@
+// Linking to the library requires access to the compiler's `rustc_driver` library.
+#![feature(rustc_private)]
+
+use cargo_check_deadlock::{Analyzer, Property, PropertyVerdict};
+
+#[test]
+fn panic_is_reachable() {
+    let report = Analyzer::from_file("./examples/programs/statement/panic.rs")
+        .properties([Property::Panic])
+        .analyze()
+        .expect("The analysis should succeed");
+
+    assert_eq!(report.property_results.len(), 1);
+    assert_eq!(report.property_results[0].property, Property::Panic);
+    assert_eq!(
+        report.property_results[0].verdict,
+        PropertyVerdict::Satisfied
+    );
+}
+
+#[test]
+fn panic_is_not_reachable() {
+    let report = Analyzer::from_file("./examples/programs/basic/hello_world.rs")
+        .properties([Property::Panic])
+        .analyze()
+        .expect("The analysis should succeed");
+
+    assert_eq!(
+        report.property_results[0].verdict,
+        PropertyVerdict::NotSatisfied
+    );
+}
+
+#[test]
+fn thread_may_never_terminate() {
+    let report = Analyzer::from_file("./examples/programs/thread/diverging.rs")
+        .properties([Property::ThreadNeverTerminates(0)])
+        .analyze()
+        .expect("The analysis should succeed");
+
+    assert_eq!(
+        report.property_results[0].verdict,
+        PropertyVerdict::Satisfied
+    );
+}
+
+#[test]
+fn panicking_thread_terminates() {
+    let report = Analyzer::from_code(
+        r#"
+use std::thread;
+
+fn main() {
+    let handle = thread::spawn(|| {
+        panic!("The thread always panics");
+    });
+    let _ = handle.join();
+}
+"#,
+    )
+    .panics(true)
+    .properties([Property::ThreadNeverTerminates(0)])
+    .analyze()
+    .expect("The analysis should succeed");
+
+    assert_eq!(
+        report.property_results[0].verdict,
+        PropertyVerdict::NotSatisfied
+    );
+}
+
+#[test]
+fn mutex_is_released_before_the_end() {
+    let report = Analyzer::from_file("./examples/programs/mutex/drop_mutex_guard_manually.rs")
+        .properties([Property::MutexHeldAtEnd(0)])
+        .analyze()
+        .expect("The analysis should succeed");
+
+    assert_eq!(
+        report.property_results[0].verdict,
+        PropertyVerdict::NotSatisfied
+    );
+}
+
+#[test]
+fn raw_formula_with_identifiers() {
+    let property: Property = "EF (DEADLOCK AND mutex(0) = 0)"
+        .parse()
+        .expect("The formula should be valid");
+    let report = Analyzer::from_file("./examples/programs/mutex/double_lock_deadlock.rs")
+        .properties([property])
+        .analyze()
+        .expect("The analysis should succeed");
+
+    assert!(report.is_deadlock());
+    assert_eq!(
+        report.property_results[0].verdict,
+        PropertyVerdict::Satisfied
+    );
+    assert!(!report.property_results[0].witness_path.is_empty());
+}
+
+#[test]
+fn properties_are_checked_when_the_precheck_skips_the_deadlock_analysis() {
+    let report = Analyzer::from_file("./examples/programs/statement/panic.rs")
+        .precheck(true)
+        .properties([Property::Panic])
+        .analyze()
+        .expect("The analysis should succeed");
+
+    assert!(report.skipped_model_checker);
+    assert_eq!(
+        report.property_results[0].verdict,
+        PropertyVerdict::Satisfied
+    );
+}
@@ -77,3 +77,412 @@ fn generates_lola_output_by_default() {
         panic!("Should not generate a .pnml file by default");
     }
 }
+
+#[test]
+fn generates_tina_and_tapaal_output() {
+    let file = assert_fs::NamedTempFile::new("valid_file.rs")
+        .expect("Could not create temporary file for test");
+    file.write_str("fn main() {}")
+        .expect("Could not write test file contents");
+    let output_folder = assert_fs::TempDir::new().expect("Could not create temporary folder");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+
+    cmd.arg("check-deadlock")
+        .arg(file.path())
+        .arg(format!(
+            "--output-folder={}",
+            output_folder.path().display()
+        ))
+        .arg("--tina")
+        .arg("--tapaal")
+        .arg("--skip-analysis");
+    cmd.assert().success();
+
+    let tina = std::fs::read_to_string(output_folder.path().join("net.net"))
+        .expect("Should generate a .net file");
+    assert!(tina.starts_with("net net\n"));
+    assert!(tina.contains("pl PROGRAM_START (1)\n"));
+    assert!(tina.contains("pl PROGRAM_END\n"));
+
+    let tapaal = std::fs::read_to_string(output_folder.path().join("net.tapaal.pnml"))
+        .expect("Should generate a .tapaal.pnml file");
+    assert!(tapaal.contains(r#"<place id="PROGRAM_START">"#));
+    assert!(tapaal.contains("<initialMarking>"));
+    assert!(tapaal.contains(r#"<place id="PROGRAM_END">"#));
+}
+
+#[test]
+fn reads_source_code_from_stdin() {
+    let folder = assert_fs::TempDir::new().expect("Could not create temporary folder for test");
+
+    let mut cmd =
+        assert_cmd::Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("-")
+        .arg("--output-folder")
+        .arg(folder.path())
+        .write_stdin(
+            "fn main() {
+                let mutex = std::sync::Mutex::new(0);
+                let _first = mutex.lock().unwrap();
+                let _second = mutex.lock().unwrap();
+            }",
+        );
+    cmd.assert().success().stdout(predicate::str::contains(
+        "Deadlock can be reached according to the model checker `LoLA`",
+    ));
+    folder.child("net.lola").assert(predicate::path::exists());
+}
+
+#[test]
+fn invalid_stdin_is_a_read_error() {
+    let mut cmd =
+        assert_cmd::Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("-")
+        .write_stdin(vec![0xff, 0xfe, 0xfd]);
+    cmd.assert()
+        .failure()
+        .code(6)
+        .stderr(predicate::str::contains(
+            "Source code could not be read from stdin",
+        ));
+}
+
+#[test]
+fn source_locations_from_stdin_use_virtual_filename() {
+    let folder = assert_fs::TempDir::new().expect("Could not create temporary folder for test");
+
+    let mut cmd =
+        assert_cmd::Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("-")
+        .arg("--output-folder")
+        .arg(folder.path())
+        .arg("--on-unsupported=skip")
+        .write_stdin(
+            "fn say_hello() {}
+fn main() {
+    let function: fn() = say_hello;
+    function();
+}",
+        );
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("at <stdin>:4:5"));
+}
+
+#[test]
+fn passes_trailing_arguments_to_rustc() {
+    let folder = assert_fs::TempDir::new().expect("Could not create temporary folder for test");
+    let file = folder.child("cfg.rs");
+    file.write_str(
+        "fn main() {
+            #[cfg(deadlock)]
+            {
+                let mutex = std::sync::Mutex::new(0);
+                let _first = mutex.lock().unwrap();
+                let _second = mutex.lock().unwrap();
+            }
+        }",
+    )
+    .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg(file.path())
+        .arg("--output-folder")
+        .arg(folder.path())
+        .arg("--")
+        .arg("--cfg")
+        .arg("deadlock");
+    cmd.assert().success().stdout(predicate::str::contains(
+        "Deadlock can be reached according to the model checker `LoLA`",
+    ));
+}
+
+#[test]
+fn reads_edition_from_cargo_manifest() {
+    let folder = assert_fs::TempDir::new().expect("Could not create temporary folder for test");
+    folder
+        .child("Cargo.toml")
+        .write_str("[package]\nname = \"example\"\nversion = \"0.1.0\"\nedition = \"2021\"\n")
+        .expect("Could not write test file contents");
+    // `TryFrom` is only part of the prelude since edition 2021.
+    let file = folder.child("src/main.rs");
+    file.write_str("fn main() { let _value = u8::try_from(300u32).unwrap_or(0); }")
+        .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg(file.path())
+        .arg("--output-folder")
+        .arg(folder.path());
+    cmd.assert().success().stdout(predicate::str::contains(
+        "The program is deadlock-free according to the model checker `LoLA`",
+    ));
+
+    // An explicit edition takes precedence over the manifest.
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg(file.path())
+        .arg("--output-folder")
+        .arg(folder.path())
+        .arg("--")
+        .arg("--edition=2015");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "no function or associated item named `try_from` found",
+    ));
+}
+
+#[test]
+fn reads_edition_inherited_from_workspace() {
+    let folder = assert_fs::TempDir::new().expect("Could not create temporary folder for test");
+    folder
+        .child("Cargo.toml")
+        .write_str(
+            "[workspace]\nmembers = [\"member\"]\n\n[workspace.package]\nedition = \"2021\"\n",
+        )
+        .expect("Could not write test file contents");
+    folder
+        .child("member/Cargo.toml")
+        .write_str("[package]\nname = \"member\"\nversion = \"0.1.0\"\nedition.workspace = true\n")
+        .expect("Could not write test file contents");
+    let file = folder.child("member/src/main.rs");
+    file.write_str("fn main() { let _value = u8::try_from(300u32).unwrap_or(0); }")
+        .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg(file.path())
+        .arg("--output-folder")
+        .arg(folder.path());
+    cmd.assert().success();
+}
+
+#[test]
+fn precheck_skips_model_checker_without_lock_cycles() {
+    let folder = assert_fs::TempDir::new().expect("Could not create temporary folder for test");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/thread/shared_counter.rs")
+        .arg("--output-folder")
+        .arg(folder.path())
+        .arg("--precheck");
+    cmd.assert().success().stdout(predicate::str::contains(
+        "The program is deadlock-free according to the lock graph",
+    ));
+}
+
+#[test]
+fn prints_lock_order_cycles() {
+    let folder = assert_fs::TempDir::new().expect("Could not create temporary folder for test");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/thread/dating_philosophers.rs")
+        .arg("--output-folder")
+        .arg(folder.path())
+        .arg("--precheck");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Deadlock can be reached according to the model checker `LoLA`",
+        ))
+        .stdout(predicate::str::contains(
+            "Lock order cycles (potential deadlocks):\n  - mutex 0 -> mutex 1 -> mutex 0",
+        ));
+}
+
+#[test]
+fn prints_lost_signals() {
+    let folder = assert_fs::TempDir::new().expect("Could not create temporary folder for test");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/condvar/self_notify_lost_signal.rs")
+        .arg("--output-folder")
+        .arg(folder.path());
+    cmd.assert().success().stdout(predicate::str::contains(
+        "Lost signals:\n  - The notification on condition variable 0 at ./examples/programs/condvar/self_notify_lost_signal.rs:5:10 may be lost, causing a deadlock",
+    ));
+}
+
+#[test]
+fn checks_requested_properties() {
+    let folder = assert_fs::TempDir::new().expect("Could not create temporary folder for test");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/statement/panic.rs")
+        .arg("--output-folder")
+        .arg(folder.path())
+        .arg("--property")
+        .arg("panic")
+        .arg("--property")
+        .arg("deadlock");
+    cmd.assert().success().stdout(predicate::str::contains(
+        "Result: The property `panic` holds according to the model checker `LoLA`\nResult: The program is deadlock-free according to the model checker `LoLA`",
+    ));
+}
+
+#[test]
+fn compares_verdicts_of_several_checkers() {
+    let folder = assert_fs::TempDir::new().expect("Could not create temporary folder for test");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/mutex/double_lock_deadlock.rs")
+        .arg("--output-folder")
+        .arg(folder.path())
+        .arg("--checker")
+        .arg("lola")
+        .arg("--checker")
+        .arg("verifypn");
+    // The verdict of `verifypn` is inconclusive if it is not installed, which does not count as a disagreement.
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Result: Deadlock can be reached according to the model checker `LoLA`",
+        ))
+        .stdout(predicate::str::contains(
+            "The model checkers agree on the property `deadlock`",
+        ));
+}
+
+#[test]
+fn uses_lola_executable_from_environment_variable() {
+    let folder = assert_fs::TempDir::new().expect("Could not create temporary folder for test");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/mutex/double_lock_deadlock.rs")
+        .arg("--output-folder")
+        .arg(folder.path())
+        .env("LOLA_PATH", "./does_not_exist/lola");
+    cmd.assert().success().stdout(predicate::str::contains(
+        "Result: The property `deadlock` could not be checked: `lola` could not be executed at ./does_not_exist/lola",
+    ));
+}
+
+#[test]
+fn lola_path_takes_precedence_over_environment_variable() {
+    let folder = assert_fs::TempDir::new().expect("Could not create temporary folder for test");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/mutex/double_lock_deadlock.rs")
+        .arg("--output-folder")
+        .arg(folder.path())
+        .arg("--lola-path")
+        .arg("./assets/lola")
+        .env("LOLA_PATH", "./does_not_exist/lola");
+    cmd.assert().success().stdout(predicate::str::contains(
+        "Result: Deadlock can be reached according to the model checker `LoLA`",
+    ));
+}
+
+#[test]
+fn timeout_gives_inconclusive_result() {
+    let folder = assert_fs::TempDir::new().expect("Could not create temporary folder for test");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/thread/dining_philosophers.rs")
+        .arg("--output-folder")
+        .arg(folder.path())
+        .arg("--timeout")
+        .arg("0");
+    cmd.assert().success().stdout(predicate::str::contains(
+        "Result: The property `deadlock` could not be checked: `lola` was killed after the timeout of 0 seconds",
+    ));
+}
+
+#[test]
+fn prints_model_checker_statistics() {
+    let folder = assert_fs::TempDir::new().expect("Could not create temporary folder for test");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/mutex/double_lock_deadlock.rs")
+        .arg("--output-folder")
+        .arg(folder.path());
+    cmd.assert().success().stdout(predicate::str::contains(
+        "Model checker statistics:\n  - `deadlock`: 3 states, 2 edges in",
+    ));
+}
+
+#[test]
+fn rejects_unknown_checker() {
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/statement/panic.rs")
+        .arg("--checker")
+        .arg("spin");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "possible values are: lola, verifypn",
+    ));
+}
+
+#[test]
+fn rejects_unknown_identifier_in_property() {
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/statement/panic.rs")
+        .arg("--property")
+        .arg("EF semaphore(0) = 0");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "Unknown identifier `semaphore` in the property",
+    ));
+}
+
+#[test]
+fn prints_reachable_panics() {
+    let folder = assert_fs::TempDir::new().expect("Could not create temporary folder for test");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/statement/panic.rs")
+        .arg("--output-folder")
+        .arg(folder.path())
+        .arg("--panics");
+    cmd.assert().success().stdout(predicate::str::contains(
+        "Reachable panics:\n  - The main thread may panic at ./examples/programs/statement/panic.rs:2:5",
+    ));
+}
+
+#[test]
+fn unanswered_panic_queries_are_not_reported_as_panic_free() {
+    let folder = assert_fs::TempDir::new().expect("Could not create temporary folder for test");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/basic/hello_world.rs")
+        .arg("--output-folder")
+        .arg(folder.path())
+        .arg("--lola-path")
+        .arg("./does_not_exist/lola")
+        .arg("--panics");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("No panic can be reached").not())
+        .stdout(predicate::str::contains(
+            "Inconclusive queries (lost signals or panics may be missing):\n  - Could not check whether the main thread may panic: `lola` could not be executed",
+        ));
+}
+
+#[test]
+fn reports_tokio_rwlock_reads_as_approximations() {
+    let folder = assert_fs::TempDir::new().expect("Could not create temporary folder for test");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg("./examples/programs/tokio/rwlock_write_while_reading.rs")
+        .arg("--output-folder")
+        .arg(folder.path());
+    cmd.assert().success().stdout(predicate::str::contains(
+        "Approximated constructs (the result may be imprecise):\n  - Shared read of a `tokio::sync::RwLock` modelled as an exclusive lock in `main__closure_0__0` at ./examples/programs/tokio/rwlock_write_while_reading.rs:10:26\n",
+    ));
+}
@@ -0,0 +1,64 @@
+mod utils;
+
+mod channel_receive_with_sender_alive {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/tokio/channel_receive_with_sender_alive.rs",
+        "./examples/results/tokio/channel_receive_with_sender_alive/"
+    );
+}
+
+mod channel_send_and_receive {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/tokio/channel_send_and_receive.rs",
+        "./examples/results/tokio/channel_send_and_receive/"
+    );
+}
+
+mod mutex_held_while_joining_task {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/tokio/mutex_held_while_joining_task.rs",
+        "./examples/results/tokio/mutex_held_while_joining_task/"
+    );
+}
+
+mod mutex_released_before_joining_task {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/tokio/mutex_released_before_joining_task.rs",
+        "./examples/results/tokio/mutex_released_before_joining_task/"
+    );
+}
+
+mod notified_without_notify {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/tokio/notified_without_notify.rs",
+        "./examples/results/tokio/notified_without_notify/"
+    );
+}
+
+mod notify_wakes_up_task {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/tokio/notify_wakes_up_task.rs",
+        "./examples/results/tokio/notify_wakes_up_task/"
+    );
+}
+
+mod rwlock_write_while_reading {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/tokio/rwlock_write_while_reading.rs",
+        "./examples/results/tokio/rwlock_write_while_reading/"
+    );
+}
+
+mod semaphore_acquired_twice {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/tokio/semaphore_acquired_twice.rs",
+        "./examples/results/tokio/semaphore_acquired_twice/"
+    );
+}
+
+mod semaphore_with_enough_permits {
+    super::utils::generate_tests_for_example_program!(
+        "./examples/programs/tokio/semaphore_with_enough_permits.rs",
+        "./examples/results/tokio/semaphore_with_enough_permits/"
+    );
+}
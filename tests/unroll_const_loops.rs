@@ -0,0 +1,181 @@
+// Linking to the library requires access to the compiler's `rustc_driver` library.
+#![feature(rustc_private)]
+
+#[path = "utils/net.rs"]
+mod net;
+
+use cargo_check_deadlock::Analyzer;
+use net::has_place;
+
+#[test]
+fn counts_iterations_of_constant_range() {
+    let report = Analyzer::from_code(
+        r"
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+fn main() {
+    let data = Arc::new(Mutex::new(0));
+    let d = Arc::clone(&data);
+    let handle = thread::spawn(move || {
+        for _ in 0..3 {
+            let mut guard = d.lock().unwrap();
+            *guard += 1;
+        }
+    });
+    handle.join().unwrap();
+}
+",
+    )
+    .unroll_const_loops(true)
+    .analyze()
+    .expect("The analysis should succeed");
+
+    assert!(report.is_deadlock_free());
+    assert!(has_place(&report.net, "RANGE_0_ITERATION_0"));
+    assert!(has_place(&report.net, "RANGE_0_ITERATION_3"));
+    assert!(!has_place(&report.net, "RANGE_0_ITERATION_4"));
+}
+
+#[test]
+fn body_of_empty_range_never_runs() {
+    let report = Analyzer::from_code(
+        r"
+use std::sync::Mutex;
+
+fn main() {
+    let mutex = Mutex::new(0);
+    for _ in 0..0 {
+        let _first = mutex.lock().unwrap();
+        let _second = mutex.lock().unwrap();
+    }
+}
+",
+    )
+    .unroll_const_loops(true)
+    .analyze()
+    .expect("The analysis should succeed");
+
+    assert!(report.is_deadlock_free());
+}
+
+#[test]
+fn body_of_empty_range_may_run_by_default() {
+    let report = Analyzer::from_code(
+        r"
+use std::sync::Mutex;
+
+fn main() {
+    let mutex = Mutex::new(0);
+    for _ in 0..0 {
+        let _first = mutex.lock().unwrap();
+        let _second = mutex.lock().unwrap();
+    }
+}
+",
+    )
+    .analyze()
+    .expect("The analysis should succeed");
+
+    assert!(!report.is_deadlock_free());
+    assert!(!has_place(&report.net, "RANGE_0_ITERATION_0"));
+}
+
+#[test]
+fn body_of_non_empty_range_runs() {
+    let report = Analyzer::from_code(
+        r"
+use std::sync::Mutex;
+
+fn main() {
+    let mutex = Mutex::new(0);
+    for _ in 0..2 {
+        let _first = mutex.lock().unwrap();
+        let _second = mutex.lock().unwrap();
+    }
+}
+",
+    )
+    .unroll_const_loops(true)
+    .analyze()
+    .expect("The analysis should succeed");
+
+    assert!(!report.is_deadlock_free());
+}
+
+#[test]
+fn break_out_of_nested_range_restarts_it() {
+    let report = Analyzer::from_code(
+        r"
+use std::sync::Mutex;
+
+fn main() {
+    let mutex = Mutex::new(0);
+    for _ in 0..2 {
+        for _ in 0..1 {
+            let guard = mutex.lock().unwrap();
+            std::mem::forget(guard);
+            break;
+        }
+    }
+}
+",
+    )
+    .unroll_const_loops(true)
+    .analyze()
+    .expect("The analysis should succeed");
+
+    assert!(!report.is_deadlock_free());
+}
+
+#[test]
+fn range_longer_than_limit_is_not_counted() {
+    let report = Analyzer::from_code(
+        r"
+use std::sync::Mutex;
+
+fn main() {
+    let mutex = Mutex::new(0);
+    for _ in 0..100 {
+        let _first = mutex.lock().unwrap();
+        let _second = mutex.lock().unwrap();
+    }
+}
+",
+    )
+    .unroll_const_loops(true)
+    .analyze()
+    .expect("The analysis should succeed");
+
+    assert!(!report.is_deadlock_free());
+    assert!(!has_place(&report.net, "RANGE_0_ITERATION_0"));
+    assert!(report
+        .approximations
+        .iter()
+        .any(|approximation| approximation
+            .construct
+            .starts_with("Loop over a range with 100 iterations, more than the 16 modelled")));
+}
+
+#[test]
+fn range_with_unknown_bound_is_not_counted() {
+    let report = Analyzer::from_code(
+        r"
+use std::sync::Mutex;
+
+fn main() {
+    let mutex = Mutex::new(0);
+    for _ in 0..std::env::args().count() {
+        let _first = mutex.lock().unwrap();
+        let _second = mutex.lock().unwrap();
+    }
+}
+",
+    )
+    .unroll_const_loops(true)
+    .analyze()
+    .expect("The analysis should succeed");
+
+    assert!(!report.is_deadlock_free());
+    assert!(!has_place(&report.net, "RANGE_0_ITERATION_0"));
+}
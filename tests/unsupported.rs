@@ -0,0 +1,300 @@
+use assert_cmd::prelude::*; // Add methods on commands
+use assert_fs::prelude::*; // Used for creating temp files
+use predicates::prelude::*; // Used for writing assertions
+use std::process::Command; // Run programs
+
+const FUNCTION_POINTER_PROGRAM: &str = r#"
+fn say_hello() {
+    println!("Hello");
+}
+
+fn main() {
+    let function: fn() = say_hello;
+    function();
+}
+"#;
+
+#[test]
+fn function_pointer_aborts_by_default() {
+    let folder = assert_fs::TempDir::new().expect("Could not create temporary folder for test");
+    let file = folder.child("function_pointer.rs");
+    file.write_str(FUNCTION_POINTER_PROGRAM)
+        .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg(file.path())
+        .arg("--output-folder")
+        .arg(folder.path());
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "TyKind::FnPtr not implemented yet",
+    ));
+}
+
+#[test]
+fn untracked_atomic_aborts_with_translation_error() {
+    let folder = assert_fs::TempDir::new().expect("Could not create temporary folder for test");
+    let file = folder.child("untracked_atomic.rs");
+    file.write_str(
+        r#"
+use std::sync::atomic::{AtomicBool, Ordering};
+
+fn get(flag: &AtomicBool) -> bool {
+    flag.load(Ordering::SeqCst)
+}
+
+fn main() {
+    let flag = AtomicBool::new(false);
+    println!("{}", get(&flag));
+}
+"#,
+    )
+    .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg(file.path())
+        .arg("--output-folder")
+        .arg(folder.path());
+    cmd.assert()
+        .failure()
+        .code(3)
+        .stderr(predicate::str::contains(
+            "Call to `std::sync::atomic::AtomicBool::load` with an untracked synchronization variable not implemented yet",
+        ))
+        .stderr(predicate::str::contains("panicked").not());
+}
+
+#[test]
+fn function_pointer_with_havoc_may_deadlock() {
+    let folder = assert_fs::TempDir::new().expect("Could not create temporary folder for test");
+    let file = folder.child("function_pointer.rs");
+    file.write_str(FUNCTION_POINTER_PROGRAM)
+        .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg(file.path())
+        .arg("--output-folder")
+        .arg(folder.path())
+        .arg("--on-unsupported=havoc");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Deadlock can be reached according to the model checker `LoLA`",
+        ))
+        .stdout(predicate::str::contains(
+            "Call through a function pointer in `main` at",
+        ))
+        .stdout(predicate::str::contains("(modelled with `havoc`)"));
+}
+
+#[test]
+fn function_pointer_with_skip_is_deadlock_free() {
+    let folder = assert_fs::TempDir::new().expect("Could not create temporary folder for test");
+    let file = folder.child("function_pointer.rs");
+    file.write_str(FUNCTION_POINTER_PROGRAM)
+        .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg(file.path())
+        .arg("--output-folder")
+        .arg(folder.path())
+        .arg("--on-unsupported=skip");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "The program is deadlock-free according to the model checker `LoLA`",
+        ))
+        .stdout(predicate::str::contains("(modelled with `skip`)"));
+}
+
+#[test]
+fn inline_assembly_with_skip_is_deadlock_free() {
+    let folder = assert_fs::TempDir::new().expect("Could not create temporary folder for test");
+    let file = folder.child("inline_assembly.rs");
+    file.write_str(
+        r#"
+fn main() {
+    unsafe {
+        std::arch::asm!("nop");
+    }
+}
+"#,
+    )
+    .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg(file.path())
+        .arg("--output-folder")
+        .arg(folder.path())
+        .arg("--on-unsupported=skip");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "The program is deadlock-free according to the model checker `LoLA`",
+        ))
+        .stdout(predicate::str::contains(
+            "TerminatorKind::InlineAsm in `main`",
+        ));
+}
+
+#[test]
+fn invalid_policy_is_rejected() {
+    let file = assert_fs::NamedTempFile::new("valid_file.rs")
+        .expect("Could not create temporary file for test");
+    file.write_str("fn main() {}")
+        .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg(file.path())
+        .arg("--on-unsupported=ignore");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "possible values are: abort, havoc, skip",
+    ));
+}
+
+const FUNCTION_POINTER_SPAWN_PROGRAM: &str = r#"
+fn work() {}
+
+fn main() {
+    let function: fn() = work;
+    let handle = std::thread::spawn(function);
+    handle.join().unwrap();
+}
+"#;
+
+#[test]
+fn function_pointer_passed_to_spawn_aborts_by_default() {
+    let folder = assert_fs::TempDir::new().expect("Could not create temporary folder for test");
+    let file = folder.child("function_pointer_spawn.rs");
+    file.write_str(FUNCTION_POINTER_SPAWN_PROGRAM)
+        .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg(file.path())
+        .arg("--output-folder")
+        .arg(folder.path());
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "TyKind::FnPtr not implemented yet",
+    ));
+}
+
+#[test]
+fn function_pointer_passed_to_spawn_with_skip_is_approximated() {
+    let folder = assert_fs::TempDir::new().expect("Could not create temporary folder for test");
+    let file = folder.child("function_pointer_spawn.rs");
+    file.write_str(FUNCTION_POINTER_SPAWN_PROGRAM)
+        .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg(file.path())
+        .arg("--output-folder")
+        .arg(folder.path())
+        .arg("--on-unsupported=skip");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "The program is deadlock-free according to the model checker `LoLA`",
+        ))
+        .stdout(predicate::str::contains(
+            "Function pointer passed to `std::thread::spawn` in `main` at",
+        ))
+        .stdout(predicate::str::contains("(modelled with `skip`)"));
+}
+
+const UNSUPPORTED_TOKIO_FUTURE_PROGRAM: &str = r#"
+mod tokio {
+    pub mod runtime {
+        pub struct Runtime;
+
+        impl Runtime {
+            pub fn new() -> std::io::Result<Self> {
+                Ok(Self)
+            }
+
+            pub fn block_on<F: std::future::Future>(&self, future: F) -> F::Output {
+                let _ = future;
+                unimplemented!()
+            }
+        }
+    }
+
+    pub mod time {
+        use std::future::Future;
+        use std::pin::Pin;
+        use std::task::{Context, Poll};
+
+        pub struct Sleep;
+
+        impl Future for Sleep {
+            type Output = ();
+
+            fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+                unimplemented!()
+            }
+        }
+
+        pub fn sleep() -> Sleep {
+            Sleep
+        }
+    }
+}
+
+fn main() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    runtime.block_on(async {
+        tokio::time::sleep().await;
+    });
+}
+"#;
+
+#[test]
+fn unsupported_tokio_future_aborts_by_default() {
+    let folder = assert_fs::TempDir::new().expect("Could not create temporary folder for test");
+    let file = folder.child("unsupported_tokio_future.rs");
+    file.write_str(UNSUPPORTED_TOKIO_FUTURE_PROGRAM)
+        .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg(file.path())
+        .arg("--output-folder")
+        .arg(folder.path())
+        .arg("--")
+        .arg("--edition=2021");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "Awaiting `tokio::time::Sleep` not implemented yet",
+    ));
+}
+
+#[test]
+fn unsupported_tokio_future_with_skip_is_approximated() {
+    let folder = assert_fs::TempDir::new().expect("Could not create temporary folder for test");
+    let file = folder.child("unsupported_tokio_future.rs");
+    file.write_str(UNSUPPORTED_TOKIO_FUTURE_PROGRAM)
+        .expect("Could not write test file contents");
+
+    let mut cmd = Command::cargo_bin("cargo-check-deadlock").expect("Command not found");
+    cmd.arg("check-deadlock")
+        .arg(file.path())
+        .arg("--output-folder")
+        .arg(folder.path())
+        .arg("--on-unsupported=skip")
+        .arg("--")
+        .arg("--edition=2021");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "The program is deadlock-free according to the model checker `LoLA`",
+        ))
+        .stdout(predicate::str::contains(
+            "Awaiting the unsupported future `tokio::time::Sleep` in",
+        ))
+        .stdout(predicate::str::contains("(modelled with `skip`)"));
+}
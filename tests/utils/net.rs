@@ -0,0 +1,11 @@
+//! Helpers for the tests that inspect the Petri net returned by the library.
+//!
+//! They are kept out of `mod.rs` because using the library requires `#![feature(rustc_private)]`
+//! in every test crate that includes them. Include them with `#[path = "utils/net.rs"] mod net;`.
+
+use cargo_check_deadlock::PetriNet;
+
+/// Checks whether the net contains a place with the given label.
+pub fn has_place(net: &PetriNet, label: &str) -> bool {
+    net.places_iter().any(|(place, _)| place.label() == label)
+}